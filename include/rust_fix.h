@@ -0,0 +1,32 @@
+/* C interface of rust-fix, built with the `ffi` feature. See src/ffi.rs. */
+
+#ifndef RUST_FIX_H
+#define RUST_FIX_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define FIX_FFI_OK 0
+#define FIX_FFI_NULL_POINTER -1
+#define FIX_FFI_INVALID_ARGUMENT -2
+
+typedef struct FixMessage FixMessage;
+
+FixMessage *fix_message_parse(const uint8_t *data, size_t len, bool check_sum_validation);
+FixMessage *fix_message_new(const char *begin_string, const char *msg_type);
+void fix_message_free(FixMessage *message);
+
+const uint8_t *fix_message_get_field(const FixMessage *message, uint32_t tag, size_t *out_len);
+int fix_message_add_field(FixMessage *message, uint32_t tag, const uint8_t *value, size_t len);
+size_t fix_message_serialize(const FixMessage *message, uint8_t *out, size_t capacity);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
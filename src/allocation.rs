@@ -0,0 +1,468 @@
+use std::time::SystemTime;
+
+use crate::{
+    fields::{FixField, Side},
+    group::{
+        expect_msg_type, get_coded, get_parsed, get_string, get_timestamp, read_group, required,
+        write_optional, GroupEntry,
+    },
+    parties::{read_parties, write_parties},
+    BeginString, FieldParseError, FixMessageBuilder, Party,
+};
+
+const MSG_TYPE_ALLOCATION_INSTRUCTION: &str = "J";
+const MSG_TYPE_ALLOCATION_REPORT: &str = "AS";
+const MSG_TYPE_ALLOCATION_INSTRUCTION_ACK: &str = "P";
+
+const TAG_NO_ORDERS: u32 = 73;
+const TAG_NO_ALLOCS: u32 = 78;
+
+const ORDER_TAGS: [u32; 2] = [11, 37];
+const ALLOC_TAGS: [u32; 7] = [79, 661, 366, 80, 467, 776, 161];
+
+/// Entry of NoOrders (73): an order whose fills are being allocated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllocationOrder {
+    /// ClOrdID (11).
+    pub cl_ord_id: String,
+    /// OrderID (37).
+    pub order_id: Option<String>,
+}
+
+/// Entry of NoAllocs (78): the share of one account. Instructions and reports fill in
+/// the quantity, acknowledgements the rejection code and text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Allocation {
+    /// AllocAccount (79).
+    pub account: String,
+    /// AllocAcctIDSource (661).
+    pub acct_id_source: Option<u32>,
+    /// AllocPrice (366).
+    pub price: Option<String>,
+    /// AllocQty (80).
+    pub qty: Option<String>,
+    /// IndividualAllocID (467).
+    pub individual_alloc_id: Option<String>,
+    /// IndividualAllocRejCode (776).
+    pub individual_alloc_rej_code: Option<u32>,
+    /// AllocText (161).
+    pub text: Option<String>,
+}
+
+/// Block shared by AllocationInstruction and AllocationReport: what was traded and
+/// how it is split between accounts.
+///
+/// Prices and quantities are kept as written, so no digits are lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocationDetails {
+    /// NoOrders (73).
+    pub orders: Vec<AllocationOrder>,
+    /// Side (54).
+    pub side: Side,
+    /// Symbol (55).
+    pub symbol: Option<String>,
+    /// SecurityID (48).
+    pub security_id: Option<String>,
+    /// SecurityIDSource (22).
+    pub security_id_source: Option<String>,
+    /// Quantity (53).
+    pub quantity: String,
+    /// AvgPx (6).
+    pub avg_px: String,
+    /// Currency (15).
+    pub currency: Option<String>,
+    pub parties: Vec<Party>,
+    /// TradeDate (75).
+    pub trade_date: String,
+    /// TransactTime (60).
+    pub transact_time: Option<SystemTime>,
+    /// NoAllocs (78).
+    pub allocs: Vec<Allocation>,
+}
+
+/// AllocationInstruction (J).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocationInstruction {
+    /// AllocID (70).
+    pub alloc_id: String,
+    /// AllocTransType (71), `0` new, `1` replace or `2` cancel.
+    pub alloc_trans_type: char,
+    /// AllocType (626).
+    pub alloc_type: u32,
+    /// RefAllocID (72), the instruction being replaced or canceled.
+    pub ref_alloc_id: Option<String>,
+    pub details: AllocationDetails,
+}
+
+/// AllocationReport (AS).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocationReport {
+    /// AllocReportID (755).
+    pub alloc_report_id: String,
+    /// AllocID (70).
+    pub alloc_id: Option<String>,
+    /// AllocTransType (71).
+    pub alloc_trans_type: char,
+    /// AllocReportType (794).
+    pub alloc_report_type: u32,
+    /// AllocStatus (87).
+    pub alloc_status: u32,
+    /// AllocRejCode (88).
+    pub alloc_rej_code: Option<u32>,
+    pub details: AllocationDetails,
+}
+
+/// AllocationInstructionAck (P).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocationInstructionAck {
+    /// AllocID (70).
+    pub alloc_id: String,
+    pub parties: Vec<Party>,
+    /// TradeDate (75).
+    pub trade_date: Option<String>,
+    /// TransactTime (60).
+    pub transact_time: SystemTime,
+    /// AllocStatus (87), e.g. `0` accepted or `1` block level reject.
+    pub alloc_status: u32,
+    /// AllocRejCode (88).
+    pub alloc_rej_code: Option<u32>,
+    /// Text (58).
+    pub text: Option<String>,
+    /// NoAllocs (78), the accounts the acknowledgement is about.
+    pub allocs: Vec<Allocation>,
+}
+
+impl Allocation {
+    pub fn new(account: &str, qty: &str) -> Self {
+        return Self {
+            account: account.to_string(),
+            qty: Some(qty.to_string()),
+            ..Self::default()
+        };
+    }
+}
+
+impl AllocationDetails {
+    pub fn new(side: Side, quantity: &str, avg_px: &str, trade_date: &str) -> Self {
+        return Self {
+            orders: vec![],
+            side,
+            symbol: None,
+            security_id: None,
+            security_id_source: None,
+            quantity: quantity.to_string(),
+            avg_px: avg_px.to_string(),
+            currency: None,
+            parties: vec![],
+            trade_date: trade_date.to_string(),
+            transact_time: None,
+            allocs: vec![],
+        };
+    }
+
+    fn write(&self, message: &mut FixMessageBuilder) {
+        if !self.orders.is_empty() {
+            message.with_value(TAG_NO_ORDERS, self.orders.len());
+
+            for order in &self.orders {
+                message.with_value(11, order.cl_ord_id.as_str());
+                write_optional(message, 37, &order.order_id);
+            }
+        }
+
+        message.with_field(self.side);
+        write_optional(message, 55, &self.symbol);
+        write_optional(message, 48, &self.security_id);
+        write_optional(message, 22, &self.security_id_source);
+        message.with_value(53, self.quantity.as_str());
+        message.with_value(6, self.avg_px.as_str());
+        write_optional(message, 15, &self.currency);
+        write_parties(message, &self.parties);
+        message.with_value(75, self.trade_date.as_str());
+        write_optional(message, 60, &self.transact_time);
+        write_allocs(message, &self.allocs);
+    }
+
+    fn read(fields: &GroupEntry) -> Result<Self, FieldParseError> {
+        let mut orders = vec![];
+
+        for entry in read_group(fields, TAG_NO_ORDERS, &ORDER_TAGS)? {
+            orders.push(AllocationOrder {
+                cl_ord_id: required(get_string(&entry, 11)?, 11)?,
+                order_id: get_string(&entry, 37)?,
+            });
+        }
+
+        return Ok(Self {
+            orders,
+            side: required(get_coded(fields, Side::TAG)?, Side::TAG)?,
+            symbol: get_string(fields, 55)?,
+            security_id: get_string(fields, 48)?,
+            security_id_source: get_string(fields, 22)?,
+            quantity: required(get_string(fields, 53)?, 53)?,
+            avg_px: required(get_string(fields, 6)?, 6)?,
+            currency: get_string(fields, 15)?,
+            parties: read_parties(fields)?,
+            trade_date: required(get_string(fields, 75)?, 75)?,
+            transact_time: get_timestamp(fields, 60)?,
+            allocs: read_allocs(fields)?,
+        });
+    }
+}
+
+fn write_allocs(message: &mut FixMessageBuilder, allocs: &[Allocation]) {
+    if allocs.is_empty() {
+        return;
+    }
+
+    message.with_value(TAG_NO_ALLOCS, allocs.len());
+
+    for alloc in allocs {
+        message.with_value(79, alloc.account.as_str());
+        write_optional(message, 661, &alloc.acct_id_source);
+        write_optional(message, 366, &alloc.price);
+        write_optional(message, 80, &alloc.qty);
+        write_optional(message, 467, &alloc.individual_alloc_id);
+        write_optional(message, 776, &alloc.individual_alloc_rej_code);
+        write_optional(message, 161, &alloc.text);
+    }
+}
+
+fn read_allocs(fields: &GroupEntry) -> Result<Vec<Allocation>, FieldParseError> {
+    let mut allocs = vec![];
+
+    for entry in read_group(fields, TAG_NO_ALLOCS, &ALLOC_TAGS)? {
+        allocs.push(Allocation {
+            account: required(get_string(&entry, 79)?, 79)?,
+            acct_id_source: get_parsed(&entry, 661)?,
+            price: get_string(&entry, 366)?,
+            qty: get_string(&entry, 80)?,
+            individual_alloc_id: get_string(&entry, 467)?,
+            individual_alloc_rej_code: get_parsed(&entry, 776)?,
+            text: get_string(&entry, 161)?,
+        });
+    }
+
+    return Ok(allocs);
+}
+
+impl AllocationInstruction {
+    pub fn new(alloc_id: &str, alloc_type: u32, details: AllocationDetails) -> Self {
+        return Self {
+            alloc_id: alloc_id.to_string(),
+            alloc_trans_type: '0',
+            alloc_type,
+            ref_alloc_id: None,
+            details,
+        };
+    }
+
+    /// The AllocationInstruction as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_ALLOCATION_INSTRUCTION);
+        message.with_value(70, self.alloc_id.as_str());
+        message.with_value(71, self.alloc_trans_type);
+        message.with_value(626, self.alloc_type);
+        write_optional(&mut message, 72, &self.ref_alloc_id);
+        self.details.write(&mut message);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for AllocationInstruction {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_ALLOCATION_INSTRUCTION)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            alloc_id: required(get_string(&fields, 70)?, 70)?,
+            alloc_trans_type: required(get_parsed(&fields, 71)?, 71)?,
+            alloc_type: required(get_parsed(&fields, 626)?, 626)?,
+            ref_alloc_id: get_string(&fields, 72)?,
+            details: AllocationDetails::read(&fields)?,
+        });
+    }
+}
+
+impl AllocationReport {
+    pub fn new(
+        alloc_report_id: &str,
+        alloc_report_type: u32,
+        alloc_status: u32,
+        details: AllocationDetails,
+    ) -> Self {
+        return Self {
+            alloc_report_id: alloc_report_id.to_string(),
+            alloc_id: None,
+            alloc_trans_type: '0',
+            alloc_report_type,
+            alloc_status,
+            alloc_rej_code: None,
+            details,
+        };
+    }
+
+    /// The AllocationReport as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_ALLOCATION_REPORT);
+        message.with_value(755, self.alloc_report_id.as_str());
+        write_optional(&mut message, 70, &self.alloc_id);
+        message.with_value(71, self.alloc_trans_type);
+        message.with_value(794, self.alloc_report_type);
+        message.with_value(87, self.alloc_status);
+        write_optional(&mut message, 88, &self.alloc_rej_code);
+        self.details.write(&mut message);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for AllocationReport {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_ALLOCATION_REPORT)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            alloc_report_id: required(get_string(&fields, 755)?, 755)?,
+            alloc_id: get_string(&fields, 70)?,
+            alloc_trans_type: required(get_parsed(&fields, 71)?, 71)?,
+            alloc_report_type: required(get_parsed(&fields, 794)?, 794)?,
+            alloc_status: required(get_parsed(&fields, 87)?, 87)?,
+            alloc_rej_code: get_parsed(&fields, 88)?,
+            details: AllocationDetails::read(&fields)?,
+        });
+    }
+}
+
+impl AllocationInstructionAck {
+    pub fn new(alloc_id: &str, alloc_status: u32, transact_time: SystemTime) -> Self {
+        return Self {
+            alloc_id: alloc_id.to_string(),
+            parties: vec![],
+            trade_date: None,
+            transact_time,
+            alloc_status,
+            alloc_rej_code: None,
+            text: None,
+            allocs: vec![],
+        };
+    }
+
+    /// The AllocationInstructionAck as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_ALLOCATION_INSTRUCTION_ACK);
+        message.with_value(70, self.alloc_id.as_str());
+        write_parties(&mut message, &self.parties);
+        write_optional(&mut message, 75, &self.trade_date);
+        message.with_value(60, self.transact_time);
+        message.with_value(87, self.alloc_status);
+        write_optional(&mut message, 88, &self.alloc_rej_code);
+        write_optional(&mut message, 58, &self.text);
+        write_allocs(&mut message, &self.allocs);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for AllocationInstructionAck {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_ALLOCATION_INSTRUCTION_ACK)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            alloc_id: required(get_string(&fields, 70)?, 70)?,
+            parties: read_parties(&fields)?,
+            trade_date: get_string(&fields, 75)?,
+            transact_time: required(get_timestamp(&fields, 60)?, 60)?,
+            alloc_status: required(get_parsed(&fields, 87)?, 87)?,
+            alloc_rej_code: get_parsed(&fields, 88)?,
+            text: get_string(&fields, 58)?,
+            allocs: read_allocs(&fields)?,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn details() -> AllocationDetails {
+        let mut details = AllocationDetails::new(Side::Buy, "1000", "20.125", "20240102");
+        details.orders = vec![AllocationOrder {
+            cl_ord_id: "ORDER1".to_string(),
+            order_id: Some("X1".to_string()),
+        }];
+        details.symbol = Some("VOD.L".to_string());
+        details.parties = vec![Party::new("FUNDMGR", "D", 13)];
+        details.allocs = vec![
+            Allocation::new("FUND_A", "600"),
+            Allocation::new("FUND_B", "400"),
+        ];
+
+        return details;
+    }
+
+    #[test]
+    fn test_allocation_instruction_round_trip() {
+        let instruction = AllocationInstruction::new("ALLOC1", 1, details());
+        let message = instruction.to_message("FIX.4.4");
+
+        assert_eq!(
+            "8=FIX.4.4|9=160|35=J|70=ALLOC1|71=0|626=1|73=1|11=ORDER1|37=X1|54=1|55=VOD.L|53=1000|6=20.125|453=1|448=FUNDMGR|447=D|452=13|75=20240102|78=2|79=FUND_A|80=600|79=FUND_B|80=400|10=091|",
+            message.to_string()
+        );
+        assert_eq!(Ok(instruction), AllocationInstruction::try_from(&message));
+    }
+
+    #[test]
+    fn test_allocation_report_round_trip() {
+        let mut report = AllocationReport::new("REP1", 3, 0, details());
+        report.alloc_id = Some("ALLOC1".to_string());
+
+        let message = report.to_message("FIX.4.4");
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+
+        assert_eq!(Ok(report), AllocationReport::try_from(&parsed));
+        assert!(matches!(
+            AllocationInstruction::try_from(&parsed),
+            Err(FieldParseError::InvalidValue { tag: 35, .. })
+        ));
+    }
+
+    #[test]
+    fn test_allocation_instruction_ack_round_trip() {
+        let mut ack =
+            AllocationInstructionAck::new("ALLOC1", 2, UNIX_EPOCH + Duration::from_secs(60));
+        ack.alloc_rej_code = Some(0);
+        ack.allocs = vec![Allocation {
+            account: "FUND_C".to_string(),
+            individual_alloc_rej_code: Some(0),
+            text: Some("Unknown account".to_string()),
+            ..Allocation::default()
+        }];
+
+        let message = ack.to_message("FIX.4.4");
+        assert_eq!(
+            Ok(ack.clone()),
+            AllocationInstructionAck::try_from(&message)
+        );
+
+        let mut message = message;
+        message.remove_value(60);
+        assert_eq!(
+            Err(FieldParseError::Missing { tag: 60 }),
+            AllocationInstructionAck::try_from(&message)
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use crate::{
+    group::{
+        expect_msg_type, get_parsed, get_string, read_group, required, write_optional, GroupEntry,
+    },
+    BeginString, FieldParseError, FixMessageBuilder,
+};
+
+const MSG_TYPE_APPLICATION_MESSAGE_REQUEST: &str = "BW";
+
+const TAG_NO_APPL_IDS: u32 = 1351;
+
+const APPL_ID_TAGS: [u32; 3] = [1355, 1182, 1183];
+
+/// ApplReqType (1347) asking for the messages in the given ranges again.
+pub const APPL_REQ_TYPE_RETRANSMISSION: u32 = 0;
+
+/// Application Sequencing component of FIX 5.0 application messages, numbering each
+/// ApplID stream independently of the session's MsgSeqNum (34).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplicationSequencing {
+    /// ApplID (1180).
+    pub appl_id: String,
+    /// ApplSeqNum (1181).
+    pub appl_seq_num: u64,
+    /// ApplLastSeqNum (1350), the previous number of a stream that is not contiguous.
+    pub appl_last_seq_num: Option<u64>,
+    /// ApplResendFlag (1352), set on messages sent again for an
+    /// ApplicationMessageRequest.
+    pub appl_resend_flag: bool,
+}
+
+impl ApplicationSequencing {
+    pub fn new(appl_id: &str, appl_seq_num: u64) -> Self {
+        return Self {
+            appl_id: appl_id.to_string(),
+            appl_seq_num,
+            appl_last_seq_num: None,
+            appl_resend_flag: false,
+        };
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends the Application Sequencing component.
+    pub fn with_appl_seq(&mut self, appl_seq: &ApplicationSequencing) {
+        self.with_value(1180, appl_seq.appl_id.as_str());
+        self.with_value(1181, appl_seq.appl_seq_num);
+        write_optional(self, 1350, &appl_seq.appl_last_seq_num);
+
+        if appl_seq.appl_resend_flag {
+            self.with_value(1352, true);
+        }
+    }
+
+    /// The Application Sequencing component, `None` when the message has no ApplID (1180).
+    pub fn get_appl_seq(&self) -> Result<Option<ApplicationSequencing>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+
+        let Some(appl_id) = get_string(&fields, 1180)? else {
+            return Ok(None);
+        };
+
+        return Ok(Some(ApplicationSequencing {
+            appl_id,
+            appl_seq_num: required(get_parsed(&fields, 1181)?, 1181)?,
+            appl_last_seq_num: get_parsed(&fields, 1350)?,
+            appl_resend_flag: get_string(&fields, 1352)?.as_deref() == Some("Y"),
+        }));
+    }
+}
+
+/// Outcome of `ApplSeqTracker::check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplSeqCheck {
+    /// The next message of the stream, or a resent one filling a gap.
+    InSequence,
+    /// Messages `begin..=end` of the stream were missed; the message itself is still
+    /// the newest one.
+    Gap { begin: u64, end: u64 },
+    /// Already seen and not flagged as resent.
+    Duplicate,
+}
+
+/// Expected ApplSeqNum (1181) per ApplID (1180) of the streams a session receives.
+/// The first message of a stream sets where it starts.
+#[derive(Debug, Clone, Default)]
+pub struct ApplSeqTracker {
+    next: HashMap<String, u64>,
+}
+
+impl ApplSeqTracker {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Checks a received message against its stream and moves the stream forward.
+    pub fn check(&mut self, appl_seq: &ApplicationSequencing) -> ApplSeqCheck {
+        let seq_num = appl_seq.appl_seq_num;
+
+        let Some(next) = self.next.get_mut(&appl_seq.appl_id) else {
+            self.next.insert(appl_seq.appl_id.clone(), seq_num + 1);
+            return ApplSeqCheck::InSequence;
+        };
+
+        if seq_num < *next {
+            if appl_seq.appl_resend_flag {
+                return ApplSeqCheck::InSequence;
+            }
+
+            return ApplSeqCheck::Duplicate;
+        }
+
+        let previous = appl_seq
+            .appl_last_seq_num
+            .unwrap_or(seq_num.saturating_sub(1));
+        let begin = *next;
+        *next = seq_num + 1;
+
+        if previous >= begin {
+            return ApplSeqCheck::Gap {
+                begin,
+                end: previous,
+            };
+        }
+
+        return ApplSeqCheck::InSequence;
+    }
+
+    pub fn get_next_expected(&self, appl_id: &str) -> Option<u64> {
+        return self.next.get(appl_id).copied();
+    }
+
+    /// Sets where a stream continues, e.g. after the counterparty reset it.
+    pub fn set_next_expected(&mut self, appl_id: &str, next: u64) {
+        self.next.insert(appl_id.to_string(), next);
+    }
+}
+
+/// Entry of NoApplIDs (1351) in an ApplicationMessageRequest.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplIdRange {
+    /// RefApplID (1355).
+    pub ref_appl_id: String,
+    /// ApplBegSeqNum (1182).
+    pub beg_seq_num: Option<u64>,
+    /// ApplEndSeqNum (1183).
+    pub end_seq_num: Option<u64>,
+}
+
+impl ApplIdRange {
+    pub fn new(ref_appl_id: &str, beg_seq_num: u64, end_seq_num: u64) -> Self {
+        return Self {
+            ref_appl_id: ref_appl_id.to_string(),
+            beg_seq_num: Some(beg_seq_num),
+            end_seq_num: Some(end_seq_num),
+        };
+    }
+}
+
+/// ApplicationMessageRequest (BW).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplicationMessageRequest {
+    /// ApplReqID (1346).
+    pub appl_req_id: String,
+    /// ApplReqType (1347).
+    pub appl_req_type: u32,
+    /// NoApplIDs (1351).
+    pub appl_ids: Vec<ApplIdRange>,
+    /// Text (58).
+    pub text: Option<String>,
+}
+
+impl ApplicationMessageRequest {
+    pub fn new(appl_req_id: &str, appl_req_type: u32) -> Self {
+        return Self {
+            appl_req_id: appl_req_id.to_string(),
+            appl_req_type,
+            appl_ids: vec![],
+            text: None,
+        };
+    }
+
+    /// A retransmission request for the gap reported by `ApplSeqTracker::check`.
+    pub fn for_gap(appl_req_id: &str, appl_id: &str, begin: u64, end: u64) -> Self {
+        let mut request = Self::new(appl_req_id, APPL_REQ_TYPE_RETRANSMISSION);
+        request.appl_ids.push(ApplIdRange::new(appl_id, begin, end));
+
+        return request;
+    }
+
+    /// The ApplicationMessageRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message =
+            FixMessageBuilder::new(begin_string, MSG_TYPE_APPLICATION_MESSAGE_REQUEST);
+        message.with_value(1346, self.appl_req_id.as_str());
+        message.with_value(1347, self.appl_req_type);
+
+        if !self.appl_ids.is_empty() {
+            message.with_value(TAG_NO_APPL_IDS, self.appl_ids.len());
+
+            for range in &self.appl_ids {
+                message.with_value(1355, range.ref_appl_id.as_str());
+                write_optional(&mut message, 1182, &range.beg_seq_num);
+                write_optional(&mut message, 1183, &range.end_seq_num);
+            }
+        }
+
+        write_optional(&mut message, 58, &self.text);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for ApplicationMessageRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_APPLICATION_MESSAGE_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let mut appl_ids = vec![];
+
+        for entry in read_group(&fields, TAG_NO_APPL_IDS, &APPL_ID_TAGS)? {
+            appl_ids.push(ApplIdRange {
+                ref_appl_id: required(get_string(&entry, 1355)?, 1355)?,
+                beg_seq_num: get_parsed(&entry, 1182)?,
+                end_seq_num: get_parsed(&entry, 1183)?,
+            });
+        }
+
+        return Ok(Self {
+            appl_req_id: required(get_string(&fields, 1346)?, 1346)?,
+            appl_req_type: required(get_parsed(&fields, 1347)?, 1347)?,
+            appl_ids,
+            text: get_string(&fields, 58)?,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_appl_seq_round_trip() {
+        let mut appl_seq = ApplicationSequencing::new("MD1", 42);
+        appl_seq.appl_last_seq_num = Some(40);
+
+        let mut message = FixMessageBuilder::new("FIXT.1.1", "X");
+        message.with_appl_seq(&appl_seq);
+
+        assert_eq!(Ok(Some(appl_seq)), message.get_appl_seq());
+        assert_eq!(
+            Ok(None),
+            FixMessageBuilder::new("FIXT.1.1", "X").get_appl_seq()
+        );
+    }
+
+    #[test]
+    fn test_appl_seq_tracker() {
+        let mut tracker = ApplSeqTracker::new();
+
+        assert_eq!(
+            ApplSeqCheck::InSequence,
+            tracker.check(&ApplicationSequencing::new("MD1", 10))
+        );
+        assert_eq!(
+            ApplSeqCheck::InSequence,
+            tracker.check(&ApplicationSequencing::new("MD1", 11))
+        );
+        assert_eq!(
+            ApplSeqCheck::Gap { begin: 12, end: 14 },
+            tracker.check(&ApplicationSequencing::new("MD1", 15))
+        );
+        assert_eq!(
+            ApplSeqCheck::Duplicate,
+            tracker.check(&ApplicationSequencing::new("MD1", 13))
+        );
+
+        let mut resent = ApplicationSequencing::new("MD1", 13);
+        resent.appl_resend_flag = true;
+        assert_eq!(ApplSeqCheck::InSequence, tracker.check(&resent));
+        assert_eq!(Some(16), tracker.get_next_expected("MD1"));
+
+        // A stream with holes by design names its previous number.
+        let mut sparse = ApplicationSequencing::new("MD1", 20);
+        sparse.appl_last_seq_num = Some(15);
+        assert_eq!(ApplSeqCheck::InSequence, tracker.check(&sparse));
+        assert_eq!(None, tracker.get_next_expected("MD2"));
+    }
+
+    #[test]
+    fn test_application_message_request_round_trip() {
+        let request = ApplicationMessageRequest::for_gap("REQ1", "MD1", 12, 14);
+
+        let message = request.to_message("FIXT.1.1");
+        assert_eq!(
+            "8=FIXT.1.1|9=55|35=BW|1346=REQ1|1347=0|1351=1|1355=MD1|1182=12|1183=14|10=213|",
+            message.to_string()
+        );
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(Ok(request), ApplicationMessageRequest::try_from(&parsed));
+    }
+}
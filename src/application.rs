@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use crate::{fields, FixMessageBuilder, Logout, SessionId};
+
+/// Returned by `FixApplication::to_app` to stop an outbound message from being sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoNotSend(pub String);
+
+/// Problem with the counterparty's messages that the session tolerated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionWarning {
+    /// SendingTime (52) differs from the local clock by more than `max_latency`.
+    SendingTimeSkew { seq_num: u64, skew: Duration },
+}
+
+/// Session-level news from the counterparty the application may have to act on,
+/// e.g. a password about to expire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// Logon carrying SessionStatus (1409), which FIXT sessions only send.
+    Logon {
+        session_status: fields::SessionStatus,
+        /// Text (58).
+        text: Option<String>,
+    },
+    /// Logout received, with the reason read from its SessionStatus or Text.
+    Logout(Logout),
+}
+
+/// Callbacks the engine invokes for every session it runs.
+///
+/// One application is shared by all sessions of an initiator or acceptor, so
+/// implementations keep their mutable state behind their own locks.
+pub trait FixApplication: Send + Sync {
+    fn on_logon(&self, _session_id: &SessionId) {}
+
+    fn on_logout(&self, _session_id: &SessionId) {}
+
+    /// Called for every inbound session-level message (Logon, Heartbeat, ResendRequest, ...).
+    fn on_admin_message(&self, _session_id: &SessionId, _message: &FixMessageBuilder) {}
+
+    /// Called for every inbound application message, in sequence number order.
+    fn on_app_message(&self, _session_id: &SessionId, _message: &FixMessageBuilder) {}
+
+    /// Called instead of `on_app_message` for messages flagged PossDupFlag (43) or
+    /// PossResend (97) that weren't delivered before. Copies of already delivered
+    /// messages are dropped by the session.
+    fn on_possible_duplicate(&self, session_id: &SessionId, message: &FixMessageBuilder) {
+        self.on_app_message(session_id, message);
+    }
+
+    /// Called before an application message is sent; returning an error drops it.
+    fn to_app(
+        &self,
+        _session_id: &SessionId,
+        _message: &mut FixMessageBuilder,
+    ) -> Result<(), DoNotSend> {
+        return Ok(());
+    }
+
+    /// Called for problems the session tolerated, e.g. with `SendingTimePolicy::Warn`.
+    fn on_session_warning(&self, _session_id: &SessionId, _warning: &SessionWarning) {}
+
+    /// Called for a Logon with SessionStatus (1409) once the session is logged on, and
+    /// for every Logout received, before the session disconnects.
+    fn on_session_event(&self, _session_id: &SessionId, _event: &SessionEvent) {}
+
+    /// Called when the session's `PresendValidator` refuses an application message.
+    fn on_send_rejected(
+        &self,
+        _session_id: &SessionId,
+        _message: &FixMessageBuilder,
+        _reason: &str,
+    ) {
+    }
+}
+
+/// Application that ignores every callback.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopApplication;
+
+impl FixApplication for NoopApplication {}
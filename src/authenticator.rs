@@ -0,0 +1,176 @@
+use std::{collections::HashMap, net::SocketAddr, sync::RwLock};
+
+use crate::{FixMessageBuilder, SessionId};
+
+/// What an acceptor knows about a counterparty when its Logon arrives.
+#[derive(Debug, Clone, Copy)]
+pub struct LogonRequest<'a> {
+    pub session_id: &'a SessionId,
+    pub logon: &'a FixMessageBuilder,
+    /// Peer address of the connection, when the transport knows it.
+    pub remote_address: Option<SocketAddr>,
+}
+
+impl LogonRequest<'_> {
+    /// Username (553).
+    pub fn get_username(&self) -> Option<String> {
+        return self.logon.get_string(553).ok().flatten();
+    }
+
+    /// Password (554).
+    pub fn get_password(&self) -> Option<String> {
+        return self.logon.get_string(554).ok().flatten();
+    }
+
+    /// NewPassword (925), sent to change the password on Logon.
+    pub fn get_new_password(&self) -> Option<String> {
+        return self.logon.get_string(925).ok().flatten();
+    }
+}
+
+/// What an authenticated counterparty may do for the rest of the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SessionPermissions {
+    /// Application messages from the counterparty are refused with a
+    /// BusinessMessageReject (35=j), e.g. for a drop copy session.
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Authorization {
+    Accept(SessionPermissions),
+    /// Answers the Logon with a Logout carrying this Text (58) and disconnects. On FIXT
+    /// a well-known wording, e.g. "Invalid username or password", adds its SessionStatus.
+    Reject(String),
+}
+
+/// Decides whether an acceptor takes an inbound Logon.
+///
+/// It's called before the Logon is answered, after the sequence number checks.
+pub trait Authenticator: Send + Sync + std::fmt::Debug {
+    fn authenticate(&self, request: &LogonRequest) -> Authorization;
+
+    /// Called when a Logon `authenticate` accepted carries NewPassword (925). An error
+    /// answers it with a Logout carrying the Text (58) and SessionStatus (1409)
+    /// NewSessionPasswordDoesNotComplyWithPolicy.
+    fn change_password(&self, _request: &LogonRequest, _new_password: &str) -> Result<(), String> {
+        return Err("Password change is not supported".to_string());
+    }
+}
+
+/// Accepts Logons whose Username (553) and Password (554) match the ones
+/// registered for the session; sessions without credentials are rejected.
+///
+/// A NewPassword (925) replaces the registered password unless it's empty or the same.
+#[derive(Debug, Default)]
+pub struct PasswordAuthenticator {
+    credentials: RwLock<HashMap<SessionId, (String, String, SessionPermissions)>>,
+}
+
+impl PasswordAuthenticator {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn add_credentials(
+        &mut self,
+        session_id: &SessionId,
+        username: &str,
+        password: &str,
+        permissions: SessionPermissions,
+    ) {
+        self.credentials.write().unwrap().insert(
+            session_id.clone(),
+            (username.to_string(), password.to_string(), permissions),
+        );
+    }
+
+    /// Current password of the session, after any change made on Logon.
+    pub fn get_password(&self, session_id: &SessionId) -> Option<String> {
+        return self
+            .credentials
+            .read()
+            .unwrap()
+            .get(session_id)
+            .map(|(_, password, _)| password.clone());
+    }
+}
+
+impl Authenticator for PasswordAuthenticator {
+    fn authenticate(&self, request: &LogonRequest) -> Authorization {
+        let credentials = self.credentials.read().unwrap();
+
+        let Some((username, password, permissions)) = credentials.get(request.session_id) else {
+            return Authorization::Reject("Unknown session".to_string());
+        };
+
+        if request.get_username().as_ref() != Some(username)
+            || request.get_password().as_ref() != Some(password)
+        {
+            return Authorization::Reject("Invalid username or password".to_string());
+        }
+
+        return Authorization::Accept(*permissions);
+    }
+
+    fn change_password(&self, request: &LogonRequest, new_password: &str) -> Result<(), String> {
+        let mut credentials = self.credentials.write().unwrap();
+
+        let Some((_, password, _)) = credentials.get_mut(request.session_id) else {
+            return Err("Unknown session".to_string());
+        };
+
+        if new_password.is_empty() || new_password == password {
+            return Err("New password does not comply with policy".to_string());
+        }
+
+        *password = new_password.to_string();
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_password_authenticator() {
+        let session_id = SessionId::new("FIX.4.4", "SELL", "BUY");
+        let mut authenticator = PasswordAuthenticator::new();
+        authenticator.add_credentials(
+            &session_id,
+            "buy",
+            "secret",
+            SessionPermissions { read_only: true },
+        );
+
+        let mut logon = FixMessageBuilder::new("FIX.4.4", "A");
+        logon.with_value(553, "buy");
+        logon.with_value(554, "wrong");
+
+        let mut request = LogonRequest {
+            session_id: &session_id,
+            logon: &logon,
+            remote_address: None,
+        };
+        assert_eq!(
+            Authorization::Reject("Invalid username or password".to_string()),
+            authenticator.authenticate(&request)
+        );
+
+        let mut logon = logon.clone();
+        logon.set_value(554, "secret");
+        request.logon = &logon;
+        assert_eq!(
+            Authorization::Accept(SessionPermissions { read_only: true }),
+            authenticator.authenticate(&request)
+        );
+
+        let other = SessionId::new("FIX.4.4", "SELL", "OTHER");
+        request.session_id = &other;
+        assert!(matches!(
+            authenticator.authenticate(&request),
+            Authorization::Reject(_)
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+/// Value of the BeginString (8) tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BeginString {
+    Fix40,
+    Fix41,
+    Fix42,
+    Fix43,
+    Fix44,
+    Fixt11,
+    Custom(String),
+}
+
+impl BeginString {
+    pub fn as_str(&self) -> &str {
+        return match self {
+            BeginString::Fix40 => "FIX.4.0",
+            BeginString::Fix41 => "FIX.4.1",
+            BeginString::Fix42 => "FIX.4.2",
+            BeginString::Fix43 => "FIX.4.3",
+            BeginString::Fix44 => "FIX.4.4",
+            BeginString::Fixt11 => "FIXT.1.1",
+            BeginString::Custom(value) => value,
+        };
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        return self.as_str().as_bytes();
+    }
+}
+
+impl From<&str> for BeginString {
+    fn from(value: &str) -> Self {
+        return match value {
+            "FIX.4.0" => BeginString::Fix40,
+            "FIX.4.1" => BeginString::Fix41,
+            "FIX.4.2" => BeginString::Fix42,
+            "FIX.4.3" => BeginString::Fix43,
+            "FIX.4.4" => BeginString::Fix44,
+            "FIXT.1.1" => BeginString::Fixt11,
+            value => BeginString::Custom(value.to_string()),
+        };
+    }
+}
+
+impl From<String> for BeginString {
+    fn from(value: String) -> Self {
+        return BeginString::from(value.as_str());
+    }
+}
+
+impl From<&[u8]> for BeginString {
+    fn from(value: &[u8]) -> Self {
+        return BeginString::from(String::from_utf8_lossy(value).as_ref());
+    }
+}
+
+impl From<&BeginString> for BeginString {
+    fn from(value: &BeginString) -> Self {
+        return value.clone();
+    }
+}
+
+impl fmt::Display for BeginString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(self.as_str());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for version in ["FIX.4.0", "FIX.4.2", "FIX.4.4", "FIXT.1.1", "FIX.5.0SP2"] {
+            assert_eq!(version, BeginString::from(version).as_str());
+        }
+
+        assert_eq!(BeginString::Fix44, BeginString::from("FIX.4.4"));
+        assert_eq!(
+            BeginString::Custom("FIX.5.0SP2".to_string()),
+            BeginString::from(b"FIX.5.0SP2".as_slice())
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Throughput drivers for measuring the codec on your own hardware and message mix
+//! (`bench` feature).
+//!
+//! Load a `BenchCorpus` from a captured stream or generated messages, then run the
+//! drivers with the configurations to compare, e.g. `ParseOptions` with and without
+//! checksum validation. Each driver does a warm-up round before timing `rounds` more.
+
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    find_fix_frame_len, parse_all, FixMessageBuilder, FixSerializeError, MessageGenerator,
+    ParseOptions,
+};
+
+/// Raw messages the parse drivers go through.
+#[derive(Debug, Clone, Default)]
+pub struct BenchCorpus {
+    messages: Vec<Vec<u8>>,
+}
+
+impl BenchCorpus {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Splits back-to-back messages, e.g. the contents of a capture or message store.
+    /// A trailing partial message is left out.
+    pub fn from_stream(buffer: &[u8]) -> Result<Self, FixSerializeError> {
+        let mut result = Self::new();
+        let mut consumed = 0;
+
+        while let Some(frame_len) = find_fix_frame_len(&buffer[consumed..])? {
+            result.push(buffer[consumed..consumed + frame_len].to_vec());
+            consumed += frame_len;
+        }
+
+        return Ok(result);
+    }
+
+    pub fn from_messages(messages: &[FixMessageBuilder]) -> Self {
+        return Self {
+            messages: messages.iter().map(FixMessageBuilder::as_bytes).collect(),
+        };
+    }
+
+    /// `count` messages of the generator's dictionary and message types.
+    pub fn from_generator(generator: &mut MessageGenerator, count: usize) -> Self {
+        let mut result = Self::new();
+
+        while result.len() < count {
+            let Some(message) = generator.next_message() else {
+                break;
+            };
+
+            result.push(message.as_bytes());
+        }
+
+        return result;
+    }
+
+    pub fn push(&mut self, message: Vec<u8>) {
+        self.messages.push(message);
+    }
+
+    pub fn get_messages(&self) -> &[Vec<u8>] {
+        return &self.messages;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.messages.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.messages.is_empty();
+    }
+
+    pub fn get_total_bytes(&self) -> usize {
+        return self.messages.iter().map(Vec::len).sum();
+    }
+
+    fn to_stream(&self) -> Vec<u8> {
+        return self.messages.concat();
+    }
+}
+
+/// Outcome of a driver run, for the timed rounds only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchReport {
+    pub messages: usize,
+    pub bytes: usize,
+    /// Messages that failed to parse; they still count towards `messages`.
+    pub failures: usize,
+    pub elapsed: Duration,
+}
+
+impl BenchReport {
+    pub fn get_messages_per_second(&self) -> f64 {
+        return self.messages as f64 / self.get_seconds();
+    }
+
+    pub fn get_megabytes_per_second(&self) -> f64 {
+        return self.bytes as f64 / 1_000_000.0 / self.get_seconds();
+    }
+
+    pub fn get_nanos_per_message(&self) -> f64 {
+        if self.messages == 0 {
+            return 0.0;
+        }
+
+        return self.elapsed.as_nanos() as f64 / self.messages as f64;
+    }
+
+    fn get_seconds(&self) -> f64 {
+        return self.elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{} messages ({} failed), {} bytes in {:?}: {:.0} msg/s, {:.1} MB/s, {:.0} ns/msg",
+            self.messages,
+            self.failures,
+            self.bytes,
+            self.elapsed,
+            self.get_messages_per_second(),
+            self.get_megabytes_per_second(),
+            self.get_nanos_per_message()
+        );
+    }
+}
+
+/// Parses every message of the corpus one by one with `options`.
+pub fn bench_parse(corpus: &BenchCorpus, options: &ParseOptions, rounds: usize) -> BenchReport {
+    return run(corpus.len(), corpus.get_total_bytes(), rounds, || {
+        let mut failures = 0;
+
+        for message in corpus.get_messages() {
+            let result = FixMessageBuilder::from_bytes_with_options(black_box(message), options);
+
+            if black_box(result).is_err() {
+                failures += 1;
+            }
+        }
+
+        return failures;
+    });
+}
+
+/// Parses the corpus as one buffer with `parse_all`, as a transport would after a read.
+/// Parsing stops at the first failure, which makes the rest of a round count as failed.
+pub fn bench_parse_stream(
+    corpus: &BenchCorpus,
+    check_sum_validation: bool,
+    rounds: usize,
+) -> BenchReport {
+    let stream = corpus.to_stream();
+
+    return run(corpus.len(), stream.len(), rounds, || {
+        let (messages, _, _) = black_box(parse_all(black_box(&stream), check_sum_validation));
+        return corpus.len() - messages.len();
+    });
+}
+
+/// Serializes every message, BodyLength and CheckSum included.
+pub fn bench_serialize(messages: &[FixMessageBuilder], rounds: usize) -> BenchReport {
+    let bytes = messages
+        .iter()
+        .map(|message| message.as_bytes().len())
+        .sum();
+
+    return run(messages.len(), bytes, rounds, || {
+        for message in messages {
+            black_box(black_box(message).as_bytes());
+        }
+
+        return 0;
+    });
+}
+
+fn run(
+    messages: usize,
+    bytes: usize,
+    rounds: usize,
+    mut round: impl FnMut() -> usize,
+) -> BenchReport {
+    round();
+
+    let mut failures = 0;
+    let start = Instant::now();
+
+    for _ in 0..rounds {
+        failures += round();
+    }
+
+    return BenchReport {
+        messages: messages * rounds,
+        bytes: bytes * rounds,
+        failures,
+        elapsed: start.elapsed(),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FixDictionary;
+
+    #[test]
+    fn test_bench_drivers() {
+        let mut generator = MessageGenerator::new(FixDictionary::fix44(), 7);
+        generator.with_msg_types(&["D", "8"]);
+
+        let corpus = BenchCorpus::from_generator(&mut generator, 20);
+        assert_eq!(20, corpus.len());
+
+        let mut stream = corpus.to_stream();
+        assert_eq!(20, BenchCorpus::from_stream(&stream).unwrap().len());
+
+        stream.extend_from_slice(b"8=FIX.4.4\x019=5");
+        assert_eq!(20, BenchCorpus::from_stream(&stream).unwrap().len());
+
+        let report = bench_parse(&corpus, &ParseOptions::default(), 3);
+        assert_eq!(60, report.messages);
+        assert_eq!(corpus.get_total_bytes() * 3, report.bytes);
+        assert_eq!(0, report.failures);
+        assert!(report.to_string().starts_with("60 messages (0 failed)"));
+
+        assert_eq!(0, bench_parse_stream(&corpus, true, 2).failures);
+
+        let mut broken = corpus.clone();
+        broken.push(b"8=FIX.4.4\x019=5\x0135=0\x0110=000\x01".to_vec());
+        assert_eq!(
+            2,
+            bench_parse(&broken, &ParseOptions::default(), 2).failures
+        );
+
+        let messages: Vec<FixMessageBuilder> =
+            (0..5).map(|_| generator.next_message().unwrap()).collect();
+        assert_eq!(10, bench_serialize(&messages, 2).messages);
+    }
+}
@@ -0,0 +1,211 @@
+//! Prints the FIX messages found in log files or stdin.
+//!
+//! ```text
+//! fix-cat [--pretty] [--dictionary FIX44.xml] [--delimiter '|'] [--type D] [--tag 55=IBM] [FILE...]
+//! ```
+
+#![allow(clippy::needless_return)]
+
+use std::{
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+use rust_fix::{find_fix_frame_len, FixDictionary, FixMessageBuilder};
+
+const USAGE: &str = "usage: fix-cat [options] [FILE...]
+
+Reads raw FIX messages from the files (stdin when none are given), skipping
+anything around them such as log timestamps, and prints one message per line
+with SOH shown as '|'.
+
+options:
+  -p, --pretty            one field per line with tag names and value descriptions
+  -d, --dictionary FILE   QuickFIX data dictionary used by --pretty (built-in FIX 4.4 by default)
+      --delimiter CHAR    field delimiter of the input when it isn't SOH, e.g. '|'
+  -t, --type MSGTYPE      only messages of this MsgType (35); may be repeated
+      --tag TAG=VALUE     only messages where TAG has VALUE; may be repeated
+  -h, --help              print this help";
+
+#[derive(Debug, Default)]
+struct Options {
+    pretty: bool,
+    dictionary: Option<String>,
+    delimiter: Option<u8>,
+    message_types: Vec<String>,
+    tags: Vec<(u32, String)>,
+    files: Vec<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                return args
+                    .next()
+                    .ok_or_else(|| format!("{} expects a value", name));
+            };
+
+            match arg.as_str() {
+                "-p" | "--pretty" => result.pretty = true,
+                "-d" | "--dictionary" => result.dictionary = Some(value(&arg)?),
+                "--delimiter" => {
+                    let delimiter = value(&arg)?;
+                    let [byte] = delimiter.as_bytes() else {
+                        return Err("--delimiter expects a single character".to_string());
+                    };
+                    result.delimiter = Some(*byte);
+                }
+                "-t" | "--type" => result.message_types.push(value(&arg)?),
+                "--tag" => {
+                    let filter = value(&arg)?;
+                    let (tag, tag_value) = filter
+                        .split_once('=')
+                        .and_then(|(tag, tag_value)| Some((tag.parse().ok()?, tag_value)))
+                        .ok_or_else(|| format!("--tag expects TAG=VALUE, got {}", filter))?;
+                    result.tags.push((tag, tag_value.to_string()));
+                }
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                _ if arg.starts_with('-') && arg != "-" => {
+                    return Err(format!("unknown option {}\n\n{}", arg, USAGE));
+                }
+                _ => result.files.push(arg),
+            }
+        }
+
+        return Ok(result);
+    }
+
+    fn is_match(&self, message: &FixMessageBuilder) -> bool {
+        if !self.message_types.is_empty()
+            && !self
+                .message_types
+                .iter()
+                .any(|message_type| message_type.as_bytes() == message.get_message_type())
+        {
+            return false;
+        }
+
+        return self
+            .tags
+            .iter()
+            .all(|(tag, value)| message.get_all(*tag).contains(&value.as_bytes()));
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let dictionary = match &options.dictionary {
+        Some(path) => match FixDictionary::from_quickfix_file(path) {
+            Ok(dictionary) => dictionary,
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => FixDictionary::fix44(),
+    };
+
+    let mut inputs = options.files.clone();
+
+    if inputs.is_empty() {
+        inputs.push("-".to_string());
+    }
+
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+
+    for input in inputs {
+        let mut buffer = vec![];
+
+        let result = match input.as_str() {
+            "-" => io::stdin().read_to_end(&mut buffer),
+            path => std::fs::File::open(path).and_then(|mut file| file.read_to_end(&mut buffer)),
+        };
+
+        if let Err(err) = result {
+            eprintln!("{}: {}", input, err);
+            return ExitCode::FAILURE;
+        }
+
+        if let Some(delimiter) = options.delimiter {
+            for byte in buffer.iter_mut().filter(|byte| **byte == delimiter) {
+                *byte = 1;
+            }
+        }
+
+        if let Err(err) = print_messages(&buffer, &options, &dictionary, &mut output) {
+            // Stop quietly when the reader went away, e.g. `fix-cat log | head`.
+            if err.kind() == io::ErrorKind::BrokenPipe {
+                return ExitCode::SUCCESS;
+            }
+
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn print_messages(
+    buffer: &[u8],
+    options: &Options,
+    dictionary: &FixDictionary,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    let mut position = 0;
+
+    while let Some(start) = find_message_start(buffer, position) {
+        let frame = &buffer[start..];
+
+        let frame_len = match find_fix_frame_len(frame) {
+            Ok(Some(frame_len)) => frame_len,
+            _ => {
+                position = start + 1;
+                continue;
+            }
+        };
+
+        let frame = &frame[..frame_len];
+        position = start + frame_len;
+
+        let Ok(message) = FixMessageBuilder::from_bytes(frame, false) else {
+            continue;
+        };
+
+        if !options.is_match(&message) {
+            continue;
+        }
+
+        if options.pretty {
+            writeln!(output, "{}", dictionary.describe(frame))?;
+        } else {
+            let line: Vec<u8> = frame
+                .iter()
+                .map(|byte| if *byte == 1 { b'|' } else { *byte })
+                .collect();
+            output.write_all(&line)?;
+            output.write_all(b"\n")?;
+        }
+    }
+
+    return output.flush();
+}
+
+fn find_message_start(buffer: &[u8], from: usize) -> Option<usize> {
+    return buffer
+        .get(from..)?
+        .windows(5)
+        .position(|window| window == b"8=FIX")
+        .map(|position| from + position);
+}
@@ -0,0 +1,140 @@
+//! Infers a QuickFIX data dictionary from the messages of session journals, as a
+//! starting point when a counterparty provides no machine-readable spec.
+//!
+//! ```text
+//! fix-infer-dictionary [--dictionary FIX44.xml] [--max-values N] JOURNAL...
+//! ```
+
+#![allow(clippy::needless_return)]
+
+use std::{
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use rust_fix::{FixDictionary, JournalReader, ObservedDictionary};
+
+const USAGE: &str = "usage: fix-infer-dictionary [options] JOURNAL...
+
+Reads every message of the journals and prints a QuickFIX data dictionary with
+the tags seen per MsgType and the value sets of coded fields. Fields present in
+every message of a type are marked required; repeating groups are listed flat.
+
+options:
+  -d, --dictionary FILE   QuickFIX data dictionary to take names and types of
+                          known tags from (built-in FIX 4.4 subset by default)
+      --max-values N      largest value set listed as coded (default 32)
+  -h, --help              print this help";
+
+#[derive(Debug, Default)]
+struct Options {
+    dictionary: Option<String>,
+    max_values: Option<usize>,
+    files: Vec<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                return args
+                    .next()
+                    .ok_or_else(|| format!("{} expects a value", name));
+            };
+
+            match arg.as_str() {
+                "-d" | "--dictionary" => result.dictionary = Some(value(&arg)?),
+                "--max-values" => {
+                    let max_values = value(&arg)?;
+                    result.max_values = Some(
+                        max_values
+                            .parse()
+                            .map_err(|_| format!("invalid --max-values {}", max_values))?,
+                    );
+                }
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                _ if arg.starts_with('-') => {
+                    return Err(format!("unknown option {}\n\n{}", arg, USAGE));
+                }
+                _ => result.files.push(arg),
+            }
+        }
+
+        if result.files.is_empty() {
+            return Err(USAGE.to_string());
+        }
+
+        return Ok(result);
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let reference = match &options.dictionary {
+        Some(path) => match FixDictionary::from_quickfix_file(path) {
+            Ok(dictionary) => dictionary,
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => FixDictionary::fix44(),
+    };
+
+    let mut observed = ObservedDictionary::new();
+
+    if let Some(max_values) = options.max_values {
+        observed.with_max_values(max_values);
+    }
+
+    for path in &options.files {
+        if let Err(err) = observe_journal(path, &reference, &mut observed) {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    eprintln!(
+        "{} messages, {} message types",
+        observed.len(),
+        observed.get_msg_types().len()
+    );
+
+    let xml = observed.to_dictionary(&reference).to_quickfix_xml();
+    let mut output = io::stdout().lock();
+
+    if let Err(err) = output
+        .write_all(xml.as_bytes())
+        .and_then(|_| output.flush())
+    {
+        if err.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("{}", err);
+        }
+
+        return ExitCode::FAILURE;
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn observe_journal(
+    path: &str,
+    reference: &FixDictionary,
+    observed: &mut ObservedDictionary,
+) -> io::Result<()> {
+    for entry in JournalReader::open(path)?.replay(false) {
+        let (_, message) = entry?;
+        observed.observe(&message, reference);
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,221 @@
+//! Validates every FIX message of a file, e.g. counterparty samples before go-live.
+//!
+//! ```text
+//! fix-validate [--dictionary FIX44.xml] [--delimiter '|'] [--no-dictionary] FILE...
+//! ```
+
+#![allow(clippy::needless_return)]
+
+use std::{
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+use rust_fix::{split_raw_fields, validate_frame, FixDictionary};
+
+const USAGE: &str = "usage: fix-validate [options] [FILE...]
+
+Checks BodyLength, CheckSum and the dictionary rules of every message found in
+the files (stdin when none are given) and prints one line per problem:
+
+  FILE:LINE:OFFSET: MSGTYPE: error
+
+where OFFSET is the byte offset of the offending field in the file. Exits with
+status 1 when any message is invalid.
+
+options:
+  -d, --dictionary FILE   QuickFIX data dictionary (built-in FIX 4.4 subset by default)
+      --no-dictionary     only check the framing, BodyLength and CheckSum
+      --delimiter CHAR    field delimiter of the input when it isn't SOH, e.g. '|'
+  -h, --help              print this help";
+
+#[derive(Debug, Default)]
+struct Options {
+    dictionary: Option<String>,
+    no_dictionary: bool,
+    delimiter: Option<u8>,
+    files: Vec<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                return args
+                    .next()
+                    .ok_or_else(|| format!("{} expects a value", name));
+            };
+
+            match arg.as_str() {
+                "-d" | "--dictionary" => result.dictionary = Some(value(&arg)?),
+                "--no-dictionary" => result.no_dictionary = true,
+                "--delimiter" => {
+                    let delimiter = value(&arg)?;
+                    let [byte] = delimiter.as_bytes() else {
+                        return Err("--delimiter expects a single character".to_string());
+                    };
+                    result.delimiter = Some(*byte);
+                }
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                _ if arg.starts_with('-') && arg != "-" => {
+                    return Err(format!("unknown option {}\n\n{}", arg, USAGE));
+                }
+                _ => result.files.push(arg),
+            }
+        }
+
+        return Ok(result);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    messages: usize,
+    invalid: usize,
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let dictionary = match (&options.dictionary, options.no_dictionary) {
+        (_, true) => None,
+        (Some(path), false) => match FixDictionary::from_quickfix_file(path) {
+            Ok(dictionary) => Some(dictionary),
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, false) => Some(FixDictionary::fix44()),
+    };
+
+    let mut inputs = options.files.clone();
+
+    if inputs.is_empty() {
+        inputs.push("-".to_string());
+    }
+
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+    let mut summary = Summary::default();
+
+    for input in inputs {
+        let mut buffer = vec![];
+
+        let result = match input.as_str() {
+            "-" => io::stdin().read_to_end(&mut buffer),
+            path => std::fs::File::open(path).and_then(|mut file| file.read_to_end(&mut buffer)),
+        };
+
+        if let Err(err) = result {
+            eprintln!("{}: {}", input, err);
+            return ExitCode::FAILURE;
+        }
+
+        if let Some(delimiter) = options.delimiter {
+            for byte in buffer.iter_mut().filter(|byte| **byte == delimiter) {
+                *byte = 1;
+            }
+        }
+
+        let result = validate_buffer(
+            &input,
+            &buffer,
+            dictionary.as_ref(),
+            &mut summary,
+            &mut output,
+        );
+
+        if let Err(err) = result {
+            if err.kind() != io::ErrorKind::BrokenPipe {
+                eprintln!("{}", err);
+            }
+
+            return ExitCode::FAILURE;
+        }
+    }
+
+    eprintln!("{} messages, {} invalid", summary.messages, summary.invalid);
+
+    if summary.invalid > 0 {
+        return ExitCode::FAILURE;
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn validate_buffer(
+    name: &str,
+    buffer: &[u8],
+    dictionary: Option<&FixDictionary>,
+    summary: &mut Summary,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    let mut line_start = 0;
+
+    for (line_index, raw_line) in buffer.split(|byte| *byte == b'\n').enumerate() {
+        let line = raw_line.strip_suffix(b"\r").unwrap_or(raw_line);
+
+        // A message runs from its `8=FIX` to the next one or the end of the line, so
+        // a wrong BodyLength is reported instead of breaking the framing.
+        let starts = find_message_starts(line);
+
+        for (index, start) in starts.iter().enumerate() {
+            let end = starts.get(index + 1).copied().unwrap_or(line.len());
+            let message = &line[*start..end];
+
+            let mut errors = validate_frame(message);
+
+            if let Some(dictionary) = dictionary {
+                errors.extend(dictionary.validate(message));
+            }
+
+            summary.messages += 1;
+
+            if errors.is_empty() {
+                continue;
+            }
+
+            summary.invalid += 1;
+
+            let message_type = split_raw_fields(message)
+                .into_iter()
+                .find(|field| field.tag == b"35")
+                .map(|field| String::from_utf8_lossy(field.value).to_string())
+                .unwrap_or_else(|| "?".to_string());
+
+            for error in errors {
+                writeln!(
+                    output,
+                    "{}:{}:{}: {}: {}",
+                    name,
+                    line_index + 1,
+                    line_start + start + error.offset,
+                    message_type,
+                    error
+                )?;
+            }
+        }
+
+        line_start += raw_line.len() + 1;
+    }
+
+    return output.flush();
+}
+
+fn find_message_starts(line: &[u8]) -> Vec<usize> {
+    return line
+        .windows(5)
+        .enumerate()
+        .filter(|(_, window)| *window == b"8=FIX")
+        .map(|(position, _)| position)
+        .collect();
+}
@@ -0,0 +1,291 @@
+use std::{io, time::SystemTime};
+
+use crate::{
+    fields::{ExecType, FixField, Side},
+    format_utc_timestamp,
+    group::{get_coded, get_string, get_timestamp, required, GroupEntry},
+    FieldParseError, FixMessageBuilder,
+};
+
+const MSG_TYPE_EXECUTION_REPORT: &[u8] = b"8";
+
+/// Columns of `TradeBlotter::write_csv`, in `TradeRecord::to_csv_row` order.
+pub const TRADE_RECORD_CSV_HEADER: &str = "exec_id,exec_type,exec_ref_id,order_id,cl_ord_id,orig_cl_ord_id,account,symbol,security_id,side,last_qty,last_px,currency,commission,comm_type,trade_date,transact_time,sending_time";
+
+/// One execution of an ExecutionReport (35=8) stream, flattened for back-office
+/// ingestion.
+///
+/// Quantities, prices and fees are kept as written; timestamps are output in the FIX
+/// UTCTimestamp format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeRecord {
+    /// ExecID (17).
+    pub exec_id: String,
+    /// `Trade` for a fill (or `PartialFill` and `Fill` before FIX 4.4), `TradeCorrect`
+    /// or `TradeCancel` for a correction or bust of the execution in `exec_ref_id`.
+    pub exec_type: ExecType,
+    /// ExecRefID (19).
+    pub exec_ref_id: Option<String>,
+    /// OrderID (37).
+    pub order_id: String,
+    /// ClOrdID (11).
+    pub cl_ord_id: Option<String>,
+    /// OrigClOrdID (41), the previous link of the order chain after a replace.
+    pub orig_cl_ord_id: Option<String>,
+    /// Account (1).
+    pub account: Option<String>,
+    /// Symbol (55).
+    pub symbol: Option<String>,
+    /// SecurityID (48).
+    pub security_id: Option<String>,
+    pub side: Side,
+    /// LastQty (32).
+    pub last_qty: String,
+    /// LastPx (31).
+    pub last_px: String,
+    /// Currency (15).
+    pub currency: Option<String>,
+    /// Commission (12).
+    pub commission: Option<String>,
+    /// CommType (13).
+    pub comm_type: Option<String>,
+    /// TradeDate (75).
+    pub trade_date: Option<String>,
+    /// TransactTime (60).
+    pub transact_time: Option<SystemTime>,
+    /// SendingTime (52).
+    pub sending_time: Option<SystemTime>,
+}
+
+impl TradeRecord {
+    /// The execution of `message`, or `None` when it is not an ExecutionReport for a
+    /// fill, correction or bust.
+    pub fn from_execution_report(
+        message: &FixMessageBuilder,
+    ) -> Result<Option<Self>, FieldParseError> {
+        if message.get_message_type() != MSG_TYPE_EXECUTION_REPORT {
+            return Ok(None);
+        }
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        let exec_type = required(get_coded(&fields, ExecType::TAG)?, ExecType::TAG)?;
+
+        if !matches!(
+            exec_type,
+            ExecType::Trade
+                | ExecType::PartialFill
+                | ExecType::Fill
+                | ExecType::TradeCorrect
+                | ExecType::TradeCancel
+        ) {
+            return Ok(None);
+        }
+
+        return Ok(Some(Self {
+            exec_id: required(get_string(&fields, 17)?, 17)?,
+            exec_type,
+            exec_ref_id: get_string(&fields, 19)?,
+            order_id: required(get_string(&fields, 37)?, 37)?,
+            cl_ord_id: get_string(&fields, 11)?,
+            orig_cl_ord_id: get_string(&fields, 41)?,
+            account: get_string(&fields, 1)?,
+            symbol: get_string(&fields, 55)?,
+            security_id: get_string(&fields, 48)?,
+            side: required(get_coded(&fields, Side::TAG)?, Side::TAG)?,
+            last_qty: required(get_string(&fields, 32)?, 32)?,
+            last_px: required(get_string(&fields, 31)?, 31)?,
+            currency: get_string(&fields, 15)?,
+            commission: get_string(&fields, 12)?,
+            comm_type: get_string(&fields, 13)?,
+            trade_date: get_string(&fields, 75)?,
+            transact_time: get_timestamp(&fields, 60)?,
+            sending_time: message.get_sending_time()?,
+        }));
+    }
+
+    /// The record as a CSV line without the line break, quoted where needed.
+    pub fn to_csv_row(&self) -> String {
+        return self
+            .get_columns()
+            .iter()
+            .map(|value| match value {
+                Some(value) if value.contains([',', '"', '\n', '\r']) => {
+                    format!("\"{}\"", value.replace('"', "\"\""))
+                }
+                Some(value) => value.clone(),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+    }
+
+    /// The record as a JSON object, with `null` for absent fields.
+    pub fn to_json(&self) -> String {
+        let members: Vec<String> = TRADE_RECORD_CSV_HEADER
+            .split(',')
+            .zip(self.get_columns())
+            .map(|(name, value)| match value {
+                Some(value) => format!("\"{}\":\"{}\"", name, escape_json(&value)),
+                None => format!("\"{}\":null", name),
+            })
+            .collect();
+
+        return format!("{{{}}}", members.join(","));
+    }
+
+    fn get_columns(&self) -> Vec<Option<String>> {
+        return vec![
+            Some(self.exec_id.clone()),
+            Some(format!("{:?}", self.exec_type)),
+            self.exec_ref_id.clone(),
+            Some(self.order_id.clone()),
+            self.cl_ord_id.clone(),
+            self.orig_cl_ord_id.clone(),
+            self.account.clone(),
+            self.symbol.clone(),
+            self.security_id.clone(),
+            Some(format!("{:?}", self.side)),
+            Some(self.last_qty.clone()),
+            Some(self.last_px.clone()),
+            self.currency.clone(),
+            self.commission.clone(),
+            self.comm_type.clone(),
+            self.trade_date.clone(),
+            self.transact_time.map(format_utc_timestamp),
+            self.sending_time.map(format_utc_timestamp),
+        ];
+    }
+}
+
+/// Trade records collected from ExecutionReports, in arrival order.
+#[derive(Debug, Clone, Default)]
+pub struct TradeBlotter {
+    records: Vec<TradeRecord>,
+}
+
+impl TradeBlotter {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Records the execution of `message`, returning whether it was one; other
+    /// messages are ignored.
+    pub fn push(&mut self, message: &FixMessageBuilder) -> Result<bool, FieldParseError> {
+        let Some(record) = TradeRecord::from_execution_report(message)? else {
+            return Ok(false);
+        };
+
+        self.records.push(record);
+
+        return Ok(true);
+    }
+
+    pub fn get_records(&self) -> &[TradeRecord] {
+        return &self.records;
+    }
+
+    /// Header line and one line per record.
+    pub fn write_csv(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        writeln!(writer, "{}", TRADE_RECORD_CSV_HEADER)?;
+
+        for record in &self.records {
+            writeln!(writer, "{}", record.to_csv_row())?;
+        }
+
+        return Ok(());
+    }
+
+    /// One JSON object per line.
+    pub fn write_json_lines(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        for record in &self.records {
+            writeln!(writer, "{}", record.to_json())?;
+        }
+
+        return Ok(());
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        match char {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            char if (char as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", char as u32)),
+            char => result.push(char),
+        }
+    }
+
+    return result;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fill(exec_id: &str, exec_type: ExecType) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "8");
+        message.with_value(52, "20240105-14:30:00.000");
+        message.with_value(37, "O1");
+        message.with_value(11, "C2");
+        message.with_value(41, "C1");
+        message.with_value(17, exec_id);
+        message.with_field(exec_type);
+        message.with_value(55, "IBM, Inc");
+        message.with_field(Side::Buy);
+        message.with_value(32, "100");
+        message.with_value(31, "143.25");
+        message.with_value(12, "1.5");
+        message.with_value(13, "3");
+        message.with_value(60, "20240105-14:29:59.876");
+
+        return message;
+    }
+
+    #[test]
+    fn test_blotter() {
+        let mut blotter = TradeBlotter::new();
+
+        assert_eq!(Ok(true), blotter.push(&fill("E1", ExecType::Trade)));
+        assert_eq!(Ok(false), blotter.push(&fill("E2", ExecType::New)));
+
+        let mut bust = fill("E3", ExecType::TradeCancel);
+        bust.with_value(19, "E1");
+        assert_eq!(Ok(true), blotter.push(&bust));
+
+        let records = blotter.get_records();
+        assert_eq!(2, records.len());
+        assert_eq!(Some("C1".to_string()), records[0].orig_cl_ord_id);
+        assert_eq!(Some("E1".to_string()), records[1].exec_ref_id);
+
+        let mut csv = vec![];
+        blotter.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            "E1,Trade,,O1,C2,C1,,\"IBM, Inc\",,Buy,100,143.25,,1.5,3,,20240105-14:29:59.876,20240105-14:30:00.000",
+            csv.lines().nth(1).unwrap()
+        );
+
+        let mut json = vec![];
+        blotter.write_json_lines(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json
+            .lines()
+            .next()
+            .unwrap()
+            .starts_with("{\"exec_id\":\"E1\",\"exec_type\":\"Trade\",\"exec_ref_id\":null,"));
+        assert_eq!(2, json.lines().count());
+
+        let mut broken = fill("E4", ExecType::Trade);
+        broken.remove_value(31);
+        assert_eq!(
+            Err(FieldParseError::Missing { tag: 31 }),
+            blotter.push(&broken)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use crate::FixMessageBuilder;
+
+const MSG_TYPE_EXECUTION_REPORT: &[u8] = b"8";
+
+/// Follows the ClOrdID (11) → OrigClOrdID (41) links left by cancel/replace requests,
+/// so any ExecutionReport can be traced back to the ClOrdID of the original order.
+///
+/// Feed it the order messages and executions in any order; links learned later still
+/// apply to reports seen earlier.
+#[derive(Debug, Clone, Default)]
+pub struct ClOrdIdResolver {
+    orig_cl_ord_ids: HashMap<String, String>,
+    cl_ord_ids_by_order_id: HashMap<String, String>,
+}
+
+impl ClOrdIdResolver {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Learns from an OrderCancelReplaceRequest (G), OrderCancelRequest (F),
+    /// ExecutionReport (8) or OrderCancelReject (9): the ClOrdID/OrigClOrdID link when
+    /// both are present, and the OrderID (37) of a report.
+    pub fn observe(&mut self, message: &FixMessageBuilder) {
+        let cl_ord_id = get_string(message, 11);
+
+        if let (Some(cl_ord_id), Some(orig_cl_ord_id)) = (&cl_ord_id, get_string(message, 41)) {
+            self.link(cl_ord_id, &orig_cl_ord_id);
+        }
+
+        if message.get_message_type() == MSG_TYPE_EXECUTION_REPORT {
+            if let (Some(cl_ord_id), Some(order_id)) = (cl_ord_id, get_string(message, 37)) {
+                self.cl_ord_ids_by_order_id.insert(order_id, cl_ord_id);
+            }
+        }
+    }
+
+    /// Records that `cl_ord_id` replaced `orig_cl_ord_id`.
+    pub fn link(&mut self, cl_ord_id: &str, orig_cl_ord_id: &str) {
+        if cl_ord_id != orig_cl_ord_id {
+            self.orig_cl_ord_ids
+                .insert(cl_ord_id.to_string(), orig_cl_ord_id.to_string());
+        }
+    }
+
+    /// ClOrdID of the original order `cl_ord_id` descends from; `cl_ord_id` itself when
+    /// it was never replaced.
+    pub fn get_root(&self, cl_ord_id: &str) -> String {
+        return self.get_chain(cl_ord_id).swap_remove(0);
+    }
+
+    /// Every ClOrdID from the original order to `cl_ord_id`. A link back into the chain
+    /// ends it rather than looping.
+    pub fn get_chain(&self, cl_ord_id: &str) -> Vec<String> {
+        let mut result = vec![cl_ord_id.to_string()];
+
+        while let Some(orig_cl_ord_id) = self.orig_cl_ord_ids.get(&result[result.len() - 1]) {
+            if result.contains(orig_cl_ord_id) {
+                break;
+            }
+
+            result.push(orig_cl_ord_id.clone());
+        }
+
+        result.reverse();
+
+        return result;
+    }
+
+    /// ClOrdID of the original order of an ExecutionReport, found through its ClOrdID
+    /// or, for reports without one such as unsolicited cancels, through its OrderID.
+    pub fn resolve(&self, report: &FixMessageBuilder) -> Option<String> {
+        let cl_ord_id = match get_string(report, 11) {
+            Some(cl_ord_id) => cl_ord_id,
+            None => self
+                .cl_ord_ids_by_order_id
+                .get(&get_string(report, 37)?)?
+                .clone(),
+        };
+
+        return Some(self.get_root(&cl_ord_id));
+    }
+}
+
+fn get_string(message: &FixMessageBuilder, tag: u32) -> Option<String> {
+    return message.get_string(tag).ok().flatten();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message(msg_type: &str, fields: &[(u32, &str)]) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new("FIX.4.4", msg_type);
+
+        for (tag, value) in fields {
+            message.with_value(*tag, *value);
+        }
+
+        return message;
+    }
+
+    #[test]
+    fn test_resolve_replace_chain() {
+        let mut resolver = ClOrdIdResolver::new();
+
+        let new_ack = message("8", &[(37, "O1"), (11, "C1"), (150, "0")]);
+        let second_replace = message("G", &[(11, "C3"), (41, "C2")]);
+        let first_replace = message("G", &[(11, "C2"), (41, "C1")]);
+        let fill = message("8", &[(37, "O1"), (11, "C3"), (41, "C2"), (150, "F")]);
+        let unsolicited_cancel = message("8", &[(37, "O1"), (150, "4")]);
+
+        for message in [&new_ack, &second_replace, &first_replace, &fill] {
+            resolver.observe(message);
+        }
+
+        assert_eq!(vec!["C1", "C2", "C3"], resolver.get_chain("C3"));
+        assert_eq!(Some("C1".to_string()), resolver.resolve(&fill));
+        assert_eq!(
+            Some("C1".to_string()),
+            resolver.resolve(&unsolicited_cancel)
+        );
+        assert_eq!("C9", resolver.get_root("C9"));
+        assert_eq!(None, resolver.resolve(&message("8", &[(37, "O2")])));
+
+        resolver.link("C1", "C3");
+        assert_eq!(vec!["C1", "C2", "C3"], resolver.get_chain("C3"));
+    }
+}
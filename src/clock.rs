@@ -0,0 +1,266 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Source of the current time used to stamp SendingTime (52).
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        return SystemTime::now();
+    }
+}
+
+/// Clock that only moves when told to, for tests.
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+}
+
+impl ManualClock {
+    pub fn new(now: SystemTime) -> Self {
+        return Self {
+            now: Mutex::new(now),
+        };
+    }
+
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        return *self.now.lock().unwrap();
+    }
+}
+
+/// Fractional digits of the UTCTimestamps a session stamps, QuickFIX's
+/// `TimestampPrecision` of 0, 3, 6 or 9. Venues differ on what they accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(try_from = "u32"))]
+pub enum TimestampPrecision {
+    Seconds,
+    #[default]
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimestampPrecision {
+    pub fn get_digits(&self) -> u32 {
+        return match self {
+            TimestampPrecision::Seconds => 0,
+            TimestampPrecision::Millis => 3,
+            TimestampPrecision::Micros => 6,
+            TimestampPrecision::Nanos => 9,
+        };
+    }
+}
+
+impl TryFrom<u32> for TimestampPrecision {
+    type Error = String;
+
+    fn try_from(digits: u32) -> Result<Self, Self::Error> {
+        return match digits {
+            0 => Ok(TimestampPrecision::Seconds),
+            3 => Ok(TimestampPrecision::Millis),
+            6 => Ok(TimestampPrecision::Micros),
+            9 => Ok(TimestampPrecision::Nanos),
+            _ => Err(format!(
+                "timestamp precision of {} digits, expected 0, 3, 6 or 9",
+                digits
+            )),
+        };
+    }
+}
+
+/// Formats a time as a FIX UTCTimestamp with milliseconds: `YYYYMMDD-HH:MM:SS.sss`.
+pub fn format_utc_timestamp(time: SystemTime) -> String {
+    return format_utc_timestamp_with_precision(time, TimestampPrecision::Millis);
+}
+
+/// Same as `format_utc_timestamp` with `precision` fractional digits, truncated; none
+/// and no dot for `TimestampPrecision::Seconds`.
+pub fn format_utc_timestamp_with_precision(
+    time: SystemTime,
+    precision: TimestampPrecision,
+) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    let seconds_of_day = seconds % 86400;
+
+    let mut result = format!(
+        "{:04}{:02}{:02}-{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+    );
+
+    let digits = precision.get_digits();
+
+    if digits > 0 {
+        let fraction = since_epoch.subsec_nanos() / 10u32.pow(9 - digits);
+        result.push_str(&format!(".{:0width$}", fraction, width = digits as usize));
+    }
+
+    return result;
+}
+
+/// Parses a FIX UTCTimestamp, `YYYYMMDD-HH:MM:SS` with up to nine fractional digits.
+pub fn parse_utc_timestamp(value: &str) -> Option<SystemTime> {
+    let (date, time) = value.split_once('-')?;
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, fraction),
+        None => (time, ""),
+    };
+
+    // Checked before slicing the date, which would panic inside a multi-byte character.
+    if date.len() != 8
+        || !date.bytes().all(|byte| byte.is_ascii_digit())
+        || time.len() != 8
+        || fraction.len() > 9
+    {
+        return None;
+    }
+
+    let number = |value: &str| -> Option<u64> {
+        if !value.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        return value.parse().ok();
+    };
+
+    let year = number(&date[..4])? as i64;
+    let month = number(&date[4..6])? as u32;
+    let day = number(&date[6..])? as u32;
+
+    let mut parts = time.split(':');
+    let hours = number(parts.next()?)?;
+    let minutes = number(parts.next()?)?;
+    let seconds = number(parts.next()?)?;
+
+    // Leap seconds (60) are accepted as FIX allows them.
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hours > 23
+        || minutes > 59
+        || seconds > 60
+    {
+        return None;
+    }
+
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        number(fraction)? * 10u64.pow(9 - fraction.len() as u32)
+    };
+
+    let days = days_from_civil(year, month, day);
+
+    if days < 0 {
+        return None;
+    }
+
+    let seconds = days as u64 * 86400 + hours * 3600 + minutes * 60 + seconds;
+    return Some(UNIX_EPOCH + Duration::new(seconds, nanos as u32));
+}
+
+// Converts a (year, month, day) date in the proleptic Gregorian calendar to days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = (year - era * 400) as u64;
+    let month_index = if month > 2 { month - 3 } else { month + 9 } as u64;
+    let day_of_year = (153 * month_index + 2) / 5 + day as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    return era * 146097 + day_of_era as i64 - 719468;
+}
+
+// Converts days since 1970-01-01 to a (year, month, day) date in the proleptic Gregorian calendar.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = (days - era * 146097) as u64;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era as i64 + era * 400;
+
+    return (if month <= 2 { year + 1 } else { year }, month, day);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_utc_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_537_467_899_643);
+
+        assert_eq!("20180920-18:24:59.643", format_utc_timestamp(time));
+        assert_eq!("19700101-00:00:00.000", format_utc_timestamp(UNIX_EPOCH));
+
+        let time = time + Duration::from_nanos(123_456);
+        let format = |digits| {
+            let precision = TimestampPrecision::try_from(digits).unwrap();
+            return format_utc_timestamp_with_precision(time, precision);
+        };
+        assert_eq!("20180920-18:24:59", format(0));
+        assert_eq!("20180920-18:24:59.643123", format(6));
+        assert_eq!("20180920-18:24:59.643123456", format(9));
+        assert_eq!(Some(time), parse_utc_timestamp(&format(9)));
+        assert!(TimestampPrecision::try_from(2).is_err());
+    }
+
+    #[test]
+    fn test_parse_utc_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_537_467_899_643);
+
+        assert_eq!(Some(time), parse_utc_timestamp("20180920-18:24:59.643"));
+        assert_eq!(
+            Some(time + Duration::from_nanos(456_789)),
+            parse_utc_timestamp("20180920-18:24:59.643456789")
+        );
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            parse_utc_timestamp("20000229-00:00:00")
+        );
+        assert_eq!(None, parse_utc_timestamp("20180920-25:24:59"));
+        assert_eq!(None, parse_utc_timestamp("2018092-18:24:59"));
+        assert_eq!(None, parse_utc_timestamp("201é920-00:00:00"));
+        assert_eq!(None, parse_utc_timestamp("20180920-1é:0:00"));
+    }
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        clock.advance(Duration::from_secs(86400 + 1));
+
+        assert_eq!("19700102-00:00:01.000", format_utc_timestamp(clock.now()));
+    }
+}
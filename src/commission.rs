@@ -0,0 +1,167 @@
+use crate::{
+    component::fix_component,
+    fields::{CommType, FixField, MiscFeeType},
+    group::{get_coded, get_parsed, get_string, read_group, write_optional, GroupEntry},
+    Component, FieldParseError, FixMessageBuilder,
+};
+
+const TAG_NO_MISC_FEES: u32 = 136;
+
+const MISC_FEE_TAGS: [u32; 5] = [TAG_NO_MISC_FEES, 137, 138, 139, 891];
+
+fix_component!(
+    /// CommissionData component of orders, executions and allocations.
+    ///
+    /// The amount is kept as written, so no digits are lost.
+    CommissionData {
+        /// Commission (12).
+        commission => 12,
+        /// CommType (13), see `get_comm_type`.
+        comm_type => 13,
+        /// CommCurrency (479).
+        comm_currency => 479,
+        /// FundRenewWaiv (497).
+        fund_renew_waiv => 497,
+    }
+);
+
+impl CommissionData {
+    pub fn new(commission: &str, comm_type: CommType) -> Self {
+        return Self {
+            commission: Some(commission.to_string()),
+            comm_type: Some(String::from_utf8_lossy(comm_type.as_bytes()).to_string()),
+            ..Self::default()
+        };
+    }
+
+    pub fn get_comm_type(&self) -> Result<Option<CommType>, FieldParseError> {
+        return match &self.comm_type {
+            Some(comm_type) => CommType::try_from(comm_type.as_bytes()).map(Some),
+            None => Ok(None),
+        };
+    }
+}
+
+/// Entry of NoMiscFees (136), a fee charged on top of the commission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiscFee {
+    /// MiscFeeAmt (137).
+    pub amount: String,
+    /// MiscFeeCurr (138).
+    pub currency: Option<String>,
+    pub fee_type: Option<MiscFeeType>,
+    /// MiscFeeBasis (891), e.g. `0` for an absolute amount or `2` for a percentage.
+    pub basis: Option<u32>,
+}
+
+impl MiscFee {
+    pub fn new(amount: &str, fee_type: MiscFeeType) -> Self {
+        return Self {
+            amount: amount.to_string(),
+            currency: None,
+            fee_type: Some(fee_type),
+            basis: None,
+        };
+    }
+}
+
+impl Component for Vec<MiscFee> {
+    const TAGS: &'static [u32] = &MISC_FEE_TAGS;
+
+    fn write(&self, message: &mut FixMessageBuilder) {
+        if self.is_empty() {
+            return;
+        }
+
+        message.with_value(TAG_NO_MISC_FEES, self.len());
+
+        for fee in self {
+            message.with_value(137, fee.amount.as_str());
+            write_optional(message, 138, &fee.currency);
+
+            if let Some(fee_type) = fee.fee_type {
+                message.with_field(fee_type);
+            }
+
+            write_optional(message, 891, &fee.basis);
+        }
+    }
+
+    fn read(fields: &[(u32, &[u8])]) -> Result<Self, FieldParseError> {
+        let mut fees = vec![];
+
+        for entry in read_group(fields, TAG_NO_MISC_FEES, &MISC_FEE_TAGS[1..])? {
+            fees.push(MiscFee {
+                amount: get_string(&entry, 137)?.unwrap_or_default(),
+                currency: get_string(&entry, 138)?,
+                fee_type: get_coded(&entry, MiscFeeType::TAG)?,
+                basis: get_parsed(&entry, 891)?,
+            });
+        }
+
+        return Ok(fees);
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends the fields of the CommissionData component that are set.
+    pub fn with_commission(&mut self, commission: &CommissionData) {
+        self.with_component(commission);
+    }
+
+    /// The CommissionData component, empty when the message carries none of its fields.
+    pub fn get_commission(&self) -> Result<CommissionData, FieldParseError> {
+        return self.get_component();
+    }
+
+    /// Appends NoMiscFees (136) and one entry per fee.
+    pub fn with_misc_fees(&mut self, fees: &[MiscFee]) {
+        self.with_component(&fees.to_vec());
+    }
+
+    /// Entries of NoMiscFees (136).
+    pub fn get_misc_fees(&self) -> Result<Vec<MiscFee>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return Vec::<MiscFee>::read(&fields);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_commission_and_fees_round_trip() {
+        let mut commission = CommissionData::new("0.0015", CommType::Percent);
+        commission.comm_currency = Some("USD".to_string());
+
+        let mut stamp = MiscFee::new("12.50", MiscFeeType::Stamp);
+        stamp.currency = Some("GBP".to_string());
+        let fees = vec![stamp, MiscFee::new("0.31", MiscFeeType::Regulatory)];
+
+        let mut report = FixMessageBuilder::new("FIX.4.4", "8");
+        report.with_value(17, "EXEC1");
+        report.with_commission(&commission);
+        report.with_misc_fees(&fees);
+        report.with_value(31, "143.25");
+
+        assert_eq!(
+            "17=EXEC1|12=0.0015|13=2|479=USD|136=2|137=12.50|138=GBP|139=5|137=0.31|139=1|31=143.25|",
+            report
+                .get_fields()
+                .map(|(tag, value)| format!("{}={}|", tag, String::from_utf8_lossy(value)))
+                .collect::<String>()
+        );
+
+        let parsed = report.get_commission().unwrap();
+        assert_eq!(commission, parsed);
+        assert_eq!(Ok(Some(CommType::Percent)), parsed.get_comm_type());
+        assert_eq!(Ok(fees), report.get_misc_fees());
+
+        report.set_value(139, "99");
+        assert!(matches!(
+            report.get_misc_fees(),
+            Err(FieldParseError::InvalidValue { tag: 139, .. })
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    group::GroupEntry, BeginString, FieldParseError, FixDictionary, FixMessageBuilder,
+    ValidationErrorKind,
+};
+
+/// Block of fields shared by many messages, e.g. Instrument or Parties, written and
+/// read the same way wherever it is embedded: in the body of a message or in an entry
+/// of a repeating group.
+///
+/// Blocks made only of optional text fields are declared with `fix_component!`.
+pub trait Component: Sized {
+    /// Tags the block may hold, nested groups included, so that a group embedding it
+    /// knows where its entries end.
+    const TAGS: &'static [u32];
+
+    /// Appends the fields that are set; an empty block writes nothing.
+    fn write(&self, message: &mut FixMessageBuilder);
+
+    /// Reads the block out of a message or group entry, with the fields in wire order.
+    fn read(fields: &[(u32, &[u8])]) -> Result<Self, FieldParseError>;
+
+    /// Checks the fields against the types and enumerations of `dictionary`.
+    fn validate(&self, dictionary: &FixDictionary) -> Vec<ValidationErrorKind> {
+        let mut message = FixMessageBuilder::new(BeginString::Custom(String::new()), "");
+        self.write(&mut message);
+
+        return dictionary.validate_fields(message.get_fields());
+    }
+}
+
+/// Declares a component of optional text fields, one per tag, and implements
+/// `Component` for it.
+macro_rules! fix_component {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$field_meta:meta])* $field:ident => $tag:expr),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: Option<String>),+
+        }
+
+        impl $crate::Component for $name {
+            const TAGS: &'static [u32] = &[$($tag),+];
+
+            fn write(&self, message: &mut $crate::FixMessageBuilder) {
+                $($crate::group::write_optional(message, $tag, &self.$field);)+
+            }
+
+            fn read(fields: &[(u32, &[u8])]) -> Result<Self, $crate::FieldParseError> {
+                return Ok(Self {
+                    $($field: $crate::group::get_string(fields, $tag)?),+
+                });
+            }
+        }
+    };
+}
+
+pub(crate) use fix_component;
+
+fix_component!(
+    /// OrderQtyData component: how much of the instrument an order is for.
+    OrderQtyData {
+        /// OrderQty (38).
+        order_qty => 38,
+        /// CashOrderQty (152).
+        cash_order_qty => 152,
+        /// OrderPercent (516).
+        order_percent => 516,
+        /// RoundingDirection (468).
+        rounding_direction => 468,
+        /// RoundingModulus (469).
+        rounding_modulus => 469,
+    }
+);
+
+impl OrderQtyData {
+    pub fn new(order_qty: &str) -> Self {
+        return Self {
+            order_qty: Some(order_qty.to_string()),
+            ..Self::default()
+        };
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends the fields of a component.
+    pub fn with_component<C: Component>(&mut self, component: &C) {
+        component.write(self);
+    }
+
+    /// Reads a component out of the message.
+    pub fn get_component<C: Component>(&self) -> Result<C, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return C::read(&fields);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Instrument, Party};
+
+    #[test]
+    fn test_component_round_trip() {
+        let instrument = Instrument::new("IBM");
+        let parties = vec![Party::new("TRADER1", "D", 12)];
+        let order_qty = OrderQtyData::new("100");
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        order.with_component(&parties);
+        order.with_component(&instrument);
+        order.with_value(54, "1");
+        order.with_component(&order_qty);
+
+        assert_eq!(
+            "8=FIX.4.4|9=65|35=D|11=ORDER1|453=1|448=TRADER1|447=D|452=12|55=IBM|54=1|38=100|10=005|",
+            order.to_string()
+        );
+        assert_eq!(Ok(instrument), order.get_component());
+        assert_eq!(Ok(parties), order.get_component());
+        assert_eq!(Ok(order_qty), order.get_component());
+    }
+
+    #[test]
+    fn test_component_validation() {
+        let dictionary = FixDictionary::fix44();
+
+        assert!(OrderQtyData::new("100").validate(&dictionary).is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::IncorrectDataFormat(38)],
+            OrderQtyData::new("a lot").validate(&dictionary)
+        );
+    }
+}
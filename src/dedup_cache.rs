@@ -0,0 +1,124 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::FixMessageBuilder;
+
+/// Identifies an application message independently of its MsgSeqNum (34).
+///
+/// Execution reports are keyed by ExecID (17), everything else by ClOrdID (11).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DedupKey {
+    message_type: Vec<u8>,
+    id: Vec<u8>,
+}
+
+impl DedupKey {
+    pub fn from_message(message: &FixMessageBuilder) -> Option<Self> {
+        let id = message.get(17).or_else(|| message.get(11))?;
+
+        return Some(Self {
+            message_type: message.get_message_type().clone(),
+            id: id.to_vec(),
+        });
+    }
+}
+
+/// Whether PossDupFlag (43) or PossResend (97) is set.
+pub fn is_possible_duplicate(message: &FixMessageBuilder) -> bool {
+    return message.is_poss_dup() || message.get(97) == Some(b"Y".as_slice());
+}
+
+/// Remembers the last `capacity` delivered messages, forgetting the oldest first.
+#[derive(Debug, Clone)]
+pub struct DedupCache {
+    keys: HashSet<DedupKey>,
+    order: VecDeque<DedupKey>,
+    capacity: usize,
+}
+
+impl DedupCache {
+    pub fn new(capacity: usize) -> Self {
+        return Self {
+            keys: HashSet::new(),
+            order: VecDeque::new(),
+            capacity,
+        };
+    }
+
+    /// Records the key, returning `false` if it was already known.
+    pub fn insert(&mut self, key: DedupKey) -> bool {
+        if self.capacity == 0 {
+            return true;
+        }
+
+        if self.keys.contains(&key) {
+            return false;
+        }
+
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+
+        self.keys.insert(key.clone());
+        self.order.push_back(key);
+
+        return true;
+    }
+
+    pub fn contains(&self, key: &DedupKey) -> bool {
+        return self.keys.contains(key);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.order.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.order.is_empty();
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn order(cl_ord_id: &str) -> FixMessageBuilder {
+        let mut result = FixMessageBuilder::new("FIX.4.4", "D");
+        result.with_value(11, cl_ord_id);
+        return result;
+    }
+
+    #[test]
+    fn test_execution_report_is_keyed_by_exec_id() {
+        let mut fill = FixMessageBuilder::new("FIX.4.4", "8");
+        fill.with_value(11, "ORDER1");
+        fill.with_value(17, "EXEC1");
+
+        let key = DedupKey::from_message(&fill).unwrap();
+
+        assert_eq!(b"EXEC1".to_vec(), key.id);
+        assert_eq!(
+            None,
+            DedupKey::from_message(&FixMessageBuilder::new("FIX.4.4", "B"))
+        );
+    }
+
+    #[test]
+    fn test_oldest_keys_are_evicted() {
+        let mut cache = DedupCache::new(2);
+
+        assert!(cache.insert(DedupKey::from_message(&order("1")).unwrap()));
+        assert!(!cache.insert(DedupKey::from_message(&order("1")).unwrap()));
+        assert!(cache.insert(DedupKey::from_message(&order("2")).unwrap()));
+        assert!(cache.insert(DedupKey::from_message(&order("3")).unwrap()));
+
+        assert_eq!(2, cache.len());
+        assert!(!cache.contains(&DedupKey::from_message(&order("1")).unwrap()));
+    }
+}
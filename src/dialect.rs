@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::{
+    FieldDef, FixDictionary, FixMessageBuilder, MemberDef, ValidationError, ValidationErrorKind,
+    STANDARD_HEADER_TAGS,
+};
+
+/// Dictionary rule a counterparty doesn't follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relaxation {
+    /// Tags missing from the dictionary are accepted.
+    UnknownTags,
+    /// Known tags are accepted in any message type.
+    TagsNotDefinedForMessage,
+    /// The tag may be left out where the dictionary requires it.
+    OptionalTag(u32),
+    /// Any value is accepted for the tag, whatever its type and enumeration.
+    AnyValue(u32),
+}
+
+impl Relaxation {
+    pub fn allows(&self, kind: &ValidationErrorKind) -> bool {
+        return match (self, kind) {
+            (Relaxation::UnknownTags, ValidationErrorKind::UnknownTag(_)) => true,
+            (
+                Relaxation::TagsNotDefinedForMessage,
+                ValidationErrorKind::TagNotDefinedForMessage(_),
+            ) => true,
+            (Relaxation::OptionalTag(tag), ValidationErrorKind::RequiredTagMissing(other)) => {
+                tag == other
+            }
+            (
+                Relaxation::AnyValue(tag),
+                ValidationErrorKind::ValueIsIncorrect(other)
+                | ValidationErrorKind::IncorrectDataFormat(other),
+            ) => tag == other,
+            _ => false,
+        };
+    }
+}
+
+/// How one venue deviates from the spec: custom tags added to a base dictionary,
+/// relaxed validation rules and the body field order it expects. Set per session
+/// with `Session::with_dialect`.
+#[derive(Debug, Clone)]
+pub struct Dialect {
+    name: String,
+    dictionary: FixDictionary,
+    relaxations: Vec<Relaxation>,
+    field_orders: HashMap<String, Vec<u32>>,
+}
+
+impl Dialect {
+    pub fn new(name: &str, base: FixDictionary) -> Self {
+        return Self {
+            name: name.to_string(),
+            dictionary: base,
+            relaxations: vec![],
+            field_orders: HashMap::new(),
+        };
+    }
+
+    pub fn get_name(&self) -> &str {
+        return &self.name;
+    }
+
+    /// The base dictionary with the custom fields added.
+    pub fn get_dictionary(&self) -> &FixDictionary {
+        return &self.dictionary;
+    }
+
+    /// Adds a venue specific field, allowed as an optional field of `msg_types`.
+    pub fn with_custom_field(&mut self, field: FieldDef, msg_types: &[&str]) {
+        let tag = field.tag;
+        self.dictionary.add_field(field);
+
+        for msg_type in msg_types {
+            let Some(message) = self.dictionary.get_message(msg_type) else {
+                continue;
+            };
+
+            if message.contains_tag(tag) {
+                continue;
+            }
+
+            let mut message = message.clone();
+            message.members.push(MemberDef::Field {
+                tag,
+                required: false,
+            });
+            self.dictionary.add_message(message);
+        }
+    }
+
+    pub fn with_relaxation(&mut self, relaxation: Relaxation) {
+        self.relaxations.push(relaxation);
+    }
+
+    /// Body field order the venue expects for `msg_type`: listed tags go right after
+    /// the header in this order, the others follow in the order they were added.
+    pub fn with_field_order(&mut self, msg_type: &str, tags: Vec<u32>) {
+        self.field_orders.insert(msg_type.to_string(), tags);
+    }
+
+    /// Same as `FixDictionary::validate` against the extended dictionary, leaving out
+    /// the errors the relaxations allow.
+    pub fn validate(&self, message: &[u8]) -> Vec<ValidationError> {
+        let mut result = self.dictionary.validate(message);
+        result.retain(|error| {
+            return !self
+                .relaxations
+                .iter()
+                .any(|relaxation| relaxation.allows(&error.kind));
+        });
+
+        return result;
+    }
+
+    /// Reorders the body fields of `message` as the venue expects; messages without a
+    /// field order for their type are left as they are.
+    pub fn apply_field_order(&self, message: &mut FixMessageBuilder) {
+        let Some(order) = self.field_orders.get(&message.get_message_type_as_string()) else {
+            return;
+        };
+
+        message.sort_fields_by_key(|tag| {
+            if STANDARD_HEADER_TAGS.contains(&tag) {
+                return 0;
+            }
+
+            return match order.iter().position(|other| *other == tag) {
+                Some(position) => position + 1,
+                None => order.len() + 1,
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FieldType;
+
+    #[test]
+    fn test_validate() {
+        let mut dialect = Dialect::new("VENUE", FixDictionary::fix44());
+        dialect.with_custom_field(
+            FieldDef {
+                tag: 9001,
+                name: "VenueAccount".to_string(),
+                field_type: FieldType::String,
+                values: vec![],
+            },
+            &["D"],
+        );
+        dialect.with_relaxation(Relaxation::OptionalTag(60));
+        dialect.with_relaxation(Relaxation::AnyValue(54));
+
+        let message = b"8=FIX.4.4\x019=0\x0135=D\x0149=A\x0156=B\x0134=1\x0152=20240102-03:04:05\x0111=X\x0155=Y\x0140=1\x0154=9\x019001=ACC\x0110=000\x01";
+
+        let kinds: Vec<ValidationErrorKind> = FixDictionary::fix44()
+            .validate(message)
+            .into_iter()
+            .map(|error| error.kind)
+            .collect();
+
+        assert_eq!(
+            vec![
+                ValidationErrorKind::ValueIsIncorrect(54),
+                ValidationErrorKind::UnknownTag(9001),
+                ValidationErrorKind::RequiredTagMissing(60),
+            ],
+            kinds
+        );
+        assert_eq!(Vec::<ValidationError>::new(), dialect.validate(message));
+    }
+
+    #[test]
+    fn test_apply_field_order() {
+        let mut dialect = Dialect::new("VENUE", FixDictionary::fix44());
+        dialect.with_field_order("D", vec![55, 11]);
+
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(49, "A");
+        message.with_value(11, "ORDER1");
+        message.with_value(54, 1);
+        message.with_value(55, "IBM");
+
+        dialect.apply_field_order(&mut message);
+
+        assert!(message
+            .to_string()
+            .contains("|35=D|49=A|55=IBM|11=ORDER1|54=1|"));
+    }
+}
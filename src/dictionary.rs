@@ -0,0 +1,1118 @@
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryError(pub String);
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "invalid data dictionary: {}", self.0);
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+/// Field types, named after the QuickFIX data dictionary ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Int,
+    Length,
+    SeqNum,
+    NumInGroup,
+    TagNum,
+    DayOfMonth,
+    Float,
+    Qty,
+    Price,
+    PriceOffset,
+    Amt,
+    Percentage,
+    Char,
+    Boolean,
+    String,
+    MultipleValueString,
+    MultipleCharValue,
+    Currency,
+    Exchange,
+    Country,
+    UtcTimestamp,
+    UtcDateOnly,
+    UtcTimeOnly,
+    LocalMktDate,
+    MonthYear,
+    Data,
+    XmlData,
+    Language,
+    Other(String),
+}
+
+impl FieldType {
+    pub fn from_quickfix_name(name: &str) -> Self {
+        return match name.to_ascii_uppercase().as_str() {
+            "INT" => FieldType::Int,
+            "LENGTH" => FieldType::Length,
+            "SEQNUM" => FieldType::SeqNum,
+            "NUMINGROUP" => FieldType::NumInGroup,
+            "TAGNUM" => FieldType::TagNum,
+            "DAYOFMONTH" => FieldType::DayOfMonth,
+            "FLOAT" => FieldType::Float,
+            "QTY" | "QUANTITY" => FieldType::Qty,
+            "PRICE" => FieldType::Price,
+            "PRICEOFFSET" => FieldType::PriceOffset,
+            "AMT" => FieldType::Amt,
+            "PERCENTAGE" => FieldType::Percentage,
+            "CHAR" => FieldType::Char,
+            "BOOLEAN" => FieldType::Boolean,
+            "STRING" => FieldType::String,
+            "MULTIPLEVALUESTRING" | "MULTIPLESTRINGVALUE" => FieldType::MultipleValueString,
+            "MULTIPLECHARVALUE" => FieldType::MultipleCharValue,
+            "CURRENCY" => FieldType::Currency,
+            "EXCHANGE" => FieldType::Exchange,
+            "COUNTRY" => FieldType::Country,
+            "UTCTIMESTAMP" => FieldType::UtcTimestamp,
+            "UTCDATEONLY" | "UTCDATE" => FieldType::UtcDateOnly,
+            "UTCTIMEONLY" => FieldType::UtcTimeOnly,
+            "LOCALMKTDATE" => FieldType::LocalMktDate,
+            "MONTHYEAR" => FieldType::MonthYear,
+            "DATA" => FieldType::Data,
+            "XMLDATA" => FieldType::XmlData,
+            "LANGUAGE" => FieldType::Language,
+            _ => FieldType::Other(name.to_string()),
+        };
+    }
+
+    /// Type name as written in a QuickFIX data dictionary.
+    pub fn get_quickfix_name(&self) -> &str {
+        return match self {
+            FieldType::Int => "INT",
+            FieldType::Length => "LENGTH",
+            FieldType::SeqNum => "SEQNUM",
+            FieldType::NumInGroup => "NUMINGROUP",
+            FieldType::TagNum => "TAGNUM",
+            FieldType::DayOfMonth => "DAYOFMONTH",
+            FieldType::Float => "FLOAT",
+            FieldType::Qty => "QTY",
+            FieldType::Price => "PRICE",
+            FieldType::PriceOffset => "PRICEOFFSET",
+            FieldType::Amt => "AMT",
+            FieldType::Percentage => "PERCENTAGE",
+            FieldType::Char => "CHAR",
+            FieldType::Boolean => "BOOLEAN",
+            FieldType::String => "STRING",
+            FieldType::MultipleValueString => "MULTIPLEVALUESTRING",
+            FieldType::MultipleCharValue => "MULTIPLECHARVALUE",
+            FieldType::Currency => "CURRENCY",
+            FieldType::Exchange => "EXCHANGE",
+            FieldType::Country => "COUNTRY",
+            FieldType::UtcTimestamp => "UTCTIMESTAMP",
+            FieldType::UtcDateOnly => "UTCDATEONLY",
+            FieldType::UtcTimeOnly => "UTCTIMEONLY",
+            FieldType::LocalMktDate => "LOCALMKTDATE",
+            FieldType::MonthYear => "MONTHYEAR",
+            FieldType::Data => "DATA",
+            FieldType::XmlData => "XMLDATA",
+            FieldType::Language => "LANGUAGE",
+            FieldType::Other(name) => name,
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDef {
+    pub tag: u32,
+    pub name: String,
+    pub field_type: FieldType,
+    /// Allowed values with their descriptions; empty when any value is allowed.
+    pub values: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberDef {
+    Field { tag: u32, required: bool },
+    Group(GroupDef),
+}
+
+/// A repeating group; the first member is the delimiter field of every entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupDef {
+    /// The NumInGroup field holding the number of entries.
+    pub count_tag: u32,
+    pub required: bool,
+    pub members: Vec<MemberDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageDef {
+    pub msg_type: String,
+    pub name: String,
+    pub is_admin: bool,
+    /// Body members in order, with components already expanded.
+    pub members: Vec<MemberDef>,
+}
+
+impl MessageDef {
+    /// Required top-level body fields.
+    pub fn get_required_tags(&self) -> Vec<u32> {
+        return required_tags(&self.members);
+    }
+
+    /// Whether the tag may appear anywhere in the body, including inside groups.
+    pub fn contains_tag(&self, tag: u32) -> bool {
+        return contains_tag(&self.members, tag);
+    }
+
+    /// The group counted by `count_tag`, nested groups included.
+    pub fn get_group(&self, count_tag: u32) -> Option<&GroupDef> {
+        return find_group(&self.members, count_tag);
+    }
+}
+
+impl GroupDef {
+    /// Tags that may appear in an entry, those of nested groups included.
+    pub fn get_member_tags(&self) -> Vec<u32> {
+        let mut result = vec![];
+
+        for member in &self.members {
+            match member {
+                MemberDef::Field { tag, .. } => result.push(*tag),
+                MemberDef::Group(group) => {
+                    result.push(group.count_tag);
+                    result.extend(group.get_member_tags());
+                }
+            }
+        }
+
+        return result;
+    }
+}
+
+/// Field and message definitions of one FIX version.
+///
+/// `FixDictionary::fix44()` covers the session layer and the common order flow
+/// messages; complete dictionaries are loaded from QuickFIX XML files with
+/// `from_quickfix_xml` (`dictionary` feature).
+#[derive(Debug, Clone, Default)]
+pub struct FixDictionary {
+    begin_string: String,
+    fields: HashMap<u32, FieldDef>,
+    field_names: HashMap<String, u32>,
+    messages: HashMap<String, MessageDef>,
+    header: Vec<MemberDef>,
+    trailer: Vec<MemberDef>,
+}
+
+impl FixDictionary {
+    pub fn new(begin_string: &str) -> Self {
+        return Self {
+            begin_string: begin_string.to_string(),
+            ..Default::default()
+        };
+    }
+
+    /// Built-in subset of FIX 4.4.
+    pub fn fix44() -> Self {
+        let mut result = Self::new("FIX.4.4");
+
+        for (tag, name, field_type) in FIX44_FIELDS {
+            result.add_field(FieldDef {
+                tag: *tag,
+                name: name.to_string(),
+                field_type: FieldType::from_quickfix_name(field_type),
+                values: vec![],
+            });
+        }
+
+        for (tag, value, description) in FIX44_VALUES {
+            if let Some(field) = result.fields.get_mut(tag) {
+                field
+                    .values
+                    .push((value.to_string(), description.to_string()));
+            }
+        }
+
+        result.header = FIX44_HEADER
+            .iter()
+            .map(|(tag, required)| MemberDef::Field {
+                tag: *tag,
+                required: *required,
+            })
+            .collect();
+        result.trailer = vec![MemberDef::Field {
+            tag: 10,
+            required: true,
+        }];
+
+        for (msg_type, name, is_admin, fields) in FIX44_MESSAGES {
+            result.add_message(MessageDef {
+                msg_type: msg_type.to_string(),
+                name: name.to_string(),
+                is_admin: *is_admin,
+                members: fields
+                    .iter()
+                    .map(|(tag, required)| MemberDef::Field {
+                        tag: *tag,
+                        required: *required,
+                    })
+                    .collect(),
+            });
+        }
+
+        return result;
+    }
+
+    pub fn get_begin_string(&self) -> &str {
+        return &self.begin_string;
+    }
+
+    pub fn add_field(&mut self, field: FieldDef) {
+        self.field_names.insert(field.name.clone(), field.tag);
+        self.fields.insert(field.tag, field);
+    }
+
+    pub fn add_message(&mut self, message: MessageDef) {
+        self.messages.insert(message.msg_type.clone(), message);
+    }
+
+    pub fn with_header(&mut self, header: Vec<MemberDef>) {
+        self.header = header;
+    }
+
+    pub fn with_trailer(&mut self, trailer: Vec<MemberDef>) {
+        self.trailer = trailer;
+    }
+
+    pub fn get_field(&self, tag: u32) -> Option<&FieldDef> {
+        return self.fields.get(&tag);
+    }
+
+    pub fn get_field_by_name(&self, name: &str) -> Option<&FieldDef> {
+        return self.fields.get(self.field_names.get(name)?);
+    }
+
+    pub fn get_tag_name(&self, tag: u32) -> Option<&str> {
+        return self.fields.get(&tag).map(|field| field.name.as_str());
+    }
+
+    /// Description of an enumerated value, e.g. `BUY` for Side (54) `1`.
+    pub fn get_value_description(&self, tag: u32, value: &[u8]) -> Option<&str> {
+        return self
+            .fields
+            .get(&tag)?
+            .values
+            .iter()
+            .find(|(allowed, _)| allowed.as_bytes() == value)
+            .map(|(_, description)| description.as_str());
+    }
+
+    pub fn get_message(&self, msg_type: &str) -> Option<&MessageDef> {
+        return self.messages.get(msg_type);
+    }
+
+    pub fn get_messages(&self) -> impl Iterator<Item = &MessageDef> {
+        return self.messages.values();
+    }
+
+    pub fn get_fields(&self) -> impl Iterator<Item = &FieldDef> {
+        return self.fields.values();
+    }
+
+    pub fn get_header(&self) -> &[MemberDef] {
+        return &self.header;
+    }
+
+    pub fn get_trailer(&self) -> &[MemberDef] {
+        return &self.trailer;
+    }
+
+    /// A group of the standard header, e.g. NoHops (627).
+    pub fn get_header_group(&self, count_tag: u32) -> Option<&GroupDef> {
+        return find_group(&self.header, count_tag);
+    }
+
+    pub fn is_header_tag(&self, tag: u32) -> bool {
+        return contains_tag(&self.header, tag);
+    }
+
+    pub fn is_trailer_tag(&self, tag: u32) -> bool {
+        return contains_tag(&self.trailer, tag);
+    }
+
+    /// One field per line of a raw message, with tag names and value descriptions,
+    /// for people to read.
+    pub fn describe(&self, message: &[u8]) -> String {
+        let mut result = String::new();
+
+        for chunk in message.split(|byte| *byte == 1) {
+            let Some(separator) = chunk.iter().position(|byte| *byte == b'=') else {
+                continue;
+            };
+
+            let (tag, value) = (&chunk[..separator], &chunk[separator + 1..]);
+            let tag_number = std::str::from_utf8(tag)
+                .ok()
+                .and_then(|tag| tag.parse::<u32>().ok());
+
+            let name = tag_number
+                .and_then(|tag| self.get_tag_name(tag))
+                .unwrap_or("");
+            let description = tag_number.and_then(|tag| self.get_value_description(tag, value));
+
+            let mut line = format!(
+                "{:>5} {:<20} = {}",
+                String::from_utf8_lossy(tag),
+                name,
+                String::from_utf8_lossy(value)
+            );
+
+            if let Some(description) = description {
+                line.push_str(&format!(" ({})", description));
+            }
+
+            if tag == b"35" {
+                if let Some(message_def) = self.get_message(&String::from_utf8_lossy(value)) {
+                    result.insert_str(
+                        0,
+                        &format!("{} ({})\n", message_def.name, message_def.msg_type),
+                    );
+                }
+            }
+
+            result.push_str(&line);
+            result.push('\n');
+        }
+
+        return result;
+    }
+
+    /// The dictionary as a QuickFIX data dictionary, readable by `from_quickfix_xml`.
+    /// Components are written expanded; messages and fields are sorted.
+    pub fn to_quickfix_xml(&self) -> String {
+        let mut parts = self.begin_string.split('.');
+        let fix_type = parts.next().unwrap_or("FIX");
+        let major = parts.next().unwrap_or("0");
+        let minor = parts.next().unwrap_or("0");
+
+        let mut result = format!(
+            "<fix type=\"{}\" major=\"{}\" minor=\"{}\" servicepack=\"0\">\n",
+            escape_xml(fix_type),
+            escape_xml(major),
+            escape_xml(minor)
+        );
+
+        result.push_str("  <header>\n");
+        self.write_xml_members(&mut result, &self.header, 2);
+        result.push_str("  </header>\n  <messages>\n");
+
+        let mut messages: Vec<&MessageDef> = self.messages.values().collect();
+        messages.sort_by(|a, b| a.msg_type.cmp(&b.msg_type));
+
+        for message in messages {
+            result.push_str(&format!(
+                "    <message name=\"{}\" msgtype=\"{}\" msgcat=\"{}\">\n",
+                escape_xml(&message.name),
+                escape_xml(&message.msg_type),
+                if message.is_admin { "admin" } else { "app" }
+            ));
+            self.write_xml_members(&mut result, &message.members, 3);
+            result.push_str("    </message>\n");
+        }
+
+        result.push_str("  </messages>\n  <trailer>\n");
+        self.write_xml_members(&mut result, &self.trailer, 2);
+        result.push_str("  </trailer>\n  <components/>\n  <fields>\n");
+
+        let mut fields: Vec<&FieldDef> = self.fields.values().collect();
+        fields.sort_by_key(|field| field.tag);
+
+        for field in fields {
+            let opening = format!(
+                "    <field number=\"{}\" name=\"{}\" type=\"{}\"",
+                field.tag,
+                escape_xml(&field.name),
+                escape_xml(field.field_type.get_quickfix_name())
+            );
+
+            if field.values.is_empty() {
+                result.push_str(&format!("{}/>\n", opening));
+                continue;
+            }
+
+            result.push_str(&format!("{}>\n", opening));
+
+            for (value, description) in &field.values {
+                result.push_str(&format!(
+                    "      <value enum=\"{}\" description=\"{}\"/>\n",
+                    escape_xml(value),
+                    escape_xml(description)
+                ));
+            }
+
+            result.push_str("    </field>\n");
+        }
+
+        result.push_str("  </fields>\n</fix>\n");
+
+        return result;
+    }
+
+    fn write_xml_members(&self, result: &mut String, members: &[MemberDef], depth: usize) {
+        let indent = "  ".repeat(depth);
+
+        for member in members {
+            let (tag, required) = match member {
+                MemberDef::Field { tag, required } => (*tag, *required),
+                MemberDef::Group(group) => (group.count_tag, group.required),
+            };
+
+            let name = match self.get_tag_name(tag) {
+                Some(name) => escape_xml(name),
+                None => tag.to_string(),
+            };
+            let required = if required { "Y" } else { "N" };
+
+            match member {
+                MemberDef::Field { .. } => result.push_str(&format!(
+                    "{}<field name=\"{}\" required=\"{}\"/>\n",
+                    indent, name, required
+                )),
+                MemberDef::Group(group) => {
+                    result.push_str(&format!(
+                        "{}<group name=\"{}\" required=\"{}\">\n",
+                        indent, name, required
+                    ));
+                    self.write_xml_members(result, &group.members, depth + 1);
+                    result.push_str(&format!("{}</group>\n", indent));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "dictionary")]
+impl FixDictionary {
+    /// Loads a QuickFIX data dictionary (`FIX44.xml` and friends); components are
+    /// expanded into the messages and groups using them.
+    pub fn from_quickfix_xml(source: &str) -> Result<Self, DictionaryError> {
+        let document =
+            roxmltree::Document::parse(source).map_err(|err| DictionaryError(err.to_string()))?;
+        let root = document.root_element();
+
+        let attribute = |node: roxmltree::Node, name: &str| -> Result<String, DictionaryError> {
+            return node
+                .attribute(name)
+                .map(|value| value.to_string())
+                .ok_or_else(|| {
+                    DictionaryError(format!("<{}> without {}", node.tag_name().name(), name))
+                });
+        };
+
+        let begin_string = match (root.attribute("type"), root.attribute("major")) {
+            (Some("FIXT"), Some(major)) => {
+                format!("FIXT.{}.{}", major, root.attribute("minor").unwrap_or("0"))
+            }
+            (_, Some(major)) => format!("FIX.{}.{}", major, root.attribute("minor").unwrap_or("0")),
+            _ => return Err(DictionaryError("<fix> without major".to_string())),
+        };
+
+        let mut result = Self::new(&begin_string);
+        let section = |name: &str| root.children().find(|node| node.has_tag_name(name));
+
+        if let Some(fields) = section("fields") {
+            for field in fields.children().filter(|node| node.has_tag_name("field")) {
+                let number = attribute(field, "number")?;
+                let tag = number
+                    .parse()
+                    .map_err(|_| DictionaryError(format!("invalid field number {}", number)))?;
+
+                result.add_field(FieldDef {
+                    tag,
+                    name: attribute(field, "name")?,
+                    field_type: FieldType::from_quickfix_name(&attribute(field, "type")?),
+                    values: field
+                        .children()
+                        .filter(|node| node.has_tag_name("value"))
+                        .map(|value| {
+                            Ok((attribute(value, "enum")?, attribute(value, "description")?))
+                        })
+                        .collect::<Result<_, DictionaryError>>()?,
+                });
+            }
+        }
+
+        let mut components = HashMap::new();
+
+        if let Some(section) = section("components") {
+            for component in section
+                .children()
+                .filter(|node| node.has_tag_name("component"))
+            {
+                components.insert(attribute(component, "name")?, component);
+            }
+        }
+
+        let resolver = XmlResolver {
+            dictionary: &result,
+            components: &components,
+        };
+
+        let header = match section("header") {
+            Some(node) => resolver.members(node, 0)?,
+            None => vec![],
+        };
+        let trailer = match section("trailer") {
+            Some(node) => resolver.members(node, 0)?,
+            None => vec![],
+        };
+
+        let mut messages = vec![];
+
+        if let Some(section) = section("messages") {
+            for message in section
+                .children()
+                .filter(|node| node.has_tag_name("message"))
+            {
+                messages.push(MessageDef {
+                    msg_type: attribute(message, "msgtype")?,
+                    name: attribute(message, "name")?,
+                    is_admin: message.attribute("msgcat") == Some("admin"),
+                    members: resolver.members(message, 0)?,
+                });
+            }
+        }
+
+        result.with_header(header);
+        result.with_trailer(trailer);
+
+        for message in messages {
+            result.add_message(message);
+        }
+
+        return Ok(result);
+    }
+
+    pub fn from_quickfix_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        return Self::from_quickfix_xml(&source).map_err(std::io::Error::other);
+    }
+}
+
+#[cfg(feature = "dictionary")]
+struct XmlResolver<'a, 'input> {
+    dictionary: &'a FixDictionary,
+    components: &'a HashMap<String, roxmltree::Node<'a, 'input>>,
+}
+
+#[cfg(feature = "dictionary")]
+impl XmlResolver<'_, '_> {
+    // Components may nest, but never deeper than this in any published dictionary.
+    const MAX_DEPTH: usize = 32;
+
+    fn members(
+        &self,
+        node: roxmltree::Node,
+        depth: usize,
+    ) -> Result<Vec<MemberDef>, DictionaryError> {
+        if depth > Self::MAX_DEPTH {
+            return Err(DictionaryError("components nested too deep".to_string()));
+        }
+
+        let mut result = vec![];
+
+        for child in node.children().filter(|node| node.is_element()) {
+            let name = child.attribute("name").unwrap_or_default();
+            let required = child.attribute("required") == Some("Y");
+
+            match child.tag_name().name() {
+                "field" => result.push(MemberDef::Field {
+                    tag: self.tag(name)?,
+                    required,
+                }),
+                "group" => result.push(MemberDef::Group(GroupDef {
+                    count_tag: self.tag(name)?,
+                    required,
+                    members: self.members(child, depth + 1)?,
+                })),
+                "component" => {
+                    let component = self
+                        .components
+                        .get(name)
+                        .ok_or_else(|| DictionaryError(format!("unknown component {}", name)))?;
+                    let members = self.members(*component, depth + 1)?;
+
+                    // Fields of an optional component aren't required on their own.
+                    result.extend(members.into_iter().map(|member| match member {
+                        MemberDef::Field { tag, .. } if !required => MemberDef::Field {
+                            tag,
+                            required: false,
+                        },
+                        MemberDef::Group(group) if !required => MemberDef::Group(GroupDef {
+                            required: false,
+                            ..group
+                        }),
+                        member => member,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        return Ok(result);
+    }
+
+    fn tag(&self, name: &str) -> Result<u32, DictionaryError> {
+        return self
+            .dictionary
+            .get_field_by_name(name)
+            .map(|field| field.tag)
+            .ok_or_else(|| DictionaryError(format!("unknown field {}", name)));
+    }
+}
+
+fn required_tags(members: &[MemberDef]) -> Vec<u32> {
+    return members
+        .iter()
+        .filter_map(|member| match member {
+            MemberDef::Field {
+                tag,
+                required: true,
+            } => Some(*tag),
+            MemberDef::Group(group) if group.required => Some(group.count_tag),
+            _ => None,
+        })
+        .collect();
+}
+
+fn contains_tag(members: &[MemberDef], tag: u32) -> bool {
+    return members.iter().any(|member| match member {
+        MemberDef::Field { tag: inner, .. } => *inner == tag,
+        MemberDef::Group(group) => group.count_tag == tag || contains_tag(&group.members, tag),
+    });
+}
+
+fn find_group(members: &[MemberDef], count_tag: u32) -> Option<&GroupDef> {
+    for member in members {
+        let MemberDef::Group(group) = member else {
+            continue;
+        };
+
+        if group.count_tag == count_tag {
+            return Some(group);
+        }
+
+        if let Some(inner) = find_group(&group.members, count_tag) {
+            return Some(inner);
+        }
+    }
+
+    return None;
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            c => result.push(c),
+        }
+    }
+
+    return result;
+}
+
+const FIX44_FIELDS: &[(u32, &str, &str)] = &[
+    (1, "Account", "STRING"),
+    (6, "AvgPx", "PRICE"),
+    (7, "BeginSeqNo", "SEQNUM"),
+    (8, "BeginString", "STRING"),
+    (9, "BodyLength", "LENGTH"),
+    (10, "CheckSum", "STRING"),
+    (11, "ClOrdID", "STRING"),
+    (14, "CumQty", "QTY"),
+    (15, "Currency", "CURRENCY"),
+    (16, "EndSeqNo", "SEQNUM"),
+    (17, "ExecID", "STRING"),
+    (21, "HandlInst", "CHAR"),
+    (31, "LastPx", "PRICE"),
+    (32, "LastQty", "QTY"),
+    (34, "MsgSeqNum", "SEQNUM"),
+    (35, "MsgType", "STRING"),
+    (36, "NewSeqNo", "SEQNUM"),
+    (37, "OrderID", "STRING"),
+    (38, "OrderQty", "QTY"),
+    (39, "OrdStatus", "CHAR"),
+    (40, "OrdType", "CHAR"),
+    (41, "OrigClOrdID", "STRING"),
+    (43, "PossDupFlag", "BOOLEAN"),
+    (44, "Price", "PRICE"),
+    (45, "RefSeqNum", "SEQNUM"),
+    (48, "SecurityID", "STRING"),
+    (49, "SenderCompID", "STRING"),
+    (50, "SenderSubID", "STRING"),
+    (52, "SendingTime", "UTCTIMESTAMP"),
+    (54, "Side", "CHAR"),
+    (55, "Symbol", "STRING"),
+    (56, "TargetCompID", "STRING"),
+    (57, "TargetSubID", "STRING"),
+    (58, "Text", "STRING"),
+    (59, "TimeInForce", "CHAR"),
+    (60, "TransactTime", "UTCTIMESTAMP"),
+    (97, "PossResend", "BOOLEAN"),
+    (98, "EncryptMethod", "INT"),
+    (99, "StopPx", "PRICE"),
+    (102, "CxlRejReason", "INT"),
+    (103, "OrdRejReason", "INT"),
+    (108, "HeartBtInt", "INT"),
+    (112, "TestReqID", "STRING"),
+    (115, "OnBehalfOfCompID", "STRING"),
+    (122, "OrigSendingTime", "UTCTIMESTAMP"),
+    (123, "GapFillFlag", "BOOLEAN"),
+    (128, "DeliverToCompID", "STRING"),
+    (141, "ResetSeqNumFlag", "BOOLEAN"),
+    (150, "ExecType", "CHAR"),
+    (151, "LeavesQty", "QTY"),
+    (371, "RefTagID", "INT"),
+    (372, "RefMsgType", "STRING"),
+    (373, "SessionRejectReason", "INT"),
+    (434, "CxlRejResponseTo", "CHAR"),
+    (553, "Username", "STRING"),
+    (554, "Password", "STRING"),
+    (789, "NextExpectedMsgSeqNum", "SEQNUM"),
+];
+
+const FIX44_VALUES: &[(u32, &str, &str)] = &[
+    (35, "0", "HEARTBEAT"),
+    (35, "1", "TEST_REQUEST"),
+    (35, "2", "RESEND_REQUEST"),
+    (35, "3", "REJECT"),
+    (35, "4", "SEQUENCE_RESET"),
+    (35, "5", "LOGOUT"),
+    (35, "8", "EXECUTION_REPORT"),
+    (35, "9", "ORDER_CANCEL_REJECT"),
+    (35, "A", "LOGON"),
+    (35, "D", "ORDER_SINGLE"),
+    (35, "F", "ORDER_CANCEL_REQUEST"),
+    (35, "G", "ORDER_CANCEL_REPLACE_REQUEST"),
+    (35, "j", "BUSINESS_MESSAGE_REJECT"),
+    (21, "1", "AUTOMATED_EXECUTION_NO_INTERVENTION"),
+    (21, "2", "AUTOMATED_EXECUTION_INTERVENTION_OK"),
+    (21, "3", "MANUAL_ORDER"),
+    (39, "0", "NEW"),
+    (39, "1", "PARTIALLY_FILLED"),
+    (39, "2", "FILLED"),
+    (39, "4", "CANCELED"),
+    (39, "6", "PENDING_CANCEL"),
+    (39, "8", "REJECTED"),
+    (39, "A", "PENDING_NEW"),
+    (39, "C", "EXPIRED"),
+    (39, "E", "PENDING_REPLACE"),
+    (40, "1", "MARKET"),
+    (40, "2", "LIMIT"),
+    (40, "3", "STOP"),
+    (40, "4", "STOP_LIMIT"),
+    (43, "Y", "YES"),
+    (43, "N", "NO"),
+    (54, "1", "BUY"),
+    (54, "2", "SELL"),
+    (54, "5", "SELL_SHORT"),
+    (59, "0", "DAY"),
+    (59, "1", "GOOD_TILL_CANCEL"),
+    (59, "3", "IMMEDIATE_OR_CANCEL"),
+    (59, "4", "FILL_OR_KILL"),
+    (59, "6", "GOOD_TILL_DATE"),
+    (97, "Y", "YES"),
+    (97, "N", "NO"),
+    (98, "0", "NONE_OTHER"),
+    (123, "Y", "YES"),
+    (123, "N", "NO"),
+    (141, "Y", "YES"),
+    (141, "N", "NO"),
+    (150, "0", "NEW"),
+    (150, "4", "CANCELED"),
+    (150, "5", "REPLACED"),
+    (150, "8", "REJECTED"),
+    (150, "F", "TRADE"),
+    (150, "I", "ORDER_STATUS"),
+];
+
+const FIX44_HEADER: &[(u32, bool)] = &[
+    (8, true),
+    (9, true),
+    (35, true),
+    (49, true),
+    (56, true),
+    (115, false),
+    (128, false),
+    (34, true),
+    (50, false),
+    (57, false),
+    (43, false),
+    (97, false),
+    (52, true),
+    (122, false),
+];
+
+type MessageTable = (&'static str, &'static str, bool, &'static [(u32, bool)]);
+
+const FIX44_MESSAGES: &[MessageTable] = &[
+    ("0", "Heartbeat", true, &[(112, false)]),
+    ("1", "TestRequest", true, &[(112, true)]),
+    ("2", "ResendRequest", true, &[(7, true), (16, true)]),
+    (
+        "3",
+        "Reject",
+        true,
+        &[
+            (45, true),
+            (371, false),
+            (372, false),
+            (373, false),
+            (58, false),
+        ],
+    ),
+    ("4", "SequenceReset", true, &[(123, false), (36, true)]),
+    ("5", "Logout", true, &[(58, false)]),
+    (
+        "A",
+        "Logon",
+        true,
+        &[
+            (98, true),
+            (108, true),
+            (141, false),
+            (789, false),
+            (553, false),
+            (554, false),
+        ],
+    ),
+    (
+        "D",
+        "NewOrderSingle",
+        false,
+        &[
+            (11, true),
+            (1, false),
+            (21, false),
+            (55, true),
+            (54, true),
+            (60, true),
+            (38, false),
+            (40, true),
+            (44, false),
+            (99, false),
+            (15, false),
+            (59, false),
+            (58, false),
+        ],
+    ),
+    (
+        "F",
+        "OrderCancelRequest",
+        false,
+        &[
+            (41, true),
+            (37, false),
+            (11, true),
+            (1, false),
+            (55, true),
+            (54, true),
+            (60, true),
+            (38, false),
+            (58, false),
+        ],
+    ),
+    (
+        "G",
+        "OrderCancelReplaceRequest",
+        false,
+        &[
+            (37, false),
+            (41, true),
+            (11, true),
+            (1, false),
+            (21, false),
+            (55, true),
+            (54, true),
+            (60, true),
+            (38, false),
+            (40, true),
+            (44, false),
+            (99, false),
+            (15, false),
+            (59, false),
+            (58, false),
+        ],
+    ),
+    (
+        "8",
+        "ExecutionReport",
+        false,
+        &[
+            (37, true),
+            (11, false),
+            (41, false),
+            (17, true),
+            (150, true),
+            (39, true),
+            (103, false),
+            (1, false),
+            (55, true),
+            (54, true),
+            (38, false),
+            (40, false),
+            (44, false),
+            (59, false),
+            (32, false),
+            (31, false),
+            (151, true),
+            (14, true),
+            (6, true),
+            (60, false),
+            (58, false),
+        ],
+    ),
+    (
+        "9",
+        "OrderCancelReject",
+        false,
+        &[
+            (37, true),
+            (11, true),
+            (41, true),
+            (39, true),
+            (434, true),
+            (102, false),
+            (58, false),
+        ],
+    ),
+    (
+        "j",
+        "BusinessMessageReject",
+        true,
+        &[(45, false), (372, true), (58, false)],
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FixMessageBuilder;
+
+    #[test]
+    fn test_fix44_lookups() {
+        let dictionary = FixDictionary::fix44();
+
+        assert_eq!(Some("Side"), dictionary.get_tag_name(54));
+        assert_eq!(Some("BUY"), dictionary.get_value_description(54, b"1"));
+        assert_eq!(
+            Some(54),
+            dictionary.get_field_by_name("Side").map(|field| field.tag)
+        );
+        assert_eq!(
+            FieldType::Price,
+            dictionary.get_field(44).unwrap().field_type
+        );
+        assert!(dictionary.is_header_tag(52));
+
+        let order = dictionary.get_message("D").unwrap();
+        assert_eq!("NewOrderSingle", order.name);
+        assert!(order.get_required_tags().contains(&55));
+        assert!(!order.contains_tag(112));
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(54, "1");
+        order.with_value(9999, "x");
+
+        assert_eq!(
+            "NewOrderSingle (D)\n    \
+                8 BeginString          = FIX.4.4\n    \
+                9 BodyLength           = 17\n   \
+               35 MsgType              = D (ORDER_SINGLE)\n   \
+               54 Side                 = 1 (BUY)\n \
+             9999                      = x\n   \
+               10 CheckSum             = 092\n",
+            FixDictionary::fix44().describe(&order.as_bytes())
+        );
+    }
+
+    #[cfg(feature = "dictionary")]
+    #[test]
+    fn test_from_quickfix_xml() {
+        let source = r#"
+            <fix type="FIX" major="4" minor="4">
+              <header>
+                <field name="BeginString" required="Y"/>
+                <field name="MsgType" required="Y"/>
+              </header>
+              <trailer>
+                <field name="CheckSum" required="Y"/>
+              </trailer>
+              <messages>
+                <message name="NewOrderList" msgtype="E" msgcat="app">
+                  <field name="ListID" required="Y"/>
+                  <component name="ListOrders" required="Y"/>
+                </message>
+              </messages>
+              <components>
+                <component name="ListOrders">
+                  <group name="NoOrders" required="Y">
+                    <field name="ClOrdID" required="Y"/>
+                    <component name="Instrument" required="N"/>
+                  </group>
+                </component>
+                <component name="Instrument">
+                  <field name="Symbol" required="Y"/>
+                </component>
+              </components>
+              <fields>
+                <field number="8" name="BeginString" type="STRING"/>
+                <field number="10" name="CheckSum" type="STRING"/>
+                <field number="11" name="ClOrdID" type="STRING"/>
+                <field number="35" name="MsgType" type="STRING">
+                  <value enum="E" description="NEW_ORDER_LIST"/>
+                </field>
+                <field number="55" name="Symbol" type="STRING"/>
+                <field number="66" name="ListID" type="STRING"/>
+                <field number="73" name="NoOrders" type="NUMINGROUP"/>
+              </fields>
+            </fix>
+        "#;
+
+        let dictionary = FixDictionary::from_quickfix_xml(source).unwrap();
+
+        assert_eq!("FIX.4.4", dictionary.get_begin_string());
+        assert_eq!(
+            Some("NEW_ORDER_LIST"),
+            dictionary.get_value_description(35, b"E")
+        );
+        assert!(dictionary.is_trailer_tag(10));
+
+        let message = dictionary.get_message("E").unwrap();
+        assert_eq!(vec![66, 73], message.get_required_tags());
+        assert!(message.contains_tag(55));
+
+        let MemberDef::Group(group) = &message.members[1] else {
+            panic!("expected NoOrders group");
+        };
+        assert_eq!(
+            MemberDef::Field {
+                tag: 55,
+                required: false
+            },
+            group.members[1]
+        );
+
+        let result =
+            FixDictionary::from_quickfix_xml(&source.replace("\"Symbol\" type", "\"Sym\" type"));
+        assert_eq!(
+            Err(DictionaryError("unknown field Symbol".to_string())),
+            result.map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::{
+    parse_utc_timestamp, FieldDef, FieldType, FixDate, FixDictionary, FixMessageBuilder, MemberDef,
+    MessageDef,
+};
+
+const TAG_BEGIN_STRING: u32 = 8;
+const TAG_BODY_LENGTH: u32 = 9;
+const TAG_MSG_TYPE: u32 = 35;
+const TAG_CHECK_SUM: u32 = 10;
+
+/// Value sets larger than this aren't taken as coded by default.
+const DEFAULT_MAX_VALUES: usize = 32;
+
+/// What the values of a tag looked like so far.
+#[derive(Debug, Clone)]
+struct TagStats {
+    /// Occurrences, repeats within a message included.
+    occurrences: usize,
+    values: BTreeSet<String>,
+    /// More distinct values than kept in `values`.
+    is_open: bool,
+    is_int: bool,
+    is_float: bool,
+    is_boolean: bool,
+    is_char: bool,
+    is_timestamp: bool,
+    is_date: bool,
+}
+
+impl TagStats {
+    fn new() -> Self {
+        return Self {
+            occurrences: 0,
+            values: BTreeSet::new(),
+            is_open: false,
+            is_int: true,
+            is_float: true,
+            is_boolean: true,
+            is_char: true,
+            is_timestamp: true,
+            is_date: true,
+        };
+    }
+
+    fn observe(&mut self, value: &[u8], max_values: usize) {
+        let value = String::from_utf8_lossy(value);
+
+        self.occurrences += 1;
+        self.is_int &= !value.is_empty() && value.parse::<i64>().is_ok();
+        self.is_float &= !value.is_empty() && value.parse::<f64>().is_ok();
+        self.is_boolean &= value == "Y" || value == "N";
+        self.is_char &= value.chars().count() == 1;
+        self.is_timestamp &= value.len() > 8 && parse_utc_timestamp(&value).is_some();
+        self.is_date &= FixDate::parse(&value).is_some();
+
+        if self.is_open || self.values.contains(value.as_ref()) {
+            return;
+        }
+
+        if self.values.len() >= max_values {
+            self.is_open = true;
+            self.values.clear();
+            return;
+        }
+
+        self.values.insert(value.to_string());
+    }
+
+    fn guess_type(&self) -> FieldType {
+        if self.is_timestamp {
+            return FieldType::UtcTimestamp;
+        }
+
+        if self.is_date {
+            return FieldType::LocalMktDate;
+        }
+
+        if self.is_boolean {
+            return FieldType::Boolean;
+        }
+
+        if self.is_int {
+            return FieldType::Int;
+        }
+
+        if self.is_float {
+            return FieldType::Float;
+        }
+
+        if self.is_char {
+            return FieldType::Char;
+        }
+
+        return FieldType::String;
+    }
+
+    /// Whether the values look like an enumeration: single characters and booleans
+    /// always, other text and integers when every value came up at least twice on average.
+    fn is_coded(&self, field_type: &FieldType) -> bool {
+        if self.is_open || self.values.is_empty() {
+            return false;
+        }
+
+        return match field_type {
+            FieldType::Char | FieldType::Boolean => true,
+            FieldType::String | FieldType::Int | FieldType::MultipleValueString => {
+                self.values.len() * 2 <= self.occurrences
+            }
+            _ => false,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct MessageStats {
+    count: usize,
+    /// Body tags in the order they were first seen.
+    tags: Vec<u32>,
+    /// Messages each body tag appeared in.
+    presence: HashMap<u32, usize>,
+}
+
+/// Approximate data dictionary learned from traffic, e.g. a journal of a counterparty
+/// that provides no machine-readable spec.
+///
+/// Feed it messages with `observe`, then take `to_dictionary` or its
+/// `to_quickfix_xml` as a starting point to edit by hand. What it can't see:
+///
+/// - Repeating groups: their fields are listed flat after the NumInGroup tag.
+/// - Optional fields the counterparty never sent.
+/// - Fields are required when every observed message of the type had them, which a
+///   small sample overstates.
+#[derive(Debug, Clone)]
+pub struct ObservedDictionary {
+    begin_string: Option<String>,
+    messages: BTreeMap<String, MessageStats>,
+    tags: HashMap<u32, TagStats>,
+    header_presence: HashMap<u32, usize>,
+    header_tags: Vec<u32>,
+    total: usize,
+    max_values: usize,
+}
+
+impl Default for ObservedDictionary {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl ObservedDictionary {
+    pub fn new() -> Self {
+        return Self {
+            begin_string: None,
+            messages: BTreeMap::new(),
+            tags: HashMap::new(),
+            header_presence: HashMap::new(),
+            header_tags: vec![],
+            total: 0,
+            max_values: DEFAULT_MAX_VALUES,
+        };
+    }
+
+    /// Tags with more distinct values than `max_values` aren't listed as coded.
+    pub fn with_max_values(&mut self, max_values: usize) {
+        self.max_values = max_values;
+    }
+
+    /// Number of messages observed.
+    pub fn len(&self) -> usize {
+        return self.total;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.total == 0;
+    }
+
+    /// MsgTypes seen, sorted.
+    pub fn get_msg_types(&self) -> Vec<&str> {
+        return self.messages.keys().map(String::as_str).collect();
+    }
+
+    /// Records the tags and values of `message`. Header tags are told apart from body
+    /// tags with `reference`, which should be the closest published dictionary.
+    pub fn observe(&mut self, message: &FixMessageBuilder, reference: &FixDictionary) {
+        if self.begin_string.is_none() {
+            let begin_string = message.get_fix_version();
+            self.begin_string = Some(String::from_utf8_lossy(begin_string.as_bytes()).to_string());
+        }
+
+        self.total += 1;
+
+        let msg_type = message.get_message_type_as_string();
+        self.tags
+            .entry(TAG_MSG_TYPE)
+            .or_insert_with(TagStats::new)
+            .observe(msg_type.as_bytes(), self.max_values);
+
+        let stats = self.messages.entry(msg_type).or_default();
+        stats.count += 1;
+
+        let mut seen = HashSet::new();
+
+        for (tag, value) in message.get_fields() {
+            self.tags
+                .entry(tag)
+                .or_insert_with(TagStats::new)
+                .observe(value, self.max_values);
+
+            if !seen.insert(tag) {
+                continue;
+            }
+
+            if reference.is_header_tag(tag) {
+                if !self.header_presence.contains_key(&tag) {
+                    self.header_tags.push(tag);
+                }
+
+                *self.header_presence.entry(tag).or_default() += 1;
+            } else if !reference.is_trailer_tag(tag) {
+                if !stats.presence.contains_key(&tag) {
+                    stats.tags.push(tag);
+                }
+
+                *stats.presence.entry(tag).or_default() += 1;
+            }
+        }
+    }
+
+    /// The dictionary seen so far. Tags `reference` knows keep its names, types and
+    /// value descriptions; unknown ones are named `Field<tag>` with a type guessed from
+    /// their values, and unknown MsgTypes `Message<MsgType>`.
+    pub fn to_dictionary(&self, reference: &FixDictionary) -> FixDictionary {
+        let begin_string = self
+            .begin_string
+            .as_deref()
+            .unwrap_or(reference.get_begin_string());
+        let mut result = FixDictionary::new(begin_string);
+
+        let mut header = vec![
+            MemberDef::Field {
+                tag: TAG_BEGIN_STRING,
+                required: true,
+            },
+            MemberDef::Field {
+                tag: TAG_BODY_LENGTH,
+                required: true,
+            },
+            MemberDef::Field {
+                tag: TAG_MSG_TYPE,
+                required: true,
+            },
+        ];
+        header.extend(self.header_tags.iter().map(|tag| MemberDef::Field {
+            tag: *tag,
+            required: self.header_presence[tag] == self.total,
+        }));
+        result.with_header(header);
+
+        result.with_trailer(vec![MemberDef::Field {
+            tag: TAG_CHECK_SUM,
+            required: true,
+        }]);
+
+        for (msg_type, stats) in &self.messages {
+            let known = reference.get_message(msg_type);
+
+            result.add_message(MessageDef {
+                msg_type: msg_type.clone(),
+                name: match known {
+                    Some(known) => known.name.clone(),
+                    None => format!("Message{}", msg_type),
+                },
+                is_admin: known.is_some_and(|known| known.is_admin),
+                members: stats
+                    .tags
+                    .iter()
+                    .map(|tag| MemberDef::Field {
+                        tag: *tag,
+                        required: stats.presence[tag] == stats.count,
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut tags: Vec<u32> = self.tags.keys().copied().collect();
+        tags.extend([TAG_BEGIN_STRING, TAG_BODY_LENGTH, TAG_CHECK_SUM]);
+
+        for tag in tags {
+            result.add_field(self.to_field_def(tag, reference));
+        }
+
+        return result;
+    }
+
+    fn to_field_def(&self, tag: u32, reference: &FixDictionary) -> FieldDef {
+        let known = reference.get_field(tag);
+        let stats = self.tags.get(&tag);
+
+        let field_type = match (known, stats) {
+            (Some(known), _) => known.field_type.clone(),
+            (None, Some(stats)) => stats.guess_type(),
+            (None, None) => FieldType::String,
+        };
+
+        let values = match stats {
+            Some(stats) if stats.is_coded(&field_type) => stats
+                .values
+                .iter()
+                .map(|value| {
+                    let description = reference
+                        .get_value_description(tag, value.as_bytes())
+                        .unwrap_or(value);
+
+                    (value.clone(), description.to_string())
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        return FieldDef {
+            tag,
+            name: match known {
+                Some(known) => known.name.clone(),
+                None => format!("Field{}", tag),
+            },
+            field_type,
+            values,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn order(cl_ord_id: &str, side: &str, custom: Option<&str>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(49, "CLIENT");
+        message.with_value(56, "BROKER");
+        message.with_value(11, cl_ord_id);
+        message.with_value(54, side);
+        message.with_value(38, "100");
+
+        if let Some(custom) = custom {
+            message.with_value(5001, custom);
+        }
+
+        return message;
+    }
+
+    #[test]
+    fn test_infer_dictionary() {
+        let reference = FixDictionary::fix44();
+        let mut observed = ObservedDictionary::new();
+
+        observed.observe(&order("A1", "1", Some("X")), &reference);
+        observed.observe(&order("A2", "2", Some("Y")), &reference);
+        observed.observe(&order("A3", "1", None), &reference);
+        observed.observe(&FixMessageBuilder::new("FIX.4.4", "0"), &reference);
+
+        assert_eq!(4, observed.len());
+        assert_eq!(vec!["0", "D"], observed.get_msg_types());
+
+        let dictionary = observed.to_dictionary(&reference);
+        assert_eq!("FIX.4.4", dictionary.get_begin_string());
+
+        let message = dictionary.get_message("D").unwrap();
+        assert_eq!("NewOrderSingle", message.name);
+        assert_eq!(vec![11, 54, 38], message.get_required_tags());
+        assert!(message.contains_tag(5001));
+        assert!(dictionary.get_message("0").unwrap().is_admin);
+
+        assert!(dictionary.is_header_tag(49));
+        assert!(!message.contains_tag(49));
+
+        assert_eq!(
+            vec![
+                ("1".to_string(), "BUY".to_string()),
+                ("2".to_string(), "SELL".to_string())
+            ],
+            dictionary.get_field(54).unwrap().values
+        );
+        assert!(dictionary.get_field(11).unwrap().values.is_empty());
+
+        let custom = dictionary.get_field(5001).unwrap();
+        assert_eq!("Field5001", custom.name);
+        assert_eq!(FieldType::Char, custom.field_type);
+        assert_eq!(2, custom.values.len());
+
+        let xml = dictionary.to_quickfix_xml();
+        assert!(xml.contains(r#"<message name="NewOrderSingle" msgtype="D" msgcat="app">"#));
+        assert!(xml.contains(r#"<field number="5001" name="Field5001" type="CHAR">"#));
+
+        #[cfg(feature = "dictionary")]
+        {
+            let parsed = FixDictionary::from_quickfix_xml(&xml).unwrap();
+            assert_eq!(
+                message.get_required_tags(),
+                parsed.get_message("D").unwrap().get_required_tags()
+            );
+            assert_eq!(Some("X"), parsed.get_value_description(5001, b"X"));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{
+    mpsc::{self, Receiver, Sender},
+    Mutex,
+};
+
+use crate::{FixApplication, FixMessageBuilder, SessionId};
+
+/// Application for drop-copy sessions (`SessionConfig::drop_copy`) that hands every
+/// inbound ExecutionReport (35=8) to each subscriber.
+///
+/// Subscribers whose receiver has been dropped are forgotten on the next report.
+#[derive(Debug, Default)]
+pub struct DropCopyFanout {
+    subscribers: Mutex<Vec<Sender<(SessionId, FixMessageBuilder)>>>,
+}
+
+impl DropCopyFanout {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn subscribe(&self) -> Receiver<(SessionId, FixMessageBuilder)> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+
+        return receiver;
+    }
+
+    pub fn get_subscriber_count(&self) -> usize {
+        return self.subscribers.lock().unwrap().len();
+    }
+}
+
+impl FixApplication for DropCopyFanout {
+    fn on_app_message(&self, session_id: &SessionId, message: &FixMessageBuilder) {
+        if message.get_message_type() != b"8" {
+            return;
+        }
+
+        self.subscribers.lock().unwrap().retain(|subscriber| {
+            subscriber
+                .send((session_id.clone(), message.clone()))
+                .is_ok()
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Session, SessionConfig, SessionSendError};
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_drop_copy_session() {
+        let session_id = SessionId::new("FIX.4.4", "COPY", "EXCHANGE");
+        let mut config = SessionConfig::new(&session_id);
+        config.drop_copy = true;
+
+        let fanout = DropCopyFanout::new();
+        let first = fanout.subscribe();
+        let second = fanout.subscribe();
+        drop(second);
+
+        let mut session = Session::from_config(config);
+        session.logon(SystemTime::now());
+        let mut reply = FixMessageBuilder::new("FIX.4.4", "A");
+        reply.with_value(34, "1");
+        reply.with_value(108, "30");
+        session.on_inbound(&reply, &fanout, SystemTime::now());
+        assert!(session.is_logged_on());
+
+        let mut report = FixMessageBuilder::new("FIX.4.4", "8");
+        report.with_value(34, "2");
+        report.with_value(52, "19700101-00:00:00");
+        report.with_value(17, "EXEC1");
+        let actions = session.on_inbound(&report, &fanout, SystemTime::now());
+        assert!(actions.is_empty());
+
+        let mut status = FixMessageBuilder::new("FIX.4.4", "h");
+        status.with_value(34, "3");
+        session.on_inbound(&status, &fanout, SystemTime::now());
+
+        // Too low and without PossDupFlag, which would end a regular session.
+        let actions = session.on_inbound(&report, &fanout, SystemTime::now());
+        assert!(actions.is_empty());
+        assert!(session.is_logged_on());
+
+        let (received_session_id, received) = first.try_recv().unwrap();
+        assert_eq!(session_id, received_session_id);
+        assert_eq!(Some("EXEC1".to_string()), received.get_string_lossy(17));
+        assert!(first.try_recv().is_err());
+        assert_eq!(1, fanout.get_subscriber_count());
+
+        let order = FixMessageBuilder::new("FIX.4.4", "D");
+        assert_eq!(
+            Err(SessionSendError::DropCopy),
+            session.send_app(order, &fanout, SystemTime::now())
+        );
+    }
+}
@@ -1,7 +1,217 @@
-#[derive(Debug)]
+use std::fmt;
+
+use crate::{
+    fields::SessionRejectReason, fix_message_builder::is_header_tag, split_raw_fields, ParseLimits,
+    RawField,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FixSerializeError {
     VersionTagNotFoundInSource,
     MessageTypeTagNotFoundInSource,
     CheckSumTagNotFoundInSource,
     InvalidCheckSum,
+    BodyLengthTagNotFoundInSource,
+    InvalidBodyLength,
+    /// A field whose tag isn't a number.
+    InvalidTag,
+    /// The message exceeds `ParseLimits::max_message_size`.
+    MessageTooLarge,
+    /// The message has more fields than `ParseLimits::max_field_count`.
+    TooManyFields,
+    /// A value is longer than `ParseLimits::max_value_length`.
+    ValueTooLong,
+    /// A header or trailer tag appears twice, typically two messages run together by
+    /// a framing bug; only reported when `ParseOptions::reject_duplicate_header_tags` is set.
+    /// `FixParseError::locate` tells which tag.
+    DuplicateHeaderTag,
+    /// BodyLength (9) doesn't match where the checksum-verified trailer is; only
+    /// reported with `BodyLengthPolicy::Report`. `find_body_length_mismatch` tells both
+    /// lengths.
+    BodyLengthMismatch,
+}
+
+/// Parse failure with its location in the source buffer, returned by
+/// `FixMessageBuilder::from_bytes_detailed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixParseError {
+    pub kind: FixSerializeError,
+    /// Tag of the offending field; `None` when the failure isn't about one field.
+    pub tag: Option<u32>,
+    /// Which occurrence of `tag` is at fault, starting at 1; 0 when the field is missing.
+    pub occurrence: usize,
+    /// Byte offset of the offending field in the source buffer.
+    pub offset: usize,
+}
+
+impl FixParseError {
+    pub fn locate(kind: FixSerializeError, payload: &[u8], limits: &ParseLimits) -> Self {
+        let fields = split_raw_fields(payload);
+
+        let (tag, field) = match kind {
+            FixSerializeError::VersionTagNotFoundInSource => (Some(8), None),
+            FixSerializeError::MessageTypeTagNotFoundInSource => (Some(35), None),
+            FixSerializeError::CheckSumTagNotFoundInSource => (Some(10), None),
+            FixSerializeError::BodyLengthTagNotFoundInSource => (Some(9), None),
+            FixSerializeError::InvalidCheckSum => (
+                Some(10),
+                fields.iter().rev().find(|field| field.tag == b"10"),
+            ),
+            FixSerializeError::InvalidBodyLength | FixSerializeError::BodyLengthMismatch => {
+                (Some(9), fields.iter().find(|field| field.tag == b"9"))
+            }
+            FixSerializeError::InvalidTag => {
+                (None, fields.iter().find(|field| field.get_tag().is_none()))
+            }
+            FixSerializeError::TooManyFields => {
+                let field = fields.get(limits.max_field_count);
+                (field.and_then(|field| field.get_tag()), field)
+            }
+            FixSerializeError::ValueTooLong => {
+                let field = fields
+                    .iter()
+                    .find(|field| field.value.len() > limits.max_value_length);
+                (field.and_then(|field| field.get_tag()), field)
+            }
+            FixSerializeError::MessageTooLarge => (None, None),
+            FixSerializeError::DuplicateHeaderTag => {
+                let field = fields.iter().enumerate().find_map(|(index, field)| {
+                    let tag = field.get_tag()?;
+                    let is_repeated = fields[..index]
+                        .iter()
+                        .any(|other| other.get_tag() == Some(tag));
+
+                    return (is_header_tag(tag) && is_repeated).then_some(field);
+                });
+                (field.and_then(|field| field.get_tag()), field)
+            }
+        };
+
+        let offset = match (&kind, field) {
+            (_, Some(field)) => field.offset,
+            (FixSerializeError::CheckSumTagNotFoundInSource, None) => payload.len(),
+            (FixSerializeError::MessageTooLarge, None) => limits.max_message_size,
+            _ => 0,
+        };
+
+        let occurrence = match field {
+            Some(field) => occurrence(&fields, field),
+            None => 0,
+        };
+
+        return Self {
+            kind,
+            tag,
+            occurrence,
+            offset,
+        };
+    }
+
+    /// RefTagID (371) of a Reject for this failure.
+    pub fn get_ref_tag_id(&self) -> Option<u32> {
+        return self.tag;
+    }
+
+    /// SessionRejectReason (373) of a Reject for this failure.
+    pub fn get_session_reject_reason(&self) -> SessionRejectReason {
+        return match self.kind {
+            FixSerializeError::VersionTagNotFoundInSource
+            | FixSerializeError::MessageTypeTagNotFoundInSource
+            | FixSerializeError::CheckSumTagNotFoundInSource
+            | FixSerializeError::BodyLengthTagNotFoundInSource => {
+                SessionRejectReason::RequiredTagMissing
+            }
+            FixSerializeError::InvalidCheckSum
+            | FixSerializeError::InvalidBodyLength
+            | FixSerializeError::BodyLengthMismatch => SessionRejectReason::ValueIsIncorrect,
+            FixSerializeError::InvalidTag => SessionRejectReason::InvalidTagNumber,
+            FixSerializeError::DuplicateHeaderTag => SessionRejectReason::TagAppearsMoreThanOnce,
+            FixSerializeError::MessageTooLarge
+            | FixSerializeError::TooManyFields
+            | FixSerializeError::ValueTooLong => SessionRejectReason::Other,
+        };
+    }
+}
+
+impl fmt::Display for FixParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at offset {}", self.kind, self.offset)?;
+
+        if let Some(tag) = self.tag {
+            write!(f, ", tag {}", tag)?;
+
+            if self.occurrence > 1 {
+                write!(f, " (occurrence {})", self.occurrence)?;
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+impl std::error::Error for FixParseError {}
+
+// 1-based position of `field` among the fields with the same tag.
+pub(crate) fn occurrence(fields: &[RawField], field: &RawField) -> usize {
+    return fields
+        .iter()
+        .filter(|other| other.tag == field.tag && other.offset <= field.offset)
+        .count();
+}
+
+/// Anomaly found while parsing that did not prevent the message from being read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The last field was not terminated by SOH.
+    MissingTrailingDelimeter,
+    /// Bytes found after the CheckSum (10) field.
+    TrailingGarbage { offset: usize, len: usize },
+    /// BeginString (8), MsgType (35) or CheckSum (10) is absent.
+    MissingTag(u32),
+    /// CheckSum (10) doesn't match the bytes before it.
+    InvalidCheckSum { declared: String, actual: String },
+    /// Field at `offset` has a tag that isn't a number; the field is dropped.
+    InvalidTag { offset: usize },
+    /// Value of the field at `offset` holds a control or non-ASCII byte at `position`.
+    UnexpectedCharacter { offset: usize, position: usize },
+    /// Header tag repeated at `offset`; the value is kept as a regular field, except
+    /// for BeginString, BodyLength and MsgType where the first one wins.
+    DuplicateHeaderTag { tag: u32, offset: usize },
+}
+
+/// Value that isn't valid UTF-8, returned by `FixMessageBuilder::get_string`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8FieldError {
+    pub tag: u32,
+}
+
+impl fmt::Display for Utf8FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "value of tag {} is not valid UTF-8", self.tag);
+    }
+}
+
+impl std::error::Error for Utf8FieldError {}
+
+impl From<Utf8FieldError> for FieldParseError {
+    fn from(err: Utf8FieldError) -> Self {
+        return FieldParseError::InvalidUtf8 { tag: err.tag };
+    }
+}
+
+/// Error returned when a field value can't be converted to the requested type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldParseError {
+    InvalidUtf8 {
+        tag: u32,
+    },
+    InvalidValue {
+        tag: u32,
+        value: String,
+        reason: String,
+    },
+    /// A field the message type requires is absent.
+    Missing {
+        tag: u32,
+    },
 }
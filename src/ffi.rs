@@ -0,0 +1,198 @@
+//! C interface (`ffi` feature) to parse, read, build and serialize messages through
+//! opaque `FixMessageBuilder` handles. `include/rust_fix.h` declares the functions;
+//! build the library with `cargo rustc --release --features ffi --crate-type cdylib`
+//! (or `staticlib`).
+//!
+//! Every handle returned by `fix_message_parse` or `fix_message_new` has to be released
+//! with `fix_message_free`. Null pointers are rejected rather than dereferenced.
+
+use std::{
+    ffi::{c_char, c_int, CStr},
+    ptr, slice,
+};
+
+use crate::FixMessageBuilder;
+
+pub const FIX_FFI_OK: c_int = 0;
+pub const FIX_FFI_NULL_POINTER: c_int = -1;
+pub const FIX_FFI_INVALID_ARGUMENT: c_int = -2;
+
+/// Parses one message from `len` bytes at `data`. Returns null when the bytes aren't
+/// a valid message.
+///
+/// # Safety
+///
+/// `data` has to point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn fix_message_parse(
+    data: *const u8,
+    len: usize,
+    check_sum_validation: bool,
+) -> *mut FixMessageBuilder {
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+
+    let payload = slice::from_raw_parts(data, len);
+
+    return match FixMessageBuilder::from_bytes(payload, check_sum_validation) {
+        Ok(message) => Box::into_raw(Box::new(message)),
+        Err(_) => ptr::null_mut(),
+    };
+}
+
+/// Creates an empty message, e.g. `fix_message_new("FIX.4.4", "D")`. Returns null when
+/// an argument is null or not UTF-8.
+///
+/// # Safety
+///
+/// Both arguments have to be null or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fix_message_new(
+    begin_string: *const c_char,
+    msg_type: *const c_char,
+) -> *mut FixMessageBuilder {
+    let (Some(begin_string), Some(msg_type)) = (to_str(begin_string), to_str(msg_type)) else {
+        return ptr::null_mut();
+    };
+
+    return Box::into_raw(Box::new(FixMessageBuilder::new(begin_string, msg_type)));
+}
+
+/// Releases a handle. Null is ignored.
+///
+/// # Safety
+///
+/// `message` has to come from this library and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn fix_message_free(message: *mut FixMessageBuilder) {
+    if !message.is_null() {
+        drop(Box::from_raw(message));
+    }
+}
+
+/// Value of the first occurrence of `tag`, not NUL-terminated, with its length stored
+/// in `out_len`. Returns null when the field is absent. The value stays valid until
+/// the message is changed or freed.
+///
+/// # Safety
+///
+/// `message` has to be a live handle and `out_len` writable.
+#[no_mangle]
+pub unsafe extern "C" fn fix_message_get_field(
+    message: *const FixMessageBuilder,
+    tag: u32,
+    out_len: *mut usize,
+) -> *const u8 {
+    if message.is_null() || out_len.is_null() {
+        return ptr::null();
+    }
+
+    return match (*message).get(tag) {
+        Some(value) => {
+            *out_len = value.len();
+            value.as_ptr()
+        }
+        None => ptr::null(),
+    };
+}
+
+/// Appends `tag` with `len` bytes at `value`.
+///
+/// # Safety
+///
+/// `message` has to be a live handle and `value` point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn fix_message_add_field(
+    message: *mut FixMessageBuilder,
+    tag: u32,
+    value: *const u8,
+    len: usize,
+) -> c_int {
+    if message.is_null() || value.is_null() {
+        return FIX_FFI_NULL_POINTER;
+    }
+
+    if tag == 0 || len == 0 {
+        return FIX_FFI_INVALID_ARGUMENT;
+    }
+
+    (*message).with_value(tag, slice::from_raw_parts(value, len));
+
+    return FIX_FFI_OK;
+}
+
+/// Serializes the message with BodyLength and CheckSum into `out`. Returns the length
+/// of the message; when it exceeds `capacity` nothing is written, so a caller can ask
+/// for the size first with a null `out` and a `capacity` of 0.
+///
+/// # Safety
+///
+/// `message` has to be a live handle and `out` null or writable for `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn fix_message_serialize(
+    message: *const FixMessageBuilder,
+    out: *mut u8,
+    capacity: usize,
+) -> usize {
+    if message.is_null() {
+        return 0;
+    }
+
+    let bytes = (*message).as_bytes();
+
+    if !out.is_null() && bytes.len() <= capacity {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    }
+
+    return bytes.len();
+}
+
+unsafe fn to_str<'a>(value: *const c_char) -> Option<&'a str> {
+    if value.is_null() {
+        return None;
+    }
+
+    return CStr::from_ptr(value).to_str().ok();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ffi_round_trip() {
+        unsafe {
+            let message = fix_message_new(c"FIX.4.4".as_ptr(), c"D".as_ptr());
+            assert!(!message.is_null());
+            assert_eq!(
+                FIX_FFI_OK,
+                fix_message_add_field(message, 11, b"ORDER1".as_ptr(), 6)
+            );
+            assert_eq!(
+                FIX_FFI_NULL_POINTER,
+                fix_message_add_field(message, 11, ptr::null(), 0)
+            );
+
+            let len = fix_message_serialize(message, ptr::null_mut(), 0);
+            let mut buffer = vec![0; len];
+            assert_eq!(
+                len,
+                fix_message_serialize(message, buffer.as_mut_ptr(), len)
+            );
+            assert_eq!((*message).as_bytes(), buffer);
+            fix_message_free(message);
+
+            let parsed = fix_message_parse(buffer.as_ptr(), buffer.len(), true);
+            assert!(!parsed.is_null());
+
+            let mut value_len = 0;
+            let value = fix_message_get_field(parsed, 11, &mut value_len);
+            assert_eq!(b"ORDER1", slice::from_raw_parts(value, value_len));
+            assert!(fix_message_get_field(parsed, 58, &mut value_len).is_null());
+            fix_message_free(parsed);
+
+            assert!(fix_message_parse(b"garbage".as_ptr(), 7, true).is_null());
+        }
+    }
+}
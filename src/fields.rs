@@ -0,0 +1,343 @@
+//! Strongly typed values of the common coded fields.
+
+use crate::{FieldParseError, FixValue};
+
+/// Coded field with a fixed tag number.
+pub trait FixField: Sized + for<'a> TryFrom<&'a [u8], Error = FieldParseError> {
+    const TAG: u32;
+
+    fn as_bytes(&self) -> &'static [u8];
+}
+
+macro_rules! fix_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident = $tag:expr, {
+            $($variant:ident => $value:expr),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl FixField for $name {
+            const TAG: u32 = $tag;
+
+            fn as_bytes(&self) -> &'static [u8] {
+                return match self {
+                    $($name::$variant => $value),+
+                };
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = FieldParseError;
+
+            fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+                $(
+                    if value == $value {
+                        return Ok($name::$variant);
+                    }
+                )+
+
+                return Err(FieldParseError::InvalidValue {
+                    tag: $tag,
+                    value: String::from_utf8_lossy(value).to_string(),
+                    reason: format!("unknown {} value", stringify!($name)),
+                });
+            }
+        }
+
+        impl From<$name> for FixValue {
+            fn from(value: $name) -> Self {
+                return FixValue::String(String::from_utf8_lossy(value.as_bytes()).to_string());
+            }
+        }
+
+        #[cfg(feature = "proptest")]
+        impl proptest::arbitrary::Arbitrary for $name {
+            type Parameters = ();
+            type Strategy = proptest::sample::Select<$name>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                return proptest::sample::select(vec![$($name::$variant),+]);
+            }
+        }
+    };
+}
+
+fix_enum!(
+    /// Side (54).
+    Side = 54, {
+        Buy => b"1",
+        Sell => b"2",
+        BuyMinus => b"3",
+        SellPlus => b"4",
+        SellShort => b"5",
+        SellShortExempt => b"6",
+        Undisclosed => b"7",
+        Cross => b"8",
+        CrossShort => b"9",
+    }
+);
+
+fix_enum!(
+    /// OrdType (40).
+    OrdType = 40, {
+        Market => b"1",
+        Limit => b"2",
+        Stop => b"3",
+        StopLimit => b"4",
+        MarketOnClose => b"5",
+        WithOrWithout => b"6",
+        LimitOrBetter => b"7",
+        LimitWithOrWithout => b"8",
+        OnBasis => b"9",
+        PreviouslyQuoted => b"D",
+        PreviouslyIndicated => b"E",
+        ForexSwap => b"G",
+        Funari => b"I",
+        MarketIfTouched => b"J",
+        MarketWithLeftOverAsLimit => b"K",
+        PreviousFundValuationPoint => b"L",
+        NextFundValuationPoint => b"M",
+        Pegged => b"P",
+    }
+);
+
+fix_enum!(
+    /// TimeInForce (59).
+    TimeInForce = 59, {
+        Day => b"0",
+        GoodTillCancel => b"1",
+        AtTheOpening => b"2",
+        ImmediateOrCancel => b"3",
+        FillOrKill => b"4",
+        GoodTillCrossing => b"5",
+        GoodTillDate => b"6",
+        AtTheClose => b"7",
+    }
+);
+
+fix_enum!(
+    /// ExecType (150). `PartialFill` and `Fill` are only used before FIX 4.4.
+    ExecType = 150, {
+        New => b"0",
+        PartialFill => b"1",
+        Fill => b"2",
+        DoneForDay => b"3",
+        Canceled => b"4",
+        Replaced => b"5",
+        PendingCancel => b"6",
+        Stopped => b"7",
+        Rejected => b"8",
+        Suspended => b"9",
+        PendingNew => b"A",
+        Calculated => b"B",
+        Expired => b"C",
+        Restated => b"D",
+        PendingReplace => b"E",
+        Trade => b"F",
+        TradeCorrect => b"G",
+        TradeCancel => b"H",
+        OrderStatus => b"I",
+    }
+);
+
+fix_enum!(
+    /// OrdStatus (39).
+    OrdStatus = 39, {
+        New => b"0",
+        PartiallyFilled => b"1",
+        Filled => b"2",
+        DoneForDay => b"3",
+        Canceled => b"4",
+        Replaced => b"5",
+        PendingCancel => b"6",
+        Stopped => b"7",
+        Rejected => b"8",
+        Suspended => b"9",
+        PendingNew => b"A",
+        Calculated => b"B",
+        Expired => b"C",
+        AcceptedForBidding => b"D",
+        PendingReplace => b"E",
+    }
+);
+
+fix_enum!(
+    /// SessionRejectReason (373).
+    SessionRejectReason = 373, {
+        InvalidTagNumber => b"0",
+        RequiredTagMissing => b"1",
+        TagNotDefinedForMessageType => b"2",
+        UndefinedTag => b"3",
+        TagSpecifiedWithoutValue => b"4",
+        ValueIsIncorrect => b"5",
+        IncorrectDataFormat => b"6",
+        DecryptionProblem => b"7",
+        SignatureProblem => b"8",
+        CompIdProblem => b"9",
+        SendingTimeAccuracyProblem => b"10",
+        InvalidMsgType => b"11",
+        XmlValidationError => b"12",
+        TagAppearsMoreThanOnce => b"13",
+        TagSpecifiedOutOfRequiredOrder => b"14",
+        RepeatingGroupFieldsOutOfOrder => b"15",
+        IncorrectNumInGroupCount => b"16",
+        NonDataValueIncludesFieldDelimiter => b"17",
+        Other => b"99",
+    }
+);
+
+fix_enum!(
+    /// BusinessRejectReason (380).
+    BusinessRejectReason = 380, {
+        Other => b"0",
+        UnknownId => b"1",
+        UnknownSecurity => b"2",
+        UnsupportedMessageType => b"3",
+        ApplicationNotAvailable => b"4",
+        ConditionallyRequiredFieldMissing => b"5",
+        NotAuthorized => b"6",
+        DeliverToFirmNotAvailable => b"7",
+    }
+);
+
+fix_enum!(
+    /// UserRequestType (924).
+    UserRequestType = 924, {
+        LogOnUser => b"1",
+        LogOffUser => b"2",
+        ChangePasswordForUser => b"3",
+        RequestIndividualUserStatus => b"4",
+    }
+);
+
+fix_enum!(
+    /// UserStatus (926).
+    UserStatus = 926, {
+        LoggedIn => b"1",
+        NotLoggedIn => b"2",
+        UserNotRecognised => b"3",
+        PasswordIncorrect => b"4",
+        PasswordChanged => b"5",
+        Other => b"6",
+    }
+);
+
+fix_enum!(
+    /// CommType (13).
+    CommType = 13, {
+        PerUnit => b"1",
+        Percent => b"2",
+        Absolute => b"3",
+        PercentageWaivedCashDiscount => b"4",
+        PercentageWaivedEnhancedUnits => b"5",
+        PointsPerBondOrContract => b"6",
+    }
+);
+
+fix_enum!(
+    /// MiscFeeType (139).
+    MiscFeeType = 139, {
+        Regulatory => b"1",
+        Tax => b"2",
+        LocalCommission => b"3",
+        ExchangeFees => b"4",
+        Stamp => b"5",
+        Levy => b"6",
+        Other => b"7",
+        Markup => b"8",
+        ConsumptionTax => b"9",
+        PerTransaction => b"10",
+        Conversion => b"11",
+        Agent => b"12",
+    }
+);
+
+fix_enum!(
+    /// MDEntryType (269).
+    MdEntryType = 269, {
+        Bid => b"0",
+        Offer => b"1",
+        Trade => b"2",
+        IndexValue => b"3",
+        OpeningPrice => b"4",
+        ClosingPrice => b"5",
+        SettlementPrice => b"6",
+        TradingSessionHighPrice => b"7",
+        TradingSessionLowPrice => b"8",
+        TradingSessionVwapPrice => b"9",
+        Imbalance => b"A",
+        TradeVolume => b"B",
+        OpenInterest => b"C",
+    }
+);
+
+fix_enum!(
+    /// MDUpdateAction (279).
+    MdUpdateAction = 279, {
+        New => b"0",
+        Change => b"1",
+        Delete => b"2",
+        DeleteThru => b"3",
+        DeleteFrom => b"4",
+        Overlay => b"5",
+    }
+);
+
+fix_enum!(
+    /// SessionStatus (1409), on Logon and Logout of FIXT sessions.
+    SessionStatus = 1409, {
+        SessionActive => b"0",
+        SessionPasswordChanged => b"1",
+        SessionPasswordDueToExpire => b"2",
+        NewSessionPasswordDoesNotComplyWithPolicy => b"3",
+        SessionLogoutComplete => b"4",
+        InvalidUsernameOrPassword => b"5",
+        AccountLocked => b"6",
+        LogonsAreNotAllowedAtThisTime => b"7",
+        PasswordExpired => b"8",
+        ReceivedMsgSeqNumTooLow => b"9",
+        ReceivedNextExpectedMsgSeqNumTooHigh => b"10",
+    }
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for side in [Side::Buy, Side::Sell, Side::SellShort, Side::CrossShort] {
+            assert_eq!(Ok(side), Side::try_from(side.as_bytes()));
+        }
+
+        assert_eq!(Ok(OrdType::Pegged), OrdType::try_from(b"P".as_slice()));
+        assert_eq!(b"3", TimeInForce::ImmediateOrCancel.as_bytes());
+        assert_eq!(150, ExecType::TAG);
+    }
+
+    #[test]
+    fn test_with_value() {
+        let mut message = crate::FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(54, Side::Sell);
+        message.with_value(40, OrdType::Limit);
+
+        assert_eq!(Some(b"2".as_slice()), message.get(54));
+        assert_eq!(Ok(Some(OrdType::Limit)), message.get_field::<OrdType>());
+    }
+
+    #[test]
+    fn test_unknown_value() {
+        let result = OrdStatus::try_from(b"Z".as_slice());
+
+        assert!(matches!(
+            result,
+            Err(FieldParseError::InvalidValue { tag: 39, .. })
+        ));
+    }
+}
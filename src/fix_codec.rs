@@ -0,0 +1,271 @@
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{
+    find_body_length_mismatch,
+    fix_frame::{apply_limits, check_declared_len, find_fix_frame_len_with_scan, TrailerScan},
+    BodyLengthPolicy, FixMessageBuilder, FixSerializeError, ParseLimits,
+};
+
+#[derive(Debug)]
+pub enum FixCodecError {
+    Io(std::io::Error),
+    Fix(FixSerializeError),
+}
+
+impl From<std::io::Error> for FixCodecError {
+    fn from(err: std::io::Error) -> Self {
+        return Self::Io(err);
+    }
+}
+
+impl From<FixSerializeError> for FixCodecError {
+    fn from(err: FixSerializeError) -> Self {
+        return Self::Fix(err);
+    }
+}
+
+/// Frames FIX messages on a byte stream using the BodyLength (9) and CheckSum (10) tags.
+///
+/// Use it with `tokio_util::codec::Framed` to get a `Stream`/`Sink` of messages.
+#[derive(Debug, Clone)]
+pub struct FixCodec {
+    check_sum_validation: bool,
+    limits: ParseLimits,
+    body_length_policy: BodyLengthPolicy,
+    trailer_scan: TrailerScan,
+}
+
+impl FixCodec {
+    /// Codec enforcing the default `ParseLimits`.
+    pub fn new(check_sum_validation: bool) -> Self {
+        return Self {
+            check_sum_validation,
+            limits: ParseLimits::default(),
+            body_length_policy: BodyLengthPolicy::default(),
+            trailer_scan: TrailerScan::default(),
+        };
+    }
+
+    pub fn with_limits(&mut self, limits: ParseLimits) {
+        self.limits = limits;
+    }
+
+    pub fn get_limits(&self) -> &ParseLimits {
+        return &self.limits;
+    }
+
+    /// With `BodyLengthPolicy::Report` the mismatch fails the decode and the frame stays
+    /// in the read buffer, e.g. for `Framed::read_buffer_mut`.
+    pub fn with_body_length_policy(&mut self, policy: BodyLengthPolicy) {
+        self.body_length_policy = policy;
+    }
+
+    /// Same as `Decoder::decode`, also returning the frame as it was received, e.g. to
+    /// journal the bytes rather than their re-serialization.
+    pub fn decode_frame(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> Result<Option<(BytesMut, FixMessageBuilder)>, FixCodecError> {
+        // Other policies may still find the trailer before the declared length.
+        if self.body_length_policy == BodyLengthPolicy::TrustBodyLength {
+            check_declared_len(src, &self.limits)?;
+        }
+
+        let result =
+            find_fix_frame_len_with_scan(src, self.body_length_policy, &mut self.trailer_scan);
+
+        // After an error the caller may take bytes out of the buffer, so the scan restarts.
+        let frame_len = match apply_limits(result, src.len(), &self.limits) {
+            Ok(Some(frame_len)) => frame_len,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                self.trailer_scan = TrailerScan::default();
+                return Err(err.into());
+            }
+        };
+
+        self.trailer_scan = TrailerScan::default();
+
+        let frame = src.split_to(frame_len);
+
+        // A frame cut at its trailer had the checksum verified on the received bytes,
+        // which parsing can't do again as it recomputes BodyLength.
+        let is_repaired = self.body_length_policy == BodyLengthPolicy::TrustCheckSum
+            && find_body_length_mismatch(&frame).is_some();
+
+        let message = FixMessageBuilder::from_bytes_with_limits(
+            &frame,
+            self.check_sum_validation && !is_repaired,
+            &self.limits,
+        )?;
+
+        return Ok(Some((frame, message)));
+    }
+}
+
+impl Default for FixCodec {
+    fn default() -> Self {
+        return Self::new(true);
+    }
+}
+
+impl Decoder for FixCodec {
+    type Item = FixMessageBuilder;
+    type Error = FixCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        return Ok(self.decode_frame(src)?.map(|(_, message)| message));
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(buf)? {
+            Some(message) => return Ok(Some(message)),
+            None => {
+                if buf.has_remaining() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "bytes remaining on stream",
+                    )
+                    .into());
+                }
+
+                return Ok(None);
+            }
+        }
+    }
+}
+
+impl Encoder<FixMessageBuilder> for FixCodec {
+    type Error = FixCodecError;
+
+    fn encode(&mut self, item: FixMessageBuilder, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.extend_from_slice(&item.as_bytes());
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_test_message(sequence: &str) -> FixMessageBuilder {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "0");
+        fix_builder.with_value(34, sequence);
+        fix_builder.with_value(49, "TESTBUY1");
+        fix_builder.with_value(56, "TESTSELL1");
+
+        return fix_builder;
+    }
+
+    #[test]
+    fn test_decode_fragmented_stream() {
+        let mut codec = FixCodec::default();
+        let mut buffer = BytesMut::new();
+
+        codec.encode(build_test_message("1"), &mut buffer).unwrap();
+        codec.encode(build_test_message("2"), &mut buffer).unwrap();
+
+        let bytes = buffer.split().freeze();
+        let (head, tail) = bytes.split_at(20);
+
+        buffer.extend_from_slice(head);
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+
+        buffer.extend_from_slice(tail);
+        let first = codec.decode(&mut buffer).unwrap().unwrap();
+        let second = codec.decode(&mut buffer).unwrap().unwrap();
+
+        assert_eq!(Some("1".to_string()), first.get_string_lossy(34));
+        assert_eq!(Some("2".to_string()), second.get_string_lossy(34));
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decode_message_too_large() {
+        let mut codec = FixCodec::default();
+        codec.with_limits(ParseLimits {
+            max_message_size: 64,
+            ..ParseLimits::default()
+        });
+
+        let mut buffer = BytesMut::new();
+        let mut message = build_test_message("1");
+        message.with_value(58, "x".repeat(64));
+
+        // Rejected before the whole message has arrived.
+        buffer.extend_from_slice(&message.as_bytes()[..65]);
+
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(FixCodecError::Fix(FixSerializeError::MessageTooLarge))
+        ));
+
+        // Known from BodyLength alone, which must not overflow the frame offset.
+        let mut buffer = BytesMut::from(&b"8=FIX.4.4\x019=18446744073709551615\x0135=0\x01"[..]);
+
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(FixCodecError::Fix(FixSerializeError::MessageTooLarge))
+        ));
+    }
+
+    #[test]
+    fn test_decode_invalid_check_sum() {
+        let mut codec = FixCodec::default();
+        let mut buffer = BytesMut::new();
+
+        let mut bytes = build_test_message("1").as_bytes();
+        let len = bytes.len();
+        bytes[len - 2] = b'9';
+        buffer.extend_from_slice(&bytes);
+
+        let result = codec.decode(&mut buffer);
+
+        assert!(matches!(
+            result,
+            Err(FixCodecError::Fix(FixSerializeError::InvalidCheckSum))
+        ));
+    }
+
+    #[test]
+    fn test_decode_trusting_check_sum() {
+        let mut codec = FixCodec::default();
+        codec.with_body_length_policy(BodyLengthPolicy::TrustCheckSum);
+
+        let mut message = b"8=FIX.4.4\x019=99\x0135=0\x0134=7\x01".to_vec();
+        let check_sum = crate::utils::checksum_bytes(&message);
+        message.extend_from_slice(b"10=");
+        message.extend_from_slice(&check_sum);
+        message.push(b'\x01');
+
+        let mut buffer = BytesMut::from(message.as_slice());
+        buffer.extend_from_slice(&build_test_message("8").as_bytes());
+
+        let first = codec.decode(&mut buffer).unwrap().unwrap();
+        let second = codec.decode(&mut buffer).unwrap().unwrap();
+
+        assert_eq!(Some("7".to_string()), first.get_string_lossy(34));
+        assert_eq!(Some("8".to_string()), second.get_string_lossy(34));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decode_frame_keeps_received_bytes() {
+        let mut codec = FixCodec::default();
+        codec.with_body_length_policy(BodyLengthPolicy::TrustCheckSum);
+
+        let mut message = b"8=FIX.4.4\x019=99\x0135=0\x0134=7\x01".to_vec();
+        let check_sum = crate::utils::checksum_bytes(&message);
+        message.extend_from_slice(b"10=");
+        message.extend_from_slice(&check_sum);
+        message.push(b'\x01');
+
+        let mut buffer = BytesMut::from(message.as_slice());
+        let (frame, parsed) = codec.decode_frame(&mut buffer).unwrap().unwrap();
+
+        assert_eq!(message, frame.to_vec());
+        assert_ne!(message, parsed.as_bytes());
+    }
+}
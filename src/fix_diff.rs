@@ -0,0 +1,136 @@
+use std::{collections::BTreeSet, fmt};
+
+use crate::FixMessageBuilder;
+
+/// Difference in one field between two messages. Repeated tags are compared by
+/// occurrence, so a group entry missing in the middle shows as changes followed by
+/// a removal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldDiff {
+    Added { tag: u32, value: String },
+    Removed { tag: u32, value: String },
+    Changed { tag: u32, old: String, new: String },
+}
+
+impl FieldDiff {
+    pub fn get_tag(&self) -> u32 {
+        return match self {
+            FieldDiff::Added { tag, .. }
+            | FieldDiff::Removed { tag, .. }
+            | FieldDiff::Changed { tag, .. } => *tag,
+        };
+    }
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            FieldDiff::Added { tag, value } => write!(f, "+ {}={}", tag, value),
+            FieldDiff::Removed { tag, value } => write!(f, "- {}={}", tag, value),
+            FieldDiff::Changed { tag, old, new } => write!(f, "~ {}: {} -> {}", tag, old, new),
+        };
+    }
+}
+
+/// Fields added, removed or changed from `a` to `b` in tag order, BeginString (8) and
+/// MsgType (35) included. Field order isn't compared.
+pub fn fix_diff(a: &FixMessageBuilder, b: &FixMessageBuilder) -> Vec<FieldDiff> {
+    let mut result = vec![];
+    let text = |value: &[u8]| String::from_utf8_lossy(value).to_string();
+
+    let a_version = a.get_fix_version();
+    let b_version = b.get_fix_version();
+
+    if a_version != b_version {
+        result.push(FieldDiff::Changed {
+            tag: 8,
+            old: a_version.as_str().to_string(),
+            new: b_version.as_str().to_string(),
+        });
+    }
+
+    if a.get_message_type() != b.get_message_type() {
+        result.push(FieldDiff::Changed {
+            tag: 35,
+            old: a.get_message_type_as_string(),
+            new: b.get_message_type_as_string(),
+        });
+    }
+
+    let a_fields = a.get_fields_by_tag(&[]);
+    let b_fields = b.get_fields_by_tag(&[]);
+    let tags: BTreeSet<u32> = a_fields.keys().chain(b_fields.keys()).copied().collect();
+
+    for tag in tags {
+        let a_values = a_fields.get(&tag).map(Vec::as_slice).unwrap_or_default();
+        let b_values = b_fields.get(&tag).map(Vec::as_slice).unwrap_or_default();
+
+        for index in 0..a_values.len().max(b_values.len()) {
+            match (a_values.get(index), b_values.get(index)) {
+                (Some(old), Some(new)) if old != new => result.push(FieldDiff::Changed {
+                    tag,
+                    old: text(old),
+                    new: text(new),
+                }),
+                (Some(old), None) => result.push(FieldDiff::Removed {
+                    tag,
+                    value: text(old),
+                }),
+                (None, Some(new)) => result.push(FieldDiff::Added {
+                    tag,
+                    value: text(new),
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    result.sort_by_key(|diff| diff.get_tag());
+    return result;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fix_diff() {
+        let mut yesterday = FixMessageBuilder::new("FIX.4.4", "D");
+        yesterday.with_value(11, "ORDER1");
+        yesterday.with_value(38, 100u64);
+        yesterday.with_value(58, "note");
+
+        let mut today = FixMessageBuilder::new("FIX.4.4", "G");
+        today.with_value(11, "ORDER1");
+        today.with_value(38, 200u64);
+        today.with_value(1, "ACC1");
+
+        let diff = fix_diff(&yesterday, &today);
+
+        assert_eq!(
+            vec![
+                FieldDiff::Added {
+                    tag: 1,
+                    value: "ACC1".to_string()
+                },
+                FieldDiff::Changed {
+                    tag: 35,
+                    old: "D".to_string(),
+                    new: "G".to_string()
+                },
+                FieldDiff::Changed {
+                    tag: 38,
+                    old: "100".to_string(),
+                    new: "200".to_string()
+                },
+                FieldDiff::Removed {
+                    tag: 58,
+                    value: "note".to_string()
+                },
+            ],
+            diff
+        );
+        assert_eq!("~ 38: 100 -> 200", diff[2].to_string());
+        assert!(fix_diff(&today, &today).is_empty());
+    }
+}
@@ -0,0 +1,765 @@
+use crate::{
+    checksum_u8, format_checksum, parse_checksum, FixMessage, FixMessageBuilder, FixSerializeError,
+    ParseLimits, FIX_DELIMETR,
+};
+
+const FIX_VERSION_PREFIX: &[u8] = b"8=";
+const FIX_BODY_LEN_PREFIX: &[u8] = b"9=";
+const FIX_CHECK_SUM_PREFIX: &[u8] = b"10=";
+// `10=` and three digits and SOH.
+const CHECK_SUM_TRAILER_LEN: usize = 7;
+
+/// Which of BodyLength (9) and CheckSum (10) to believe when they disagree: BodyLength
+/// doesn't point at the trailer, but a `10=` trailer matching the checksum of the bytes
+/// before it is found elsewhere. Venues differ on which one they get wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyLengthPolicy {
+    /// Frame by BodyLength alone: wait for more bytes when it runs past the trailer,
+    /// fail with `InvalidBodyLength` when it stops short of it.
+    #[default]
+    TrustBodyLength,
+    /// Frame at the trailer, whose checksum has then been verified on the received
+    /// bytes. Parse such a frame without checksum validation: the parser checks the
+    /// checksum of the message as rewritten, with the right BodyLength.
+    TrustCheckSum,
+    /// Fail with `BodyLengthMismatch`, leaving the caller to take or drop the frame
+    /// after looking at `find_body_length_mismatch`.
+    Report,
+}
+
+/// BodyLength (9) against the body length up to the trailer that holds the checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLengthMismatch {
+    pub declared: usize,
+    pub actual: usize,
+    /// Length of the message up to the SOH after that trailer.
+    pub frame_len: usize,
+}
+
+/// Returns the length of the first complete FIX message in `buffer`.
+///
+/// `Ok(None)` means the buffer holds only the beginning of a message and more
+/// bytes are needed. The message must start at the first byte of the buffer.
+pub fn find_fix_frame_len(buffer: &[u8]) -> Result<Option<usize>, FixSerializeError> {
+    let Some((body_start, body_len)) = find_body_start(buffer)? else {
+        return Ok(None);
+    };
+
+    let Some(check_sum_start) = body_start.checked_add(body_len) else {
+        return Err(FixSerializeError::InvalidBodyLength);
+    };
+
+    if buffer.len() <= check_sum_start {
+        return Ok(None);
+    }
+
+    let trailer = &buffer[check_sum_start..];
+
+    if trailer.len() < FIX_CHECK_SUM_PREFIX.len() {
+        if FIX_CHECK_SUM_PREFIX.starts_with(trailer) {
+            return Ok(None);
+        }
+
+        return Err(FixSerializeError::InvalidBodyLength);
+    }
+
+    if !trailer.starts_with(FIX_CHECK_SUM_PREFIX) {
+        return Err(FixSerializeError::InvalidBodyLength);
+    }
+
+    let Some(check_sum_end) = find_delimeter(buffer, check_sum_start) else {
+        return Ok(None);
+    };
+
+    return Ok(Some(check_sum_end + 1));
+}
+
+/// Same as `find_fix_frame_len`, with `policy` deciding what happens when BodyLength
+/// misses the trailer but a trailer with the right checksum is there.
+pub fn find_fix_frame_len_with_policy(
+    buffer: &[u8],
+    policy: BodyLengthPolicy,
+) -> Result<Option<usize>, FixSerializeError> {
+    return find_fix_frame_len_with_scan(buffer, policy, &mut TrailerScan::default());
+}
+
+// Where the search for a trailer stopped in a buffer still waiting for the rest of a
+// message, so stream decoders only scan the bytes appended since. Reset it whenever
+// the start of the buffer moves.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TrailerScan {
+    position: usize,
+    sum: u8,
+}
+
+pub(crate) fn find_fix_frame_len_with_scan(
+    buffer: &[u8],
+    policy: BodyLengthPolicy,
+    scan: &mut TrailerScan,
+) -> Result<Option<usize>, FixSerializeError> {
+    let result = find_fix_frame_len(buffer);
+
+    if policy == BodyLengthPolicy::TrustBodyLength
+        || !matches!(result, Ok(None) | Err(FixSerializeError::InvalidBodyLength))
+    {
+        return result;
+    }
+
+    let Some(mismatch) = find_body_length_mismatch_with_scan(buffer, scan) else {
+        return result;
+    };
+
+    if policy == BodyLengthPolicy::TrustCheckSum {
+        return Ok(Some(mismatch.frame_len));
+    }
+
+    return Err(FixSerializeError::BodyLengthMismatch);
+}
+
+/// How BodyLength disagrees with the first trailer that holds the checksum of the bytes
+/// before it. `None` when they agree, no such trailer is in `buffer` yet or the header is
+/// unreadable.
+pub fn find_body_length_mismatch(buffer: &[u8]) -> Option<BodyLengthMismatch> {
+    return find_body_length_mismatch_with_scan(buffer, &mut TrailerScan::default());
+}
+
+fn find_body_length_mismatch_with_scan(
+    buffer: &[u8],
+    scan: &mut TrailerScan,
+) -> Option<BodyLengthMismatch> {
+    let (body_start, declared) = find_body_start(buffer).ok()??;
+    let frame_len = find_check_sum_trailer(buffer, body_start, scan)?;
+    let actual = frame_len - CHECK_SUM_TRAILER_LEN - body_start;
+
+    if actual == declared {
+        return None;
+    }
+
+    return Some(BodyLengthMismatch {
+        declared,
+        actual,
+        frame_len,
+    });
+}
+
+/// Same as `find_fix_frame_len`, but fails with `MessageTooLarge` as soon as the
+/// message is known to be larger than `limits.max_message_size`, so callers never
+/// buffer more than that while waiting for the rest of a message.
+pub fn find_fix_frame_len_with_limits(
+    buffer: &[u8],
+    limits: &ParseLimits,
+) -> Result<Option<usize>, FixSerializeError> {
+    check_declared_len(buffer, limits)?;
+
+    return apply_limits(find_fix_frame_len(buffer), buffer.len(), limits);
+}
+
+// Fails with `MessageTooLarge` when the declared BodyLength alone puts the message over
+// `limits.max_message_size`, before any offset is computed from it.
+pub(crate) fn check_declared_len(
+    buffer: &[u8],
+    limits: &ParseLimits,
+) -> Result<(), FixSerializeError> {
+    let Ok(Some((body_start, body_len))) = find_body_start(buffer) else {
+        return Ok(());
+    };
+
+    let max_body_len = limits
+        .max_message_size
+        .saturating_sub(body_start + CHECK_SUM_TRAILER_LEN);
+
+    if body_len > max_body_len {
+        return Err(FixSerializeError::MessageTooLarge);
+    }
+
+    return Ok(());
+}
+
+// Turns a frame that is, or would be, over `limits.max_message_size` into `MessageTooLarge`.
+pub(crate) fn apply_limits(
+    result: Result<Option<usize>, FixSerializeError>,
+    buffer_len: usize,
+    limits: &ParseLimits,
+) -> Result<Option<usize>, FixSerializeError> {
+    return match result? {
+        Some(frame_len) if frame_len > limits.max_message_size => {
+            Err(FixSerializeError::MessageTooLarge)
+        }
+        None if buffer_len > limits.max_message_size => Err(FixSerializeError::MessageTooLarge),
+        result => Ok(result),
+    };
+}
+
+/// Buffers bytes as they come off a stream and cuts them into complete FIX messages,
+/// however the reads split or coalesce them.
+///
+/// Frames are found with `find_fix_frame_len_with_policy` and are returned as received,
+/// e.g. for `FixMessageView::from_bytes`. After an error the offending bytes stay
+/// buffered, so the same error comes back until the decoder is cleared.
+#[derive(Debug, Clone, Default)]
+pub struct FixFrameDecoder {
+    buffer: Vec<u8>,
+    // Bytes of the buffer already returned as frames, dropped on the next `feed`.
+    consumed: usize,
+    limits: ParseLimits,
+    body_length_policy: BodyLengthPolicy,
+    trailer_scan: TrailerScan,
+}
+
+impl FixFrameDecoder {
+    /// Decoder enforcing the default `ParseLimits`.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// A message over `limits.max_message_size` fails with `MessageTooLarge` before it
+    /// is buffered in full.
+    pub fn with_limits(&mut self, limits: ParseLimits) {
+        self.limits = limits;
+    }
+
+    pub fn get_limits(&self) -> &ParseLimits {
+        return &self.limits;
+    }
+
+    /// With `BodyLengthPolicy::TrustCheckSum`, frames whose BodyLength was wrong are
+    /// still returned; tell them apart with `find_body_length_mismatch`.
+    pub fn with_body_length_policy(&mut self, policy: BodyLengthPolicy) {
+        self.body_length_policy = policy;
+    }
+
+    /// Appends bytes read from the stream.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        if self.consumed > 0 {
+            self.buffer.drain(..self.consumed);
+            self.consumed = 0;
+        }
+
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// The next complete message, or `None` until more bytes are fed.
+    pub fn next_frame(&mut self) -> Result<Option<&[u8]>, FixSerializeError> {
+        let buffer = &self.buffer[self.consumed..];
+
+        // Other policies may still find the trailer before the declared length.
+        if self.body_length_policy == BodyLengthPolicy::TrustBodyLength {
+            check_declared_len(buffer, &self.limits)?;
+        }
+
+        let result =
+            find_fix_frame_len_with_scan(buffer, self.body_length_policy, &mut self.trailer_scan);
+
+        let Some(frame_len) = apply_limits(result, buffer.len(), &self.limits)? else {
+            return Ok(None);
+        };
+
+        self.trailer_scan = TrailerScan::default();
+        let start = self.consumed;
+        self.consumed += frame_len;
+
+        return Ok(Some(&self.buffer[start..self.consumed]));
+    }
+
+    /// Bytes fed but not returned as frames yet.
+    pub fn len(&self) -> usize {
+        return self.buffer.len() - self.consumed;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Drops the buffered bytes, e.g. after an error before reading on.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.consumed = 0;
+        self.trailer_scan = TrailerScan::default();
+    }
+}
+
+/// Splits a buffer holding several back-to-back FIX messages and parses each of them.
+///
+/// Returns the parsed messages, the number of bytes they occupied and the error that
+/// stopped parsing, if any. Bytes after `consumed` are either a trailing partial message
+/// that should be kept for the next read or, when an error is returned, the offending
+/// message.
+pub fn parse_all(
+    buffer: &[u8],
+    check_sum_validation: bool,
+) -> (Vec<FixMessageBuilder>, usize, Option<FixSerializeError>) {
+    let mut messages = vec![];
+    let mut consumed = 0;
+
+    while consumed < buffer.len() {
+        let frame_len = match find_fix_frame_len(&buffer[consumed..]) {
+            Ok(Some(frame_len)) => frame_len,
+            Ok(None) => break,
+            Err(err) => return (messages, consumed, Some(err)),
+        };
+
+        let frame = &buffer[consumed..consumed + frame_len];
+
+        match FixMessageBuilder::from_bytes(frame, check_sum_validation) {
+            Ok(message) => messages.push(message),
+            Err(err) => return (messages, consumed, Some(err)),
+        }
+
+        consumed += frame_len;
+    }
+
+    return (messages, consumed, None);
+}
+
+/// Appends the serialized messages to `buffer` back to back, reserving the space for
+/// all of them at once.
+pub fn serialize_batch(messages: &[FixMessage], buffer: &mut Vec<u8>) {
+    let len = messages
+        .iter()
+        .map(|message| message.as_bytes().len())
+        .sum();
+    buffer.reserve(len);
+
+    for message in messages {
+        buffer.extend_from_slice(message.as_bytes());
+    }
+}
+
+/// Reads back a buffer written by `serialize_batch`, keeping each message's bytes as
+/// they were written. Unlike `parse_all` the whole buffer has to be complete messages;
+/// a truncated last one is `InvalidBodyLength`.
+pub fn deserialize_batch(
+    buffer: &[u8],
+    check_sum_validation: bool,
+) -> Result<Vec<FixMessage>, FixSerializeError> {
+    let mut messages = vec![];
+    let mut consumed = 0;
+
+    while consumed < buffer.len() {
+        let Some(frame_len) = find_fix_frame_len(&buffer[consumed..])? else {
+            return Err(FixSerializeError::InvalidBodyLength);
+        };
+
+        let frame = &buffer[consumed..consumed + frame_len];
+        let builder = FixMessageBuilder::from_bytes(frame, check_sum_validation)?;
+        let check_sum = read_check_sum(frame, check_sum_validation)?;
+        messages.push(FixMessage::new(builder, frame.to_vec(), check_sum));
+
+        consumed += frame_len;
+    }
+
+    return Ok(messages);
+}
+
+// CheckSum (10) of a frame cut by `find_fix_frame_len`, whose trailer starts where
+// BodyLength points. Kept as received when it isn't three digits and isn't validated.
+fn read_check_sum(frame: &[u8], check_sum_validation: bool) -> Result<String, FixSerializeError> {
+    let Some((body_start, body_len)) = find_body_start(frame)? else {
+        return Err(FixSerializeError::InvalidBodyLength);
+    };
+
+    let value = &frame[body_start + body_len + FIX_CHECK_SUM_PREFIX.len()..frame.len() - 1];
+
+    return match parse_checksum(value) {
+        Some(sum) => Ok(String::from_utf8_lossy(&format_checksum(sum)).to_string()),
+        None if !check_sum_validation => Ok(String::from_utf8_lossy(value).to_string()),
+        None => Err(FixSerializeError::InvalidCheckSum),
+    };
+}
+
+// Where the body starts, right after BodyLength, and the BodyLength value. `Ok(None)`
+// until the BodyLength field is complete.
+fn find_body_start(buffer: &[u8]) -> Result<Option<(usize, usize)>, FixSerializeError> {
+    if buffer.len() < FIX_VERSION_PREFIX.len() {
+        if FIX_VERSION_PREFIX.starts_with(buffer) {
+            return Ok(None);
+        }
+
+        return Err(FixSerializeError::VersionTagNotFoundInSource);
+    }
+
+    if !buffer.starts_with(FIX_VERSION_PREFIX) {
+        return Err(FixSerializeError::VersionTagNotFoundInSource);
+    }
+
+    let Some(version_end) = find_delimeter(buffer, 0) else {
+        return Ok(None);
+    };
+
+    let body_len_start = version_end + 1;
+    let rest = &buffer[body_len_start..];
+
+    if rest.len() < FIX_BODY_LEN_PREFIX.len() {
+        if FIX_BODY_LEN_PREFIX.starts_with(rest) {
+            return Ok(None);
+        }
+
+        return Err(FixSerializeError::BodyLengthTagNotFoundInSource);
+    }
+
+    if !rest.starts_with(FIX_BODY_LEN_PREFIX) {
+        return Err(FixSerializeError::BodyLengthTagNotFoundInSource);
+    }
+
+    let Some(body_len_end) = find_delimeter(buffer, body_len_start) else {
+        return Ok(None);
+    };
+
+    let body_len =
+        parse_body_len(&buffer[body_len_start + FIX_BODY_LEN_PREFIX.len()..body_len_end])?;
+
+    return Ok(Some((body_len_end + 1, body_len)));
+}
+
+// Frame length up to the first complete `10=` trailer after `body_start` holding the
+// checksum of the bytes before it. Resumes from `scan`, leaving it at the first position
+// with too few bytes after it to hold a trailer.
+fn find_check_sum_trailer(
+    buffer: &[u8],
+    body_start: usize,
+    scan: &mut TrailerScan,
+) -> Option<usize> {
+    if scan.position < body_start || scan.position > buffer.len() {
+        scan.position = body_start;
+        scan.sum = checksum_u8(&buffer[..body_start]);
+    }
+
+    let mut sum = scan.sum;
+    let scan_end = buffer.len().saturating_sub(CHECK_SUM_TRAILER_LEN - 1);
+
+    for position in scan.position..scan_end.max(scan.position) {
+        let trailer = &buffer[position..];
+
+        if buffer[position - 1] == FIX_DELIMETR
+            && trailer.starts_with(FIX_CHECK_SUM_PREFIX)
+            && trailer[CHECK_SUM_TRAILER_LEN - 1] == FIX_DELIMETR
+            && parse_checksum(&trailer[FIX_CHECK_SUM_PREFIX.len()..CHECK_SUM_TRAILER_LEN - 1])
+                == Some(sum)
+        {
+            return Some(position + CHECK_SUM_TRAILER_LEN);
+        }
+
+        sum = sum.wrapping_add(buffer[position]);
+    }
+
+    scan.position = scan_end.max(scan.position);
+    scan.sum = sum;
+
+    return None;
+}
+
+fn find_delimeter(buffer: &[u8], from: usize) -> Option<usize> {
+    return buffer[from..]
+        .iter()
+        .position(|byte| byte == &FIX_DELIMETR)
+        .map(|position| from + position);
+}
+
+fn parse_body_len(value: &[u8]) -> Result<usize, FixSerializeError> {
+    if value.is_empty() || !value.iter().all(|byte| byte.is_ascii_digit()) {
+        return Err(FixSerializeError::InvalidBodyLength);
+    }
+
+    let mut result: usize = 0;
+
+    for byte in value {
+        result = result
+            .checked_mul(10)
+            .and_then(|result| result.checked_add((byte - b'0') as usize))
+            .ok_or(FixSerializeError::InvalidBodyLength)?;
+    }
+
+    return Ok(result);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FixMessageView;
+
+    fn build_test_message(sequence: &str) -> Vec<u8> {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "A");
+        fix_builder.with_value(34, sequence);
+        fix_builder.with_value(49, "TESTBUY1");
+        fix_builder.with_value(56, "TESTSELL1");
+
+        return fix_builder.as_bytes();
+    }
+
+    #[test]
+    fn test_find_frame_len() {
+        let message = build_test_message("1");
+
+        let result = find_fix_frame_len(&message).unwrap();
+
+        assert_eq!(Some(message.len()), result);
+    }
+
+    #[test]
+    fn test_find_frame_len_partial() {
+        let message = build_test_message("1");
+
+        for len in 0..message.len() {
+            let result = find_fix_frame_len(&message[..len]).unwrap();
+            assert_eq!(None, result);
+        }
+    }
+
+    #[test]
+    fn test_find_frame_len_body_len_overflow() {
+        let message = b"8=FIX.4.4\x019=18446744073709551615\x0135=0\x01";
+
+        assert!(matches!(
+            find_fix_frame_len(message),
+            Err(FixSerializeError::InvalidBodyLength)
+        ));
+        assert!(matches!(
+            find_fix_frame_len_with_limits(message, &ParseLimits::default()),
+            Err(FixSerializeError::MessageTooLarge)
+        ));
+        assert!(matches!(
+            deserialize_batch(message, true),
+            Err(FixSerializeError::InvalidBodyLength)
+        ));
+
+        let mut decoder = FixFrameDecoder::new();
+        decoder.feed(message);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(FixSerializeError::MessageTooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_find_frame_len_invalid_body_len() {
+        let message = b"8=FIX.4.4\x019=5\x0135=A\x0134=1\x0110=000\x01";
+
+        let result = find_fix_frame_len(message);
+
+        assert!(matches!(result, Err(FixSerializeError::InvalidBodyLength)));
+    }
+
+    fn with_body_len(body_len: &str, body: &[u8]) -> Vec<u8> {
+        let mut message = format!("8=FIX.4.4\x019={}\x01", body_len).into_bytes();
+        message.extend_from_slice(body);
+        let check_sum = crate::utils::checksum_bytes(&message);
+        message.extend_from_slice(b"10=");
+        message.extend_from_slice(&check_sum);
+        message.push(FIX_DELIMETR);
+
+        return message;
+    }
+
+    #[test]
+    fn test_body_length_policy() {
+        let body = b"35=A\x0134=1\x01";
+        let too_short = with_body_len("5", body);
+        let too_long = with_body_len("40", body);
+
+        assert_eq!(
+            Ok(Some(too_short.len())),
+            find_fix_frame_len_with_policy(&too_short, BodyLengthPolicy::TrustCheckSum)
+        );
+        assert_eq!(
+            Ok(Some(too_long.len())),
+            find_fix_frame_len_with_policy(&too_long, BodyLengthPolicy::TrustCheckSum)
+        );
+        assert_eq!(
+            Err(FixSerializeError::BodyLengthMismatch),
+            find_fix_frame_len_with_policy(&too_short, BodyLengthPolicy::Report)
+        );
+        assert_eq!(
+            Some(BodyLengthMismatch {
+                declared: 5,
+                actual: 10,
+                frame_len: too_short.len(),
+            }),
+            find_body_length_mismatch(&too_short)
+        );
+        assert_eq!(
+            Err(FixSerializeError::InvalidBodyLength),
+            find_fix_frame_len_with_policy(&too_short, BodyLengthPolicy::TrustBodyLength)
+        );
+        assert_eq!(
+            Ok(None),
+            find_fix_frame_len_with_policy(&too_long, BodyLengthPolicy::TrustBodyLength)
+        );
+
+        // A trailer with a wrong checksum is no evidence against BodyLength.
+        let mut bad_check_sum = too_short.clone();
+        let len = bad_check_sum.len();
+        bad_check_sum[len - 2] = if bad_check_sum[len - 2] == b'0' {
+            b'1'
+        } else {
+            b'0'
+        };
+        assert_eq!(
+            Err(FixSerializeError::InvalidBodyLength),
+            find_fix_frame_len_with_policy(&bad_check_sum, BodyLengthPolicy::TrustCheckSum)
+        );
+
+        let message = build_test_message("1");
+        assert_eq!(None, find_body_length_mismatch(&message));
+        assert_eq!(
+            Ok(Some(message.len())),
+            find_fix_frame_len_with_policy(&message, BodyLengthPolicy::Report)
+        );
+        assert_eq!(
+            Ok(None),
+            find_fix_frame_len_with_policy(&message[..20], BodyLengthPolicy::Report)
+        );
+    }
+
+    #[test]
+    fn test_parse_all_with_partial_tail() {
+        let first = build_test_message("1");
+        let second = build_test_message("2");
+        let third = build_test_message("3");
+
+        let mut buffer = vec![];
+        buffer.extend_from_slice(&first);
+        buffer.extend_from_slice(&second);
+        buffer.extend_from_slice(&third[..10]);
+
+        let (messages, consumed, err) = parse_all(&buffer, true);
+
+        assert!(err.is_none());
+        assert_eq!(2, messages.len());
+        assert_eq!(first.len() + second.len(), consumed);
+        assert_eq!(Some("2".to_string()), messages[1].get_string_lossy(34));
+    }
+
+    #[test]
+    fn test_parse_all_stops_on_garbage() {
+        let first = build_test_message("1");
+
+        let mut buffer = vec![];
+        buffer.extend_from_slice(&first);
+        buffer.extend_from_slice(b"garbage");
+
+        let (messages, consumed, err) = parse_all(&buffer, true);
+
+        assert_eq!(1, messages.len());
+        assert_eq!(first.len(), consumed);
+        assert!(matches!(
+            err,
+            Some(FixSerializeError::VersionTagNotFoundInSource)
+        ));
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let messages: Vec<FixMessage> = ["1", "2", "3"]
+            .iter()
+            .map(|sequence| FixMessage::from_bytes(&build_test_message(sequence), true).unwrap())
+            .collect();
+
+        let mut buffer = vec![];
+        serialize_batch(&messages, &mut buffer);
+
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.as_bytes().len())
+                .sum::<usize>(),
+            buffer.len()
+        );
+        let read = deserialize_batch(&buffer, true).unwrap();
+        assert_eq!(messages, read);
+        assert_eq!(messages[2].get_check_sum(), read[2].get_check_sum());
+        assert_eq!(
+            Err(FixSerializeError::InvalidBodyLength),
+            deserialize_batch(&buffer[..buffer.len() - 1], true)
+        );
+        assert_eq!(Ok(vec![]), deserialize_batch(&[], true));
+
+        // A CheckSum that isn't three digits isn't cut out of the last three bytes.
+        let short = b"8=FIX.4.4\x019=5\x0135=0\x0110=7\x01";
+        assert_eq!(
+            "7",
+            deserialize_batch(short, false).unwrap()[0].get_check_sum()
+        );
+        assert_eq!(
+            Err(FixSerializeError::InvalidCheckSum),
+            deserialize_batch(short, true)
+        );
+    }
+
+    #[test]
+    fn test_frame_decoder_scans_for_trailer_incrementally() {
+        let too_long = with_body_len("99", b"35=0\x0134=7\x0158=padding\x01");
+        let (body_start, _) = find_body_start(&too_long).unwrap().unwrap();
+        let mut stream = too_long.clone();
+        stream.extend_from_slice(&build_test_message("8"));
+
+        let mut decoder = FixFrameDecoder::new();
+        decoder.with_body_length_policy(BodyLengthPolicy::TrustCheckSum);
+        let mut frames = vec![];
+
+        for (index, byte) in stream.iter().enumerate() {
+            decoder.feed(&[*byte]);
+
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                frames.push(frame.to_vec());
+            }
+
+            // Only the bytes too close to the end to hold a trailer are scanned again.
+            let fed = index + 1;
+            if fed < too_long.len() && fed >= body_start + CHECK_SUM_TRAILER_LEN - 1 {
+                assert_eq!(
+                    fed + 1 - CHECK_SUM_TRAILER_LEN,
+                    decoder.trailer_scan.position
+                );
+            }
+        }
+
+        assert_eq!(vec![too_long, build_test_message("8")], frames);
+    }
+
+    #[test]
+    fn test_frame_decoder() {
+        let mut stream = build_test_message("1");
+        stream.extend_from_slice(&build_test_message("2"));
+        stream.extend_from_slice(&build_test_message("3")[..20]);
+
+        let mut decoder = FixFrameDecoder::new();
+        let mut seq_nums = vec![];
+
+        // Reads of 7 bytes cut through every field and message boundary.
+        for chunk in stream.chunks(7) {
+            decoder.feed(chunk);
+
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                let view = FixMessageView::from_bytes(frame, true).unwrap();
+                seq_nums.push(view.get_msg_seq_num().unwrap().unwrap());
+            }
+        }
+
+        assert_eq!(vec![1, 2], seq_nums);
+        assert_eq!(20, decoder.len());
+
+        decoder.feed(&build_test_message("3")[20..]);
+        assert_eq!(
+            Some(build_test_message("3").as_slice()),
+            decoder.next_frame().unwrap()
+        );
+        assert!(decoder.is_empty());
+
+        decoder.feed(b"garbage");
+        assert_eq!(
+            Err(FixSerializeError::VersionTagNotFoundInSource),
+            decoder.next_frame()
+        );
+        decoder.clear();
+
+        decoder.with_limits(ParseLimits {
+            max_message_size: 16,
+            ..ParseLimits::default()
+        });
+        decoder.feed(&build_test_message("4"));
+        assert_eq!(
+            Err(FixSerializeError::MessageTooLarge),
+            decoder.next_frame()
+        );
+    }
+}
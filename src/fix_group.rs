@@ -0,0 +1,327 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    fields::FixField,
+    group::{read_group, GroupEntry},
+    FieldParseError, FixDictionary, FixMessageBuilder, FixMessageView, FixValue,
+};
+
+/// One entry of a repeating group, its fields in wire order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixGroupEntry {
+    fields: Vec<(u32, Vec<u8>)>,
+}
+
+/// A repeating group to write with `FixMessageBuilder::with_group`: the count tag,
+/// e.g. NoMDEntries (268), followed by the entries in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixGroup {
+    count_tag: u32,
+    entries: Vec<FixGroupEntry>,
+}
+
+impl FixGroupEntry {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Appends a field; the first one of every entry is the group's delimiter.
+    pub fn with_value(&mut self, tag: u32, value: impl Into<FixValue>) {
+        self.fields.push((tag, value.into().into_bytes()));
+    }
+
+    pub fn with_field<F: FixField>(&mut self, field: F) {
+        self.fields.push((F::TAG, field.as_bytes().to_vec()));
+    }
+
+    /// Appends a group nested in this entry, e.g. NoPartyIDs (453) in an allocation.
+    pub fn with_group(&mut self, group: &FixGroup) {
+        group.write(&mut self.fields);
+    }
+
+    fn from_fields(fields: &[(u32, &[u8])]) -> Self {
+        return Self {
+            fields: fields
+                .iter()
+                .map(|(tag, value)| (*tag, value.to_vec()))
+                .collect(),
+        };
+    }
+
+    /// First value of `tag` in the entry, nested groups included.
+    pub fn get(&self, tag: u32) -> Option<&[u8]> {
+        return self
+            .fields
+            .iter()
+            .find(|(inner, _)| *inner == tag)
+            .map(|(_, value)| value.as_slice());
+    }
+
+    pub fn get_as<T>(&self, tag: u32) -> Result<Option<T>, FieldParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        let Ok(value) = std::str::from_utf8(value) else {
+            return Err(FieldParseError::InvalidUtf8 { tag });
+        };
+
+        return match value.parse() {
+            Ok(result) => Ok(Some(result)),
+            Err(err) => Err(FieldParseError::InvalidValue {
+                tag,
+                value: value.to_string(),
+                reason: err.to_string(),
+            }),
+        };
+    }
+
+    pub fn get_string_lossy(&self, tag: u32) -> Option<String> {
+        return self
+            .get(tag)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+    }
+
+    /// Fields of the entry in order, starting with the delimiter.
+    pub fn get_fields(&self) -> impl Iterator<Item = (u32, &[u8])> {
+        return self
+            .fields
+            .iter()
+            .map(|(tag, value)| (*tag, value.as_slice()));
+    }
+
+    /// Entries of a group nested in this entry, see `FixMessageBuilder::get_group`.
+    pub fn get_group(
+        &self,
+        count_tag: u32,
+        members: &[u32],
+    ) -> Result<Vec<FixGroupEntry>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return to_entries(read_group(&fields, count_tag, members)?);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.fields.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.fields.is_empty();
+    }
+}
+
+impl FixGroup {
+    pub fn new(count_tag: u32) -> Self {
+        return Self::from_entries(count_tag, vec![]);
+    }
+
+    /// A group of entries read with `FixMessageBuilder::get_group`, e.g. to copy it.
+    pub fn from_entries(count_tag: u32, entries: Vec<FixGroupEntry>) -> Self {
+        return Self { count_tag, entries };
+    }
+
+    /// Starts a new entry and returns it to fill in.
+    pub fn add_entry(&mut self) -> &mut FixGroupEntry {
+        self.entries.push(FixGroupEntry::new());
+        return self.entries.last_mut().unwrap();
+    }
+
+    pub fn with_entry(&mut self, entry: FixGroupEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn get_count_tag(&self) -> u32 {
+        return self.count_tag;
+    }
+
+    pub fn get_entries(&self) -> &[FixGroupEntry] {
+        return &self.entries;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    // An empty group is left out rather than written with a count of 0.
+    fn write(&self, fields: &mut Vec<(u32, Vec<u8>)>) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        fields.push((self.count_tag, self.entries.len().to_string().into_bytes()));
+
+        for entry in &self.entries {
+            fields.extend(entry.fields.iter().cloned());
+        }
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends the count tag of `group` and its entries, nested groups included.
+    pub fn with_group(&mut self, group: &FixGroup) {
+        let mut fields = vec![];
+        group.write(&mut fields);
+
+        for (tag, value) in fields {
+            self.with_value(tag, value);
+        }
+    }
+
+    /// Entries of the repeating group counted by `count_tag`, e.g. NoMDEntries (268).
+    ///
+    /// The tag right after the count tag delimits the entries; an entry runs while its
+    /// tags are in `members`, which has to list the tags of nested groups too. Empty
+    /// when the count tag is absent, an error when the entries found don't match the
+    /// count. Relies on the fields being in wire order, which the builder keeps whether
+    /// the message was built or parsed.
+    pub fn get_group(
+        &self,
+        count_tag: u32,
+        members: &[u32],
+    ) -> Result<Vec<FixGroupEntry>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return to_entries(read_group(&fields, count_tag, members)?);
+    }
+
+    /// Same as `get_group`, with the members taken from the dictionary's definition of
+    /// the group in this MsgType or the header.
+    pub fn get_group_with_dictionary(
+        &self,
+        count_tag: u32,
+        dictionary: &FixDictionary,
+    ) -> Result<Vec<FixGroupEntry>, FieldParseError> {
+        let msg_type = self.get_message_type_as_string();
+        let group = dictionary
+            .get_message(&msg_type)
+            .and_then(|message| message.get_group(count_tag))
+            .or_else(|| dictionary.get_header_group(count_tag));
+
+        let Some(group) = group else {
+            return Err(FieldParseError::InvalidValue {
+                tag: count_tag,
+                value: self.get_string_lossy(count_tag).unwrap_or_default(),
+                reason: format!("no group {} in MsgType {}", count_tag, msg_type),
+            });
+        };
+
+        return self.get_group(count_tag, &group.get_member_tags());
+    }
+}
+
+impl FixMessageView<'_> {
+    /// Same as `FixMessageBuilder::get_group`; the entries are copied out of the payload.
+    pub fn get_group(
+        &self,
+        count_tag: u32,
+        members: &[u32],
+    ) -> Result<Vec<FixGroupEntry>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return to_entries(read_group(&fields, count_tag, members)?);
+    }
+}
+
+fn to_entries(entries: Vec<GroupEntry>) -> Result<Vec<FixGroupEntry>, FieldParseError> {
+    return Ok(entries
+        .iter()
+        .map(|entry| FixGroupEntry::from_fields(entry))
+        .collect());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GroupDef, MemberDef, MessageDef};
+
+    #[test]
+    fn test_get_group() {
+        let (message, _) = FixMessageBuilder::from_bytes_lenient(
+            b"8=FIX.4.4\x019=0\x0135=W\x01262=MD1\x0155=EURUSD\x01268=2\x01\
+              269=0\x01270=1.0851\x01271=1000000\x01\
+              269=1\x01270=1.0853\x01271=2000000\x01\
+              58=done\x0110=000\x01",
+        );
+
+        let entries = message.get_group(268, &[269, 270, 271]).unwrap();
+        assert_eq!(2, entries.len());
+        assert_eq!(Some(b"1".as_slice()), entries[1].get(269));
+        assert_eq!(Ok(Some(1.0853)), entries[1].get_as::<f64>(270));
+        assert_eq!(3, entries[0].len());
+        assert_eq!(None, entries[0].get(58));
+
+        assert!(message.get_group(146, &[55]).unwrap().is_empty());
+        assert!(matches!(
+            message.get_group(268, &[269, 270]),
+            Err(FieldParseError::InvalidValue { tag: 268, .. })
+        ));
+
+        let mut dictionary = FixDictionary::new("FIX.4.4");
+        dictionary.add_message(MessageDef {
+            msg_type: "W".to_string(),
+            name: "MarketDataSnapshotFullRefresh".to_string(),
+            is_admin: false,
+            members: vec![MemberDef::Group(GroupDef {
+                count_tag: 268,
+                required: true,
+                members: [269, 270, 271]
+                    .into_iter()
+                    .map(|tag| MemberDef::Field {
+                        tag,
+                        required: false,
+                    })
+                    .collect(),
+            })],
+        });
+
+        assert_eq!(
+            entries,
+            message.get_group_with_dictionary(268, &dictionary).unwrap()
+        );
+        assert!(message.get_group_with_dictionary(146, &dictionary).is_err());
+    }
+
+    #[test]
+    fn test_with_group() {
+        let mut allocs = FixGroup::new(78);
+
+        for (account, qty) in [("ACC1", 600), ("ACC2", 400)] {
+            let mut parties = FixGroup::new(453);
+            let party = parties.add_entry();
+            party.with_value(448, "BROKER1");
+            party.with_value(452, 1);
+
+            let entry = allocs.add_entry();
+            entry.with_value(79, account);
+            entry.with_value(80, qty);
+            entry.with_group(&parties);
+        }
+
+        let mut message = FixMessageBuilder::new("FIX.4.4", "J");
+        message.with_value(70, "AL1");
+        message.with_group(&allocs);
+        message.with_group(&FixGroup::new(136));
+        message.with_value(58, "done");
+
+        assert_eq!(
+            "70=AL1|78=2|79=ACC1|80=600|453=1|448=BROKER1|452=1|\
+             79=ACC2|80=400|453=1|448=BROKER1|452=1|58=done|",
+            message
+                .get_fields()
+                .map(|(tag, value)| format!("{}={}|", tag, String::from_utf8_lossy(value)))
+                .collect::<String>()
+        );
+
+        let entries = message.get_group(78, &[79, 80, 453, 448, 452]).unwrap();
+        assert_eq!(allocs.get_entries(), entries.as_slice());
+        assert_eq!(
+            Some(b"BROKER1".as_slice()),
+            entries[1].get_group(453, &[448, 452]).unwrap()[0].get(448)
+        );
+    }
+}
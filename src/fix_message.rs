@@ -0,0 +1,228 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+    sync::Arc,
+    time::SystemTime,
+};
+
+use crate::{
+    write_fix_string, BeginString, FieldParseError, FixDictionary, FixMessageBuilder,
+    FixSerializeError, FixValue, Utf8FieldError,
+};
+
+/// Frozen FIX message with its serialized form cached.
+///
+/// Cloning only bumps a reference counter, so the same message can be handed to
+/// several threads without compiling it again.
+#[derive(Clone)]
+pub struct FixMessage {
+    inner: Arc<FixMessageInner>,
+}
+
+struct FixMessageInner {
+    builder: FixMessageBuilder,
+    bytes: Vec<u8>,
+    check_sum: String,
+}
+
+impl FixMessage {
+    pub(crate) fn new(builder: FixMessageBuilder, bytes: Vec<u8>, check_sum: String) -> Self {
+        return Self {
+            inner: Arc::new(FixMessageInner {
+                builder,
+                bytes,
+                check_sum,
+            }),
+        };
+    }
+
+    pub fn from_bytes(
+        payload: &[u8],
+        check_sum_validation: bool,
+    ) -> Result<Self, FixSerializeError> {
+        let builder = FixMessageBuilder::from_bytes(payload, check_sum_validation)?;
+        return Ok(builder.build());
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        return &self.inner.bytes;
+    }
+
+    pub fn get_check_sum(&self) -> &str {
+        return &self.inner.check_sum;
+    }
+
+    pub fn as_builder(&self) -> &FixMessageBuilder {
+        return &self.inner.builder;
+    }
+
+    /// Returns an editable copy of the message.
+    pub fn to_builder(&self) -> FixMessageBuilder {
+        return self.inner.builder.clone();
+    }
+
+    pub fn semantically_equals(&self, other: &Self, ignore: &[u32]) -> bool {
+        return self
+            .inner
+            .builder
+            .semantically_equals(&other.inner.builder, ignore);
+    }
+
+    pub fn get_fix_version(&self) -> BeginString {
+        return self.inner.builder.get_fix_version();
+    }
+
+    pub fn get_message_type(&self) -> &Vec<u8> {
+        return self.inner.builder.get_message_type();
+    }
+
+    pub fn get_message_type_as_string(&self) -> String {
+        return self.inner.builder.get_message_type_as_string();
+    }
+
+    pub fn get(&self, tag: u32) -> Option<&[u8]> {
+        return self.inner.builder.get(tag);
+    }
+
+    pub fn get_sender_comp_id(&self) -> Option<&str> {
+        return self.inner.builder.get_sender_comp_id();
+    }
+
+    pub fn get_target_comp_id(&self) -> Option<&str> {
+        return self.inner.builder.get_target_comp_id();
+    }
+
+    pub fn get_msg_seq_num(&self) -> Result<Option<u64>, FieldParseError> {
+        return self.inner.builder.get_msg_seq_num();
+    }
+
+    pub fn get_sending_time(&self) -> Result<Option<SystemTime>, FieldParseError> {
+        return self.inner.builder.get_sending_time();
+    }
+
+    pub fn get_orig_sending_time(&self) -> Result<Option<SystemTime>, FieldParseError> {
+        return self.inner.builder.get_orig_sending_time();
+    }
+
+    pub fn is_poss_dup(&self) -> bool {
+        return self.inner.builder.is_poss_dup();
+    }
+
+    pub fn get_typed(
+        &self,
+        tag: u32,
+        dictionary: &FixDictionary,
+    ) -> Result<Option<FixValue>, FieldParseError> {
+        return self.inner.builder.get_typed(tag, dictionary);
+    }
+
+    pub fn get_raw(&self, tag: &[u8]) -> Option<&[u8]> {
+        return self.inner.builder.get_raw(tag);
+    }
+
+    pub fn get_all(&self, tag: u32) -> Vec<&[u8]> {
+        return self.inner.builder.get_all(tag);
+    }
+
+    pub fn get_as<T>(&self, tag: u32) -> Result<Option<T>, FieldParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        return self.inner.builder.get_as(tag);
+    }
+
+    pub fn get_string(&self, tag: u32) -> Result<Option<String>, Utf8FieldError> {
+        return self.inner.builder.get_string(tag);
+    }
+
+    pub fn get_string_lossy(&self, tag: u32) -> Option<String> {
+        return self.inner.builder.get_string_lossy(tag);
+    }
+
+    pub fn get_all_strings(&self, tag: u32) -> Result<Vec<String>, Utf8FieldError> {
+        return self.inner.builder.get_all_strings(tag);
+    }
+
+    #[deprecated(note = "use `get_string` or `get_string_lossy`")]
+    #[allow(deprecated)]
+    pub fn get_value_string(&self, key: &str) -> Option<String> {
+        return self.inner.builder.get_value_string(key);
+    }
+
+    #[deprecated(note = "use `get_all_strings`")]
+    #[allow(deprecated)]
+    pub fn get_values_string(&self, key: &str) -> Vec<String> {
+        return self.inner.builder.get_values_string(key);
+    }
+
+    pub fn to_redacted_string(&self, tags: &[u32]) -> String {
+        return self.inner.builder.to_redacted_string(tags);
+    }
+}
+
+/// Same bytes on the wire.
+impl PartialEq for FixMessage {
+    fn eq(&self, other: &Self) -> bool {
+        return self.inner.bytes == other.inner.bytes;
+    }
+}
+
+impl Eq for FixMessage {}
+
+/// The message as it goes on the wire with SOH shown as `|`.
+impl fmt::Display for FixMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write_fix_string(f, &self.inner.bytes);
+    }
+}
+
+impl fmt::Debug for FixMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return fmt::Display::fmt(self, f);
+    }
+}
+
+impl From<FixMessageBuilder> for FixMessage {
+    fn from(builder: FixMessageBuilder) -> Self {
+        return builder.build();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_build_caches_bytes() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "A");
+        fix_builder.with_value(34, "1092");
+        fix_builder.with_value(49, "TESTBUY1");
+        fix_builder.with_value(52, "20180920-18:24:59.643");
+        fix_builder.with_value(56, "TESTSELL1");
+        fix_builder.with_value(98, "0");
+        fix_builder.with_value(108, "60");
+
+        let bytes = fix_builder.as_bytes();
+        let message = fix_builder.build();
+        let cloned = message.clone();
+
+        assert_eq!(bytes.as_slice(), message.as_bytes());
+        assert_eq!("178", message.get_check_sum());
+        assert_eq!(message.as_bytes().as_ptr(), cloned.as_bytes().as_ptr());
+        assert_send_sync::<FixMessage>();
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "0");
+        fix_builder.with_value(34, "2");
+
+        let message = FixMessage::from_bytes(&fix_builder.as_bytes(), true).unwrap();
+
+        assert_eq!("0", message.get_message_type_as_string());
+        assert_eq!(Some("2".to_string()), message.get_string_lossy(34));
+    }
+}
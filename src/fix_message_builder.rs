@@ -1,19 +1,65 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    str::FromStr,
+    sync::Arc,
+    time::SystemTime,
+};
+
 use crate::{
-    split_fix_to_tags,
-    utils::{bytes_to_fix_string, calculate_check_sum, compile_fix_chunk},
-    FixSerializeError,
+    fields::FixField,
+    format_utc_timestamp_with_precision, parse_tag, parse_utc_timestamp, split_fix_to_tags,
+    split_fix_to_tags_with_warnings, split_raw_fields, telemetry, trace,
+    utils::{calculate_check_sum, checksum_u8, compile_fix_chunk, format_checksum, parse_checksum},
+    write_fix_string, BeginString, ChecksumAccumulator, Clock, FieldParseError, FieldType,
+    FixDictionary, FixMessage, FixMessageTemplate, FixParseError, FixSerializeError, FixTags,
+    FixValue, ParseLimits, ParseOptions, ParseWarning, Signer, TimestampPrecision, Utf8FieldError,
+    DEFAULT_SKIPPED_TAGS,
 };
 
 pub const FIX_VERSION: &[u8] = b"8";
 pub const FIX_BODY_LEN: &[u8] = b"9";
 pub const FIX_CHECK_SUM: &[u8] = b"10";
 pub const FIX_MESSAGE_TYPE: &[u8] = b"35";
+pub const FIX_SENDING_TIME: &[u8] = b"52";
+pub const FIX_ORIG_SENDING_TIME: &[u8] = b"122";
+pub const FIX_POSS_DUP_FLAG: &[u8] = b"43";
+
+const TAG_BEGIN_STRING: u32 = 8;
+const TAG_BODY_LENGTH: u32 = 9;
+const TAG_CHECK_SUM: u32 = 10;
+const TAG_MSG_TYPE: u32 = 35;
+const TAG_MSG_SEQ_NUM: u32 = 34;
+const TAG_POSS_DUP_FLAG: u32 = 43;
+const TAG_SENDER_COMP_ID: u32 = 49;
+const TAG_SENDING_TIME: u32 = 52;
+const TAG_TARGET_COMP_ID: u32 = 56;
+const TAG_ORIG_SENDING_TIME: u32 = 122;
+const TAG_SIGNATURE_LENGTH: u32 = 93;
+const TAG_SIGNATURE: u32 = 89;
+
+/// Standard header fields that follow BeginString, BodyLength and MsgType.
+pub const STANDARD_HEADER_TAGS: &[u32] = &[
+    49, 56, 115, 128, 90, 91, 34, 50, 142, 57, 143, 116, 144, 129, 145, 43, 97, 52, 122, 212, 213,
+    347, 369, 627, 1128, 1129,
+];
+
+/// BodyLength (9), CheckSum (10), MsgSeqNum (34) and SendingTime (52), which differ
+/// between otherwise identical messages.
+pub const VOLATILE_TAGS: &[u32] = &[9, 10, 34, 52];
+
+/// Password (554), NewPassword (925) and RawData (96).
+pub const DEFAULT_REDACTED_TAGS: &[u32] = &[554, 925, 96];
+pub const REDACTED_VALUE: &[u8] = b"***";
 
 #[derive(Clone)]
 pub struct FixMessageBuilder {
     fix_version: Vec<u8>,
     message_type: Vec<u8>,
-    data: Vec<(Vec<u8>, Vec<u8>)>,
+    data: Vec<(u32, Vec<u8>)>,
+    clock: Option<Arc<dyn Clock>>,
+    timestamp_precision: TimestampPrecision,
+    signer: Option<Arc<dyn Signer>>,
 }
 
 impl FixMessageBuilder {
@@ -21,73 +67,389 @@ impl FixMessageBuilder {
         payload: &[u8],
         check_sum_validation: bool,
     ) -> Result<Self, FixSerializeError> {
+        let timer = telemetry::Timer::start();
         let tags = split_fix_to_tags(payload);
+        let result = Self::from_tags(payload, &tags, check_sum_validation, DEFAULT_SKIPPED_TAGS);
+        telemetry::parsed(&result, timer);
+
+        return result;
+    }
+
+    /// Same as `from_bytes_with_limits`, with the checksum validation, limits and the
+    /// tags left out of the message fields taken from `options`.
+    pub fn from_bytes_with_options(
+        payload: &[u8],
+        options: &ParseOptions,
+    ) -> Result<Self, FixSerializeError> {
+        let timer = telemetry::Timer::start();
+        let result = Self::parse_with_options(payload, options);
+        telemetry::parsed(&result, timer);
 
-        let Some(version) = tags.get(FIX_VERSION) else{
-            println!(
-                "Tag not found: {:?}. Str: {}",
-                payload.clone(),
-                String::from_utf8(payload.clone().to_vec()).unwrap()
-            );
+        return result;
+    }
+
+    fn parse_with_options(
+        payload: &[u8],
+        options: &ParseOptions,
+    ) -> Result<Self, FixSerializeError> {
+        options.limits.check(payload)?;
+
+        if options.reject_duplicate_header_tags && has_duplicate_header_tag(payload) {
+            return Err(FixSerializeError::DuplicateHeaderTag);
+        }
+
+        let tags = split_fix_to_tags(payload);
+        let result = Self::from_tags(payload, &tags, false, &options.skipped_tags)?;
+
+        // Skipped tags don't make it into the builder, so the checksum is taken over
+        // the received bytes instead of the recompiled message.
+        if options.check_sum_validation {
+            let fields = split_raw_fields(payload);
+            let Some(check_sum) = fields.iter().rev().find(|field| field.tag == FIX_CHECK_SUM)
+            else {
+                return Err(FixSerializeError::CheckSumTagNotFoundInSource);
+            };
+
+            if parse_checksum(check_sum.value) != Some(checksum_u8(&payload[..check_sum.offset])) {
+                return Err(FixSerializeError::InvalidCheckSum);
+            }
+        }
+
+        return Ok(result);
+    }
+
+    /// Same as `from_bytes_with_limits`, but failures tell which field is at fault and
+    /// where it is in `payload`.
+    pub fn from_bytes_detailed(
+        payload: &[u8],
+        check_sum_validation: bool,
+        limits: &ParseLimits,
+    ) -> Result<Self, FixParseError> {
+        return Self::from_bytes_with_limits(payload, check_sum_validation, limits)
+            .map_err(|kind| FixParseError::locate(kind, payload, limits));
+    }
+
+    /// Same as `from_bytes`, but rejects messages exceeding `limits` before parsing them.
+    pub fn from_bytes_with_limits(
+        payload: &[u8],
+        check_sum_validation: bool,
+        limits: &ParseLimits,
+    ) -> Result<Self, FixSerializeError> {
+        limits.check(payload)?;
+        return Self::from_bytes(payload, check_sum_validation);
+    }
+
+    /// Same as `from_bytes`, but tolerates a missing SOH after the last field and stray
+    /// bytes after the CheckSum field, reporting them as warnings.
+    pub fn from_bytes_with_warnings(
+        payload: &[u8],
+        check_sum_validation: bool,
+    ) -> Result<(Self, Vec<ParseWarning>), FixSerializeError> {
+        let (tags, warnings) = split_fix_to_tags_with_warnings(payload);
+        let result = Self::from_tags(payload, &tags, check_sum_validation, DEFAULT_SKIPPED_TAGS)?;
+
+        return Ok((result, warnings));
+    }
+
+    /// Reads whatever can be read from `payload`, never failing: problems that
+    /// `from_bytes` would reject are reported as warnings, fields with a malformed tag
+    /// are dropped and the fields keep their wire order. Meant for recovering messages
+    /// from damaged logs rather than for live sessions.
+    pub fn from_bytes_lenient(payload: &[u8]) -> (Self, Vec<ParseWarning>) {
+        let mut result = Self::new(BeginString::Custom(String::new()), "");
+        let mut warnings = vec![];
+        let mut seen = vec![];
+        let mut check_sum = None;
+
+        for field in split_raw_fields(payload) {
+            let Some(tag) = field.get_tag() else {
+                warnings.push(ParseWarning::InvalidTag {
+                    offset: field.offset,
+                });
+                continue;
+            };
+
+            if let Some(position) = field
+                .value
+                .iter()
+                .position(|byte| !(0x20..0x7f).contains(byte))
+            {
+                warnings.push(ParseWarning::UnexpectedCharacter {
+                    offset: field.offset,
+                    position: field.offset + field.tag.len() + 1 + position,
+                });
+            }
+
+            if is_header_tag(tag) && seen.contains(&tag) {
+                warnings.push(ParseWarning::DuplicateHeaderTag {
+                    tag,
+                    offset: field.offset,
+                });
+            }
+
+            let is_first = !seen.contains(&tag);
+            seen.push(tag);
+
+            match tag {
+                TAG_BEGIN_STRING if is_first => result.fix_version = field.value.to_vec(),
+                TAG_MSG_TYPE if is_first => result.message_type = field.value.to_vec(),
+                TAG_BEGIN_STRING | TAG_BODY_LENGTH | TAG_MSG_TYPE => {}
+                TAG_CHECK_SUM => {
+                    check_sum = Some(field);
+                    break;
+                }
+                _ => result.with_value_as_bytes(tag, field.value.to_vec()),
+            }
+        }
+
+        for tag in [TAG_BEGIN_STRING, TAG_MSG_TYPE, TAG_CHECK_SUM] {
+            if !seen.contains(&tag) {
+                warnings.push(ParseWarning::MissingTag(tag));
+            }
+        }
+
+        if let Some(check_sum) = check_sum {
+            let actual = checksum_u8(&payload[..check_sum.offset]);
+
+            if parse_checksum(check_sum.value) != Some(actual) {
+                warnings.push(ParseWarning::InvalidCheckSum {
+                    declared: String::from_utf8_lossy(check_sum.value).to_string(),
+                    actual: String::from_utf8_lossy(&format_checksum(actual)).to_string(),
+                });
+            }
+
+            let end = check_sum.offset + check_sum.tag.len() + check_sum.value.len() + 2;
+
+            if end < payload.len() {
+                warnings.push(ParseWarning::TrailingGarbage {
+                    offset: end,
+                    len: payload.len() - end,
+                });
+            } else if end > payload.len() {
+                warnings.push(ParseWarning::MissingTrailingDelimeter);
+            }
+        } else if !payload.is_empty() && payload.last() != Some(&1) {
+            warnings.push(ParseWarning::MissingTrailingDelimeter);
+        }
+
+        return (result, warnings);
+    }
+
+    fn from_tags(
+        payload: &[u8],
+        tags: &FixTags,
+        check_sum_validation: bool,
+        skipped_tags: &[u32],
+    ) -> Result<Self, FixSerializeError> {
+        let result = Self::parse_tags(tags, check_sum_validation, skipped_tags);
+        trace::parsed(payload, &result);
+
+        return result;
+    }
+
+    fn parse_tags(
+        tags: &FixTags,
+        check_sum_validation: bool,
+        skipped_tags: &[u32],
+    ) -> Result<Self, FixSerializeError> {
+        let find = |tag: &[u8]| {
+            return tags
+                .iter()
+                .find(|(inner, _)| inner.as_slice() == tag)
+                .map(|(_, value)| value);
+        };
+
+        let Some(version) = find(FIX_VERSION) else {
+            trace::tag_not_found(FIX_VERSION);
 
             return Err(FixSerializeError::VersionTagNotFoundInSource);
         };
 
-        let Some(message_type) = tags.get(FIX_MESSAGE_TYPE) else{
-            return Err(FixSerializeError::MessageTypeTagNotFoundInSource)
+        let Some(message_type) = find(FIX_MESSAGE_TYPE) else {
+            return Err(FixSerializeError::MessageTypeTagNotFoundInSource);
         };
 
-        let source_check_sum = tags.get(FIX_CHECK_SUM);
+        let source_check_sum = find(FIX_CHECK_SUM);
 
-        if check_sum_validation == true && source_check_sum.is_none() {
+        if check_sum_validation && source_check_sum.is_none() {
             return Err(FixSerializeError::CheckSumTagNotFoundInSource);
         }
 
-        let version = version.first().unwrap();
-        let message_type = message_type.first().unwrap();
-
         let mut result = Self {
             fix_version: version.clone(),
             message_type: message_type.clone(),
-            data: vec![],
+            data: Vec::with_capacity(tags.len()),
+            clock: None,
+            timestamp_precision: TimestampPrecision::default(),
+            signer: None,
         };
 
-        let to_skip = vec![FIX_BODY_LEN, FIX_VERSION, FIX_CHECK_SUM];
-
-        for (tag, values) in &tags {
-            for value in values {
-                if to_skip.contains(&tag.as_slice()) {
-                    continue;
-                }
+        for (tag, value) in tags {
+            let Some(tag) = parse_tag(tag) else {
+                return Err(FixSerializeError::InvalidTag);
+            };
 
-                result.with_value_as_bytes(tag.clone(), value.clone())
+            if [TAG_BEGIN_STRING, TAG_MSG_TYPE].contains(&tag) || skipped_tags.contains(&tag) {
+                continue;
             }
+
+            result.with_value_as_bytes(tag, value.clone())
         }
 
-        if check_sum_validation {
-            if source_check_sum.unwrap().first().unwrap()
-                != &result.calculate_check_sum().as_bytes().to_vec()
-            {
-                return Err(FixSerializeError::InvalidCheckSum);
-            }
+        if check_sum_validation
+            && parse_checksum(source_check_sum.unwrap()) != Some(result.calculate_check_sum())
+        {
+            return Err(FixSerializeError::InvalidCheckSum);
         }
 
         return Ok(result);
     }
 
-    pub fn new(version: &str, message_type: &str) -> Self {
+    pub fn new(version: impl Into<BeginString>, message_type: &str) -> Self {
         return Self {
-            fix_version: version.as_bytes().to_vec(),
+            fix_version: version.into().as_bytes().to_vec(),
             message_type: message_type.as_bytes().to_vec(),
             data: vec![],
+            clock: None,
+            timestamp_precision: TimestampPrecision::default(),
+            signer: None,
         };
     }
 
     pub fn as_bytes(&self) -> Vec<u8> {
-        return self.compile_message();
+        let timer = telemetry::Timer::start();
+        let result = self.compile_message();
+        telemetry::serialized(timer);
+
+        return result;
+    }
+
+    pub fn get(&self, tag: u32) -> Option<&[u8]> {
+        for (inner_key, value) in &self.data {
+            if *inner_key == tag {
+                return Some(value);
+            }
+        }
+
+        return None;
+    }
+
+    pub fn get_raw(&self, tag: &[u8]) -> Option<&[u8]> {
+        return self.get(parse_tag(tag)?);
+    }
+
+    pub fn get_all(&self, tag: u32) -> Vec<&[u8]> {
+        let mut result = vec![];
+
+        for (inner_key, value) in &self.data {
+            if *inner_key == tag {
+                result.push(value.as_slice())
+            }
+        }
+
+        return result;
+    }
+
+    pub fn get_all_raw(&self, tag: &[u8]) -> Vec<&[u8]> {
+        let Some(tag) = parse_tag(tag) else {
+            return vec![];
+        };
+
+        return self.get_all(tag);
+    }
+
+    pub fn get_as<T>(&self, tag: u32) -> Result<Option<T>, FieldParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        let Ok(value) = std::str::from_utf8(value) else {
+            return Err(FieldParseError::InvalidUtf8 { tag });
+        };
+
+        return match value.parse() {
+            Ok(result) => Ok(Some(result)),
+            Err(err) => Err(FieldParseError::InvalidValue {
+                tag,
+                value: value.to_string(),
+                reason: err.to_string(),
+            }),
+        };
+    }
+
+    /// SenderCompID (49); `None` when absent or not UTF-8.
+    pub fn get_sender_comp_id(&self) -> Option<&str> {
+        return std::str::from_utf8(self.get(TAG_SENDER_COMP_ID)?).ok();
+    }
+
+    /// TargetCompID (56); `None` when absent or not UTF-8.
+    pub fn get_target_comp_id(&self) -> Option<&str> {
+        return std::str::from_utf8(self.get(TAG_TARGET_COMP_ID)?).ok();
+    }
+
+    /// MsgSeqNum (34).
+    pub fn get_msg_seq_num(&self) -> Result<Option<u64>, FieldParseError> {
+        return self.get_as(TAG_MSG_SEQ_NUM);
+    }
+
+    /// SendingTime (52).
+    pub fn get_sending_time(&self) -> Result<Option<SystemTime>, FieldParseError> {
+        return self.get_utc_timestamp(TAG_SENDING_TIME);
+    }
+
+    /// OrigSendingTime (122).
+    pub fn get_orig_sending_time(&self) -> Result<Option<SystemTime>, FieldParseError> {
+        return self.get_utc_timestamp(TAG_ORIG_SENDING_TIME);
+    }
+
+    /// Whether PossDupFlag (43) is set.
+    pub fn is_poss_dup(&self) -> bool {
+        return self.get(TAG_POSS_DUP_FLAG) == Some(b"Y".as_slice());
+    }
+
+    fn get_utc_timestamp(&self, tag: u32) -> Result<Option<SystemTime>, FieldParseError> {
+        let Some(value) = self.get_as::<String>(tag)? else {
+            return Ok(None);
+        };
+
+        return match parse_utc_timestamp(&value) {
+            Some(result) => Ok(Some(result)),
+            None => Err(FieldParseError::InvalidValue {
+                tag,
+                value,
+                reason: "invalid UTCTimestamp".to_string(),
+            }),
+        };
     }
 
+    /// Decodes the first value of `tag` according to its type in `dictionary`; tags the
+    /// dictionary doesn't know come back as `String`, or `Data` when not UTF-8.
+    pub fn get_typed(
+        &self,
+        tag: u32,
+        dictionary: &FixDictionary,
+    ) -> Result<Option<FixValue>, FieldParseError> {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        let field_type = match dictionary.get_field(tag) {
+            Some(field) => &field.field_type,
+            None if std::str::from_utf8(value).is_ok() => &FieldType::String,
+            None => &FieldType::Data,
+        };
+
+        return FixValue::parse(tag, field_type, value).map(Some);
+    }
+
+    #[deprecated(note = "use `get` or `get_raw`")]
     pub fn get_value(&self, key: Vec<u8>) -> Option<&Vec<u8>> {
+        let key = parse_tag(&key)?;
+
         for (inner_key, value) in &self.data {
             if inner_key == &key {
                 return Some(value);
@@ -97,11 +459,13 @@ impl FixMessageBuilder {
         return None;
     }
 
+    #[deprecated(note = "use `get_all` or `get_all_raw`")]
     pub fn get_values(&self, key: Vec<u8>) -> Vec<&Vec<u8>> {
         let mut result = vec![];
+        let key = parse_tag(&key);
 
         for (inner_key, value) in &self.data {
-            if inner_key == &key {
+            if Some(*inner_key) == key {
                 result.push(value)
             }
         }
@@ -109,15 +473,61 @@ impl FixMessageBuilder {
         return result;
     }
 
+    pub fn get_fix_version(&self) -> BeginString {
+        return BeginString::from(self.fix_version.as_slice());
+    }
+
+    /// Re-stamps the message with another BeginString, e.g. when routing between versions.
+    pub fn set_fix_version(&mut self, version: impl Into<BeginString>) {
+        self.fix_version = version.into().as_bytes().to_vec();
+    }
+
     pub fn get_message_type(&self) -> &Vec<u8> {
         return &self.message_type;
     }
 
     pub fn get_message_type_as_string(&self) -> String {
-        return String::from_utf8(self.message_type.clone()).unwrap();
+        return String::from_utf8_lossy(&self.message_type).to_string();
     }
 
+    /// First value of `tag` as text; bytes that aren't UTF-8 are an error rather than
+    /// a panic.
+    pub fn get_string(&self, tag: u32) -> Result<Option<String>, Utf8FieldError> {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        return match std::str::from_utf8(value) {
+            Ok(value) => Ok(Some(value.to_string())),
+            Err(_) => Err(Utf8FieldError { tag }),
+        };
+    }
+
+    /// First value of `tag` as text, with bytes that aren't UTF-8 replaced by U+FFFD.
+    pub fn get_string_lossy(&self, tag: u32) -> Option<String> {
+        return self
+            .get(tag)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+    }
+
+    /// Every value of `tag` as text, in message order.
+    pub fn get_all_strings(&self, tag: u32) -> Result<Vec<String>, Utf8FieldError> {
+        let mut result = vec![];
+
+        for value in self.get_all(tag) {
+            match std::str::from_utf8(value) {
+                Ok(value) => result.push(value.to_string()),
+                Err(_) => return Err(Utf8FieldError { tag }),
+            }
+        }
+
+        return Ok(result);
+    }
+
+    #[deprecated(note = "use `get_string` or `get_string_lossy`")]
     pub fn get_value_as_string(&self, key: Vec<u8>) -> Option<String> {
+        let key = parse_tag(&key)?;
+
         for (inner_key, value) in &self.data {
             if inner_key == &key {
                 return Some(String::from_utf8(value.clone()).unwrap());
@@ -127,10 +537,13 @@ impl FixMessageBuilder {
         return None;
     }
 
+    #[deprecated(note = "use `get_all_strings`")]
     pub fn get_values_as_string(&self, key: Vec<u8>) -> Vec<String> {
         let mut result = vec![];
+        let key = parse_tag(&key);
+
         for (inner_key, value) in &self.data {
-            if inner_key == &key {
+            if Some(*inner_key) == key {
                 result.push(String::from_utf8(value.clone()).unwrap());
             }
         }
@@ -138,9 +551,12 @@ impl FixMessageBuilder {
         return result;
     }
 
+    #[deprecated(note = "use `get_string` or `get_string_lossy`")]
     pub fn get_value_string(&self, key: &str) -> Option<String> {
+        let key = parse_tag(key.as_bytes())?;
+
         for (inner_key, value) in &self.data {
-            if inner_key == &key.as_bytes() {
+            if inner_key == &key {
                 return Some(String::from_utf8(value.clone()).unwrap());
             }
         }
@@ -148,10 +564,13 @@ impl FixMessageBuilder {
         return None;
     }
 
+    #[deprecated(note = "use `get_all_strings`")]
     pub fn get_values_string(&self, key: &str) -> Vec<String> {
         let mut result = vec![];
+        let key = parse_tag(key.as_bytes());
+
         for (inner_key, value) in &self.data {
-            if inner_key == &key.as_bytes() {
+            if Some(*inner_key) == key {
                 result.push(String::from_utf8(value.clone()).unwrap());
             }
         }
@@ -159,18 +578,221 @@ impl FixMessageBuilder {
         return result;
     }
 
-    pub fn with_value(&mut self, key: i32, value: &str) {
-        self.data.push((
-            key.to_string().as_bytes().to_vec(),
-            value.as_bytes().to_vec(),
-        ));
+    /// Appends a field; the value can be text, a number, a flag, a timestamp or raw bytes.
+    pub fn with_value(&mut self, key: u32, value: impl Into<FixValue>) {
+        self.data.push((key, value.into().into_bytes()));
+    }
+
+    /// Replaces the first value of `key`, or appends the field when it is missing.
+    pub fn set_value(&mut self, key: u32, value: impl Into<FixValue>) {
+        let value = value.into().into_bytes();
+
+        match self
+            .data
+            .iter_mut()
+            .find(|(inner_key, _)| inner_key == &key)
+        {
+            Some((_, inner_value)) => *inner_value = value,
+            None => self.data.push((key, value)),
+        }
+    }
+
+    /// Inserts a field at `index` among the fields that follow MsgType (35).
+    pub fn insert_value(&mut self, index: usize, key: u32, value: impl Into<FixValue>) {
+        let index = index.min(self.data.len());
+        self.data.insert(index, (key, value.into().into_bytes()));
+    }
+
+    /// Removes every value of `key`, returning how many were removed.
+    pub fn remove_value(&mut self, key: u32) -> usize {
+        let len = self.data.len();
+        self.data.retain(|(inner_key, _)| inner_key != &key);
+
+        return len - self.data.len();
+    }
+
+    /// Whether both messages have the same version, type and fields once `ignore`d tags
+    /// are left out. Fields of different tags may come in any order, repeated tags
+    /// must keep theirs; pass `VOLATILE_TAGS` to compare an expected message with a
+    /// sent one.
+    pub fn semantically_equals(&self, other: &Self, ignore: &[u32]) -> bool {
+        return self.fix_version == other.fix_version
+            && self.message_type == other.message_type
+            && self.get_fields_by_tag(ignore) == other.get_fields_by_tag(ignore);
+    }
+
+    pub(crate) fn get_fields_by_tag(&self, ignore: &[u32]) -> BTreeMap<u32, Vec<&[u8]>> {
+        let mut result: BTreeMap<u32, Vec<&[u8]>> = BTreeMap::new();
+
+        for (tag, value) in self.get_fields() {
+            if !ignore.contains(&tag) {
+                result.entry(tag).or_default().push(value);
+            }
+        }
+
+        return result;
+    }
+
+    /// Fields that follow MsgType (35), in order.
+    pub fn get_fields(&self) -> impl Iterator<Item = (u32, &[u8])> {
+        return self
+            .data
+            .iter()
+            .map(|(tag, value)| (*tag, value.as_slice()));
     }
 
-    fn with_value_as_bytes(&mut self, key: Vec<u8>, value: Vec<u8>) {
+    /// Lets `f` change the tag and value of every field that follows MsgType (35).
+    pub fn for_each_field_mut(&mut self, mut f: impl FnMut(&mut u32, &mut Vec<u8>)) {
+        for (tag, value) in self.data.iter_mut() {
+            f(tag, value);
+        }
+    }
+
+    /// Stable sort of the fields that follow MsgType (35) by a key computed from the tag.
+    pub fn sort_fields_by_key<K: Ord>(&mut self, mut key: impl FnMut(u32) -> K) {
+        self.data.sort_by_key(|(tag, _)| key(*tag));
+    }
+
+    /// Values of every body field keyed by tag; repeated tags keep their order.
+    pub fn to_map(&self) -> BTreeMap<u32, Vec<String>> {
+        let mut result: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+
+        for (key, value) in &self.data {
+            result
+                .entry(*key)
+                .or_default()
+                .push(String::from_utf8_lossy(value).to_string());
+        }
+
+        return result;
+    }
+
+    /// Builds a message from a tag map with the fields in tag order. BeginString (8),
+    /// BodyLength (9), MsgType (35) and CheckSum (10) in the map are ignored.
+    pub fn from_map(
+        version: impl Into<BeginString>,
+        message_type: &str,
+        map: &BTreeMap<u32, Vec<String>>,
+    ) -> Self {
+        let mut result = Self::new(version, message_type);
+        let to_skip = [
+            TAG_BEGIN_STRING,
+            TAG_BODY_LENGTH,
+            TAG_MSG_TYPE,
+            TAG_CHECK_SUM,
+        ];
+
+        for (tag, values) in map {
+            if to_skip.contains(tag) {
+                continue;
+            }
+
+            for value in values {
+                result.with_value_as_bytes(*tag, value.as_bytes().to_vec());
+            }
+        }
+
+        return result;
+    }
+
+    pub fn with_field<F: FixField>(&mut self, field: F) {
+        self.with_value_as_bytes(F::TAG, field.as_bytes().to_vec());
+    }
+
+    pub fn get_field<F: FixField>(&self) -> Result<Option<F>, FieldParseError> {
+        return self.get(F::TAG).map(F::try_from).transpose();
+    }
+
+    /// Stamps SendingTime (52) from `clock` every time the message is serialized.
+    ///
+    /// When PossDupFlag (43) is `Y`, the previous SendingTime is kept in OrigSendingTime (122).
+    pub fn with_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = Some(clock);
+    }
+
+    /// Fractional digits of the SendingTime stamped from the clock, milliseconds by default.
+    pub fn with_timestamp_precision(&mut self, precision: TimestampPrecision) {
+        self.timestamp_precision = precision;
+    }
+
+    /// Signs the message every time it is serialized, replacing any SignatureLength (93)
+    /// and Signature (89) fields with freshly computed ones at the end of the message.
+    pub fn with_signer(&mut self, signer: Arc<dyn Signer>) {
+        self.signer = Some(signer);
+    }
+
+    /// Returns a copy of the message with the values of `tags` masked, for logging.
+    pub fn redact(&self, tags: &[u32]) -> Self {
+        let mut result = self.clone();
+
+        for (key, value) in result.data.iter_mut() {
+            if tags.contains(key) {
+                *value = REDACTED_VALUE.to_vec();
+            }
+        }
+
+        return result;
+    }
+
+    pub fn to_redacted_string(&self, tags: &[u32]) -> String {
+        return self.redact(tags).to_string();
+    }
+
+    fn with_value_as_bytes(&mut self, key: u32, value: Vec<u8>) {
         self.data.push((key, value));
     }
 
+    /// Freezes the message into a template that per-message builders are copied from.
+    pub fn template(&self) -> FixMessageTemplate {
+        return FixMessageTemplate::new(self.clone());
+    }
+
+    /// Copy of the message keeping only the standard header fields, e.g. to reply on
+    /// the same session or to start the next message of a batch.
+    pub fn clone_with_header(&self) -> Self {
+        let mut result = Self {
+            fix_version: self.fix_version.clone(),
+            message_type: self.message_type.clone(),
+            data: vec![],
+            clock: self.clock.clone(),
+            timestamp_precision: self.timestamp_precision,
+            signer: self.signer.clone(),
+        };
+
+        for (key, value) in &self.data {
+            if STANDARD_HEADER_TAGS.contains(key) {
+                result.data.push((*key, value.clone()));
+            }
+        }
+
+        return result;
+    }
+
+    pub(crate) fn clone_with_capacity(&self, extra_fields: usize) -> Self {
+        let mut data = Vec::with_capacity(self.data.len() + extra_fields);
+        data.extend(self.data.iter().cloned());
+
+        return Self {
+            fix_version: self.fix_version.clone(),
+            message_type: self.message_type.clone(),
+            data,
+            clock: self.clock.clone(),
+            timestamp_precision: self.timestamp_precision,
+            signer: self.signer.clone(),
+        };
+    }
+
+    pub fn build(self) -> FixMessage {
+        let (bytes, check_sum) = self.compile_message_with_check_sum();
+        return FixMessage::new(self, bytes, check_sum);
+    }
+
     fn compile_message(&self) -> Vec<u8> {
+        let (result, _) = self.compile_message_with_check_sum();
+        return result;
+    }
+
+    fn compile_message_with_check_sum(&self) -> (Vec<u8>, String) {
         let mut result = compile_fix_chunk(FIX_VERSION, &self.fix_version);
 
         let (body_len, body) = self.compile_body();
@@ -181,50 +803,142 @@ impl FixMessageBuilder {
         ));
         result.extend_from_slice(&body);
 
-        result.extend_from_slice(&compile_fix_chunk(
-            FIX_CHECK_SUM,
-            calculate_check_sum(&result).as_bytes(),
-        ));
+        let check_sum = calculate_check_sum(&result);
+        result.extend_from_slice(&compile_fix_chunk(FIX_CHECK_SUM, check_sum.as_bytes()));
 
-        return result;
+        return (result, check_sum);
     }
 
-    fn calculate_check_sum(&self) -> String {
-        let mut result = compile_fix_chunk(FIX_VERSION, &self.fix_version);
-
+    fn calculate_check_sum(&self) -> u8 {
         let (body_len, body) = self.compile_body();
 
-        result.extend_from_slice(&compile_fix_chunk(
+        let mut result = ChecksumAccumulator::new();
+        result.update(&compile_fix_chunk(FIX_VERSION, &self.fix_version));
+        result.update(&compile_fix_chunk(
             FIX_BODY_LEN,
             body_len.to_string().as_bytes(),
         ));
-        result.extend_from_slice(&body);
+        result.update(&body);
 
-        return calculate_check_sum(&result);
+        return result.get_sum();
     }
 
     fn compile_body(&self) -> (usize, Vec<u8>) {
         let mut body: Vec<u8> = compile_fix_chunk(FIX_MESSAGE_TYPE, &self.message_type);
 
+        if let Some(clock) = &self.clock {
+            let sending_time =
+                format_utc_timestamp_with_precision(clock.now(), self.timestamp_precision);
+            body.extend_from_slice(&compile_fix_chunk(
+                FIX_SENDING_TIME,
+                sending_time.as_bytes(),
+            ));
+
+            let is_poss_dup = self.is_poss_dup();
+            let orig_sending_time = self.get(TAG_ORIG_SENDING_TIME);
+
+            if let (true, None, Some(value)) =
+                (is_poss_dup, orig_sending_time, self.get(TAG_SENDING_TIME))
+            {
+                body.extend_from_slice(&compile_fix_chunk(FIX_ORIG_SENDING_TIME, value));
+            }
+        }
+
         for (key, value) in &self.data {
-            let data_to_insert = compile_fix_chunk(key, value);
+            if [TAG_BODY_LENGTH, TAG_CHECK_SUM].contains(key) {
+                continue;
+            }
+
+            if self.clock.is_some() && *key == TAG_SENDING_TIME {
+                continue;
+            }
+
+            if self.signer.is_some() && [TAG_SIGNATURE_LENGTH, TAG_SIGNATURE].contains(key) {
+                continue;
+            }
+
+            let data_to_insert = compile_fix_chunk(key.to_string().as_bytes(), value);
             body.extend_from_slice(&data_to_insert)
         }
 
+        if let Some(signer) = &self.signer {
+            let signature = signer.sign(&body);
+            let signature_len = signature.len().to_string();
+
+            body.extend_from_slice(&compile_fix_chunk(b"93", signature_len.as_bytes()));
+            body.extend_from_slice(&compile_fix_chunk(b"89", &signature));
+        }
+
         return (body.len(), body);
     }
 }
 
-impl ToString for FixMessageBuilder {
-    fn to_string(&self) -> String {
-        let bytes = self.compile_message();
-        return bytes_to_fix_string(&bytes);
+// BeginString, BodyLength, MsgType, CheckSum and the standard header fields.
+pub(crate) fn is_header_tag(tag: u32) -> bool {
+    return [
+        TAG_BEGIN_STRING,
+        TAG_BODY_LENGTH,
+        TAG_MSG_TYPE,
+        TAG_CHECK_SUM,
+    ]
+    .contains(&tag)
+        || STANDARD_HEADER_TAGS.contains(&tag);
+}
+
+fn has_duplicate_header_tag(payload: &[u8]) -> bool {
+    let mut seen = vec![];
+
+    for tag in split_raw_fields(payload)
+        .iter()
+        .filter_map(|field| field.get_tag())
+        .filter(|tag| is_header_tag(*tag))
+    {
+        if seen.contains(&tag) {
+            return true;
+        }
+
+        seen.push(tag);
+    }
+
+    return false;
+}
+
+/// Same version, type and fields in the same order; the clock and signer aren't compared.
+impl PartialEq for FixMessageBuilder {
+    fn eq(&self, other: &Self) -> bool {
+        return self.fix_version == other.fix_version
+            && self.message_type == other.message_type
+            && self.data == other.data;
+    }
+}
+
+impl Eq for FixMessageBuilder {}
+
+/// The message as it goes on the wire with SOH shown as `|`.
+impl fmt::Display for FixMessageBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write_fix_string(f, &self.compile_message());
+    }
+}
+
+impl fmt::Debug for FixMessageBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return fmt::Display::fmt(self, f);
     }
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::unnecessary_to_owned,
+    // `&String` callers keep compiling against `with_value(impl Into<FixValue>)`.
+    clippy::needless_borrows_for_generic_args
+)]
 mod test {
     use super::*;
+    use crate::fields::{OrdType, Side, TimeInForce};
+    use crate::ManualClock;
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn test_to_fix_string() {
@@ -346,6 +1060,516 @@ mod test {
     }
 
     #[test]
+    fn test_round_trip_keeps_field_order() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "W");
+        fix_builder.with_value(52, "20180920-18:24:59.643");
+        fix_builder.with_value(49, "FEED");
+        fix_builder.with_value(56, "CLIENT");
+        fix_builder.with_value(34, 7);
+        fix_builder.with_value(55, "EURUSD");
+        fix_builder.with_value(268, 2);
+
+        for (side, price) in [("0", "1.0851"), ("1", "1.0853")] {
+            fix_builder.with_value(269, side);
+            fix_builder.with_value(270, price);
+        }
+
+        let payload = fix_builder.as_bytes();
+
+        let parsed = FixMessageBuilder::from_bytes(&payload, true).unwrap();
+        assert_eq!(payload, parsed.as_bytes());
+        assert_eq!(
+            vec![52, 49, 56, 34, 55, 268, 269, 270, 269, 270],
+            parsed.get_fields().map(|(tag, _)| tag).collect::<Vec<_>>()
+        );
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_with_warnings(&payload, true).unwrap();
+        assert_eq!(payload, parsed.as_bytes());
+    }
+
+    #[test]
+    fn test_from_bytes_with_warnings() {
+        let fix_string = b"8=FIX.4.4\x019=5\x0135=0\x0110=163\x01garbage";
+
+        let (builder, warnings) =
+            FixMessageBuilder::from_bytes_with_warnings(fix_string, true).unwrap();
+
+        assert_eq!("0", builder.get_message_type_as_string());
+        assert_eq!(
+            vec![ParseWarning::TrailingGarbage { offset: 26, len: 7 }],
+            warnings
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_with_warnings_missing_final_delimeter() {
+        let fix_string = b"8=FIX.4.4\x019=5\x0135=0\x0110=163";
+
+        let (_, warnings) = FixMessageBuilder::from_bytes_with_warnings(fix_string, true).unwrap();
+
+        assert_eq!(vec![ParseWarning::MissingTrailingDelimeter], warnings);
+    }
+
+    #[test]
+    fn test_from_bytes_lenient() {
+        let fix_string =
+            b"8=FIX.4.4\x019=20\x0135=0\x0149=A\x0149=B\x01x=1\x0158=\x07\x0110=000\x01tail";
+
+        let (builder, warnings) = FixMessageBuilder::from_bytes_lenient(fix_string);
+
+        assert_eq!(BeginString::Fix44, builder.get_fix_version());
+        assert_eq!("0", builder.get_message_type_as_string());
+        assert_eq!(vec![b"A".as_slice(), b"B".as_slice()], builder.get_all(49));
+        assert_eq!(Some(b"\x07".as_slice()), builder.get(58));
+        assert_eq!(
+            vec![
+                ParseWarning::DuplicateHeaderTag {
+                    tag: 49,
+                    offset: 25
+                },
+                ParseWarning::InvalidTag { offset: 30 },
+                ParseWarning::UnexpectedCharacter {
+                    offset: 34,
+                    position: 37
+                },
+                ParseWarning::InvalidCheckSum {
+                    declared: "000".to_string(),
+                    actual: calculate_check_sum(&fix_string[..39]),
+                },
+                ParseWarning::TrailingGarbage { offset: 46, len: 4 },
+            ],
+            warnings
+        );
+
+        let (builder, warnings) = FixMessageBuilder::from_bytes_lenient(b"55=X\x0154=1");
+
+        assert_eq!(Some(b"1".as_slice()), builder.get(54));
+        assert_eq!(
+            vec![
+                ParseWarning::MissingTag(8),
+                ParseWarning::MissingTag(35),
+                ParseWarning::MissingTag(10),
+                ParseWarning::MissingTrailingDelimeter,
+            ],
+            warnings
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_with_options() {
+        let fix_string = b"8=FIX.4.4\x019=18\x0135=0\x01112=x\x019001=1\x0110=090\x01";
+
+        let mut options = ParseOptions::default();
+        options.keep_tag(9);
+        options.skip_tag(9001);
+
+        let builder = FixMessageBuilder::from_bytes_with_options(fix_string, &options).unwrap();
+
+        assert_eq!(Some(b"18".as_slice()), builder.get(9));
+        assert_eq!(None, builder.get(9001));
+        assert_eq!(None, builder.get(10));
+        assert_eq!(
+            b"8=FIX.4.4\x019=11\x0135=0\x01112=x\x0110=026\x01".to_vec(),
+            builder.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_header_accessors() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_value(49, "SENDER");
+        fix_builder.with_value(56, "TARGET");
+        fix_builder.with_value(34, 12u64);
+        fix_builder.with_value(52, "20240102-03:04:05.006");
+        fix_builder.with_value(43, true);
+
+        assert_eq!(Some("SENDER"), fix_builder.get_sender_comp_id());
+        assert_eq!(Some("TARGET"), fix_builder.get_target_comp_id());
+        assert_eq!(Ok(Some(12)), fix_builder.get_msg_seq_num());
+        assert_eq!(
+            parse_utc_timestamp("20240102-03:04:05.006"),
+            fix_builder.get_sending_time().unwrap()
+        );
+        assert_eq!(Ok(None), fix_builder.get_orig_sending_time());
+        assert!(fix_builder.is_poss_dup());
+
+        fix_builder.set_value(52, "yesterday");
+        assert!(matches!(
+            fix_builder.get_sending_time(),
+            Err(FieldParseError::InvalidValue { tag: 52, .. })
+        ));
+    }
+
+    #[test]
+    fn test_msg_type_is_not_a_body_field() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_value(11, "ORDER1");
+        let payload = fix_builder.as_bytes();
+
+        let parsed = FixMessageBuilder::from_bytes(&payload, true).unwrap();
+        assert_eq!(b"D", parsed.get_message_type().as_slice());
+        assert_eq!(None, parsed.get(35));
+        assert_eq!(
+            vec![11],
+            parsed.get_fields().map(|(tag, _)| tag).collect::<Vec<_>>()
+        );
+        assert_eq!(payload, parsed.as_bytes());
+    }
+
+    #[test]
+    fn test_duplicate_header_tags() {
+        let fix_string = b"8=FIX.4.4\x019=5\x0135=0\x0135=D\x0110=000\x01";
+
+        let mut options = ParseOptions {
+            check_sum_validation: false,
+            ..Default::default()
+        };
+        assert!(FixMessageBuilder::from_bytes_with_options(fix_string, &options).is_ok());
+
+        options.reject_duplicate_header_tags = true;
+        assert!(matches!(
+            FixMessageBuilder::from_bytes_with_options(fix_string, &options),
+            Err(FixSerializeError::DuplicateHeaderTag)
+        ));
+
+        let error = FixParseError::locate(
+            FixSerializeError::DuplicateHeaderTag,
+            fix_string,
+            &options.limits,
+        );
+        assert_eq!(
+            (Some(35), 2, 19),
+            (error.tag, error.occurrence, error.offset)
+        );
+    }
+
+    #[test]
+    fn test_display_and_debug() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "0");
+        fix_builder.with_value(58, b"\xff".as_slice());
+
+        let expected = "8=FIX.4.4|9=10|35=0|58=\u{FFFD}|10=";
+        assert!(format!("{}", fix_builder).starts_with(expected));
+        assert!(format!("{:?}", fix_builder).starts_with(expected));
+        assert_eq!(format!("{}", fix_builder), fix_builder.to_string());
+    }
+
+    #[test]
+    fn test_equality() {
+        let mut expected = FixMessageBuilder::new("FIX.4.4", "D");
+        expected.with_value(11, "ORDER1");
+        expected.with_value(55, "IBM");
+
+        let mut sent = FixMessageBuilder::new("FIX.4.4", "D");
+        sent.with_value(34, 7u64);
+        sent.with_value(55, "IBM");
+        sent.with_value(11, "ORDER1");
+
+        assert_eq!(expected, expected.clone());
+        assert_ne!(expected, sent);
+        assert!(!expected.semantically_equals(&sent, &[]));
+        assert!(expected.semantically_equals(&sent, VOLATILE_TAGS));
+
+        let parsed = FixMessageBuilder::from_bytes(&sent.as_bytes(), true).unwrap();
+        assert!(parsed.semantically_equals(&expected, VOLATILE_TAGS));
+
+        sent.set_value(55, "MSFT");
+        assert!(!expected.semantically_equals(&sent, VOLATILE_TAGS));
+    }
+
+    #[test]
+    fn test_get_slices() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "A");
+        fix_builder.with_value(49, "TESTBUY1");
+        fix_builder.with_value(49, "TESTBUY2");
+
+        assert_eq!(Some(b"TESTBUY1".as_slice()), fix_builder.get(49));
+        assert_eq!(Some(b"TESTBUY1".as_slice()), fix_builder.get_raw(b"49"));
+        assert_eq!(None, fix_builder.get(56));
+        assert_eq!(
+            vec![b"TESTBUY1".as_slice(), b"TESTBUY2".as_slice()],
+            fix_builder.get_all(49)
+        );
+    }
+
+    #[test]
+    fn test_get_as() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_value(38, "100");
+        fix_builder.with_value(44, "1.25");
+        fix_builder.with_value(58, "abc");
+
+        assert_eq!(Ok(Some(100u64)), fix_builder.get_as::<u64>(38));
+        assert_eq!(Ok(Some(1.25f64)), fix_builder.get_as::<f64>(44));
+        assert_eq!(Ok(None), fix_builder.get_as::<u64>(99));
+        assert!(matches!(
+            fix_builder.get_as::<u64>(58),
+            Err(FieldParseError::InvalidValue { tag: 58, .. })
+        ));
+    }
+
+    #[test]
+    fn test_fix_version() {
+        let mut fix_builder = FixMessageBuilder::new(BeginString::Fix44, "A");
+        assert_eq!(BeginString::Fix44, fix_builder.get_fix_version());
+
+        fix_builder.set_fix_version(BeginString::Fix42);
+
+        assert_eq!(BeginString::Fix42, fix_builder.get_fix_version());
+        assert!(fix_builder.to_string().starts_with("8=FIX.4.2|"));
+    }
+
+    #[test]
+    fn test_typed_fields() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_field(Side::Sell);
+        fix_builder.with_field(OrdType::Limit);
+
+        assert_eq!(Some(b"2".as_slice()), fix_builder.get(54));
+        assert_eq!(Ok(Some(Side::Sell)), fix_builder.get_field::<Side>());
+        assert_eq!(Ok(Some(OrdType::Limit)), fix_builder.get_field::<OrdType>());
+        assert_eq!(Ok(None), fix_builder.get_field::<TimeInForce>());
+    }
+
+    #[test]
+    fn test_sending_time_from_clock() {
+        let clock = Arc::new(ManualClock::new(
+            UNIX_EPOCH + Duration::from_millis(1_537_467_899_643),
+        ));
+
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "0");
+        fix_builder.with_value(34, "1");
+        fix_builder.with_clock(clock.clone());
+
+        assert_eq!(
+            "8=FIX.4.4|9=35|35=0|52=20180920-18:24:59.643|34=1|10=144|",
+            fix_builder.to_string()
+        );
+
+        clock.advance(Duration::from_secs(1));
+        assert!(fix_builder
+            .to_string()
+            .contains("|52=20180920-18:25:00.643|"));
+
+        fix_builder.with_timestamp_precision(TimestampPrecision::Micros);
+        assert!(fix_builder
+            .to_string()
+            .contains("|52=20180920-18:25:00.643000|"));
+    }
+
+    #[test]
+    fn test_orig_sending_time_on_resend() {
+        let clock = Arc::new(ManualClock::new(
+            UNIX_EPOCH + Duration::from_millis(1_537_467_900_000),
+        ));
+
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_value(43, "Y");
+        fix_builder.with_value(52, "20180920-18:24:59.643");
+        fix_builder.with_clock(clock);
+
+        let message = fix_builder.to_string();
+
+        assert!(message.contains("|52=20180920-18:25:00.000|122=20180920-18:24:59.643|43=Y|"));
+    }
+
+    #[test]
+    fn test_set_insert_remove_value() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_value(11, "A1");
+        fix_builder.with_value(58, "first");
+        fix_builder.with_value(58, "second");
+
+        fix_builder.set_value(11, "A2");
+        fix_builder.set_value(55, "EURUSD");
+        fix_builder.insert_value(0, 34, "7");
+
+        assert_eq!(2, fix_builder.remove_value(58));
+        assert_eq!(
+            "8=FIX.4.4|9=26|35=D|34=7|11=A2|55=EURUSD|10=089|",
+            fix_builder.to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_detailed() {
+        let limits = ParseLimits::default();
+        let payload = b"8=FIX.4.4\x019=17\x0135=0\x0158=a\x0158=b\x0110=000\x01";
+
+        let result = FixMessageBuilder::from_bytes_detailed(payload, true, &limits);
+
+        assert_eq!(
+            Err(FixParseError {
+                kind: FixSerializeError::InvalidCheckSum,
+                tag: Some(10),
+                occurrence: 1,
+                offset: 30,
+            }),
+            result.map(|_| ())
+        );
+
+        let limits = ParseLimits {
+            max_field_count: 4,
+            ..limits
+        };
+        let err = FixMessageBuilder::from_bytes_detailed(payload, false, &limits)
+            .map(|_| ())
+            .unwrap_err();
+
+        assert_eq!(FixSerializeError::TooManyFields, err.kind);
+        assert_eq!((Some(58), 2, 25), (err.tag, err.occurrence, err.offset));
+
+        let payload = b"8=FIX.4.4\x019=17\x0135=0\x01x=1\x0110=000\x01";
+        let err = FixMessageBuilder::from_bytes_detailed(payload, false, &ParseLimits::default())
+            .map(|_| ())
+            .unwrap_err();
+
+        assert_eq!(
+            crate::fields::SessionRejectReason::InvalidTagNumber,
+            err.get_session_reject_reason()
+        );
+        assert_eq!(
+            (FixSerializeError::InvalidTag, None, 20),
+            (err.kind, err.tag, err.offset)
+        );
+    }
+
+    #[test]
+    fn test_invalid_tag() {
+        let result =
+            FixMessageBuilder::from_bytes(b"8=FIX.4.4\x019=11\x0135=0\x01x=1\x0110=000\x01", false);
+
+        assert!(matches!(result, Err(FixSerializeError::InvalidTag)));
+    }
+
+    #[test]
+    fn test_typed_values() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(38, 100);
+        message.with_value(44, FixValue::decimal(10125, 2));
+        message.with_value(54, '1');
+        message.with_value(43, true);
+        message.with_value(55, String::from("IBM"));
+        message.set_value(38, 200u64);
+
+        assert_eq!(
+            "8=FIX.4.4|9=39|35=D|38=200|44=101.25|54=1|43=Y|55=IBM|10=076|",
+            message.to_string()
+        );
+    }
+
+    #[test]
+    fn test_get_typed() {
+        let dictionary = FixDictionary::fix44();
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(44, "101.25");
+        message.with_value(54, "1");
+        message.with_value(9999, "x");
+
+        assert_eq!(
+            Ok(Some(FixValue::decimal(10125, 2))),
+            message.get_typed(44, &dictionary)
+        );
+        assert_eq!(
+            Ok(Some(FixValue::Char('1'))),
+            message.get_typed(54, &dictionary)
+        );
+        assert_eq!(
+            Ok(Some(FixValue::String("x".to_string()))),
+            message.get_typed(9999, &dictionary)
+        );
+        assert_eq!(Ok(None), message.get_typed(38, &dictionary));
+    }
+
+    #[derive(Debug)]
+    struct LengthSigner;
+
+    impl Signer for LengthSigner {
+        fn sign(&self, body: &[u8]) -> Vec<u8> {
+            return body.len().to_string().into_bytes();
+        }
+    }
+
+    #[test]
+    fn test_signature_trailer() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(55, "IBM");
+        message.with_value(89, "stale");
+        message.with_signer(Arc::new(LengthSigner));
+
+        let bytes = message.as_bytes();
+
+        assert_eq!(
+            "8=FIX.4.4|9=23|35=D|55=IBM|93=2|89=12|10=085|",
+            message.to_string()
+        );
+        assert!(FixMessageBuilder::from_bytes(&bytes, true).is_ok());
+    }
+
+    #[test]
+    fn test_clone_with_header() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(49, "BUY");
+        message.with_value(56, "SELL");
+        message.with_value(34, 7);
+        message.with_value(11, "1");
+        message.with_value(55, "IBM");
+
+        let result = message.clone_with_header();
+
+        assert_eq!(
+            "8=FIX.4.4|9=25|35=D|49=BUY|56=SELL|34=7|10=057|",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_map_from_map() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(55, "IBM");
+        message.with_value(11, "1");
+        message.with_value(55, "MSFT");
+
+        let map = message.to_map();
+
+        assert_eq!(
+            BTreeMap::from([
+                (11, vec!["1".to_string()]),
+                (55, vec!["IBM".to_string(), "MSFT".to_string()]),
+            ]),
+            map
+        );
+
+        let mut map = map;
+        map.insert(35, vec!["8".to_string()]);
+
+        let result = FixMessageBuilder::from_map("FIX.4.4", "D", &map);
+
+        assert_eq!(
+            "8=FIX.4.4|9=25|35=D|11=1|55=IBM|55=MSFT|10=028|",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "A");
+        fix_builder.with_value(49, "TESTBUY1");
+        fix_builder.with_value(554, "secret");
+        fix_builder.with_value(925, "new-secret");
+
+        let redacted = fix_builder.to_redacted_string(DEFAULT_REDACTED_TAGS);
+
+        assert!(redacted.contains("|49=TESTBUY1|554=***|925=***|"));
+        assert!(!redacted.contains("secret"));
+        assert_eq!(
+            Some("secret".to_string()),
+            fix_builder.get_string_lossy(554)
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn test_get_few_values_with_same_tag() {
         let fix_string = b"8=FIX.4.49=8735=A34=109249=TESTBUY149=TESTBUY252=20180920-18:24:59.64356=TESTSELL198=0108=6010=194";
 
@@ -365,4 +1589,35 @@ mod test {
         assert_eq!("TESTBUY1", tag49[0]);
         assert_eq!("TESTBUY2", tag49[1]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_all_strings_with_same_tag() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "A");
+        fix_builder.with_value(49, "TESTBUY1");
+        fix_builder.with_value(49, "TESTBUY2");
+        fix_builder.with_value(56, "TESTSELL1");
+
+        let tag49 = fix_builder.get_all_strings(49).unwrap();
+        assert_eq!(vec!["TESTBUY1", "TESTBUY2"], tag49);
+        assert_eq!(Ok(vec![]), fix_builder.get_all_strings(50));
+    }
+
+    #[test]
+    fn test_get_string_with_invalid_utf8() {
+        let mut fix_builder = FixMessageBuilder::new("FIX.4.4", "D");
+        fix_builder.with_value(11, "ORDER1");
+        fix_builder.with_value(58, b"caf\xe9".as_slice());
+
+        assert_eq!(Ok(Some("ORDER1".to_string())), fix_builder.get_string(11));
+        assert_eq!(Ok(None), fix_builder.get_string(1));
+        assert_eq!(Err(Utf8FieldError { tag: 58 }), fix_builder.get_string(58));
+        assert_eq!(
+            Some("caf\u{fffd}".to_string()),
+            fix_builder.get_string_lossy(58)
+        );
+        assert_eq!(
+            Err(Utf8FieldError { tag: 58 }),
+            fix_builder.get_all_strings(58)
+        );
+    }
+}
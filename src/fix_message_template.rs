@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use crate::FixMessageBuilder;
+
+/// Frozen prototype message, e.g. version, comp IDs, account and other static fields,
+/// that per-order messages start from.
+///
+/// Cloning the template only bumps a reference counter; `new_message` copies the
+/// prototype into a builder with room for the per-message fields already reserved.
+#[derive(Debug, Clone)]
+pub struct FixMessageTemplate {
+    prototype: Arc<FixMessageBuilder>,
+    extra_fields: usize,
+}
+
+impl FixMessageTemplate {
+    pub fn new(prototype: FixMessageBuilder) -> Self {
+        return Self {
+            prototype: Arc::new(prototype),
+            extra_fields: DEFAULT_EXTRA_FIELDS,
+        };
+    }
+
+    /// Number of fields callers are expected to append to each message.
+    pub fn with_extra_fields(&mut self, extra_fields: usize) {
+        self.extra_fields = extra_fields;
+    }
+
+    pub fn get_prototype(&self) -> &FixMessageBuilder {
+        return &self.prototype;
+    }
+
+    pub fn new_message(&self) -> FixMessageBuilder {
+        return self.prototype.clone_with_capacity(self.extra_fields);
+    }
+}
+
+const DEFAULT_EXTRA_FIELDS: usize = 16;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new_message() {
+        let mut prototype = FixMessageBuilder::new("FIX.4.4", "D");
+        prototype.with_value(49, "BUY");
+        prototype.with_value(56, "SELL");
+        prototype.with_value(1, "ACC");
+
+        let template = prototype.template();
+
+        let mut first = template.new_message();
+        first.with_value(11, "1");
+        let mut second = template.new_message();
+        second.with_value(11, "2");
+
+        assert_eq!(
+            "8=FIX.4.4|9=31|35=D|49=BUY|56=SELL|1=ACC|11=1|10=097|",
+            first.to_string()
+        );
+        assert_eq!(Some(b"2".as_slice()), second.get(11));
+        assert_eq!(None, template.get_prototype().get(11));
+    }
+}
@@ -0,0 +1,341 @@
+use std::{fmt::Display, str::FromStr, time::SystemTime};
+
+use crate::{
+    fields::FixField,
+    parse_checksum, parse_tag, parse_utc_timestamp,
+    utils::{checksum_u8, FIX_DELIMETR},
+    BeginString, FieldParseError, FieldType, FixDictionary, FixMessageBuilder, FixSerializeError,
+    FixValue, Utf8FieldError, DEFAULT_SKIPPED_TAGS, FIX_CHECK_SUM, FIX_MESSAGE_TYPE, FIX_VERSION,
+};
+
+const TAG_MSG_SEQ_NUM: u32 = 34;
+const TAG_POSS_DUP_FLAG: u32 = 43;
+const TAG_SENDER_COMP_ID: u32 = 49;
+const TAG_SENDING_TIME: u32 = 52;
+const TAG_TARGET_COMP_ID: u32 = 56;
+const TAG_ORIG_SENDING_TIME: u32 = 122;
+
+/// Read-only message borrowing its tags and values from the received bytes, for hot
+/// paths such as market data where `FixMessageBuilder` would allocate per field.
+///
+/// Accessors match the builder's, and the fields that follow MsgType (35) are kept in
+/// wire order without BodyLength (9) and CheckSum (10). Take `to_builder` to change or
+/// keep the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessageView<'a> {
+    fix_version: &'a [u8],
+    message_type: &'a [u8],
+    data: Vec<(u32, &'a [u8])>,
+}
+
+impl<'a> FixMessageView<'a> {
+    /// Same checks as `FixMessageBuilder::from_bytes`; the CheckSum is taken over the
+    /// received bytes.
+    pub fn from_bytes(
+        payload: &'a [u8],
+        check_sum_validation: bool,
+    ) -> Result<Self, FixSerializeError> {
+        let mut fix_version = None;
+        let mut message_type = None;
+        let mut check_sum = None;
+        let mut data = vec![];
+        let mut offset = 0;
+
+        // Bytes after the last SOH aren't a complete field and are left out.
+        for chunk in payload.split(|byte| *byte == FIX_DELIMETR) {
+            if offset + chunk.len() >= payload.len() {
+                break;
+            }
+
+            let (tag, value) = match chunk.iter().position(|byte| *byte == b'=') {
+                Some(separator) => (&chunk[..separator], &chunk[separator + 1..]),
+                None => (chunk, &chunk[chunk.len()..]),
+            };
+
+            let Some(parsed_tag) = parse_tag(tag) else {
+                return Err(FixSerializeError::InvalidTag);
+            };
+
+            match tag {
+                FIX_VERSION => {
+                    fix_version.get_or_insert(value);
+                }
+                FIX_MESSAGE_TYPE => {
+                    message_type.get_or_insert(value);
+                }
+                FIX_CHECK_SUM => check_sum = Some((offset, value)),
+                _ if DEFAULT_SKIPPED_TAGS.contains(&parsed_tag) => {}
+                _ => data.push((parsed_tag, value)),
+            }
+
+            offset += chunk.len() + 1;
+        }
+
+        let Some(fix_version) = fix_version else {
+            return Err(FixSerializeError::VersionTagNotFoundInSource);
+        };
+
+        let Some(message_type) = message_type else {
+            return Err(FixSerializeError::MessageTypeTagNotFoundInSource);
+        };
+
+        if check_sum_validation {
+            let Some((offset, value)) = check_sum else {
+                return Err(FixSerializeError::CheckSumTagNotFoundInSource);
+            };
+
+            if parse_checksum(value) != Some(checksum_u8(&payload[..offset])) {
+                return Err(FixSerializeError::InvalidCheckSum);
+            }
+        }
+
+        return Ok(Self {
+            fix_version,
+            message_type,
+            data,
+        });
+    }
+
+    pub fn get(&self, tag: u32) -> Option<&'a [u8]> {
+        return self
+            .get_fields()
+            .find(|(inner, _)| *inner == tag)
+            .map(|(_, value)| value);
+    }
+
+    pub fn get_raw(&self, tag: &[u8]) -> Option<&'a [u8]> {
+        return self.get(parse_tag(tag)?);
+    }
+
+    pub fn get_all(&self, tag: u32) -> Vec<&'a [u8]> {
+        return self
+            .get_fields()
+            .filter(|(inner, _)| *inner == tag)
+            .map(|(_, value)| value)
+            .collect();
+    }
+
+    pub fn get_all_raw(&self, tag: &[u8]) -> Vec<&'a [u8]> {
+        let Some(tag) = parse_tag(tag) else {
+            return vec![];
+        };
+
+        return self.get_all(tag);
+    }
+
+    pub fn get_as<T>(&self, tag: u32) -> Result<Option<T>, FieldParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        let Ok(value) = std::str::from_utf8(value) else {
+            return Err(FieldParseError::InvalidUtf8 { tag });
+        };
+
+        return match value.parse() {
+            Ok(result) => Ok(Some(result)),
+            Err(err) => Err(FieldParseError::InvalidValue {
+                tag,
+                value: value.to_string(),
+                reason: err.to_string(),
+            }),
+        };
+    }
+
+    /// SenderCompID (49); `None` when absent or not UTF-8.
+    pub fn get_sender_comp_id(&self) -> Option<&'a str> {
+        return std::str::from_utf8(self.get(TAG_SENDER_COMP_ID)?).ok();
+    }
+
+    /// TargetCompID (56); `None` when absent or not UTF-8.
+    pub fn get_target_comp_id(&self) -> Option<&'a str> {
+        return std::str::from_utf8(self.get(TAG_TARGET_COMP_ID)?).ok();
+    }
+
+    /// MsgSeqNum (34).
+    pub fn get_msg_seq_num(&self) -> Result<Option<u64>, FieldParseError> {
+        return self.get_as(TAG_MSG_SEQ_NUM);
+    }
+
+    /// SendingTime (52).
+    pub fn get_sending_time(&self) -> Result<Option<SystemTime>, FieldParseError> {
+        return self.get_utc_timestamp(TAG_SENDING_TIME);
+    }
+
+    /// OrigSendingTime (122).
+    pub fn get_orig_sending_time(&self) -> Result<Option<SystemTime>, FieldParseError> {
+        return self.get_utc_timestamp(TAG_ORIG_SENDING_TIME);
+    }
+
+    /// Whether PossDupFlag (43) is set.
+    pub fn is_poss_dup(&self) -> bool {
+        return self.get(TAG_POSS_DUP_FLAG) == Some(b"Y".as_slice());
+    }
+
+    fn get_utc_timestamp(&self, tag: u32) -> Result<Option<SystemTime>, FieldParseError> {
+        let Some(value) = self.get_as::<String>(tag)? else {
+            return Ok(None);
+        };
+
+        return match parse_utc_timestamp(&value) {
+            Some(result) => Ok(Some(result)),
+            None => Err(FieldParseError::InvalidValue {
+                tag,
+                value,
+                reason: "invalid UTCTimestamp".to_string(),
+            }),
+        };
+    }
+
+    /// Same as `FixMessageBuilder::get_typed`.
+    pub fn get_typed(
+        &self,
+        tag: u32,
+        dictionary: &FixDictionary,
+    ) -> Result<Option<FixValue>, FieldParseError> {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        let field_type = match dictionary.get_field(tag) {
+            Some(field) => &field.field_type,
+            None if std::str::from_utf8(value).is_ok() => &FieldType::String,
+            None => &FieldType::Data,
+        };
+
+        return FixValue::parse(tag, field_type, value).map(Some);
+    }
+
+    pub fn get_field<F: FixField>(&self) -> Result<Option<F>, FieldParseError> {
+        return self.get(F::TAG).map(F::try_from).transpose();
+    }
+
+    pub fn get_fix_version(&self) -> BeginString {
+        return BeginString::from(self.fix_version);
+    }
+
+    pub fn get_message_type(&self) -> &'a [u8] {
+        return self.message_type;
+    }
+
+    pub fn get_message_type_as_string(&self) -> String {
+        return String::from_utf8_lossy(self.message_type).to_string();
+    }
+
+    /// First value of `tag` as text, borrowed from the payload.
+    pub fn get_str(&self, tag: u32) -> Result<Option<&'a str>, Utf8FieldError> {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        return match std::str::from_utf8(value) {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(Utf8FieldError { tag }),
+        };
+    }
+
+    pub fn get_string(&self, tag: u32) -> Result<Option<String>, Utf8FieldError> {
+        return Ok(self.get_str(tag)?.map(|value| value.to_string()));
+    }
+
+    pub fn get_string_lossy(&self, tag: u32) -> Option<String> {
+        return self
+            .get(tag)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+    }
+
+    pub fn get_all_strings(&self, tag: u32) -> Result<Vec<String>, Utf8FieldError> {
+        let mut result = vec![];
+
+        for value in self.get_all(tag) {
+            match std::str::from_utf8(value) {
+                Ok(value) => result.push(value.to_string()),
+                Err(_) => return Err(Utf8FieldError { tag }),
+            }
+        }
+
+        return Ok(result);
+    }
+
+    /// Fields that follow MsgType (35), in wire order.
+    pub fn get_fields(&self) -> impl Iterator<Item = (u32, &'a [u8])> + '_ {
+        return self.data.iter().map(|(tag, value)| (*tag, *value));
+    }
+
+    /// Copies the message into a builder, e.g. to keep it past the payload.
+    pub fn to_builder(&self) -> FixMessageBuilder {
+        let mut result =
+            FixMessageBuilder::new(self.get_fix_version(), &self.get_message_type_as_string());
+
+        for (tag, value) in self.get_fields() {
+            result.with_value(tag, value.to_vec());
+        }
+
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_view_matches_builder() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "W");
+        message.with_value(34, 12);
+        message.with_value(49, "FEED");
+        message.with_value(52, "20180920-18:14:19.492");
+        message.with_value(56, "CLIENT");
+        message.with_value(55, "EURUSD");
+        message.with_value(268, 2);
+
+        for (side, price) in [("0", "1.0851"), ("1", "1.0853")] {
+            message.with_value(269, side);
+            message.with_value(270, price);
+        }
+
+        message.with_value(58, "café");
+        let mut payload = message.as_bytes();
+        let len = payload.len();
+
+        let view = FixMessageView::from_bytes(&payload, true).unwrap();
+        let builder = FixMessageBuilder::from_bytes(&payload, true).unwrap();
+
+        assert_eq!(builder.get_fix_version(), view.get_fix_version());
+        assert_eq!(b"W".as_slice(), view.get_message_type());
+        assert_eq!(Some("FEED"), view.get_sender_comp_id());
+        assert_eq!(Ok(Some(12)), view.get_msg_seq_num());
+        assert_eq!(builder.get_sending_time(), view.get_sending_time());
+        assert_eq!(vec![b"0".as_slice(), b"1"], view.get_all_raw(b"269"));
+        assert_eq!(Ok(Some(1.0851)), view.get_as::<f64>(270));
+        assert_eq!(Ok(Some("café")), view.get_str(58));
+        assert_eq!(None, view.get(10));
+        assert_eq!(
+            vec![269, 270, 269, 270],
+            view.get_fields()
+                .skip(6)
+                .take(4)
+                .map(|(tag, _)| tag)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(b"1".as_slice()),
+            view.get_group(268, &[269, 270]).unwrap()[1].get(269)
+        );
+        assert!(view.to_builder().semantically_equals(&builder, &[]));
+
+        payload[len - 2] = if payload[len - 2] == b'0' { b'1' } else { b'0' };
+        assert!(matches!(
+            FixMessageView::from_bytes(&payload, true),
+            Err(FixSerializeError::InvalidCheckSum)
+        ));
+        assert!(matches!(
+            FixMessageView::from_bytes(b"8=FIX.4.4\x0135=0\x01x=1\x01", false),
+            Err(FixSerializeError::InvalidTag)
+        ));
+    }
+}
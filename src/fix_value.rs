@@ -0,0 +1,295 @@
+use std::time::SystemTime;
+
+use crate::{format_utc_timestamp, parse_utc_timestamp, FieldParseError, FieldType};
+
+/// Value of a field before it is encoded, so callers don't format numbers, flags and
+/// timestamps by hand.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixValue {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    /// `mantissa * 10^-scale`, written without going through a float.
+    Decimal {
+        mantissa: i64,
+        scale: u32,
+    },
+    Char(char),
+    /// Written as `Y` or `N`.
+    Boolean(bool),
+    String(String),
+    Data(Vec<u8>),
+    /// Written as a UTCTimestamp with milliseconds.
+    UtcTimestamp(SystemTime),
+}
+
+impl FixValue {
+    pub fn decimal(mantissa: i64, scale: u32) -> Self {
+        return FixValue::Decimal { mantissa, scale };
+    }
+
+    /// Encodes the value as it goes on the wire.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        return match self {
+            FixValue::Int(value) => value.to_string().into_bytes(),
+            FixValue::UInt(value) => value.to_string().into_bytes(),
+            FixValue::Float(value) => value.to_string().into_bytes(),
+            FixValue::Decimal { mantissa, scale } => format_decimal(*mantissa, *scale).into_bytes(),
+            FixValue::Char(value) => value.to_string().into_bytes(),
+            FixValue::Boolean(true) => b"Y".to_vec(),
+            FixValue::Boolean(false) => b"N".to_vec(),
+            FixValue::String(value) => value.as_bytes().to_vec(),
+            FixValue::Data(value) => value.clone(),
+            FixValue::UtcTimestamp(value) => format_utc_timestamp(*value).into_bytes(),
+        };
+    }
+
+    /// Decodes a field value into the variant matching its dictionary type. Prices,
+    /// quantities and other floats come back as `Decimal` to keep every digit.
+    pub fn parse(tag: u32, field_type: &FieldType, value: &[u8]) -> Result<Self, FieldParseError> {
+        let invalid = |reason: &str| FieldParseError::InvalidValue {
+            tag,
+            value: String::from_utf8_lossy(value).to_string(),
+            reason: reason.to_string(),
+        };
+
+        if matches!(field_type, FieldType::Data | FieldType::XmlData) {
+            return Ok(FixValue::Data(value.to_vec()));
+        }
+
+        let Ok(text) = std::str::from_utf8(value) else {
+            return Err(FieldParseError::InvalidUtf8 { tag });
+        };
+
+        return match field_type {
+            FieldType::Int
+            | FieldType::Length
+            | FieldType::SeqNum
+            | FieldType::NumInGroup
+            | FieldType::TagNum
+            | FieldType::DayOfMonth => match text.parse::<i64>() {
+                Ok(value) => Ok(FixValue::Int(value)),
+                Err(_) => text
+                    .parse::<u64>()
+                    .map(FixValue::UInt)
+                    .map_err(|_| invalid("not an integer")),
+            },
+            FieldType::Float
+            | FieldType::Qty
+            | FieldType::Price
+            | FieldType::PriceOffset
+            | FieldType::Amt
+            | FieldType::Percentage => match parse_decimal(text) {
+                Some((mantissa, scale)) => Ok(FixValue::Decimal { mantissa, scale }),
+                None => Err(invalid("not a decimal number")),
+            },
+            FieldType::Char => {
+                let mut chars = text.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(value), None) => Ok(FixValue::Char(value)),
+                    _ => Err(invalid("not a single character")),
+                }
+            }
+            FieldType::Boolean => match text {
+                "Y" => Ok(FixValue::Boolean(true)),
+                "N" => Ok(FixValue::Boolean(false)),
+                _ => Err(invalid("not Y or N")),
+            },
+            FieldType::UtcTimestamp => parse_utc_timestamp(text)
+                .map(FixValue::UtcTimestamp)
+                .ok_or_else(|| invalid("not a UTCTimestamp")),
+            _ => Ok(FixValue::String(text.to_string())),
+        };
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        return match self {
+            FixValue::String(value) => value.into_bytes(),
+            FixValue::Data(value) => value,
+            value => value.to_bytes(),
+        };
+    }
+}
+
+fn format_decimal(mantissa: i64, scale: u32) -> String {
+    if scale == 0 {
+        return mantissa.to_string();
+    }
+
+    let digits = mantissa.unsigned_abs().to_string();
+    let digits = format!("{:0>width$}", digits, width = scale as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale as usize);
+    let sign = if mantissa < 0 { "-" } else { "" };
+
+    return format!("{}{}.{}", sign, whole, fraction);
+}
+
+// Splits `-123.4500` into (-1234500, 4) without going through a float.
+fn parse_decimal(text: &str) -> Option<(i64, u32)> {
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => (-1, digits),
+        None => (1, text),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let mut mantissa: i64 = 0;
+
+    for byte in whole.bytes().chain(fraction.bytes()) {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+
+        mantissa = mantissa
+            .checked_mul(10)?
+            .checked_add((byte - b'0') as i64)?;
+    }
+
+    return Some((sign * mantissa, fraction.len() as u32));
+}
+
+macro_rules! fix_value_from {
+    ($variant:ident($inner:ty): $($source:ty),+) => {
+        $(
+            impl From<$source> for FixValue {
+                fn from(value: $source) -> Self {
+                    return FixValue::$variant(value as $inner);
+                }
+            }
+        )+
+    };
+}
+
+fix_value_from!(Int(i64): i8, i16, i32, i64, isize, u8, u16, u32);
+fix_value_from!(UInt(u64): u64, usize);
+fix_value_from!(Float(f64): f32, f64);
+
+impl From<char> for FixValue {
+    fn from(value: char) -> Self {
+        return FixValue::Char(value);
+    }
+}
+
+impl From<bool> for FixValue {
+    fn from(value: bool) -> Self {
+        return FixValue::Boolean(value);
+    }
+}
+
+impl From<&str> for FixValue {
+    fn from(value: &str) -> Self {
+        return FixValue::String(value.to_string());
+    }
+}
+
+impl From<String> for FixValue {
+    fn from(value: String) -> Self {
+        return FixValue::String(value);
+    }
+}
+
+impl From<&String> for FixValue {
+    fn from(value: &String) -> Self {
+        return FixValue::String(value.clone());
+    }
+}
+
+impl From<&[u8]> for FixValue {
+    fn from(value: &[u8]) -> Self {
+        return FixValue::Data(value.to_vec());
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for FixValue {
+    fn from(value: &[u8; N]) -> Self {
+        return FixValue::Data(value.to_vec());
+    }
+}
+
+impl From<Vec<u8>> for FixValue {
+    fn from(value: Vec<u8>) -> Self {
+        return FixValue::Data(value);
+    }
+}
+
+impl From<SystemTime> for FixValue {
+    fn from(value: SystemTime) -> Self {
+        return FixValue::UtcTimestamp(value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_to_bytes() {
+        let cases: Vec<(FixValue, &[u8])> = vec![
+            (FixValue::from(-5), b"-5"),
+            (FixValue::from(u64::MAX), b"18446744073709551615"),
+            (FixValue::from(101.25), b"101.25"),
+            (FixValue::from(100.0), b"100"),
+            (FixValue::decimal(12345, 2), b"123.45"),
+            (FixValue::decimal(-5, 3), b"-0.005"),
+            (FixValue::decimal(7, 0), b"7"),
+            (FixValue::from('2'), b"2"),
+            (FixValue::from(true), b"Y"),
+            (FixValue::from(false), b"N"),
+            (FixValue::from("IBM"), b"IBM"),
+            (FixValue::from(b"\x00\x01"), b"\x00\x01"),
+            (
+                FixValue::from(UNIX_EPOCH + Duration::from_millis(1_537_467_899_643)),
+                b"20180920-18:24:59.643",
+            ),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(expected, value.to_bytes().as_slice(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(FixValue::decimal(-12, 0)),
+            FixValue::parse(38, &FieldType::Qty, b"-12")
+        );
+        assert_eq!(
+            Ok(FixValue::decimal(-1234500, 4)),
+            FixValue::parse(44, &FieldType::Price, b"-123.4500")
+        );
+        assert_eq!(
+            Ok(FixValue::Int(7)),
+            FixValue::parse(34, &FieldType::SeqNum, b"7")
+        );
+        assert_eq!(
+            Ok(FixValue::Char('1')),
+            FixValue::parse(54, &FieldType::Char, b"1")
+        );
+        assert_eq!(
+            Ok(FixValue::Boolean(true)),
+            FixValue::parse(43, &FieldType::Boolean, b"Y")
+        );
+        assert_eq!(
+            Ok(FixValue::UtcTimestamp(
+                UNIX_EPOCH + Duration::from_millis(1_537_467_899_643)
+            )),
+            FixValue::parse(52, &FieldType::UtcTimestamp, b"20180920-18:24:59.643")
+        );
+        assert_eq!(
+            Ok(FixValue::Data(vec![0, 1])),
+            FixValue::parse(96, &FieldType::Data, b"\x00\x01")
+        );
+        assert_eq!(
+            Ok(FixValue::String("IBM".to_string())),
+            FixValue::parse(55, &FieldType::String, b"IBM")
+        );
+        assert!(FixValue::parse(54, &FieldType::Char, b"12").is_err());
+        assert!(FixValue::parse(44, &FieldType::Price, b"1.2.3").is_err());
+    }
+}
@@ -0,0 +1,467 @@
+use std::{fmt, time::SystemTime};
+
+use crate::{
+    component::fix_component,
+    fields::{FixField, Side},
+    group::{
+        expect_msg_type, get_coded, get_date, get_string, get_timestamp, read_group, required,
+        write_optional, GroupEntry,
+    },
+    BeginString, Component, FieldParseError, FixDate, FixMessageBuilder, FixValue, SettlType,
+};
+
+const MSG_TYPE_QUOTE_REQUEST: &str = "R";
+const MSG_TYPE_QUOTE: &str = "S";
+
+const TAG_NO_RELATED_SYM: u32 = 146;
+
+const QUOTE_REQUEST_SYM_TAGS: [u32; 9] = [55, 54, 38, 15, 63, 64, 193, 192, 1];
+
+/// Currency pair carried in Symbol (55), `EURUSD` or `EUR/USD` for EUR priced in USD.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CurrencyPair {
+    /// Base currency, the one bought or sold.
+    pub base: String,
+    /// Quote currency, the one the price is expressed in.
+    pub quote: String,
+}
+
+impl CurrencyPair {
+    pub fn new(base: &str, quote: &str) -> Self {
+        return Self {
+            base: base.to_string(),
+            quote: quote.to_string(),
+        };
+    }
+
+    /// Parses `EURUSD` or `EUR/USD`; `None` unless both sides are three uppercase
+    /// letters.
+    pub fn parse(symbol: &str) -> Option<Self> {
+        let (base, quote) = match symbol.split_once('/') {
+            Some(sides) => sides,
+            None if symbol.len() == 6 && symbol.is_ascii() => symbol.split_at(3),
+            None => return None,
+        };
+
+        let is_currency =
+            |code: &str| code.len() == 3 && code.bytes().all(|byte| byte.is_ascii_uppercase());
+
+        if !is_currency(base) || !is_currency(quote) {
+            return None;
+        }
+
+        return Some(Self::new(base, quote));
+    }
+
+    /// The pair quoted the other way round, e.g. USDEUR for EURUSD.
+    pub fn inverse(&self) -> Self {
+        return Self::new(&self.quote, &self.base);
+    }
+
+    /// `EUR/USD`, for venues that want the separator; `to_string` gives `EURUSD`.
+    pub fn to_slashed(&self) -> String {
+        return format!("{}/{}", self.base, self.quote);
+    }
+}
+
+impl fmt::Display for CurrencyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}{}", self.base, self.quote);
+    }
+}
+
+impl From<CurrencyPair> for FixValue {
+    fn from(value: CurrencyPair) -> Self {
+        return FixValue::String(value.to_string());
+    }
+}
+
+fix_component!(
+    /// Spot rates and forward points of a two-way FX quote. The all-in prices are
+    /// BidPx (132) and OfferPx (133); points are kept as written, sign included.
+    FxQuoteRates {
+        /// BidSpotRate (188).
+        bid_spot_rate => 188,
+        /// BidForwardPoints (189).
+        bid_forward_points => 189,
+        /// OfferSpotRate (190).
+        offer_spot_rate => 190,
+        /// OfferForwardPoints (191).
+        offer_forward_points => 191,
+        /// BidForwardPoints2 (642), far leg of a swap.
+        bid_forward_points2 => 642,
+        /// OfferForwardPoints2 (643), far leg of a swap.
+        offer_forward_points2 => 643,
+    }
+);
+
+fix_component!(
+    /// Spot rate and forward points of an FX fill, LastPx (31) being the all-in rate.
+    FxFillRates {
+        /// LastSpotRate (194).
+        last_spot_rate => 194,
+        /// LastForwardPoints (195).
+        last_forward_points => 195,
+        /// LastForwardPoints2 (641), far leg of a swap.
+        last_forward_points2 => 641,
+    }
+);
+
+/// QuoteRequest (R) for one currency pair, written as a single NoRelatedSym (146) entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FxQuoteRequest {
+    /// QuoteReqID (131).
+    pub quote_req_id: String,
+    /// Symbol (55).
+    pub pair: CurrencyPair,
+    /// `None` for a two-way quote.
+    pub side: Option<Side>,
+    /// OrderQty (38), in `currency`.
+    pub order_qty: String,
+    /// Currency (15), the dealt currency.
+    pub currency: Option<String>,
+    pub settl_type: Option<SettlType>,
+    /// SettlDate (64), for a `BrokenDate` or to pin the value date.
+    pub settl_date: Option<FixDate>,
+    /// SettlDate2 (193), far value date of a swap.
+    pub settl_date2: Option<FixDate>,
+    /// OrderQty2 (192), far amount of a swap.
+    pub order_qty2: Option<String>,
+    /// Account (1).
+    pub account: Option<String>,
+}
+
+/// Quote (S) for one currency pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FxQuote {
+    /// QuoteReqID (131) of the request answered, `None` for a streaming quote.
+    pub quote_req_id: Option<String>,
+    /// QuoteID (117).
+    pub quote_id: String,
+    /// Symbol (55).
+    pub pair: CurrencyPair,
+    pub side: Option<Side>,
+    /// OrderQty (38).
+    pub order_qty: Option<String>,
+    /// Currency (15).
+    pub currency: Option<String>,
+    pub settl_type: Option<SettlType>,
+    /// SettlDate (64).
+    pub settl_date: Option<FixDate>,
+    /// SettlDate2 (193).
+    pub settl_date2: Option<FixDate>,
+    /// OrderQty2 (192).
+    pub order_qty2: Option<String>,
+    /// BidPx (132), all-in.
+    pub bid_px: Option<String>,
+    /// OfferPx (133), all-in.
+    pub offer_px: Option<String>,
+    /// BidSize (134).
+    pub bid_size: Option<String>,
+    /// OfferSize (135).
+    pub offer_size: Option<String>,
+    pub rates: FxQuoteRates,
+    /// ValidUntilTime (62).
+    pub valid_until_time: Option<SystemTime>,
+}
+
+impl FxQuoteRequest {
+    /// Spot request: `Regular` settlement of `order_qty` in the base currency.
+    pub fn spot(quote_req_id: &str, pair: CurrencyPair, order_qty: &str) -> Self {
+        return Self {
+            quote_req_id: quote_req_id.to_string(),
+            currency: Some(pair.base.clone()),
+            pair,
+            side: None,
+            order_qty: order_qty.to_string(),
+            settl_type: Some(SettlType::Regular),
+            settl_date: None,
+            settl_date2: None,
+            order_qty2: None,
+            account: None,
+        };
+    }
+
+    /// Outright forward settling as `settl_type`, e.g. a `Tenor` or `BrokenDate` with
+    /// `settl_date` set.
+    pub fn forward(
+        quote_req_id: &str,
+        pair: CurrencyPair,
+        order_qty: &str,
+        settl_type: SettlType,
+    ) -> Self {
+        return Self {
+            settl_type: Some(settl_type),
+            ..Self::spot(quote_req_id, pair, order_qty)
+        };
+    }
+
+    /// Swap of `order_qty` between the two value dates.
+    pub fn swap(
+        quote_req_id: &str,
+        pair: CurrencyPair,
+        order_qty: &str,
+        near_date: FixDate,
+        far_date: FixDate,
+    ) -> Self {
+        return Self {
+            settl_type: Some(SettlType::BrokenDate),
+            settl_date: Some(near_date),
+            settl_date2: Some(far_date),
+            order_qty2: Some(order_qty.to_string()),
+            ..Self::spot(quote_req_id, pair, order_qty)
+        };
+    }
+
+    /// The QuoteRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_QUOTE_REQUEST);
+        message.with_value(131, self.quote_req_id.as_str());
+        message.with_value(TAG_NO_RELATED_SYM, 1);
+        message.with_value(55, self.pair.clone());
+
+        if let Some(side) = self.side {
+            message.with_field(side);
+        }
+
+        message.with_value(38, self.order_qty.as_str());
+        write_optional(&mut message, 15, &self.currency);
+        write_optional(&mut message, 63, &self.settl_type);
+        write_optional(&mut message, 64, &self.settl_date);
+        write_optional(&mut message, 193, &self.settl_date2);
+        write_optional(&mut message, 192, &self.order_qty2);
+        write_optional(&mut message, 1, &self.account);
+
+        return message;
+    }
+}
+
+/// Reads the first NoRelatedSym (146) entry.
+impl TryFrom<&FixMessageBuilder> for FxQuoteRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_QUOTE_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let entries = read_group(&fields, TAG_NO_RELATED_SYM, &QUOTE_REQUEST_SYM_TAGS)?;
+        let entry = required(entries.first(), TAG_NO_RELATED_SYM)?;
+
+        return Ok(Self {
+            quote_req_id: required(get_string(&fields, 131)?, 131)?,
+            pair: get_pair(entry)?,
+            side: get_coded(entry, Side::TAG)?,
+            order_qty: required(get_string(entry, 38)?, 38)?,
+            currency: get_string(entry, 15)?,
+            settl_type: get_settl_type(entry)?,
+            settl_date: get_date(entry, 64)?,
+            settl_date2: get_date(entry, 193)?,
+            order_qty2: get_string(entry, 192)?,
+            account: get_string(entry, 1)?,
+        });
+    }
+}
+
+impl FxQuote {
+    /// Two-way quote of all-in prices.
+    pub fn two_way(quote_id: &str, pair: CurrencyPair, bid_px: &str, offer_px: &str) -> Self {
+        return Self {
+            quote_req_id: None,
+            quote_id: quote_id.to_string(),
+            pair,
+            side: None,
+            order_qty: None,
+            currency: None,
+            settl_type: None,
+            settl_date: None,
+            settl_date2: None,
+            order_qty2: None,
+            bid_px: Some(bid_px.to_string()),
+            offer_px: Some(offer_px.to_string()),
+            bid_size: None,
+            offer_size: None,
+            rates: FxQuoteRates::default(),
+            valid_until_time: None,
+        };
+    }
+
+    /// Answer to `request` echoing its pair, side, amounts and value dates, with the
+    /// prices left to fill in.
+    pub fn from_request(quote_id: &str, request: &FxQuoteRequest) -> Self {
+        return Self {
+            quote_req_id: Some(request.quote_req_id.clone()),
+            quote_id: quote_id.to_string(),
+            pair: request.pair.clone(),
+            side: request.side,
+            order_qty: Some(request.order_qty.clone()),
+            currency: request.currency.clone(),
+            settl_type: request.settl_type,
+            settl_date: request.settl_date,
+            settl_date2: request.settl_date2,
+            order_qty2: request.order_qty2.clone(),
+            bid_px: None,
+            offer_px: None,
+            bid_size: None,
+            offer_size: None,
+            rates: FxQuoteRates::default(),
+            valid_until_time: None,
+        };
+    }
+
+    /// The Quote as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_QUOTE);
+        write_optional(&mut message, 131, &self.quote_req_id);
+        message.with_value(117, self.quote_id.as_str());
+        message.with_value(55, self.pair.clone());
+
+        if let Some(side) = self.side {
+            message.with_field(side);
+        }
+
+        write_optional(&mut message, 38, &self.order_qty);
+        write_optional(&mut message, 15, &self.currency);
+        write_optional(&mut message, 63, &self.settl_type);
+        write_optional(&mut message, 64, &self.settl_date);
+        write_optional(&mut message, 193, &self.settl_date2);
+        write_optional(&mut message, 192, &self.order_qty2);
+        write_optional(&mut message, 132, &self.bid_px);
+        write_optional(&mut message, 133, &self.offer_px);
+        write_optional(&mut message, 134, &self.bid_size);
+        write_optional(&mut message, 135, &self.offer_size);
+        write_optional(&mut message, 62, &self.valid_until_time);
+        message.with_component(&self.rates);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for FxQuote {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_QUOTE)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            quote_req_id: get_string(&fields, 131)?,
+            quote_id: required(get_string(&fields, 117)?, 117)?,
+            pair: get_pair(&fields)?,
+            side: get_coded(&fields, Side::TAG)?,
+            order_qty: get_string(&fields, 38)?,
+            currency: get_string(&fields, 15)?,
+            settl_type: get_settl_type(&fields)?,
+            settl_date: get_date(&fields, 64)?,
+            settl_date2: get_date(&fields, 193)?,
+            order_qty2: get_string(&fields, 192)?,
+            bid_px: get_string(&fields, 132)?,
+            offer_px: get_string(&fields, 133)?,
+            bid_size: get_string(&fields, 134)?,
+            offer_size: get_string(&fields, 135)?,
+            rates: FxQuoteRates::read(&fields)?,
+            valid_until_time: get_timestamp(&fields, 62)?,
+        });
+    }
+}
+
+impl FixMessageBuilder {
+    /// Symbol (55) read as a currency pair.
+    pub fn get_currency_pair(&self) -> Result<Option<CurrencyPair>, FieldParseError> {
+        let Some(value) = self.get(55) else {
+            return Ok(None);
+        };
+
+        return get_pair(&[(55, value)]).map(Some);
+    }
+}
+
+fn get_pair(entry: &[(u32, &[u8])]) -> Result<CurrencyPair, FieldParseError> {
+    let symbol = required(get_string(entry, 55)?, 55)?;
+
+    return CurrencyPair::parse(&symbol).ok_or(FieldParseError::InvalidValue {
+        tag: 55,
+        value: symbol,
+        reason: "not a currency pair".to_string(),
+    });
+}
+
+fn get_settl_type(entry: &[(u32, &[u8])]) -> Result<Option<SettlType>, FieldParseError> {
+    return match entry.iter().find(|(tag, _)| *tag == 63) {
+        Some((_, value)) => SettlType::try_from(*value).map(Some),
+        None => Ok(None),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_currency_pair() {
+        let pair = CurrencyPair::new("EUR", "USD");
+
+        assert_eq!(Some(pair.clone()), CurrencyPair::parse("EURUSD"));
+        assert_eq!(Some(pair.clone()), CurrencyPair::parse("EUR/USD"));
+        assert_eq!(None, CurrencyPair::parse("EUR/US"));
+        assert_eq!(None, CurrencyPair::parse("eurusd"));
+        assert_eq!("EURUSD", pair.to_string());
+        assert_eq!("EUR/USD", pair.to_slashed());
+        assert_eq!("USDEUR", pair.inverse().to_string());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(55, "GBP/JPY");
+        assert_eq!(
+            Ok(Some(CurrencyPair::new("GBP", "JPY"))),
+            order.get_currency_pair()
+        );
+
+        order.set_value(55, "IBM");
+        assert!(matches!(
+            order.get_currency_pair(),
+            Err(FieldParseError::InvalidValue { tag: 55, .. })
+        ));
+    }
+
+    #[test]
+    fn test_fx_quote_round_trip() {
+        let pair = CurrencyPair::new("EUR", "USD");
+        let mut request = FxQuoteRequest::forward(
+            "QR1",
+            pair.clone(),
+            "1000000",
+            SettlType::Tenor(crate::TenorUnit::Month, 1),
+        );
+        request.side = Some(Side::Buy);
+
+        let message = request.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=58|35=R|131=QR1|146=1|55=EURUSD|54=1|38=1000000|15=EUR|63=M1|10=181|",
+            message.to_string()
+        );
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(Ok(request.clone()), FxQuoteRequest::try_from(&parsed));
+
+        let mut quote = FxQuote::from_request("Q1", &request);
+        quote.bid_px = Some("1.08512".to_string());
+        quote.offer_px = Some("1.08530".to_string());
+        quote.rates.bid_spot_rate = Some("1.08400".to_string());
+        quote.rates.bid_forward_points = Some("0.00112".to_string());
+
+        let (parsed, _) =
+            FixMessageBuilder::from_bytes_lenient(&quote.to_message("FIX.4.4").as_bytes());
+        assert_eq!(Ok(quote), FxQuote::try_from(&parsed));
+
+        let swap = FxQuoteRequest::swap(
+            "QR2",
+            pair,
+            "5000000",
+            FixDate::new(2024, 4, 2),
+            FixDate::new(2024, 5, 2),
+        );
+        let message = swap.to_message("FIX.4.4");
+        assert_eq!(Some(b"20240502".as_slice()), message.get(193));
+        assert_eq!(Ok(swap), FxQuoteRequest::try_from(&message));
+    }
+}
@@ -0,0 +1,183 @@
+//! Repeating groups in the flat field list of a `FixMessageBuilder`.
+//!
+//! Reading relies on the fields being in wire order, which `FixMessageBuilder` keeps
+//! for messages built field by field and for parsed ones alike.
+
+use std::{str::FromStr, time::SystemTime};
+
+use crate::{
+    fields::FixField, parse_utc_timestamp, FieldParseError, FixDate, FixMessageBuilder, FixValue,
+};
+
+pub(crate) type GroupEntry<'a> = Vec<(u32, &'a [u8])>;
+
+/// Entries of the group counted by `count_tag`. An entry starts at the tag the first
+/// entry starts with and runs while the tags are in `members`, which has to include
+/// the tags of nested groups. `fields` have to be in wire order, as
+/// `FixMessageBuilder::get_fields` returns them.
+pub(crate) fn read_group<'a>(
+    fields: &[(u32, &'a [u8])],
+    count_tag: u32,
+    members: &[u32],
+) -> Result<Vec<GroupEntry<'a>>, FieldParseError> {
+    let Some(position) = fields.iter().position(|(tag, _)| *tag == count_tag) else {
+        return Ok(vec![]);
+    };
+
+    let count: usize = parse(count_tag, fields[position].1)?;
+    let mut entries: Vec<GroupEntry> = vec![];
+    let mut delimiter = None;
+
+    for (tag, value) in &fields[position + 1..] {
+        if !members.contains(tag) {
+            break;
+        }
+
+        if *delimiter.get_or_insert(*tag) == *tag {
+            entries.push(vec![]);
+        }
+
+        entries.last_mut().unwrap().push((*tag, value));
+    }
+
+    if entries.len() != count {
+        return Err(FieldParseError::InvalidValue {
+            tag: count_tag,
+            value: count.to_string(),
+            reason: format!("group has {} entries", entries.len()),
+        });
+    }
+
+    return Ok(entries);
+}
+
+pub(crate) fn get_string(
+    entry: &[(u32, &[u8])],
+    tag: u32,
+) -> Result<Option<String>, FieldParseError> {
+    let Some((_, value)) = entry.iter().find(|(entry_tag, _)| *entry_tag == tag) else {
+        return Ok(None);
+    };
+
+    return match std::str::from_utf8(value) {
+        Ok(value) => Ok(Some(value.to_string())),
+        Err(_) => Err(FieldParseError::InvalidUtf8 { tag }),
+    };
+}
+
+/// Coded value stored under another tag than the field's own, e.g. LegSide (624)
+/// taking the values of Side (54).
+pub(crate) fn get_coded<F: FixField>(
+    entry: &[(u32, &[u8])],
+    tag: u32,
+) -> Result<Option<F>, FieldParseError> {
+    let Some((_, value)) = entry.iter().find(|(entry_tag, _)| *entry_tag == tag) else {
+        return Ok(None);
+    };
+
+    return match F::try_from(value) {
+        Ok(value) => Ok(Some(value)),
+        Err(FieldParseError::InvalidValue { value, reason, .. }) => {
+            Err(FieldParseError::InvalidValue { tag, value, reason })
+        }
+        Err(FieldParseError::InvalidUtf8 { .. }) => Err(FieldParseError::InvalidUtf8 { tag }),
+        Err(err) => Err(err),
+    };
+}
+
+pub(crate) fn get_parsed<T: FromStr>(
+    entry: &[(u32, &[u8])],
+    tag: u32,
+) -> Result<Option<T>, FieldParseError> {
+    let Some(value) = get_string(entry, tag)? else {
+        return Ok(None);
+    };
+
+    return match value.parse() {
+        Ok(parsed) => Ok(Some(parsed)),
+        Err(_) => Err(FieldParseError::InvalidValue {
+            tag,
+            value,
+            reason: format!("not a valid {}", std::any::type_name::<T>()),
+        }),
+    };
+}
+
+pub(crate) fn get_timestamp(
+    entry: &[(u32, &[u8])],
+    tag: u32,
+) -> Result<Option<SystemTime>, FieldParseError> {
+    let Some(value) = get_string(entry, tag)? else {
+        return Ok(None);
+    };
+
+    return match parse_utc_timestamp(&value) {
+        Some(time) => Ok(Some(time)),
+        None => Err(FieldParseError::InvalidValue {
+            tag,
+            value,
+            reason: "not a UTCTimestamp".to_string(),
+        }),
+    };
+}
+
+/// LocalMktDate such as SettlDate (64), written `YYYYMMDD`.
+pub(crate) fn get_date(
+    entry: &[(u32, &[u8])],
+    tag: u32,
+) -> Result<Option<FixDate>, FieldParseError> {
+    let Some(value) = get_string(entry, tag)? else {
+        return Ok(None);
+    };
+
+    return match FixDate::parse(&value) {
+        Some(date) => Ok(Some(date)),
+        None => Err(FieldParseError::InvalidValue {
+            tag,
+            value,
+            reason: "not a LocalMktDate".to_string(),
+        }),
+    };
+}
+
+pub(crate) fn required<T>(value: Option<T>, tag: u32) -> Result<T, FieldParseError> {
+    return value.ok_or(FieldParseError::Missing { tag });
+}
+
+/// Fails unless the message has the MsgType (35) a typed message is read from.
+pub(crate) fn expect_msg_type(
+    message: &FixMessageBuilder,
+    msg_type: &str,
+) -> Result<(), FieldParseError> {
+    if message.get_message_type() == msg_type.as_bytes() {
+        return Ok(());
+    }
+
+    return Err(FieldParseError::InvalidValue {
+        tag: 35,
+        value: message.get_message_type_as_string(),
+        reason: format!("expected MsgType {}", msg_type),
+    });
+}
+
+pub(crate) fn write_optional<T: Clone + Into<FixValue>>(
+    message: &mut FixMessageBuilder,
+    tag: u32,
+    value: &Option<T>,
+) {
+    if let Some(value) = value {
+        message.with_value(tag, value.clone());
+    }
+}
+
+fn parse(tag: u32, value: &[u8]) -> Result<usize, FieldParseError> {
+    let Ok(text) = std::str::from_utf8(value) else {
+        return Err(FieldParseError::InvalidUtf8 { tag });
+    };
+
+    return text.parse().map_err(|_| FieldParseError::InvalidValue {
+        tag,
+        value: text.to_string(),
+        reason: "not a group count".to_string(),
+    });
+}
@@ -0,0 +1,49 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_util::Stream;
+use tokio::sync::mpsc;
+
+use crate::{FixApplication, FixMessage, FixMessageBuilder, SessionId};
+
+/// Application turning the inbound application messages of every session it serves
+/// into an `InboundStream`.
+///
+/// Messages are dropped once the stream is.
+#[derive(Debug)]
+pub struct StreamApplication {
+    messages: mpsc::UnboundedSender<(SessionId, FixMessage)>,
+}
+
+/// Inbound application messages, in the order each session delivered them. Ends when
+/// the `StreamApplication` is dropped along with the transports holding it.
+#[derive(Debug)]
+pub struct InboundStream {
+    messages: mpsc::UnboundedReceiver<(SessionId, FixMessage)>,
+}
+
+impl StreamApplication {
+    pub fn new() -> (Self, InboundStream) {
+        let (messages, receiver) = mpsc::unbounded_channel();
+
+        return (Self { messages }, InboundStream { messages: receiver });
+    }
+}
+
+impl FixApplication for StreamApplication {
+    fn on_app_message(&self, session_id: &SessionId, message: &FixMessageBuilder) {
+        let _ = self
+            .messages
+            .send((session_id.clone(), message.clone().build()));
+    }
+}
+
+impl Stream for InboundStream {
+    type Item = (SessionId, FixMessage);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        return self.messages.poll_recv(cx);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    group::{get_string, read_group, write_optional, GroupEntry},
+    Component, FieldParseError, FixMessageBuilder,
+};
+
+const TAG_NO_SECURITY_ALT_ID: u32 = 454;
+
+const ALT_ID_TAGS: [u32; 2] = [455, 456];
+
+/// Tags of the Instrument component in their FIX 4.4 order, for groups that embed it.
+pub(crate) const INSTRUMENT_TAGS: [u32; 16] = [
+    55, 65, 48, 22, 454, 455, 456, 460, 461, 167, 200, 541, 202, 231, 207, 107,
+];
+
+/// Entry of NoSecurityAltID (454).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityAltId {
+    /// SecurityAltID (455).
+    pub id: String,
+    /// SecurityAltIDSource (456).
+    pub source: Option<String>,
+}
+
+/// Instrument component, the security a message is about.
+///
+/// Prices and multipliers are kept as written, so no digits are lost.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Instrument {
+    /// Symbol (55).
+    pub symbol: Option<String>,
+    /// SymbolSfx (65).
+    pub symbol_sfx: Option<String>,
+    /// SecurityID (48).
+    pub security_id: Option<String>,
+    /// SecurityIDSource (22).
+    pub security_id_source: Option<String>,
+    /// NoSecurityAltID (454).
+    pub security_alt_ids: Vec<SecurityAltId>,
+    /// Product (460).
+    pub product: Option<String>,
+    /// CFICode (461).
+    pub cfi_code: Option<String>,
+    /// SecurityType (167).
+    pub security_type: Option<String>,
+    /// MaturityMonthYear (200).
+    pub maturity_month_year: Option<String>,
+    /// MaturityDate (541).
+    pub maturity_date: Option<String>,
+    /// StrikePrice (202).
+    pub strike_price: Option<String>,
+    /// ContractMultiplier (231).
+    pub contract_multiplier: Option<String>,
+    /// SecurityExchange (207).
+    pub security_exchange: Option<String>,
+    /// SecurityDesc (107).
+    pub security_desc: Option<String>,
+}
+
+impl Instrument {
+    pub fn new(symbol: &str) -> Self {
+        return Self {
+            symbol: Some(symbol.to_string()),
+            ..Self::default()
+        };
+    }
+
+    /// Whether no field of the component is set, in which case nothing is written.
+    pub fn is_empty(&self) -> bool {
+        return *self == Self::default();
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends the fields of the Instrument component that are set.
+    pub fn with_instrument(&mut self, instrument: &Instrument) {
+        write_instrument(self, instrument);
+    }
+
+    /// The Instrument component, empty when the message carries none of its fields.
+    pub fn get_instrument(&self) -> Result<Instrument, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return read_instrument(&fields);
+    }
+}
+
+impl Component for Instrument {
+    const TAGS: &'static [u32] = &INSTRUMENT_TAGS;
+
+    fn write(&self, message: &mut FixMessageBuilder) {
+        write_instrument(message, self);
+    }
+
+    fn read(fields: &[(u32, &[u8])]) -> Result<Self, FieldParseError> {
+        return read_instrument(fields);
+    }
+}
+
+pub(crate) fn write_instrument(message: &mut FixMessageBuilder, instrument: &Instrument) {
+    write_optional(message, 55, &instrument.symbol);
+    write_optional(message, 65, &instrument.symbol_sfx);
+    write_optional(message, 48, &instrument.security_id);
+    write_optional(message, 22, &instrument.security_id_source);
+
+    if !instrument.security_alt_ids.is_empty() {
+        message.with_value(TAG_NO_SECURITY_ALT_ID, instrument.security_alt_ids.len());
+
+        for alt_id in &instrument.security_alt_ids {
+            message.with_value(455, alt_id.id.as_str());
+            write_optional(message, 456, &alt_id.source);
+        }
+    }
+
+    write_optional(message, 460, &instrument.product);
+    write_optional(message, 461, &instrument.cfi_code);
+    write_optional(message, 167, &instrument.security_type);
+    write_optional(message, 200, &instrument.maturity_month_year);
+    write_optional(message, 541, &instrument.maturity_date);
+    write_optional(message, 202, &instrument.strike_price);
+    write_optional(message, 231, &instrument.contract_multiplier);
+    write_optional(message, 207, &instrument.security_exchange);
+    write_optional(message, 107, &instrument.security_desc);
+}
+
+/// Instrument found in `fields`, which may be the whole message or an entry of a group
+/// embedding the component.
+pub(crate) fn read_instrument(fields: &[(u32, &[u8])]) -> Result<Instrument, FieldParseError> {
+    let mut security_alt_ids = vec![];
+
+    for alt_id in read_group(fields, TAG_NO_SECURITY_ALT_ID, &ALT_ID_TAGS)? {
+        security_alt_ids.push(SecurityAltId {
+            id: get_string(&alt_id, 455)?.unwrap_or_default(),
+            source: get_string(&alt_id, 456)?,
+        });
+    }
+
+    return Ok(Instrument {
+        symbol: get_string(fields, 55)?,
+        symbol_sfx: get_string(fields, 65)?,
+        security_id: get_string(fields, 48)?,
+        security_id_source: get_string(fields, 22)?,
+        security_alt_ids,
+        product: get_string(fields, 460)?,
+        cfi_code: get_string(fields, 461)?,
+        security_type: get_string(fields, 167)?,
+        maturity_month_year: get_string(fields, 200)?,
+        maturity_date: get_string(fields, 541)?,
+        strike_price: get_string(fields, 202)?,
+        contract_multiplier: get_string(fields, 231)?,
+        security_exchange: get_string(fields, 207)?,
+        security_desc: get_string(fields, 107)?,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_instrument_round_trip() {
+        let mut instrument = Instrument::new("VOD");
+        instrument.security_id = Some("GB00BH4HKS39".to_string());
+        instrument.security_id_source = Some("4".to_string());
+        instrument.security_alt_ids = vec![SecurityAltId {
+            id: "BH4HKS3".to_string(),
+            source: Some("2".to_string()),
+        }];
+        instrument.security_exchange = Some("XLON".to_string());
+
+        let mut message = FixMessageBuilder::new("FIX.4.4", "d");
+        message.with_instrument(&instrument);
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(instrument, parsed.get_instrument().unwrap());
+        assert!(FixMessageBuilder::new("FIX.4.4", "d")
+            .get_instrument()
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    fields::{FixField, Side},
+    group::{get_coded, get_string, read_group, write_optional, GroupEntry},
+    FieldParseError, FixMessageBuilder,
+};
+
+const TAG_NO_LEGS: u32 = 555;
+const TAG_NO_LEG_SECURITY_ALT_ID: u32 = 604;
+
+const ALT_ID_TAGS: [u32; 2] = [605, 606];
+
+// InstrumentLeg component tags in their FIX 4.4 order, then the NoLegs entry tags
+// of NewOrderMultileg.
+const INSTRUMENT_LEG_TAGS: [u32; 14] = [
+    600, 601, 602, 603, 607, 608, 609, 610, 611, 612, 614, 556, 623, 624,
+];
+const LEG_TAGS: [u32; 4] = [687, 564, 566, 654];
+
+/// Entry of NoLegSecurityAltID (604).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegSecurityAltId {
+    /// LegSecurityAltID (605).
+    pub id: String,
+    /// LegSecurityAltIDSource (606).
+    pub source: Option<String>,
+}
+
+/// InstrumentLeg component, the security traded in one leg of a multileg order.
+///
+/// Prices and quantities are kept as written, so no digits are lost.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstrumentLeg {
+    /// LegSymbol (600).
+    pub symbol: Option<String>,
+    /// LegSymbolSfx (601).
+    pub symbol_sfx: Option<String>,
+    /// LegSecurityID (602).
+    pub security_id: Option<String>,
+    /// LegSecurityIDSource (603).
+    pub security_id_source: Option<String>,
+    /// NoLegSecurityAltID (604).
+    pub security_alt_ids: Vec<LegSecurityAltId>,
+    /// LegProduct (607).
+    pub product: Option<String>,
+    /// LegCFICode (608).
+    pub cfi_code: Option<String>,
+    /// LegSecurityType (609).
+    pub security_type: Option<String>,
+    /// LegMaturityMonthYear (610).
+    pub maturity_month_year: Option<String>,
+    /// LegMaturityDate (611).
+    pub maturity_date: Option<String>,
+    /// LegStrikePrice (612).
+    pub strike_price: Option<String>,
+    /// LegContractMultiplier (614).
+    pub contract_multiplier: Option<String>,
+    /// LegCurrency (556).
+    pub currency: Option<String>,
+    /// LegRatioQty (623).
+    pub ratio_qty: Option<String>,
+    /// LegSide (624).
+    pub side: Option<Side>,
+}
+
+/// Entry of NoLegs (555): the leg's instrument plus its order details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Leg {
+    pub instrument: InstrumentLeg,
+    /// LegQty (687).
+    pub qty: Option<String>,
+    /// LegPositionEffect (564).
+    pub position_effect: Option<String>,
+    /// LegPrice (566).
+    pub price: Option<String>,
+    /// LegRefID (654).
+    pub ref_id: Option<String>,
+}
+
+impl InstrumentLeg {
+    pub fn new(symbol: &str) -> Self {
+        return Self {
+            symbol: Some(symbol.to_string()),
+            ..Self::default()
+        };
+    }
+
+    fn write(&self, message: &mut FixMessageBuilder) {
+        write_optional(message, 600, &self.symbol);
+        write_optional(message, 601, &self.symbol_sfx);
+        write_optional(message, 602, &self.security_id);
+        write_optional(message, 603, &self.security_id_source);
+
+        if !self.security_alt_ids.is_empty() {
+            message.with_value(TAG_NO_LEG_SECURITY_ALT_ID, self.security_alt_ids.len());
+
+            for alt_id in &self.security_alt_ids {
+                message.with_value(605, alt_id.id.as_str());
+                write_optional(message, 606, &alt_id.source);
+            }
+        }
+
+        write_optional(message, 607, &self.product);
+        write_optional(message, 608, &self.cfi_code);
+        write_optional(message, 609, &self.security_type);
+        write_optional(message, 610, &self.maturity_month_year);
+        write_optional(message, 611, &self.maturity_date);
+        write_optional(message, 612, &self.strike_price);
+        write_optional(message, 614, &self.contract_multiplier);
+        write_optional(message, 556, &self.currency);
+        write_optional(message, 623, &self.ratio_qty);
+
+        if let Some(side) = self.side {
+            message.with_value(624, side.as_bytes().to_vec());
+        }
+    }
+
+    fn read(entry: &GroupEntry) -> Result<Self, FieldParseError> {
+        let mut security_alt_ids = vec![];
+
+        for alt_id in read_group(entry, TAG_NO_LEG_SECURITY_ALT_ID, &ALT_ID_TAGS)? {
+            security_alt_ids.push(LegSecurityAltId {
+                id: get_string(&alt_id, 605)?.unwrap_or_default(),
+                source: get_string(&alt_id, 606)?,
+            });
+        }
+
+        return Ok(Self {
+            symbol: get_string(entry, 600)?,
+            symbol_sfx: get_string(entry, 601)?,
+            security_id: get_string(entry, 602)?,
+            security_id_source: get_string(entry, 603)?,
+            security_alt_ids,
+            product: get_string(entry, 607)?,
+            cfi_code: get_string(entry, 608)?,
+            security_type: get_string(entry, 609)?,
+            maturity_month_year: get_string(entry, 610)?,
+            maturity_date: get_string(entry, 611)?,
+            strike_price: get_string(entry, 612)?,
+            contract_multiplier: get_string(entry, 614)?,
+            currency: get_string(entry, 556)?,
+            ratio_qty: get_string(entry, 623)?,
+            side: get_coded(entry, 624)?,
+        });
+    }
+}
+
+impl Leg {
+    pub fn new(instrument: InstrumentLeg) -> Self {
+        return Self {
+            instrument,
+            ..Self::default()
+        };
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends NoLegs (555) and one entry per leg, in the order the FIX 4.4
+    /// dictionary expects.
+    pub fn with_legs(&mut self, legs: &[Leg]) {
+        self.with_value(TAG_NO_LEGS, legs.len());
+
+        for leg in legs {
+            leg.instrument.write(self);
+            write_optional(self, 687, &leg.qty);
+            write_optional(self, 564, &leg.position_effect);
+            write_optional(self, 566, &leg.price);
+            write_optional(self, 654, &leg.ref_id);
+        }
+    }
+
+    /// Entries of NoLegs (555); fails when the count doesn't match the entries found.
+    pub fn get_legs(&self) -> Result<Vec<Leg>, FieldParseError> {
+        let fields: Vec<(u32, &[u8])> = self.get_fields().collect();
+        let members: Vec<u32> = INSTRUMENT_LEG_TAGS
+            .iter()
+            .chain(&[TAG_NO_LEG_SECURITY_ALT_ID])
+            .chain(&ALT_ID_TAGS)
+            .chain(&LEG_TAGS)
+            .copied()
+            .collect();
+
+        let mut legs = vec![];
+
+        for entry in read_group(&fields, TAG_NO_LEGS, &members)? {
+            legs.push(Leg {
+                instrument: InstrumentLeg::read(&entry)?,
+                qty: get_string(&entry, 687)?,
+                position_effect: get_string(&entry, 564)?,
+                price: get_string(&entry, 566)?,
+                ref_id: get_string(&entry, 654)?,
+            });
+        }
+
+        return Ok(legs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spread() -> Vec<Leg> {
+        let mut near = InstrumentLeg::new("ESZ4");
+        near.security_alt_ids = vec![
+            LegSecurityAltId {
+                id: "US1234".to_string(),
+                source: Some("4".to_string()),
+            },
+            LegSecurityAltId {
+                id: "ESZ24".to_string(),
+                source: None,
+            },
+        ];
+        near.ratio_qty = Some("1".to_string());
+        near.side = Some(Side::Buy);
+
+        let mut far = Leg::new(InstrumentLeg::new("ESH5"));
+        far.instrument.side = Some(Side::Sell);
+        far.qty = Some("10".to_string());
+        far.price = Some("5012.25".to_string());
+
+        return vec![Leg::new(near), far];
+    }
+
+    #[test]
+    fn test_legs_round_trip() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "AB");
+        message.with_value(11, "SPREAD1");
+        message.with_legs(&spread());
+        message.with_value(60, "20240101-00:00:00");
+
+        assert_eq!(
+            "8=FIX.4.4|9=132|35=AB|11=SPREAD1|555=2|600=ESZ4|604=2|605=US1234|606=4|605=ESZ24|623=1|624=1|600=ESH5|624=2|687=10|566=5012.25|60=20240101-00:00:00|10=253|",
+            message.to_string()
+        );
+        assert_eq!(spread(), message.get_legs().unwrap());
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(spread(), parsed.get_legs().unwrap());
+    }
+
+    #[test]
+    fn test_wrong_leg_count() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "AB");
+        message.with_legs(&spread());
+        message.set_value(555, 3);
+
+        assert!(matches!(
+            message.get_legs(),
+            Err(FieldParseError::InvalidValue { tag: 555, .. })
+        ));
+        assert!(FixMessageBuilder::new("FIX.4.4", "AB")
+            .get_legs()
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{FixMessageBuilder, FixSerializeError, ParseLimits, SessionId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            Direction::Inbound => "IN",
+            Direction::Outbound => "OUT",
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub timestamp: SystemTime,
+    pub direction: Direction,
+    pub session_id: SessionId,
+    pub message: Vec<u8>,
+}
+
+impl JournalEntry {
+    pub fn parse(
+        &self,
+        check_sum_validation: bool,
+    ) -> Result<FixMessageBuilder, FixSerializeError> {
+        return FixMessageBuilder::from_bytes(&self.message, check_sum_validation);
+    }
+}
+
+/// Append-only file of every raw message a session sent or received.
+///
+/// Each entry is a line `<nanoseconds since epoch>\t<IN|OUT>\t<session id>\t<length>\t`
+/// followed by exactly `length` bytes of the message and a newline, so messages holding
+/// newlines in data fields are read back intact.
+pub struct Journal {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl Journal {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        return Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        });
+    }
+
+    pub fn append(
+        &self,
+        direction: Direction,
+        session_id: &SessionId,
+        message: &[u8],
+        now: SystemTime,
+    ) -> std::io::Result<()> {
+        let nanos = now
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let mut writer = self.writer.lock().unwrap();
+
+        write!(
+            writer,
+            "{}\t{}\t{}\t{}\t",
+            nanos,
+            direction.as_str(),
+            session_id,
+            message.len()
+        )?;
+        writer.write_all(message)?;
+        writer.write_all(b"\n")?;
+
+        return writer.flush();
+    }
+}
+
+/// Reads a journal back entry by entry.
+pub struct JournalReader<R: BufRead> {
+    reader: R,
+    limits: ParseLimits,
+}
+
+impl JournalReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        return Ok(Self::new(BufReader::new(File::open(path)?)));
+    }
+}
+
+impl<R: BufRead> JournalReader<R> {
+    /// Reader refusing entries over the default `ParseLimits::max_message_size`.
+    pub fn new(reader: R) -> Self {
+        return Self {
+            reader,
+            limits: ParseLimits::default(),
+        };
+    }
+
+    /// An entry whose length is over `limits.max_message_size` is `InvalidData`, read
+    /// before anything is allocated for it.
+    pub fn with_limits(&mut self, limits: ParseLimits) {
+        self.limits = limits;
+    }
+
+    /// Parses every message in order, e.g. to replay a session for a post-mortem.
+    pub fn replay(
+        self,
+        check_sum_validation: bool,
+    ) -> impl Iterator<Item = std::io::Result<(JournalEntry, FixMessageBuilder)>> {
+        return self.map(move |entry| {
+            let entry = entry?;
+            let message = entry
+                .parse(check_sum_validation)
+                .map_err(|err| invalid_data(format!("{:?}", err)))?;
+
+            return Ok((entry, message));
+        });
+    }
+
+    fn read_entry(&mut self) -> std::io::Result<Option<JournalEntry>> {
+        let mut header = vec![];
+
+        // The header ends at the fourth tab.
+        for _ in 0..4 {
+            let read = self.reader.read_until(b'\t', &mut header)?;
+
+            if read == 0 && header.is_empty() {
+                return Ok(None);
+            }
+
+            if header.last() != Some(&b'\t') {
+                return Err(invalid_data("truncated journal entry".to_string()));
+            }
+        }
+
+        let header = String::from_utf8(header)
+            .map_err(|_| invalid_data("journal header is not UTF-8".to_string()))?;
+        let fields: Vec<&str> = header.trim_end_matches('\t').split('\t').collect();
+
+        let [nanos, direction, session_id, len] = fields[..] else {
+            return Err(invalid_data(format!("invalid journal header {}", header)));
+        };
+
+        let nanos: u64 = nanos
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid journal timestamp {}", nanos)))?;
+        let direction = match direction {
+            "IN" => Direction::Inbound,
+            "OUT" => Direction::Outbound,
+            _ => return Err(invalid_data(format!("invalid direction {}", direction))),
+        };
+        let session_id: SessionId = session_id
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid session id {}", session_id)))?;
+        let len: usize = len
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid message length {}", len)))?;
+
+        // A corrupt length mustn't size an allocation.
+        let Some(entry_len) = len
+            .checked_add(1)
+            .filter(|_| len <= self.limits.max_message_size)
+        else {
+            return Err(invalid_data(format!("journal message too large {}", len)));
+        };
+
+        let mut message = vec![];
+        (&mut self.reader)
+            .take(entry_len as u64)
+            .read_to_end(&mut message)?;
+
+        if message.len() < entry_len {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+
+        if message.pop() != Some(b'\n') {
+            return Err(invalid_data("journal entry isn't terminated".to_string()));
+        }
+
+        return Ok(Some(JournalEntry {
+            timestamp: UNIX_EPOCH + Duration::from_nanos(nanos),
+            direction,
+            session_id,
+            message,
+        }));
+    }
+}
+
+impl<R: BufRead> Iterator for JournalReader<R> {
+    type Item = std::io::Result<JournalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.read_entry().transpose();
+    }
+}
+
+fn invalid_data(text: String) -> std::io::Error {
+    return std::io::Error::new(std::io::ErrorKind::InvalidData, text);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write_and_replay() {
+        let path = std::env::temp_dir().join(format!("rust-fix-{}.journal", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let session_id = SessionId::new("FIX.4.4", "BUY", "SELL");
+        let now = UNIX_EPOCH + Duration::from_nanos(1_537_467_899_123_456_789);
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(58, "two\nlines");
+
+        let journal = Journal::open(&path).unwrap();
+        journal
+            .append(Direction::Outbound, &session_id, &order.as_bytes(), now)
+            .unwrap();
+        journal
+            .append(Direction::Inbound, &session_id, b"8=FIX.4.4\x01", now)
+            .unwrap();
+
+        let entries: Vec<JournalEntry> = JournalReader::open(&path)
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+
+        assert_eq!(2, entries.len());
+        assert_eq!(now, entries[0].timestamp);
+        assert_eq!(Direction::Outbound, entries[0].direction);
+        assert_eq!(session_id, entries[0].session_id);
+        assert_eq!(order.as_bytes(), entries[0].message);
+
+        let replayed: Vec<_> = JournalReader::open(&path).unwrap().replay(true).collect();
+        assert_eq!(
+            Some(b"two\nlines".as_slice()),
+            replayed[0].as_ref().unwrap().1.get(58)
+        );
+        assert!(replayed[1].is_err());
+
+        let mut reader = JournalReader::new(b"0\tIN\tFIX.4.4:BUY->SELL\t6\t8=FIX\n".as_slice());
+        reader.with_limits(ParseLimits {
+            max_message_size: 5,
+            ..ParseLimits::default()
+        });
+        assert_eq!(
+            std::io::ErrorKind::InvalidData,
+            reader.next().unwrap().unwrap_err().kind()
+        );
+
+        for len in ["999999999999999", &usize::MAX.to_string()] {
+            let entry = format!("0\tIN\tFIX.4.4:BUY->SELL\t{}\t8=FIX\n", len);
+            let error = JournalReader::new(entry.as_bytes())
+                .next()
+                .unwrap()
+                .unwrap_err();
+            assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,252 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use crate::{split_raw_fields, Journal, JournalEntry};
+
+/// Picks a subset of journal entries, e.g. to cut a multi-GB production journal down
+/// to a corpus for tests and benchmarks.
+///
+/// An entry is kept when it passes the time range, MsgType and symbol filters, is the
+/// Nth of the entries that passed them, and its MsgType hasn't reached the cap yet.
+/// The raw message is looked at without parsing it, so entries with a broken BodyLength
+/// or CheckSum are sampled like any other.
+#[derive(Debug, Clone)]
+pub struct JournalSampler {
+    every: usize,
+    from: Option<SystemTime>,
+    until: Option<SystemTime>,
+    msg_types: Vec<Vec<u8>>,
+    symbols: Vec<Vec<u8>>,
+    max_per_type: Option<usize>,
+    matched: usize,
+    per_type: HashMap<Vec<u8>, usize>,
+}
+
+impl Default for JournalSampler {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl JournalSampler {
+    /// Keeps every entry until narrowed down.
+    pub fn new() -> Self {
+        return Self {
+            every: 1,
+            from: None,
+            until: None,
+            msg_types: vec![],
+            symbols: vec![],
+            max_per_type: None,
+            matched: 0,
+            per_type: HashMap::new(),
+        };
+    }
+
+    /// Keeps one in `every` entries, the first, the `every + 1`th and so on.
+    pub fn with_every(&mut self, every: usize) {
+        self.every = every.max(1);
+    }
+
+    /// Keeps entries journaled at or after `from` and before `until`.
+    pub fn with_time_range(&mut self, from: Option<SystemTime>, until: Option<SystemTime>) {
+        self.from = from;
+        self.until = until;
+    }
+
+    /// Keeps these MsgTypes (35) only.
+    pub fn with_msg_types(&mut self, msg_types: &[&str]) {
+        self.msg_types = msg_types
+            .iter()
+            .map(|msg_type| msg_type.as_bytes().to_vec())
+            .collect();
+    }
+
+    /// Keeps messages with one of these Symbols (55), in the body or a repeating group.
+    pub fn with_symbols(&mut self, symbols: &[&str]) {
+        self.symbols = symbols
+            .iter()
+            .map(|symbol| symbol.as_bytes().to_vec())
+            .collect();
+    }
+
+    /// Keeps at most `max_per_type` entries of each MsgType, so heartbeats and market
+    /// data don't crowd out the rarer messages.
+    pub fn with_max_per_type(&mut self, max_per_type: usize) {
+        self.max_per_type = Some(max_per_type);
+    }
+
+    /// Whether to keep `entry`. Counts towards `with_every` and `with_max_per_type`, so
+    /// call it once per entry, in journal order.
+    pub fn accept(&mut self, entry: &JournalEntry) -> bool {
+        if self.from.is_some_and(|from| entry.timestamp < from)
+            || self.until.is_some_and(|until| entry.timestamp >= until)
+        {
+            return false;
+        }
+
+        let fields = split_raw_fields(&entry.message);
+        let msg_type = fields
+            .iter()
+            .find(|field| field.tag == b"35")
+            .map(|field| field.value)
+            .unwrap_or_default();
+
+        if !self.msg_types.is_empty()
+            && !self
+                .msg_types
+                .iter()
+                .any(|inner| inner.as_slice() == msg_type)
+        {
+            return false;
+        }
+
+        if !self.symbols.is_empty()
+            && !fields.iter().any(|field| {
+                field.tag == b"55" && self.symbols.iter().any(|inner| inner == field.value)
+            })
+        {
+            return false;
+        }
+
+        self.matched += 1;
+
+        if !(self.matched - 1).is_multiple_of(self.every) {
+            return false;
+        }
+
+        if let Some(max_per_type) = self.max_per_type {
+            let count = self.per_type.entry(msg_type.to_vec()).or_default();
+
+            if *count >= max_per_type {
+                return false;
+            }
+
+            *count += 1;
+        }
+
+        return true;
+    }
+
+    /// The entries of `entries` to keep; read errors are passed through.
+    pub fn sample<I>(mut self, entries: I) -> impl Iterator<Item = std::io::Result<JournalEntry>>
+    where
+        I: IntoIterator<Item = std::io::Result<JournalEntry>>,
+    {
+        return entries.into_iter().filter(move |entry| match entry {
+            Ok(entry) => self.accept(entry),
+            Err(_) => true,
+        });
+    }
+
+    /// Appends the entries to keep to `journal` with their original timestamps and
+    /// returns how many were written.
+    pub fn copy<I>(self, entries: I, journal: &Journal) -> std::io::Result<usize>
+    where
+        I: IntoIterator<Item = std::io::Result<JournalEntry>>,
+    {
+        let mut result = 0;
+
+        for entry in self.sample(entries) {
+            let entry = entry?;
+            journal.append(
+                entry.direction,
+                &entry.session_id,
+                &entry.message,
+                entry.timestamp,
+            )?;
+            result += 1;
+        }
+
+        return Ok(result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+    use crate::{Direction, FixMessageBuilder, JournalReader, SessionId};
+
+    #[test]
+    fn test_sample_journal() {
+        let source =
+            std::env::temp_dir().join(format!("rust-fix-{}-full.journal", std::process::id()));
+        let target =
+            std::env::temp_dir().join(format!("rust-fix-{}-sample.journal", std::process::id()));
+        let _ = std::fs::remove_file(&source);
+        let _ = std::fs::remove_file(&target);
+
+        let session_id = SessionId::new("FIX.4.4", "BUY", "SELL");
+        let journal = Journal::open(&source).unwrap();
+
+        for index in 0..20u64 {
+            let mut message = match index % 4 {
+                0 => FixMessageBuilder::new("FIX.4.4", "0"),
+                _ => FixMessageBuilder::new("FIX.4.4", "D"),
+            };
+
+            if index % 4 != 0 {
+                message.with_value(55, if index % 2 == 0 { "MSFT" } else { "AAPL" });
+            }
+
+            journal
+                .append(
+                    Direction::Inbound,
+                    &session_id,
+                    &message.as_bytes(),
+                    UNIX_EPOCH + Duration::from_secs(index),
+                )
+                .unwrap();
+        }
+
+        let sample = |sampler: JournalSampler| -> Vec<JournalEntry> {
+            return sampler
+                .sample(JournalReader::open(&source).unwrap())
+                .map(|entry| entry.unwrap())
+                .collect();
+        };
+
+        let mut sampler = JournalSampler::new();
+        sampler.with_every(5);
+        let entries = sample(sampler);
+        assert_eq!(4, entries.len());
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(5), entries[1].timestamp);
+
+        let mut sampler = JournalSampler::new();
+        sampler.with_msg_types(&["D"]);
+        sampler.with_symbols(&["MSFT"]);
+        sampler.with_time_range(Some(UNIX_EPOCH + Duration::from_secs(3)), None);
+        assert_eq!(4, sample(sampler).len());
+
+        let mut sampler = JournalSampler::new();
+        sampler.with_max_per_type(2);
+        sampler.with_time_range(None, Some(UNIX_EPOCH + Duration::from_secs(10)));
+        let written = sampler
+            .copy(
+                JournalReader::open(&source).unwrap(),
+                &Journal::open(&target).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(4, written);
+
+        let copied: Vec<JournalEntry> = JournalReader::open(&target)
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+        assert_eq!(
+            vec![0, 1, 2, 4],
+            copied
+                .iter()
+                .map(|entry| entry
+                    .timestamp
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs())
+                .collect::<Vec<_>>()
+        );
+
+        std::fs::remove_file(&source).unwrap();
+        std::fs::remove_file(&target).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+use crate::SessionId;
+
+/// Decides which of several instances runs a session, for hot/standby setups.
+///
+/// Transports call `acquire` before connecting or accepting a Logon and then once a
+/// second while connected; a session whose leadership is lost is disconnected. It
+/// should hold leadership only for a short lease, so a standby takes over soon after
+/// the leader dies.
+pub trait Leadership: Send + Sync {
+    /// Becomes or stays the leader of the session, returning whether this instance leads.
+    fn acquire(&self, session_id: &SessionId) -> std::io::Result<bool>;
+
+    fn release(&self, session_id: &SessionId) -> std::io::Result<()>;
+}
+
+// Sequence numbers are reloaded on every takeover, the previous leader moved them on.
+#[cfg(any(not(target_arch = "wasm32"), feature = "async"))]
+pub(crate) fn acquire_leadership(
+    leadership: Option<&dyn Leadership>,
+    session: &mut crate::Session,
+) -> bool {
+    let Some(leadership) = leadership else {
+        return true;
+    };
+
+    if !matches!(leadership.acquire(session.get_session_id()), Ok(true)) {
+        return false;
+    }
+
+    return session.reload_seq_nums().is_ok();
+}
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "async"))]
+pub(crate) fn is_leader(leadership: Option<&dyn Leadership>, session_id: &SessionId) -> bool {
+    return match leadership {
+        Some(leadership) => matches!(leadership.acquire(session_id), Ok(true)),
+        None => true,
+    };
+}
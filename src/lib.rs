@@ -1,9 +1,179 @@
+#![allow(clippy::needless_return)]
+
+mod allocation;
+mod appl_seq;
+mod application;
+mod authenticator;
+mod begin_string;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod blotter;
+mod cl_ord_id_resolver;
+mod clock;
+mod commission;
+mod component;
+mod dedup_cache;
+mod dialect;
+mod dictionary;
+mod dictionary_inference;
+mod drop_copy;
 mod errors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fields;
+#[cfg(feature = "codec")]
+mod fix_codec;
+mod fix_diff;
+mod fix_frame;
+mod fix_group;
+mod fix_message;
 mod fix_message_builder;
+mod fix_message_template;
+mod fix_message_view;
 mod fix_serializetion;
+mod fix_value;
+mod fx;
+mod group;
+#[cfg(feature = "async")]
+mod inbound_stream;
+mod instrument;
+mod instrument_leg;
+mod journal;
+mod journal_sampler;
+mod leadership;
+mod logout;
+mod md_entry;
+mod message_generator;
+mod message_store;
+mod middleware;
+mod news;
+mod order_mass_cancel;
+mod order_status_request;
+mod outbound_queue;
+mod parse_limits;
+mod parse_options;
+mod parties;
+mod presend_validator;
+#[cfg(feature = "python")]
+pub mod python;
+mod quickfix_log;
+mod rate_limiter;
+mod reconnect;
+#[cfg(feature = "redis")]
+mod redis_store;
+mod routing;
+mod security_definition;
+mod security_list;
+mod seq_num_store;
+mod session;
+mod session_config;
+mod session_id;
+mod session_registry;
+mod session_schedule;
+mod settlement;
+mod signer;
+#[cfg(feature = "sled")]
+mod sled_store;
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod subscription_manager;
+#[cfg(not(target_arch = "wasm32"))]
+mod sync_transport;
+mod tag_rewriter;
+mod telemetry;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "async")]
+mod tokio_transport;
+mod trace;
+mod trade_capture;
+mod translation;
+mod user_request;
 mod utils;
+mod validation;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+pub use allocation::*;
+pub use appl_seq::*;
+pub use application::*;
+pub use authenticator::*;
+pub use begin_string::*;
+pub use blotter::*;
+pub use cl_ord_id_resolver::*;
+pub use clock::*;
+pub use commission::*;
+pub use component::*;
+pub use dedup_cache::*;
+pub use dialect::*;
+pub use dictionary::*;
+pub use dictionary_inference::*;
+pub use drop_copy::*;
 pub use errors::*;
+#[cfg(feature = "codec")]
+pub use fix_codec::*;
+pub use fix_diff::*;
+pub use fix_frame::*;
+pub use fix_group::*;
+pub use fix_message::*;
 pub use fix_message_builder::*;
+pub use fix_message_template::*;
+pub use fix_message_view::*;
 pub use fix_serializetion::{FixDeserializeModel, FixSerializeModel};
+pub use fix_value::*;
+pub use fx::*;
+#[cfg(feature = "async")]
+pub use inbound_stream::*;
+pub use instrument::*;
+pub use instrument_leg::*;
+pub use journal::*;
+pub use journal_sampler::*;
+pub use leadership::*;
+pub use logout::*;
+pub use md_entry::*;
+pub use message_generator::*;
+pub use message_store::*;
+pub use middleware::*;
+pub use news::*;
+pub use order_mass_cancel::*;
+pub use order_status_request::*;
+pub use outbound_queue::*;
+pub use parse_limits::*;
+pub use parse_options::*;
+pub use parties::*;
+pub use presend_validator::*;
+pub use quickfix_log::*;
+pub use rate_limiter::*;
+pub use reconnect::*;
+#[cfg(feature = "redis")]
+pub use redis_store::*;
+pub use routing::*;
+pub use security_definition::*;
+pub use security_list::*;
+pub use seq_num_store::*;
+pub use session::*;
+pub use session_config::*;
+pub use session_id::*;
+pub use session_registry::*;
+pub use session_schedule::*;
+pub use settlement::*;
+pub use signer::*;
+#[cfg(feature = "sled")]
+pub use sled_store::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::*;
+pub use subscription_manager::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sync_transport::*;
+pub use tag_rewriter::*;
+#[cfg(feature = "async")]
+pub use tokio_transport::*;
+pub use trade_capture::*;
+pub use translation::*;
+pub use user_request::*;
 pub use utils::*;
+pub use validation::*;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
@@ -0,0 +1,259 @@
+use crate::{
+    fields::{FixField, SessionStatus},
+    group::{expect_msg_type, get_coded, get_string, GroupEntry},
+    BeginString, FieldParseError, FixMessageBuilder,
+};
+
+const MSG_TYPE_LOGOUT: &str = "5";
+
+/// Why a session was logged out, from SessionStatus (1409) or else read from Text (58).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogoutReason {
+    /// Ordinary end of the session, e.g. at the end of the day; also a Logout without
+    /// SessionStatus or Text.
+    Normal,
+    /// MsgSeqNum (34) lower than expected, usually a sequence number reset on one side
+    /// only.
+    SeqNumTooLow,
+    /// NextExpectedMsgSeqNum (789) of a Logon higher than what was sent.
+    NextExpectedSeqNumTooHigh,
+    InvalidCredentials,
+    AccountLocked,
+    PasswordExpired,
+    /// A new password was refused.
+    PasswordPolicy,
+    /// Logon outside the session hours.
+    OutsideSessionHours,
+    /// No Heartbeat or answer to a TestRequest in time.
+    HeartbeatTimeout,
+    /// Both sides kept rejecting each other's messages.
+    RejectStorm,
+    /// Anything the SessionStatus or Text don't tell; see the Text.
+    Other,
+}
+
+impl LogoutReason {
+    /// SessionStatus (1409) standing for the reason, where FIXT has one.
+    pub fn get_session_status(&self) -> Option<SessionStatus> {
+        return match self {
+            LogoutReason::Normal => Some(SessionStatus::SessionLogoutComplete),
+            LogoutReason::SeqNumTooLow => Some(SessionStatus::ReceivedMsgSeqNumTooLow),
+            LogoutReason::NextExpectedSeqNumTooHigh => {
+                Some(SessionStatus::ReceivedNextExpectedMsgSeqNumTooHigh)
+            }
+            LogoutReason::InvalidCredentials => Some(SessionStatus::InvalidUsernameOrPassword),
+            LogoutReason::AccountLocked => Some(SessionStatus::AccountLocked),
+            LogoutReason::PasswordExpired => Some(SessionStatus::PasswordExpired),
+            LogoutReason::PasswordPolicy => {
+                Some(SessionStatus::NewSessionPasswordDoesNotComplyWithPolicy)
+            }
+            LogoutReason::OutsideSessionHours => Some(SessionStatus::LogonsAreNotAllowedAtThisTime),
+            LogoutReason::HeartbeatTimeout | LogoutReason::RejectStorm | LogoutReason::Other => {
+                None
+            }
+        };
+    }
+
+    /// Text (58) a Logout for the reason carries by default.
+    pub fn get_text(&self) -> Option<&'static str> {
+        return match self {
+            LogoutReason::Normal | LogoutReason::Other => None,
+            LogoutReason::SeqNumTooLow => Some("MsgSeqNum too low"),
+            LogoutReason::NextExpectedSeqNumTooHigh => Some("NextExpectedMsgSeqNum too high"),
+            LogoutReason::InvalidCredentials => Some("Invalid username or password"),
+            LogoutReason::AccountLocked => Some("Account locked"),
+            LogoutReason::PasswordExpired => Some("Password expired"),
+            LogoutReason::PasswordPolicy => Some("New password does not comply with policy"),
+            LogoutReason::OutsideSessionHours => Some("Logons are not allowed at this time"),
+            LogoutReason::HeartbeatTimeout => Some("Heartbeat timeout"),
+            LogoutReason::RejectStorm => Some("Reject storm"),
+        };
+    }
+
+    fn from_session_status(session_status: SessionStatus) -> Option<Self> {
+        return match session_status {
+            SessionStatus::SessionLogoutComplete => Some(LogoutReason::Normal),
+            SessionStatus::ReceivedMsgSeqNumTooLow => Some(LogoutReason::SeqNumTooLow),
+            SessionStatus::ReceivedNextExpectedMsgSeqNumTooHigh => {
+                Some(LogoutReason::NextExpectedSeqNumTooHigh)
+            }
+            SessionStatus::InvalidUsernameOrPassword => Some(LogoutReason::InvalidCredentials),
+            SessionStatus::AccountLocked => Some(LogoutReason::AccountLocked),
+            SessionStatus::PasswordExpired => Some(LogoutReason::PasswordExpired),
+            SessionStatus::NewSessionPasswordDoesNotComplyWithPolicy => {
+                Some(LogoutReason::PasswordPolicy)
+            }
+            SessionStatus::LogonsAreNotAllowedAtThisTime => Some(LogoutReason::OutsideSessionHours),
+            SessionStatus::SessionActive
+            | SessionStatus::SessionPasswordChanged
+            | SessionStatus::SessionPasswordDueToExpire => None,
+        };
+    }
+
+    // Counterparties on FIX 4.x only say it in the Text, in their own words.
+    pub(crate) fn from_text(text: &str) -> Self {
+        let text = text.to_ascii_lowercase();
+        let has = |part: &str| text.contains(part);
+
+        if text.trim().is_empty() {
+            return LogoutReason::Normal;
+        }
+
+        if has("seqnum too low") || has("seq num too low") || has("sequence number too low") {
+            return LogoutReason::SeqNumTooLow;
+        }
+
+        if has("too high") && (has("nextexpected") || has("next expected")) {
+            return LogoutReason::NextExpectedSeqNumTooHigh;
+        }
+
+        if has("password") && has("expired") {
+            return LogoutReason::PasswordExpired;
+        }
+
+        if has("password") && has("policy") {
+            return LogoutReason::PasswordPolicy;
+        }
+
+        if has("locked") {
+            return LogoutReason::AccountLocked;
+        }
+
+        if has("invalid") && (has("password") || has("user") || has("credential")) {
+            return LogoutReason::InvalidCredentials;
+        }
+
+        if has("not allowed at this time") || (has("outside") && has("hours")) {
+            return LogoutReason::OutsideSessionHours;
+        }
+
+        if has("heartbeat") || has("test request") || has("testrequest") {
+            return LogoutReason::HeartbeatTimeout;
+        }
+
+        if has("reject storm") {
+            return LogoutReason::RejectStorm;
+        }
+
+        return LogoutReason::Other;
+    }
+}
+
+/// Logout (5) with its reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Logout {
+    pub reason: LogoutReason,
+    /// SessionStatus (1409), defined for FIXT sessions only.
+    pub session_status: Option<SessionStatus>,
+    /// Text (58).
+    pub text: Option<String>,
+}
+
+impl Logout {
+    /// Logout for `reason` with its standard SessionStatus and Text.
+    pub fn new(reason: LogoutReason) -> Self {
+        return Self {
+            reason,
+            session_status: reason.get_session_status(),
+            text: reason.get_text().map(|text| text.to_string()),
+        };
+    }
+
+    /// Replaces the standard Text, e.g. to name the sequence numbers.
+    pub fn with_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+
+    /// The Logout as a message body. SessionStatus is left out before FIXT, where
+    /// counterparties would reject it as an undefined tag.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let begin_string = begin_string.into();
+        let is_fixt = begin_string.as_bytes().starts_with(b"FIXT");
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_LOGOUT);
+
+        if let Some(session_status) = self.session_status.filter(|_| is_fixt) {
+            message.with_field(session_status);
+        }
+
+        if let Some(text) = &self.text {
+            message.with_value(58, text.as_str());
+        }
+
+        return message;
+    }
+}
+
+/// The reason comes from SessionStatus (1409) when it names one, otherwise from
+/// well-known wordings in Text (58).
+impl TryFrom<&FixMessageBuilder> for Logout {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_LOGOUT)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let session_status: Option<SessionStatus> = get_coded(&fields, SessionStatus::TAG)?;
+        let text = get_string(&fields, 58)?;
+
+        let reason = session_status
+            .and_then(LogoutReason::from_session_status)
+            .unwrap_or_else(|| LogoutReason::from_text(text.as_deref().unwrap_or_default()));
+
+        return Ok(Self {
+            reason,
+            session_status,
+            text,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_logout_reasons() {
+        let logout = Logout::new(LogoutReason::PasswordExpired);
+        let message = logout.to_message("FIXT.1.1");
+        assert_eq!(Some(b"8".as_slice()), message.get(1409));
+        assert_eq!(Ok(logout.clone()), Logout::try_from(&message));
+        assert_eq!(
+            None,
+            Logout::new(LogoutReason::PasswordExpired)
+                .to_message("FIX.4.4")
+                .get(1409)
+        );
+
+        let parse = |fields: &[(u32, &str)]| -> Logout {
+            let mut message = FixMessageBuilder::new("FIX.4.4", "5");
+
+            for (tag, value) in fields {
+                message.with_value(*tag, *value);
+            }
+
+            return Logout::try_from(&message).unwrap();
+        };
+
+        assert_eq!(LogoutReason::Normal, parse(&[]).reason);
+        assert_eq!(
+            LogoutReason::SeqNumTooLow,
+            parse(&[(58, "MsgSeqNum too low, expecting 12 but received 3")]).reason
+        );
+        assert_eq!(
+            LogoutReason::AccountLocked,
+            parse(&[(1409, "6"), (58, "Contact the help desk")]).reason
+        );
+        assert_eq!(
+            LogoutReason::HeartbeatTimeout,
+            parse(&[(58, "Timed out waiting for heartbeat")]).reason
+        );
+        assert_eq!(LogoutReason::Other, parse(&[(58, "Maintenance")]).reason);
+
+        let mut message = FixMessageBuilder::new("FIXT.1.1", "5");
+        message.with_value(1409, "99");
+        assert!(matches!(
+            Logout::try_from(&message),
+            Err(FieldParseError::InvalidValue { tag: 1409, .. })
+        ));
+    }
+}
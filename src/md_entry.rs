@@ -0,0 +1,254 @@
+use std::str::FromStr;
+
+use crate::{
+    fields::{FixField, MdEntryType, MdUpdateAction, Side},
+    group::{get_coded, get_parsed, get_string, read_group, required, write_optional, GroupEntry},
+    Component, FieldParseError, FixMessageBuilder,
+};
+
+const TAG_NO_MD_ENTRIES: u32 = 268;
+
+const MD_ENTRY_TAGS: [u32; 16] = [
+    TAG_NO_MD_ENTRIES,
+    279,
+    269,
+    278,
+    280,
+    55,
+    270,
+    15,
+    271,
+    272,
+    273,
+    276,
+    277,
+    346,
+    290,
+    1023,
+];
+
+impl MdEntryType {
+    /// Whether the entry is a level of the order book, a bid or an offer.
+    pub fn is_book(&self) -> bool {
+        return matches!(self, MdEntryType::Bid | MdEntryType::Offer);
+    }
+
+    /// Side of the book: buyers for a bid, sellers for an offer.
+    pub fn get_side(&self) -> Option<Side> {
+        return match self {
+            MdEntryType::Bid => Some(Side::Buy),
+            MdEntryType::Offer => Some(Side::Sell),
+            _ => None,
+        };
+    }
+}
+
+/// Entry of NoMDEntries (268), in a MarketDataSnapshotFullRefresh (W) or a
+/// MarketDataIncrementalRefresh (X).
+///
+/// Prices and sizes are kept as written; `get_px_as` and `get_size_as` parse them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdEntry {
+    /// Only in an incremental refresh.
+    pub update_action: Option<MdUpdateAction>,
+    pub entry_type: MdEntryType,
+    /// MDEntryID (278).
+    pub entry_id: Option<String>,
+    /// MDEntryRefID (280), the entry a `Change` or `Delete` refers to.
+    pub entry_ref_id: Option<String>,
+    /// Symbol (55), set in an incremental refresh covering several instruments.
+    pub symbol: Option<String>,
+    /// MDEntryPx (270).
+    pub px: Option<String>,
+    /// Currency (15).
+    pub currency: Option<String>,
+    /// MDEntrySize (271).
+    pub size: Option<String>,
+    /// MDEntryDate (272).
+    pub entry_date: Option<String>,
+    /// MDEntryTime (273).
+    pub entry_time: Option<String>,
+    /// QuoteCondition (276).
+    pub quote_condition: Option<String>,
+    /// TradeCondition (277).
+    pub trade_condition: Option<String>,
+    /// NumberOfOrders (346) at the level.
+    pub number_of_orders: Option<u32>,
+    /// MDEntryPositionNo (290), 1 for the top of the book.
+    pub position_no: Option<u32>,
+    /// MDPriceLevel (1023), 1 for the best price.
+    pub price_level: Option<u32>,
+}
+
+impl MdEntry {
+    pub fn new(entry_type: MdEntryType) -> Self {
+        return Self {
+            update_action: None,
+            entry_type,
+            entry_id: None,
+            entry_ref_id: None,
+            symbol: None,
+            px: None,
+            currency: None,
+            size: None,
+            entry_date: None,
+            entry_time: None,
+            quote_condition: None,
+            trade_condition: None,
+            number_of_orders: None,
+            position_no: None,
+            price_level: None,
+        };
+    }
+
+    /// Entry of `entry_type` at `px` for `size`.
+    pub fn with_px(entry_type: MdEntryType, px: &str, size: &str) -> Self {
+        return Self {
+            px: Some(px.to_string()),
+            size: Some(size.to_string()),
+            ..Self::new(entry_type)
+        };
+    }
+
+    /// MDEntryPx (270) parsed, e.g. as `f64` or a decimal type.
+    pub fn get_px_as<T: FromStr>(&self) -> Result<Option<T>, FieldParseError> {
+        return parse_as(270, &self.px);
+    }
+
+    /// MDEntrySize (271) parsed, e.g. as `u64` or `f64`.
+    pub fn get_size_as<T: FromStr>(&self) -> Result<Option<T>, FieldParseError> {
+        return parse_as(271, &self.size);
+    }
+
+    /// Position in the book, MDEntryPositionNo (290) or else MDPriceLevel (1023).
+    pub fn get_position(&self) -> Option<u32> {
+        return self.position_no.or(self.price_level);
+    }
+}
+
+impl Component for Vec<MdEntry> {
+    const TAGS: &'static [u32] = &MD_ENTRY_TAGS;
+
+    fn write(&self, message: &mut FixMessageBuilder) {
+        if self.is_empty() {
+            return;
+        }
+
+        message.with_value(TAG_NO_MD_ENTRIES, self.len());
+
+        for entry in self {
+            if let Some(update_action) = entry.update_action {
+                message.with_field(update_action);
+            }
+
+            message.with_field(entry.entry_type);
+            write_optional(message, 278, &entry.entry_id);
+            write_optional(message, 280, &entry.entry_ref_id);
+            write_optional(message, 55, &entry.symbol);
+            write_optional(message, 270, &entry.px);
+            write_optional(message, 15, &entry.currency);
+            write_optional(message, 271, &entry.size);
+            write_optional(message, 272, &entry.entry_date);
+            write_optional(message, 273, &entry.entry_time);
+            write_optional(message, 276, &entry.quote_condition);
+            write_optional(message, 277, &entry.trade_condition);
+            write_optional(message, 346, &entry.number_of_orders);
+            write_optional(message, 290, &entry.position_no);
+            write_optional(message, 1023, &entry.price_level);
+        }
+    }
+
+    fn read(fields: &[(u32, &[u8])]) -> Result<Self, FieldParseError> {
+        let mut entries = vec![];
+
+        for entry in read_group(fields, TAG_NO_MD_ENTRIES, &MD_ENTRY_TAGS[1..])? {
+            entries.push(MdEntry {
+                update_action: get_coded(&entry, MdUpdateAction::TAG)?,
+                entry_type: required(get_coded(&entry, MdEntryType::TAG)?, MdEntryType::TAG)?,
+                entry_id: get_string(&entry, 278)?,
+                entry_ref_id: get_string(&entry, 280)?,
+                symbol: get_string(&entry, 55)?,
+                px: get_string(&entry, 270)?,
+                currency: get_string(&entry, 15)?,
+                size: get_string(&entry, 271)?,
+                entry_date: get_string(&entry, 272)?,
+                entry_time: get_string(&entry, 273)?,
+                quote_condition: get_string(&entry, 276)?,
+                trade_condition: get_string(&entry, 277)?,
+                number_of_orders: get_parsed(&entry, 346)?,
+                position_no: get_parsed(&entry, 290)?,
+                price_level: get_parsed(&entry, 1023)?,
+            });
+        }
+
+        return Ok(entries);
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends NoMDEntries (268) and one entry per market data entry.
+    pub fn with_md_entries(&mut self, entries: &[MdEntry]) {
+        self.with_component(&entries.to_vec());
+    }
+
+    /// Entries of NoMDEntries (268).
+    pub fn get_md_entries(&self) -> Result<Vec<MdEntry>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return Vec::<MdEntry>::read(&fields);
+    }
+}
+
+fn parse_as<T: FromStr>(tag: u32, value: &Option<String>) -> Result<Option<T>, FieldParseError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+
+    return get_parsed(&[(tag, value.as_bytes())], tag);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_md_entries_round_trip() {
+        let mut bid = MdEntry::with_px(MdEntryType::Bid, "1.08512", "1000000");
+        bid.update_action = Some(MdUpdateAction::Change);
+        bid.position_no = Some(1);
+        let mut trade = MdEntry::with_px(MdEntryType::Trade, "1.08520", "250000");
+        trade.update_action = Some(MdUpdateAction::New);
+        let mut delete = MdEntry::new(MdEntryType::Offer);
+        delete.update_action = Some(MdUpdateAction::Delete);
+        delete.entry_ref_id = Some("O7".to_string());
+
+        let entries = vec![bid, trade, delete];
+
+        let mut refresh = FixMessageBuilder::new("FIX.4.4", "X");
+        refresh.with_value(262, "MD1");
+        refresh.with_md_entries(&entries);
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&refresh.as_bytes());
+        let parsed = parsed.get_md_entries().unwrap();
+        assert_eq!(entries, parsed);
+
+        assert_eq!(Ok(Some(1.08512)), parsed[0].get_px_as::<f64>());
+        assert_eq!(Ok(Some(250000u64)), parsed[1].get_size_as());
+        assert_eq!(Ok(None), parsed[2].get_px_as::<f64>());
+        assert_eq!(Some(1), parsed[0].get_position());
+        assert_eq!(Some(Side::Buy), parsed[0].entry_type.get_side());
+        assert!(!parsed[1].entry_type.is_book());
+
+        let mut broken = MdEntry::new(MdEntryType::Bid);
+        broken.size = Some("lots".to_string());
+        assert!(matches!(
+            broken.get_size_as::<u64>(),
+            Err(FieldParseError::InvalidValue { tag: 271, .. })
+        ));
+
+        refresh.set_value(269, "Z");
+        assert!(matches!(
+            refresh.get_md_entries(),
+            Err(FieldParseError::InvalidValue { tag: 269, .. })
+        ));
+    }
+}
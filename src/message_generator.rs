@@ -0,0 +1,407 @@
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{format_utc_timestamp, FieldType, FixDictionary, FixMessageBuilder, MemberDef};
+
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const CURRENCIES: &[&str] = &["USD", "EUR", "GBP", "JPY", "CHF"];
+const EXCHANGES: &[&str] = &["XNYS", "XNAS", "XLON", "XETR"];
+const COUNTRIES: &[&str] = &["US", "GB", "DE", "JP"];
+
+// Tags the builder writes itself.
+const GENERATED_TAGS: &[u32] = &[8, 9, 35, 10];
+
+/// Builds random, syntactically valid messages of a dictionary, e.g. for fuzz
+/// corpora, benchmarks and soak tests of downstream systems.
+///
+/// Values follow the field types and enumerations, required fields are always
+/// present and optional ones are added with `optional_field_ratio`. The same seed
+/// gives the same messages. Used as an iterator it never ends.
+#[derive(Debug, Clone)]
+pub struct MessageGenerator {
+    dictionary: Arc<FixDictionary>,
+    random_state: u64,
+    msg_types: Vec<String>,
+    optional_field_ratio: f64,
+    max_value_len: usize,
+    max_group_entries: usize,
+    max_group_depth: usize,
+}
+
+impl MessageGenerator {
+    /// Generates every application message of the dictionary.
+    pub fn new(dictionary: FixDictionary, seed: u64) -> Self {
+        let mut msg_types: Vec<String> = dictionary
+            .get_messages()
+            .filter(|message| !message.is_admin)
+            .map(|message| message.msg_type.clone())
+            .collect();
+        msg_types.sort();
+
+        return Self {
+            dictionary: Arc::new(dictionary),
+            random_state: seed | 1,
+            msg_types,
+            optional_field_ratio: 0.5,
+            max_value_len: 16,
+            max_group_entries: 3,
+            max_group_depth: 2,
+        };
+    }
+
+    /// Only generates these message types; types missing from the dictionary are ignored.
+    pub fn with_msg_types(&mut self, msg_types: &[&str]) {
+        self.msg_types = msg_types
+            .iter()
+            .filter(|msg_type| self.dictionary.get_message(msg_type).is_some())
+            .map(|msg_type| msg_type.to_string())
+            .collect();
+    }
+
+    /// Share of optional fields and groups added, from 0 (required only) to 1 (all).
+    pub fn with_optional_field_ratio(&mut self, ratio: f64) {
+        self.optional_field_ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    /// Longest free-text value, which bounds the message size together with the groups.
+    pub fn with_max_value_len(&mut self, max_value_len: usize) {
+        self.max_value_len = max_value_len.max(1);
+    }
+
+    pub fn with_max_group_entries(&mut self, max_group_entries: usize) {
+        self.max_group_entries = max_group_entries.max(1);
+    }
+
+    /// Groups nested deeper than this are left out, even required ones.
+    pub fn with_max_group_depth(&mut self, max_group_depth: usize) {
+        self.max_group_depth = max_group_depth;
+    }
+
+    pub fn get_dictionary(&self) -> &FixDictionary {
+        return &self.dictionary;
+    }
+
+    /// Message of one of the configured types picked at random, `None` when there's none.
+    pub fn next_message(&mut self) -> Option<FixMessageBuilder> {
+        if self.msg_types.is_empty() {
+            return None;
+        }
+
+        let index = self.next_below(self.msg_types.len());
+        let msg_type = self.msg_types[index].clone();
+
+        return self.generate(&msg_type);
+    }
+
+    /// Message of the given type, `None` when the dictionary doesn't define it.
+    pub fn generate(&mut self, msg_type: &str) -> Option<FixMessageBuilder> {
+        let dictionary = self.dictionary.clone();
+        let message_def = dictionary.get_message(msg_type)?;
+        let mut result = FixMessageBuilder::new(dictionary.get_begin_string(), msg_type);
+
+        for member in dictionary.get_header() {
+            if let MemberDef::Field {
+                tag,
+                required: true,
+            } = member
+            {
+                if !GENERATED_TAGS.contains(tag) {
+                    let value = self.generate_value(*tag);
+                    result.with_value(*tag, value);
+                }
+            }
+        }
+
+        self.generate_members(&mut result, &message_def.members, 0);
+
+        return Some(result);
+    }
+
+    fn generate_members(
+        &mut self,
+        message: &mut FixMessageBuilder,
+        members: &[MemberDef],
+        depth: usize,
+    ) {
+        let mut index = 0;
+
+        while index < members.len() {
+            match &members[index] {
+                MemberDef::Field { tag, required } => {
+                    let data_tag = self.get_data_tag_after(*tag, members.get(index + 1));
+
+                    if *required || self.next_bool(self.optional_field_ratio) {
+                        match data_tag {
+                            // The Length field has to tell the size of the Data field after it.
+                            Some(data_tag) => {
+                                let value = self.next_text(self.max_value_len);
+                                message.with_value(*tag, value.len() as u64);
+                                message.with_value(data_tag, value);
+                            }
+                            None => {
+                                let value = self.generate_value(*tag);
+                                message.with_value(*tag, value);
+                            }
+                        }
+                    }
+
+                    if data_tag.is_some() {
+                        index += 1;
+                    }
+                }
+                MemberDef::Group(group) => {
+                    let include = group.required || self.next_bool(self.optional_field_ratio);
+
+                    if include && depth < self.max_group_depth {
+                        let entries = 1 + self.next_below(self.max_group_entries);
+                        message.with_value(group.count_tag, entries as u64);
+
+                        for _ in 0..entries {
+                            self.generate_entry(message, &group.members, depth + 1);
+                        }
+                    }
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    // The delimiter field starts every entry, so it's always there.
+    fn generate_entry(
+        &mut self,
+        message: &mut FixMessageBuilder,
+        members: &[MemberDef],
+        depth: usize,
+    ) {
+        let Some((first, rest)) = members.split_first() else {
+            return;
+        };
+
+        let first = match first {
+            MemberDef::Field { tag, .. } => MemberDef::Field {
+                tag: *tag,
+                required: true,
+            },
+            MemberDef::Group(group) => {
+                let mut group = group.clone();
+                group.required = true;
+                MemberDef::Group(group)
+            }
+        };
+
+        self.generate_members(message, std::slice::from_ref(&first), depth);
+        self.generate_members(message, rest, depth);
+    }
+
+    fn get_data_tag_after(&self, tag: u32, next: Option<&MemberDef>) -> Option<u32> {
+        let is_length = self
+            .dictionary
+            .get_field(tag)
+            .is_some_and(|field| field.field_type == FieldType::Length);
+
+        let Some(MemberDef::Field { tag: next_tag, .. }) = next else {
+            return None;
+        };
+
+        let is_data = self
+            .dictionary
+            .get_field(*next_tag)
+            .is_some_and(|field| matches!(field.field_type, FieldType::Data | FieldType::XmlData));
+
+        return (is_length && is_data).then_some(*next_tag);
+    }
+
+    fn generate_value(&mut self, tag: u32) -> String {
+        let dictionary = self.dictionary.clone();
+
+        let Some(field) = dictionary.get_field(tag) else {
+            return self.next_text(self.max_value_len);
+        };
+
+        if !field.values.is_empty() {
+            let index = self.next_below(field.values.len());
+            return field.values[index].0.clone();
+        }
+
+        let field_type = field.field_type.clone();
+
+        return match field_type {
+            FieldType::Int | FieldType::Length | FieldType::SeqNum | FieldType::TagNum => {
+                (1 + self.next_below(1_000_000)).to_string()
+            }
+            // A NumInGroup outside a group definition can't have entries.
+            FieldType::NumInGroup => "0".to_string(),
+            FieldType::DayOfMonth => (1 + self.next_below(31)).to_string(),
+            FieldType::Float
+            | FieldType::Qty
+            | FieldType::Price
+            | FieldType::PriceOffset
+            | FieldType::Amt
+            | FieldType::Percentage => {
+                format!("{}.{:02}", self.next_below(100_000), self.next_below(100))
+            }
+            FieldType::Char => {
+                let index = self.next_below(26);
+                ((b'A' + index as u8) as char).to_string()
+            }
+            FieldType::Boolean => if self.next_bool(0.5) { "Y" } else { "N" }.to_string(),
+            FieldType::Currency => self.pick(CURRENCIES),
+            FieldType::Exchange => self.pick(EXCHANGES),
+            FieldType::Country => self.pick(COUNTRIES),
+            FieldType::Language => "en".to_string(),
+            FieldType::UtcTimestamp => format_utc_timestamp(self.next_time()),
+            FieldType::UtcDateOnly | FieldType::LocalMktDate => {
+                format_utc_timestamp(self.next_time())[..8].to_string()
+            }
+            FieldType::UtcTimeOnly => format_utc_timestamp(self.next_time())[9..].to_string(),
+            FieldType::MonthYear => format_utc_timestamp(self.next_time())[..6].to_string(),
+            _ => self.next_text(self.max_value_len),
+        };
+    }
+
+    fn pick(&mut self, values: &[&str]) -> String {
+        let index = self.next_below(values.len());
+        return values[index].to_string();
+    }
+
+    // Between 2000 and 2037.
+    fn next_time(&mut self) -> std::time::SystemTime {
+        let seconds = 946_684_800 + self.next_below(1_200_000_000) as u64;
+        return UNIX_EPOCH + Duration::from_millis(seconds * 1000 + self.next_below(1000) as u64);
+    }
+
+    fn next_text(&mut self, max_len: usize) -> String {
+        let len = 1 + self.next_below(max_len);
+
+        return (0..len)
+            .map(|_| ALPHANUMERIC[self.next_below(ALPHANUMERIC.len())] as char)
+            .collect();
+    }
+
+    fn next_bool(&mut self, probability: f64) -> bool {
+        return self.next_random() < probability;
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        return (self.next_random() * bound as f64) as usize % bound.max(1);
+    }
+
+    // xorshift64, like the reconnect jitter; not meant to be unpredictable.
+    fn next_random(&mut self) -> f64 {
+        let mut x = self.random_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.random_state = x;
+
+        return (x >> 11) as f64 / (1u64 << 53) as f64;
+    }
+}
+
+impl Iterator for MessageGenerator {
+    type Item = FixMessageBuilder;
+
+    fn next(&mut self) -> Option<FixMessageBuilder> {
+        return self.next_message();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{FieldDef, GroupDef, MessageDef, ValidationError};
+
+    fn field(tag: u32, name: &str, field_type: FieldType) -> FieldDef {
+        return FieldDef {
+            tag,
+            name: name.to_string(),
+            field_type,
+            values: vec![],
+        };
+    }
+
+    #[test]
+    fn test_generate_valid_messages() {
+        let dictionary = FixDictionary::fix44();
+        let generator = MessageGenerator::new(dictionary.clone(), 42);
+
+        for message in generator.take(200) {
+            let bytes = message.as_bytes();
+
+            assert_eq!(Vec::<ValidationError>::new(), dictionary.validate(&bytes));
+            assert_eq!(
+                message,
+                FixMessageBuilder::from_bytes_lenient(&bytes).0,
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_messages() {
+        let first: Vec<FixMessageBuilder> = MessageGenerator::new(FixDictionary::fix44(), 7)
+            .take(10)
+            .collect();
+        let second: Vec<FixMessageBuilder> = MessageGenerator::new(FixDictionary::fix44(), 7)
+            .take(10)
+            .collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_groups_and_mix() {
+        let mut dictionary = FixDictionary::fix44();
+        dictionary.add_field(field(453, "NoPartyIDs", FieldType::NumInGroup));
+        dictionary.add_field(field(448, "PartyID", FieldType::String));
+        dictionary.add_field(field(802, "NoPartySubIDs", FieldType::NumInGroup));
+        dictionary.add_field(field(523, "PartySubID", FieldType::String));
+        dictionary.add_message(MessageDef {
+            msg_type: "U1".to_string(),
+            name: "Parties".to_string(),
+            is_admin: false,
+            members: vec![MemberDef::Group(GroupDef {
+                count_tag: 453,
+                required: true,
+                members: vec![
+                    MemberDef::Field {
+                        tag: 448,
+                        required: false,
+                    },
+                    MemberDef::Group(GroupDef {
+                        count_tag: 802,
+                        required: true,
+                        members: vec![MemberDef::Field {
+                            tag: 523,
+                            required: true,
+                        }],
+                    }),
+                ],
+            })],
+        });
+
+        let mut generator = MessageGenerator::new(dictionary, 1);
+        generator.with_msg_types(&["U1", "ZZ"]);
+        generator.with_max_group_entries(1);
+
+        let message = generator.next().unwrap();
+        assert_eq!("U1", message.get_message_type_as_string());
+        assert_eq!(
+            vec![453, 448, 802, 523],
+            message
+                .get_fields()
+                .map(|(tag, _)| tag)
+                .filter(|tag| ![49, 56, 34, 52].contains(tag))
+                .collect::<Vec<u32>>()
+        );
+
+        generator.with_max_group_depth(1);
+        let message = generator.next().unwrap();
+        assert_eq!(None, message.get_string_lossy(802));
+        assert_eq!(Some("1".to_string()), message.get_string_lossy(453));
+    }
+}
@@ -0,0 +1,69 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+/// Keeps sent messages so they can be resent when the counterparty asks for them.
+pub trait MessageStore: Send + Sync + std::fmt::Debug {
+    fn save(&self, seq_num: u64, message: &[u8]) -> std::io::Result<()>;
+
+    /// Stored messages with sequence numbers in `begin..=end`, in order.
+    fn get_range(&self, begin: u64, end: u64) -> std::io::Result<Vec<(u64, Vec<u8>)>>;
+
+    /// Forgets every message, called when sequence numbers are reset.
+    fn reset(&self) -> std::io::Result<()>;
+}
+
+/// Keeps messages only for the life of the process.
+#[derive(Debug, Default)]
+pub struct MemoryMessageStore {
+    messages: Mutex<BTreeMap<u64, Vec<u8>>>,
+}
+
+impl MemoryMessageStore {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+}
+
+impl MessageStore for MemoryMessageStore {
+    fn save(&self, seq_num: u64, message: &[u8]) -> std::io::Result<()> {
+        self.messages
+            .lock()
+            .unwrap()
+            .insert(seq_num, message.to_vec());
+        return Ok(());
+    }
+
+    fn get_range(&self, begin: u64, end: u64) -> std::io::Result<Vec<(u64, Vec<u8>)>> {
+        if begin > end {
+            return Ok(vec![]);
+        }
+
+        return Ok(self
+            .messages
+            .lock()
+            .unwrap()
+            .range(begin..=end)
+            .map(|(seq_num, message)| (*seq_num, message.clone()))
+            .collect());
+    }
+
+    fn reset(&self) -> std::io::Result<()> {
+        self.messages.lock().unwrap().clear();
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_range() {
+        let store = MemoryMessageStore::new();
+        store.save(1, b"one").unwrap();
+        store.save(3, b"three").unwrap();
+        store.save(4, b"four").unwrap();
+
+        assert_eq!(vec![(3, b"three".to_vec())], store.get_range(2, 3).unwrap());
+        assert!(store.get_range(5, 2).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{FixMessageBuilder, SessionId};
+
+/// What a `Middleware` wants done with a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MiddlewareAction {
+    /// Hand the message, as amended, to the next middleware.
+    Continue,
+    /// Stop here: an outbound message isn't sent and an inbound one isn't delivered
+    /// to the application.
+    Drop(String),
+}
+
+/// Hook the session runs on every application message in both directions, for
+/// concerns like enrichment, redaction, compliance checks or metrics. Middlewares
+/// run in the order they were added, outbound ones after `FixApplication::to_app`
+/// and inbound ones before the message is delivered. Session-level messages are
+/// left to the session.
+///
+/// A dropped inbound message still counts towards the sequence numbers.
+pub trait Middleware: Send + Sync + std::fmt::Debug {
+    fn on_outbound(
+        &self,
+        _session_id: &SessionId,
+        _message: &mut FixMessageBuilder,
+    ) -> MiddlewareAction {
+        return MiddlewareAction::Continue;
+    }
+
+    fn on_inbound(
+        &self,
+        _session_id: &SessionId,
+        _message: &mut FixMessageBuilder,
+    ) -> MiddlewareAction {
+        return MiddlewareAction::Continue;
+    }
+}
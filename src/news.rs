@@ -0,0 +1,255 @@
+use std::time::SystemTime;
+
+use crate::{
+    group::{
+        expect_msg_type, get_parsed, get_string, get_timestamp, read_group, required,
+        write_optional, GroupEntry,
+    },
+    instrument::{read_instrument, write_instrument, INSTRUMENT_TAGS},
+    BeginString, FieldParseError, FixMessageBuilder, Instrument,
+};
+
+const MSG_TYPE_NEWS: &str = "B";
+const MSG_TYPE_EMAIL: &str = "C";
+
+const TAG_NO_RELATED_SYM: u32 = 146;
+const TAG_LINES_OF_TEXT: u32 = 33;
+
+/// News (B), e.g. a venue announcing a halt or a change to trading hours.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct News {
+    /// OrigTime (42).
+    pub orig_time: Option<SystemTime>,
+    /// Urgency (61), `0` normal, `1` flash or `2` background.
+    pub urgency: Option<char>,
+    /// Headline (148).
+    pub headline: String,
+    /// NoRelatedSym (146), the instruments the news is about.
+    pub related_instruments: Vec<Instrument>,
+    /// LinesOfText (33), each line a Text (58). Required, so at least one line.
+    pub lines: Vec<String>,
+    /// URLLink (149).
+    pub url_link: Option<String>,
+}
+
+/// Email (C), free text between the two counterparties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    /// EmailThreadID (164).
+    pub email_thread_id: String,
+    /// EmailType (94), `0` new, `1` reply or `2` admin reply.
+    pub email_type: char,
+    /// OrigTime (42).
+    pub orig_time: Option<SystemTime>,
+    /// Subject (147).
+    pub subject: String,
+    /// NoRelatedSym (146).
+    pub related_instruments: Vec<Instrument>,
+    /// OrderID (37).
+    pub order_id: Option<String>,
+    /// ClOrdID (11).
+    pub cl_ord_id: Option<String>,
+    /// LinesOfText (33), each line a Text (58). Required, so at least one line.
+    pub lines: Vec<String>,
+}
+
+impl News {
+    pub fn new(headline: &str, lines: Vec<String>) -> Self {
+        return Self {
+            orig_time: None,
+            urgency: None,
+            headline: headline.to_string(),
+            related_instruments: vec![],
+            lines,
+            url_link: None,
+        };
+    }
+
+    /// The lines of text joined with newlines.
+    pub fn get_text(&self) -> String {
+        return self.lines.join("\n");
+    }
+
+    /// The News as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_NEWS);
+        write_optional(&mut message, 42, &self.orig_time);
+        write_optional(&mut message, 61, &self.urgency);
+        message.with_value(148, self.headline.as_str());
+        write_related_instruments(&mut message, &self.related_instruments);
+        write_lines(&mut message, &self.lines);
+        write_optional(&mut message, 149, &self.url_link);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for News {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_NEWS)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            orig_time: get_timestamp(&fields, 42)?,
+            urgency: get_parsed(&fields, 61)?,
+            headline: required(get_string(&fields, 148)?, 148)?,
+            related_instruments: read_related_instruments(&fields)?,
+            lines: read_lines(&fields)?,
+            url_link: get_string(&fields, 149)?,
+        });
+    }
+}
+
+impl Email {
+    pub fn new(email_thread_id: &str, email_type: char, subject: &str, lines: Vec<String>) -> Self {
+        return Self {
+            email_thread_id: email_thread_id.to_string(),
+            email_type,
+            orig_time: None,
+            subject: subject.to_string(),
+            related_instruments: vec![],
+            order_id: None,
+            cl_ord_id: None,
+            lines,
+        };
+    }
+
+    /// The lines of text joined with newlines.
+    pub fn get_text(&self) -> String {
+        return self.lines.join("\n");
+    }
+
+    /// The Email as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_EMAIL);
+        message.with_value(164, self.email_thread_id.as_str());
+        message.with_value(94, self.email_type);
+        write_optional(&mut message, 42, &self.orig_time);
+        message.with_value(147, self.subject.as_str());
+        write_related_instruments(&mut message, &self.related_instruments);
+        write_optional(&mut message, 37, &self.order_id);
+        write_optional(&mut message, 11, &self.cl_ord_id);
+        write_lines(&mut message, &self.lines);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for Email {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_EMAIL)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            email_thread_id: required(get_string(&fields, 164)?, 164)?,
+            email_type: required(get_parsed(&fields, 94)?, 94)?,
+            orig_time: get_timestamp(&fields, 42)?,
+            subject: required(get_string(&fields, 147)?, 147)?,
+            related_instruments: read_related_instruments(&fields)?,
+            order_id: get_string(&fields, 37)?,
+            cl_ord_id: get_string(&fields, 11)?,
+            lines: read_lines(&fields)?,
+        });
+    }
+}
+
+fn write_related_instruments(message: &mut FixMessageBuilder, instruments: &[Instrument]) {
+    if instruments.is_empty() {
+        return;
+    }
+
+    message.with_value(TAG_NO_RELATED_SYM, instruments.len());
+
+    for instrument in instruments {
+        write_instrument(message, instrument);
+    }
+}
+
+fn read_related_instruments(fields: &GroupEntry) -> Result<Vec<Instrument>, FieldParseError> {
+    let mut instruments = vec![];
+
+    for entry in read_group(fields, TAG_NO_RELATED_SYM, &INSTRUMENT_TAGS)? {
+        instruments.push(read_instrument(&entry)?);
+    }
+
+    return Ok(instruments);
+}
+
+fn write_lines(message: &mut FixMessageBuilder, lines: &[String]) {
+    message.with_value(TAG_LINES_OF_TEXT, lines.len());
+
+    for line in lines {
+        message.with_value(58, line.as_str());
+    }
+}
+
+fn read_lines(fields: &GroupEntry) -> Result<Vec<String>, FieldParseError> {
+    let mut lines = vec![];
+
+    for entry in read_group(fields, TAG_LINES_OF_TEXT, &[58, 354, 355])? {
+        lines.push(get_string(&entry, 58)?.unwrap_or_default());
+    }
+
+    if lines.is_empty() {
+        return Err(FieldParseError::Missing {
+            tag: TAG_LINES_OF_TEXT,
+        });
+    }
+
+    return Ok(lines);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_news_round_trip() {
+        let mut news = News::new(
+            "Trading halt",
+            vec![
+                "Trading in VOD.L is halted.".to_string(),
+                "Resumption follows by separate notice.".to_string(),
+            ],
+        );
+        news.urgency = Some('1');
+        news.related_instruments = vec![Instrument::new("VOD.L")];
+
+        let message = news.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=120|35=B|61=1|148=Trading halt|146=1|55=VOD.L|33=2|58=Trading in VOD.L is halted.|58=Resumption follows by separate notice.|10=050|",
+            message.to_string()
+        );
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        let parsed = News::try_from(&parsed).unwrap();
+        assert_eq!(
+            "Trading in VOD.L is halted.\nResumption follows by separate notice.",
+            parsed.get_text()
+        );
+        assert_eq!(news, parsed);
+    }
+
+    #[test]
+    fn test_email_round_trip() {
+        let mut email = Email::new("T1", '0', "Late fill", vec!["Please check".to_string()]);
+        email.cl_ord_id = Some("ORDER1".to_string());
+
+        let message = email.to_message("FIX.4.4");
+        assert_eq!(Ok(email), Email::try_from(&message));
+
+        let mut message = message;
+        message.remove_value(33);
+        message.remove_value(58);
+        assert_eq!(
+            Err(FieldParseError::Missing { tag: 33 }),
+            Email::try_from(&message)
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::time::SystemTime;
+
+use crate::{
+    fields::{FixField, Side},
+    group::{
+        expect_msg_type, get_coded, get_parsed, get_string, get_timestamp, read_group, required,
+        write_optional, GroupEntry,
+    },
+    instrument::{read_instrument, write_instrument},
+    BeginString, FieldParseError, FixMessageBuilder, Instrument,
+};
+
+const MSG_TYPE_ORDER_MASS_CANCEL_REQUEST: &str = "q";
+const MSG_TYPE_ORDER_MASS_CANCEL_REPORT: &str = "r";
+
+const TAG_NO_AFFECTED_ORDERS: u32 = 534;
+
+const AFFECTED_ORDER_TAGS: [u32; 3] = [41, 535, 536];
+
+/// MassCancelRequestType (530) to cancel every open order of the session, the usual
+/// kill switch.
+pub const MASS_CANCEL_ALL_ORDERS: char = '7';
+
+/// OrderMassCancelRequest (q).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderMassCancelRequest {
+    /// ClOrdID (11).
+    pub cl_ord_id: String,
+    /// MassCancelRequestType (530), e.g. `1` for a security or `7` for all orders.
+    pub mass_cancel_request_type: char,
+    /// TradingSessionID (336).
+    pub trading_session_id: Option<String>,
+    /// Instrument to cancel orders in, empty unless the request type names one.
+    pub instrument: Instrument,
+    /// Side (54), to cancel one side only.
+    pub side: Option<Side>,
+    /// TransactTime (60).
+    pub transact_time: SystemTime,
+    /// Text (58).
+    pub text: Option<String>,
+}
+
+/// Entry of NoAffectedOrders (534).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AffectedOrder {
+    /// OrigClOrdID (41).
+    pub orig_cl_ord_id: String,
+    /// AffectedOrderID (535).
+    pub order_id: Option<String>,
+    /// AffectedSecondaryOrderID (536).
+    pub secondary_order_id: Option<String>,
+}
+
+/// OrderMassCancelReport (r), the answer to an OrderMassCancelRequest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderMassCancelReport {
+    /// ClOrdID (11) of the request.
+    pub cl_ord_id: Option<String>,
+    /// OrderID (37), the counterparty's ID for the request.
+    pub order_id: String,
+    /// MassCancelRequestType (530).
+    pub mass_cancel_request_type: char,
+    /// MassCancelResponse (531), `0` when the request was rejected, otherwise the
+    /// request type carried out.
+    pub mass_cancel_response: char,
+    /// MassCancelRejectReason (532).
+    pub mass_cancel_reject_reason: Option<u32>,
+    /// TotalAffectedOrders (533).
+    pub total_affected_orders: Option<u32>,
+    /// NoAffectedOrders (534).
+    pub affected_orders: Vec<AffectedOrder>,
+    /// TradingSessionID (336).
+    pub trading_session_id: Option<String>,
+    pub instrument: Instrument,
+    /// Side (54).
+    pub side: Option<Side>,
+    /// TransactTime (60).
+    pub transact_time: Option<SystemTime>,
+    /// Text (58).
+    pub text: Option<String>,
+}
+
+impl OrderMassCancelRequest {
+    pub fn new(cl_ord_id: &str, mass_cancel_request_type: char, transact_time: SystemTime) -> Self {
+        return Self {
+            cl_ord_id: cl_ord_id.to_string(),
+            mass_cancel_request_type,
+            trading_session_id: None,
+            instrument: Instrument::default(),
+            side: None,
+            transact_time,
+            text: None,
+        };
+    }
+
+    /// The OrderMassCancelRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_ORDER_MASS_CANCEL_REQUEST);
+        message.with_value(11, self.cl_ord_id.as_str());
+        message.with_value(530, self.mass_cancel_request_type);
+        write_optional(&mut message, 336, &self.trading_session_id);
+        write_instrument(&mut message, &self.instrument);
+
+        if let Some(side) = self.side {
+            message.with_field(side);
+        }
+
+        message.with_value(60, self.transact_time);
+        write_optional(&mut message, 58, &self.text);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for OrderMassCancelRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_ORDER_MASS_CANCEL_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            cl_ord_id: required(get_string(&fields, 11)?, 11)?,
+            mass_cancel_request_type: required(get_parsed(&fields, 530)?, 530)?,
+            trading_session_id: get_string(&fields, 336)?,
+            instrument: read_instrument(&fields)?,
+            side: get_coded(&fields, Side::TAG)?,
+            transact_time: required(get_timestamp(&fields, 60)?, 60)?,
+            text: get_string(&fields, 58)?,
+        });
+    }
+}
+
+impl OrderMassCancelReport {
+    pub fn new(order_id: &str, mass_cancel_request_type: char, mass_cancel_response: char) -> Self {
+        return Self {
+            cl_ord_id: None,
+            order_id: order_id.to_string(),
+            mass_cancel_request_type,
+            mass_cancel_response,
+            mass_cancel_reject_reason: None,
+            total_affected_orders: None,
+            affected_orders: vec![],
+            trading_session_id: None,
+            instrument: Instrument::default(),
+            side: None,
+            transact_time: None,
+            text: None,
+        };
+    }
+
+    /// Whether the counterparty refused to cancel anything.
+    pub fn is_rejected(&self) -> bool {
+        return self.mass_cancel_response == '0';
+    }
+
+    /// The OrderMassCancelReport as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_ORDER_MASS_CANCEL_REPORT);
+        write_optional(&mut message, 11, &self.cl_ord_id);
+        message.with_value(37, self.order_id.as_str());
+        message.with_value(530, self.mass_cancel_request_type);
+        message.with_value(531, self.mass_cancel_response);
+        write_optional(&mut message, 532, &self.mass_cancel_reject_reason);
+        write_optional(&mut message, 533, &self.total_affected_orders);
+
+        if !self.affected_orders.is_empty() {
+            message.with_value(TAG_NO_AFFECTED_ORDERS, self.affected_orders.len());
+
+            for order in &self.affected_orders {
+                message.with_value(41, order.orig_cl_ord_id.as_str());
+                write_optional(&mut message, 535, &order.order_id);
+                write_optional(&mut message, 536, &order.secondary_order_id);
+            }
+        }
+
+        write_optional(&mut message, 336, &self.trading_session_id);
+        write_instrument(&mut message, &self.instrument);
+
+        if let Some(side) = self.side {
+            message.with_field(side);
+        }
+
+        write_optional(&mut message, 60, &self.transact_time);
+        write_optional(&mut message, 58, &self.text);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for OrderMassCancelReport {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_ORDER_MASS_CANCEL_REPORT)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let mut affected_orders = vec![];
+
+        for entry in read_group(&fields, TAG_NO_AFFECTED_ORDERS, &AFFECTED_ORDER_TAGS)? {
+            affected_orders.push(AffectedOrder {
+                orig_cl_ord_id: required(get_string(&entry, 41)?, 41)?,
+                order_id: get_string(&entry, 535)?,
+                secondary_order_id: get_string(&entry, 536)?,
+            });
+        }
+
+        return Ok(Self {
+            cl_ord_id: get_string(&fields, 11)?,
+            order_id: required(get_string(&fields, 37)?, 37)?,
+            mass_cancel_request_type: required(get_parsed(&fields, 530)?, 530)?,
+            mass_cancel_response: required(get_parsed(&fields, 531)?, 531)?,
+            mass_cancel_reject_reason: get_parsed(&fields, 532)?,
+            total_affected_orders: get_parsed(&fields, 533)?,
+            affected_orders,
+            trading_session_id: get_string(&fields, 336)?,
+            instrument: read_instrument(&fields)?,
+            side: get_coded(&fields, Side::TAG)?,
+            transact_time: get_timestamp(&fields, 60)?,
+            text: get_string(&fields, 58)?,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_order_mass_cancel_request_round_trip() {
+        let request = OrderMassCancelRequest::new(
+            "KILL1",
+            MASS_CANCEL_ALL_ORDERS,
+            UNIX_EPOCH + Duration::from_secs(60),
+        );
+
+        let message = request.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=45|35=q|11=KILL1|530=7|60=19700101-00:01:00.000|10=217|",
+            message.to_string()
+        );
+        assert_eq!(Ok(request), OrderMassCancelRequest::try_from(&message));
+    }
+
+    #[test]
+    fn test_order_mass_cancel_report_round_trip() {
+        let mut report = OrderMassCancelReport::new("MC1", '1', '1');
+        report.cl_ord_id = Some("KILL2".to_string());
+        report.total_affected_orders = Some(2);
+        report.affected_orders = vec![
+            AffectedOrder {
+                orig_cl_ord_id: "ORDER1".to_string(),
+                order_id: Some("X1".to_string()),
+                secondary_order_id: None,
+            },
+            AffectedOrder {
+                orig_cl_ord_id: "ORDER2".to_string(),
+                order_id: Some("X2".to_string()),
+                secondary_order_id: None,
+            },
+        ];
+        report.instrument = Instrument::new("VOD.L");
+        report.side = Some(Side::Sell);
+
+        let message = report.to_message("FIX.4.4");
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        let parsed = OrderMassCancelReport::try_from(&parsed).unwrap();
+        assert!(!parsed.is_rejected());
+        assert_eq!(report, parsed);
+
+        let mut message = message;
+        message.remove_value(531);
+        assert_eq!(
+            Err(FieldParseError::Missing { tag: 531 }),
+            OrderMassCancelReport::try_from(&message)
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    fields::{FixField, Side},
+    group::{expect_msg_type, get_coded, get_string, required, write_optional, GroupEntry},
+    instrument::{read_instrument, write_instrument},
+    parties::{read_parties, write_parties},
+    BeginString, FieldParseError, FixMessageBuilder, Instrument, Party,
+};
+
+const MSG_TYPE_ORDER_STATUS_REQUEST: &str = "H";
+
+/// OrderStatusRequest (H), answered with an ExecutionReport carrying ExecType `I`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderStatusRequest {
+    /// OrderID (37).
+    pub order_id: Option<String>,
+    /// ClOrdID (11).
+    pub cl_ord_id: String,
+    pub parties: Vec<Party>,
+    /// OrdStatusReqID (790), echoed back in the ExecutionReport.
+    pub ord_status_req_id: Option<String>,
+    /// Account (1).
+    pub account: Option<String>,
+    pub instrument: Instrument,
+    /// Side (54).
+    pub side: Side,
+}
+
+impl OrderStatusRequest {
+    pub fn new(cl_ord_id: &str, instrument: Instrument, side: Side) -> Self {
+        return Self {
+            order_id: None,
+            cl_ord_id: cl_ord_id.to_string(),
+            parties: vec![],
+            ord_status_req_id: None,
+            account: None,
+            instrument,
+            side,
+        };
+    }
+
+    /// The OrderStatusRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_ORDER_STATUS_REQUEST);
+        write_optional(&mut message, 37, &self.order_id);
+        message.with_value(11, self.cl_ord_id.as_str());
+        write_parties(&mut message, &self.parties);
+        write_optional(&mut message, 790, &self.ord_status_req_id);
+        write_optional(&mut message, 1, &self.account);
+        write_instrument(&mut message, &self.instrument);
+        message.with_field(self.side);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for OrderStatusRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_ORDER_STATUS_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            order_id: get_string(&fields, 37)?,
+            cl_ord_id: required(get_string(&fields, 11)?, 11)?,
+            parties: read_parties(&fields)?,
+            ord_status_req_id: get_string(&fields, 790)?,
+            account: get_string(&fields, 1)?,
+            instrument: read_instrument(&fields)?,
+            side: required(get_coded(&fields, Side::TAG)?, Side::TAG)?,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_order_status_request_round_trip() {
+        let mut request = OrderStatusRequest::new("ORDER1", Instrument::new("VOD.L"), Side::Buy);
+        request.order_id = Some("X1".to_string());
+        request.ord_status_req_id = Some("RECON1".to_string());
+
+        let message = request.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=46|35=H|37=X1|11=ORDER1|790=RECON1|55=VOD.L|54=1|10=215|",
+            message.to_string()
+        );
+        assert_eq!(Ok(request), OrderStatusRequest::try_from(&message));
+
+        let mut message = message;
+        message.set_value(54, "Z");
+        assert!(matches!(
+            OrderStatusRequest::try_from(&message),
+            Err(FieldParseError::InvalidValue { tag: 54, .. })
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+
+use crate::{is_admin_message_type, FixMessageBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundPriority {
+    Admin,
+    App,
+}
+
+/// Returned when the application lane is full; the message is handed back.
+pub struct OutboundQueueFull(pub FixMessageBuilder);
+
+/// Messages waiting to be sent by a session, split into priority lanes.
+///
+/// Session-level messages (Heartbeat, ResendRequest, ...) always go out before
+/// application messages and never count against the capacity, so a backed-up
+/// order flow can't starve the session. Messages are queued before they are
+/// stamped with a sequence number, which keeps the wire order gap free.
+pub struct OutboundQueue {
+    admin: VecDeque<FixMessageBuilder>,
+    app: VecDeque<FixMessageBuilder>,
+    capacity: usize,
+}
+
+impl OutboundQueue {
+    pub fn new(capacity: usize) -> Self {
+        return Self {
+            admin: VecDeque::new(),
+            app: VecDeque::new(),
+            capacity,
+        };
+    }
+
+    pub fn push(&mut self, message: FixMessageBuilder) -> Result<(), OutboundQueueFull> {
+        if is_admin_message_type(message.get_message_type()) {
+            self.admin.push_back(message);
+            return Ok(());
+        }
+
+        if self.is_full() {
+            return Err(OutboundQueueFull(message));
+        }
+
+        self.app.push_back(message);
+        return Ok(());
+    }
+
+    pub fn pop(&mut self) -> Option<(OutboundPriority, FixMessageBuilder)> {
+        if let Some(message) = self.admin.pop_front() {
+            return Some((OutboundPriority::Admin, message));
+        }
+
+        return self
+            .app
+            .pop_front()
+            .map(|message| (OutboundPriority::App, message));
+    }
+
+    /// Lane of the message `pop` would return next.
+    pub fn peek_priority(&self) -> Option<OutboundPriority> {
+        if !self.admin.is_empty() {
+            return Some(OutboundPriority::Admin);
+        }
+
+        if !self.app.is_empty() {
+            return Some(OutboundPriority::App);
+        }
+
+        return None;
+    }
+
+    /// Backpressure signal: no more application messages are accepted until some are sent.
+    pub fn is_full(&self) -> bool {
+        return self.app.len() >= self.capacity;
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        return self.capacity;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.admin.len() + self.app.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.admin.is_empty() && self.app.is_empty();
+    }
+
+    /// Drops every queued message, e.g. when the connection is lost.
+    pub fn clear(&mut self) {
+        self.admin.clear();
+        self.app.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message(message_type: &str, id: &str) -> FixMessageBuilder {
+        let mut result = FixMessageBuilder::new("FIX.4.4", message_type);
+        result.with_value(11, id);
+        return result;
+    }
+
+    #[test]
+    fn test_admin_messages_jump_ahead() {
+        let mut queue = OutboundQueue::new(10);
+        queue.push(message("D", "1")).ok().unwrap();
+        queue.push(message("D", "2")).ok().unwrap();
+        queue.push(message("2", "resend")).ok().unwrap();
+
+        let order: Vec<String> = std::iter::from_fn(|| queue.pop())
+            .map(|(_, message)| message.get_string_lossy(11).unwrap())
+            .collect();
+
+        assert_eq!(vec!["resend", "1", "2"], order);
+    }
+
+    #[test]
+    fn test_backpressure() {
+        let mut queue = OutboundQueue::new(1);
+        queue.push(message("D", "1")).ok().unwrap();
+
+        assert!(queue.is_full());
+        assert!(queue.push(message("D", "2")).is_err());
+        assert!(queue.push(message("0", "heartbeat")).is_ok());
+        assert_eq!(2, queue.len());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{FixSerializeError, SessionConfig, FIX_DELIMETR, FIX_EQUALS};
+
+/// Caps enforced while parsing, so a broken or hostile counterparty can't make the
+/// parser buffer or allocate without bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Whole message, from `8=` to the SOH after the CheckSum.
+    pub max_message_size: usize,
+    pub max_field_count: usize,
+    pub max_value_length: usize,
+}
+
+impl ParseLimits {
+    pub fn unlimited() -> Self {
+        return Self {
+            max_message_size: usize::MAX,
+            max_field_count: usize::MAX,
+            max_value_length: usize::MAX,
+        };
+    }
+
+    pub fn from_config(config: &SessionConfig) -> Self {
+        return Self {
+            max_message_size: config.max_message_size,
+            max_field_count: config.max_field_count,
+            max_value_length: config.max_value_length,
+        };
+    }
+
+    /// Checks a complete message against the limits without allocating.
+    pub fn check(&self, message: &[u8]) -> Result<(), FixSerializeError> {
+        if message.len() > self.max_message_size {
+            return Err(FixSerializeError::MessageTooLarge);
+        }
+
+        let mut field_count = 0;
+        let mut value_start = None;
+
+        for (offset, byte) in message.iter().enumerate() {
+            if *byte == FIX_DELIMETR {
+                field_count += 1;
+
+                if field_count > self.max_field_count {
+                    return Err(FixSerializeError::TooManyFields);
+                }
+
+                value_start = None;
+                continue;
+            }
+
+            match value_start {
+                Some(start) if offset - start >= self.max_value_length => {
+                    return Err(FixSerializeError::ValueTooLong);
+                }
+                None if *byte == FIX_EQUALS => value_start = Some(offset + 1),
+                _ => {}
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+impl Default for ParseLimits {
+    /// 1 MiB messages, 10 000 fields and 64 KiB values.
+    fn default() -> Self {
+        return Self {
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            max_field_count: DEFAULT_MAX_FIELD_COUNT,
+            max_value_length: DEFAULT_MAX_VALUE_LENGTH,
+        };
+    }
+}
+
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+pub const DEFAULT_MAX_FIELD_COUNT: usize = 10_000;
+pub const DEFAULT_MAX_VALUE_LENGTH: usize = 64 * 1024;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let message = b"8=FIX.4.4\x019=5\x0135=0\x0110=163\x01";
+        let limits = ParseLimits {
+            max_message_size: message.len(),
+            max_field_count: 4,
+            max_value_length: 7,
+        };
+
+        assert!(limits.check(message).is_ok());
+
+        let too_large = ParseLimits {
+            max_message_size: message.len() - 1,
+            ..limits
+        };
+        assert!(matches!(
+            too_large.check(message),
+            Err(FixSerializeError::MessageTooLarge)
+        ));
+
+        let too_many_fields = ParseLimits {
+            max_field_count: 3,
+            ..limits
+        };
+        assert!(matches!(
+            too_many_fields.check(message),
+            Err(FixSerializeError::TooManyFields)
+        ));
+
+        let too_long = ParseLimits {
+            max_value_length: 6,
+            ..limits
+        };
+        assert!(matches!(
+            too_long.check(message),
+            Err(FixSerializeError::ValueTooLong)
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::ParseLimits;
+
+/// BodyLength (9) and CheckSum (10), which are recomputed when the message is written.
+pub const DEFAULT_SKIPPED_TAGS: &[u32] = &[9, 10];
+
+/// Controls how `FixMessageBuilder::from_bytes_with_options` reads a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub check_sum_validation: bool,
+    /// Tags left out of the message fields. BeginString (8) and MsgType (35) are always
+    /// taken out, as the builder keeps them apart.
+    pub skipped_tags: Vec<u32>,
+    pub limits: ParseLimits,
+    /// Strict mode: fail with `FixSerializeError::DuplicateHeaderTag` instead of
+    /// keeping the first BeginString, MsgType, CheckSum or other header field when
+    /// one appears twice.
+    pub reject_duplicate_header_tags: bool,
+}
+
+impl ParseOptions {
+    /// Keeps `tag` among the message fields, e.g. to look at the received BodyLength.
+    pub fn keep_tag(&mut self, tag: u32) {
+        self.skipped_tags.retain(|skipped| *skipped != tag);
+    }
+
+    /// Leaves `tag` out of the message fields, e.g. a custom header tag added by a gateway.
+    pub fn skip_tag(&mut self, tag: u32) {
+        if !self.skipped_tags.contains(&tag) {
+            self.skipped_tags.push(tag);
+        }
+    }
+
+    pub fn is_skipped(&self, tag: u32) -> bool {
+        return self.skipped_tags.contains(&tag);
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        return Self {
+            check_sum_validation: true,
+            skipped_tags: DEFAULT_SKIPPED_TAGS.to_vec(),
+            limits: ParseLimits::default(),
+            reject_duplicate_header_tags: false,
+        };
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    group::{get_parsed, get_string, read_group, write_optional, GroupEntry},
+    Component, FieldParseError, FixMessageBuilder,
+};
+
+const TAG_NO_PARTY_IDS: u32 = 453;
+const TAG_NO_PARTY_SUB_IDS: u32 = 802;
+
+const PARTY_SUB_ID_TAGS: [u32; 2] = [523, 803];
+
+/// Tags of the Parties component, for groups that embed it.
+pub(crate) const PARTIES_TAGS: [u32; 7] = [TAG_NO_PARTY_IDS, 448, 447, 452, 802, 523, 803];
+
+/// Entry of NoPartySubIDs (802).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartySubId {
+    /// PartySubID (523).
+    pub id: String,
+    /// PartySubIDType (803).
+    pub sub_id_type: Option<u32>,
+}
+
+/// Entry of NoPartyIDs (453), identifying a firm, trader, algorithm, client and so on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Party {
+    /// PartyID (448).
+    pub id: String,
+    /// PartyIDSource (447), e.g. `N` for an LEI or `P` for a MiFID short code.
+    pub id_source: Option<String>,
+    /// PartyRole (452).
+    pub role: Option<u32>,
+    /// NoPartySubIDs (802).
+    pub sub_ids: Vec<PartySubId>,
+}
+
+impl Party {
+    pub fn new(id: &str, id_source: &str, role: u32) -> Self {
+        return Self {
+            id: id.to_string(),
+            id_source: Some(id_source.to_string()),
+            role: Some(role),
+            sub_ids: vec![],
+        };
+    }
+}
+
+impl FixMessageBuilder {
+    /// Appends the Parties component: NoPartyIDs (453) and one entry per party.
+    pub fn with_parties(&mut self, parties: &[Party]) {
+        write_parties(self, parties);
+    }
+
+    /// Entries of NoPartyIDs (453); fails when the count doesn't match the entries found.
+    pub fn get_parties(&self) -> Result<Vec<Party>, FieldParseError> {
+        let fields: GroupEntry = self.get_fields().collect();
+        return read_parties(&fields);
+    }
+}
+
+impl Component for Vec<Party> {
+    const TAGS: &'static [u32] = &PARTIES_TAGS;
+
+    fn write(&self, message: &mut FixMessageBuilder) {
+        write_parties(message, self);
+    }
+
+    fn read(fields: &[(u32, &[u8])]) -> Result<Self, FieldParseError> {
+        return read_parties(fields);
+    }
+}
+
+pub(crate) fn write_parties(message: &mut FixMessageBuilder, parties: &[Party]) {
+    if parties.is_empty() {
+        return;
+    }
+
+    message.with_value(TAG_NO_PARTY_IDS, parties.len());
+
+    for party in parties {
+        message.with_value(448, party.id.as_str());
+        write_optional(message, 447, &party.id_source);
+
+        if let Some(role) = party.role {
+            message.with_value(452, role);
+        }
+
+        if !party.sub_ids.is_empty() {
+            message.with_value(TAG_NO_PARTY_SUB_IDS, party.sub_ids.len());
+
+            for sub_id in &party.sub_ids {
+                message.with_value(523, sub_id.id.as_str());
+
+                if let Some(sub_id_type) = sub_id.sub_id_type {
+                    message.with_value(803, sub_id_type);
+                }
+            }
+        }
+    }
+}
+
+/// Parties found in `fields`, which may be the whole message or an entry of a group
+/// embedding the component.
+pub(crate) fn read_parties(fields: &[(u32, &[u8])]) -> Result<Vec<Party>, FieldParseError> {
+    let mut parties = vec![];
+
+    for entry in read_group(fields, TAG_NO_PARTY_IDS, &PARTIES_TAGS[1..])? {
+        let mut sub_ids = vec![];
+
+        for sub_id in read_group(&entry, TAG_NO_PARTY_SUB_IDS, &PARTY_SUB_ID_TAGS)? {
+            sub_ids.push(PartySubId {
+                id: get_string(&sub_id, 523)?.unwrap_or_default(),
+                sub_id_type: get_parsed(&sub_id, 803)?,
+            });
+        }
+
+        parties.push(Party {
+            id: get_string(&entry, 448)?.unwrap_or_default(),
+            id_source: get_string(&entry, 447)?,
+            role: get_parsed(&entry, 452)?,
+            sub_ids,
+        });
+    }
+
+    return Ok(parties);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parties_round_trip() {
+        let mut trader = Party::new("TRADER1", "D", 12);
+        trader.sub_ids.push(PartySubId {
+            id: "DESK7".to_string(),
+            sub_id_type: Some(27),
+        });
+        let parties = vec![
+            Party::new("529900T8BM49AURSDO55", "N", 1),
+            trader,
+            Party::new("ALGO42", "P", 122),
+        ];
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        order.with_parties(&parties);
+        order.with_value(54, "1");
+
+        assert_eq!(
+            "453=3|448=529900T8BM49AURSDO55|447=N|452=1|448=TRADER1|447=D|452=12|802=1|523=DESK7|803=27|448=ALGO42|447=P|452=122|54=1|",
+            order
+                .get_fields()
+                .skip(1)
+                .map(|(tag, value)| format!("{}={}|", tag, String::from_utf8_lossy(value)))
+                .collect::<String>()
+        );
+        assert_eq!(parties, order.get_parties().unwrap());
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&order.as_bytes());
+        assert_eq!(parties, parsed.get_parties().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_party_role() {
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(453, 1);
+        order.with_value(448, "TRADER1");
+        order.with_value(452, "X");
+
+        assert!(matches!(
+            order.get_parties(),
+            Err(FieldParseError::InvalidValue { tag: 452, .. })
+        ));
+        assert!(FixMessageBuilder::new("FIX.4.4", "D")
+            .get_parties()
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::{FixMessageBuilder, SessionId};
+
+/// Last check of an application message before the session sends it; it may amend
+/// the message or refuse it. Refused messages are reported to
+/// `FixApplication::on_send_rejected` and `send_app` fails with
+/// `SessionSendError::Rejected`.
+pub trait PresendValidator: Send + Sync + std::fmt::Debug {
+    fn validate(
+        &self,
+        session_id: &SessionId,
+        message: &mut FixMessageBuilder,
+    ) -> Result<(), String>;
+}
+
+/// Refuses messages missing any of the tags required for their MsgType.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequiredTagsValidator {
+    required: HashMap<String, Vec<u32>>,
+}
+
+impl RequiredTagsValidator {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn require(&mut self, msg_type: &str, tag: u32) {
+        self.required
+            .entry(msg_type.to_string())
+            .or_default()
+            .push(tag);
+    }
+}
+
+impl PresendValidator for RequiredTagsValidator {
+    fn validate(
+        &self,
+        _session_id: &SessionId,
+        message: &mut FixMessageBuilder,
+    ) -> Result<(), String> {
+        let msg_type = message.get_message_type_as_string();
+
+        for tag in self.required.get(&msg_type).into_iter().flatten() {
+            if message.get(*tag).is_none() {
+                return Err(format!("Tag {} is required in MsgType {}", tag, msg_type));
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_required_tags() {
+        let session_id = SessionId::new("FIX.4.4", "BUY", "SELL");
+        let mut validator = RequiredTagsValidator::new();
+        validator.require("D", 1);
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        assert_eq!(
+            Err("Tag 1 is required in MsgType D".to_string()),
+            validator.validate(&session_id, &mut order)
+        );
+
+        order.with_value(1, "ACC1");
+        assert_eq!(Ok(()), validator.validate(&session_id, &mut order));
+
+        let mut cancel = FixMessageBuilder::new("FIX.4.4", "F");
+        assert_eq!(Ok(()), validator.validate(&session_id, &mut cancel));
+    }
+}
@@ -0,0 +1,237 @@
+//! Python bindings (`python` feature) over the same parser the sessions use, for
+//! research and operations scripts reading FIX logs. Build the extension module with
+//! maturin, e.g. `maturin develop --features python`, then `import rust_fix`.
+//!
+//! Values go in and out as `str`; parse failures raise `ValueError`.
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+use crate::{parse_all, FixMessage, FixMessageBuilder};
+
+#[pyclass(name = "FixMessageBuilder", module = "rust_fix")]
+#[derive(Clone)]
+pub struct PyFixMessageBuilder {
+    inner: FixMessageBuilder,
+}
+
+#[pyclass(name = "FixMessage", module = "rust_fix", frozen)]
+#[derive(Clone)]
+pub struct PyFixMessage {
+    inner: FixMessage,
+}
+
+#[pymethods]
+impl PyFixMessageBuilder {
+    #[new]
+    fn new(begin_string: &str, msg_type: &str) -> Self {
+        return Self {
+            inner: FixMessageBuilder::new(begin_string, msg_type),
+        };
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (data, check_sum_validation = true))]
+    fn from_bytes(data: &[u8], check_sum_validation: bool) -> PyResult<Self> {
+        return match FixMessageBuilder::from_bytes(data, check_sum_validation) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(err) => Err(PyValueError::new_err(format!("{:?}", err))),
+        };
+    }
+
+    /// Never fails and keeps the fields in wire order; meant for damaged logs.
+    #[staticmethod]
+    fn from_bytes_lenient(data: &[u8]) -> Self {
+        let (inner, _) = FixMessageBuilder::from_bytes_lenient(data);
+        return Self { inner };
+    }
+
+    #[getter]
+    fn begin_string(&self) -> String {
+        return self.inner.get_fix_version().to_string();
+    }
+
+    #[getter]
+    fn msg_type(&self) -> String {
+        return self.inner.get_message_type_as_string();
+    }
+
+    fn get(&self, tag: u32) -> Option<String> {
+        return self
+            .inner
+            .get(tag)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+    }
+
+    fn get_all(&self, tag: u32) -> Vec<String> {
+        return self
+            .inner
+            .get_all(tag)
+            .into_iter()
+            .map(|value| String::from_utf8_lossy(value).to_string())
+            .collect();
+    }
+
+    /// Body fields as `(tag, value)` pairs.
+    fn fields(&self) -> Vec<(u32, String)> {
+        return self
+            .inner
+            .get_fields()
+            .map(|(tag, value)| (tag, String::from_utf8_lossy(value).to_string()))
+            .collect();
+    }
+
+    fn with_value(&mut self, tag: u32, value: &str) {
+        self.inner.with_value(tag, value);
+    }
+
+    fn set_value(&mut self, tag: u32, value: &str) {
+        self.inner.set_value(tag, value);
+    }
+
+    fn remove_value(&mut self, tag: u32) -> usize {
+        return self.inner.remove_value(tag);
+    }
+
+    fn as_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        return PyBytes::new(py, &self.inner.as_bytes());
+    }
+
+    fn build(&self) -> PyFixMessage {
+        return PyFixMessage {
+            inner: self.inner.clone().build(),
+        };
+    }
+
+    fn __str__(&self) -> String {
+        return self.inner.to_string();
+    }
+
+    fn __repr__(&self) -> String {
+        return format!("FixMessageBuilder('{}')", self.inner);
+    }
+}
+
+#[pymethods]
+impl PyFixMessage {
+    #[staticmethod]
+    #[pyo3(signature = (data, check_sum_validation = true))]
+    fn from_bytes(data: &[u8], check_sum_validation: bool) -> PyResult<Self> {
+        return match FixMessage::from_bytes(data, check_sum_validation) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(err) => Err(PyValueError::new_err(format!("{:?}", err))),
+        };
+    }
+
+    #[getter]
+    fn msg_type(&self) -> String {
+        return self.inner.get_message_type_as_string();
+    }
+
+    #[getter]
+    fn check_sum(&self) -> String {
+        return self.inner.get_check_sum().to_string();
+    }
+
+    fn get(&self, tag: u32) -> Option<String> {
+        return self
+            .inner
+            .get(tag)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+    }
+
+    fn as_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        return PyBytes::new(py, self.inner.as_bytes());
+    }
+
+    fn to_builder(&self) -> PyFixMessageBuilder {
+        return PyFixMessageBuilder {
+            inner: self.inner.to_builder(),
+        };
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        return self.inner == other.inner;
+    }
+
+    fn __str__(&self) -> String {
+        return self.inner.to_string();
+    }
+
+    fn __repr__(&self) -> String {
+        return format!("FixMessage('{}')", self.inner);
+    }
+}
+
+/// Every complete message in `data`, e.g. a whole journal or capture file. Raises
+/// `ValueError` at the first malformed one.
+#[pyfunction(name = "parse_all")]
+#[pyo3(signature = (data, check_sum_validation = true))]
+fn py_parse_all(data: &[u8], check_sum_validation: bool) -> PyResult<Vec<PyFixMessageBuilder>> {
+    let (messages, consumed, err) = parse_all(data, check_sum_validation);
+
+    if let Some(err) = err {
+        return Err(PyValueError::new_err(format!(
+            "{:?} at byte {}",
+            err, consumed
+        )));
+    }
+
+    return Ok(messages
+        .into_iter()
+        .map(|inner| PyFixMessageBuilder { inner })
+        .collect());
+}
+
+#[pymodule]
+fn rust_fix(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyFixMessageBuilder>()?;
+    module.add_class::<PyFixMessage>()?;
+    module.add_function(wrap_pyfunction!(py_parse_all, module)?)?;
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pyo3::ffi::c_str;
+
+    #[test]
+    fn test_python_module() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "rust_fix").unwrap();
+            rust_fix(&module).unwrap();
+
+            let locals = pyo3::types::PyDict::new(py);
+            locals.set_item("rust_fix", module).unwrap();
+
+            py.run(
+                c_str!(
+                    r#"
+order = rust_fix.FixMessageBuilder("FIX.4.4", "D")
+order.with_value(11, "ORDER1")
+order.with_value(54, "1")
+data = order.as_bytes() * 2
+
+messages = rust_fix.parse_all(data)
+assert [m.get(11) for m in messages] == ["ORDER1", "ORDER1"]
+assert messages[0].msg_type == "D"
+assert sorted(messages[0].fields()) == [(11, "ORDER1"), (54, "1")]
+assert rust_fix.FixMessage.from_bytes(data[: len(data) // 2]).check_sum == order.build().check_sum
+
+try:
+    rust_fix.FixMessageBuilder.from_bytes(b"garbage")
+    raise AssertionError("garbage parsed")
+except ValueError:
+    pass
+"#
+                ),
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use crate::{format_utc_timestamp, parse_utc_timestamp, FixMessageBuilder, SessionId};
+
+const SEPARATOR: &[u8] = b" : ";
+
+/// One line of a QuickFIX `messages.log` or `event.log`: `YYYYMMDD-HH:MM:SS.sss : <text>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFixLogEntry {
+    pub timestamp: SystemTime,
+    pub text: Vec<u8>,
+}
+
+impl QuickFixLogEntry {
+    pub fn parse_line(line: &[u8]) -> Option<Self> {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        let position = line
+            .windows(SEPARATOR.len())
+            .position(|window| window == SEPARATOR)?;
+        let timestamp = std::str::from_utf8(&line[..position]).ok()?;
+
+        return Some(Self {
+            timestamp: parse_utc_timestamp(timestamp)?,
+            text: line[position + SEPARATOR.len()..].to_vec(),
+        });
+    }
+
+    /// Parses the text as a FIX message, as found in `messages.log`.
+    pub fn parse_message(&self) -> Option<FixMessageBuilder> {
+        return FixMessageBuilder::from_bytes(&self.text, false).ok();
+    }
+}
+
+/// Reads a QuickFIX log line by line, skipping lines without a timestamp.
+pub struct QuickFixLogReader<R: BufRead> {
+    reader: R,
+}
+
+impl QuickFixLogReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        return Ok(Self::new(BufReader::new(File::open(path)?)));
+    }
+}
+
+impl<R: BufRead> QuickFixLogReader<R> {
+    pub fn new(reader: R) -> Self {
+        return Self { reader };
+    }
+
+    /// The entries of a `messages.log` parsed as FIX messages.
+    pub fn messages(
+        self,
+    ) -> impl Iterator<Item = std::io::Result<(SystemTime, FixMessageBuilder)>> {
+        return self.filter_map(|entry| match entry {
+            Ok(entry) => Some(Ok((entry.timestamp, entry.parse_message()?))),
+            Err(err) => Some(Err(err)),
+        });
+    }
+}
+
+impl<R: BufRead> Iterator for QuickFixLogReader<R> {
+    type Item = std::io::Result<QuickFixLogEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut line = vec![];
+
+            match self.reader.read_until(b'\n', &mut line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    if let Some(entry) = QuickFixLogEntry::parse_line(&line) {
+                        return Some(Ok(entry));
+                    }
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// Writes `<prefix>.messages.current.log` and `<prefix>.event.current.log` the way
+/// QuickFIX's `FileLog` does, so its tooling can read them.
+pub struct QuickFixLog {
+    messages: Mutex<BufWriter<File>>,
+    events: Mutex<BufWriter<File>>,
+}
+
+impl QuickFixLog {
+    pub fn open(directory: impl AsRef<Path>, session_id: &SessionId) -> std::io::Result<Self> {
+        let prefix = Self::get_file_prefix(session_id);
+        let open = |name: String| -> std::io::Result<BufWriter<File>> {
+            let path: PathBuf = directory.as_ref().join(name);
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            return Ok(BufWriter::new(file));
+        };
+
+        return Ok(Self {
+            messages: Mutex::new(open(format!("{}.messages.current.log", prefix))?),
+            events: Mutex::new(open(format!("{}.event.current.log", prefix))?),
+        });
+    }
+
+    /// QuickFIX's file name prefix, e.g. `FIX.4.4-BUY-SELL`.
+    pub fn get_file_prefix(session_id: &SessionId) -> String {
+        let mut result = format!(
+            "{}-{}",
+            session_id.get_begin_string(),
+            session_id.get_sender_comp_id()
+        );
+
+        if let Some(sender_sub_id) = session_id.get_sender_sub_id() {
+            result.push('_');
+            result.push_str(sender_sub_id);
+        }
+
+        result.push('-');
+        result.push_str(session_id.get_target_comp_id());
+
+        if let Some(target_sub_id) = session_id.get_target_sub_id() {
+            result.push('_');
+            result.push_str(target_sub_id);
+        }
+
+        if let Some(qualifier) = session_id.get_qualifier() {
+            result.push('-');
+            result.push_str(qualifier);
+        }
+
+        return result;
+    }
+
+    pub fn on_message(&self, message: &[u8], now: SystemTime) -> std::io::Result<()> {
+        return write_line(&mut self.messages.lock().unwrap(), message, now);
+    }
+
+    pub fn on_event(&self, text: &str, now: SystemTime) -> std::io::Result<()> {
+        return write_line(&mut self.events.lock().unwrap(), text.as_bytes(), now);
+    }
+}
+
+fn write_line(writer: &mut BufWriter<File>, text: &[u8], now: SystemTime) -> std::io::Result<()> {
+    writer.write_all(format_utc_timestamp(now).as_bytes())?;
+    writer.write_all(SEPARATOR)?;
+    writer.write_all(text)?;
+    writer.write_all(b"\n")?;
+
+    return writer.flush();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_read_quickfix_messages_log() {
+        let log = b"20180920-18:24:59.643 : 8=FIX.4.4\x019=5\x0135=0\x0110=163\x01\n\
+            garbage\n\
+            20180920-18:25:00 : 8=FIX.4.4\x019=5\x0135=A\x0110=178\x01\r\n";
+
+        let messages: Vec<(SystemTime, FixMessageBuilder)> = QuickFixLogReader::new(log.as_slice())
+            .messages()
+            .map(|entry| entry.unwrap())
+            .collect();
+
+        assert_eq!(2, messages.len());
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_millis(1_537_467_899_643),
+            messages[0].0
+        );
+        assert_eq!("A", messages[1].1.get_message_type_as_string());
+    }
+
+    #[test]
+    fn test_write_quickfix_log() {
+        let directory = std::env::temp_dir().join(format!("rust-fix-log-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let mut session_id = SessionId::new("FIX.4.4", "BUY", "SELL");
+        session_id.with_qualifier("MD");
+        let now = UNIX_EPOCH + Duration::from_millis(1_537_467_899_643);
+
+        let log = QuickFixLog::open(&directory, &session_id).unwrap();
+        log.on_event("Created session", now).unwrap();
+
+        let events =
+            std::fs::read_to_string(directory.join("FIX.4.4-BUY-SELL-MD.event.current.log"))
+                .unwrap();
+        assert_eq!("20180920-18:24:59.643 : Created session\n", events);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::{Duration, SystemTime};
+
+use crate::SessionConfig;
+
+/// Token bucket limiting how many messages a session sends.
+///
+/// The bucket holds up to `burst_size` tokens and refills at `messages_per_second`;
+/// every message takes one token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimiter {
+    messages_per_second: f64,
+    burst_size: f64,
+    tokens: f64,
+    last_refill: Option<SystemTime>,
+}
+
+impl RateLimiter {
+    pub fn new(messages_per_second: u32, burst_size: u32) -> Self {
+        let burst_size = burst_size.max(1) as f64;
+
+        return Self {
+            messages_per_second: messages_per_second.max(1) as f64,
+            burst_size,
+            tokens: burst_size,
+            last_refill: None,
+        };
+    }
+
+    /// Returns a limiter when `max_messages_per_second` is set, bursting up to the same rate
+    /// unless `burst_size` says otherwise.
+    pub fn from_config(config: &SessionConfig) -> Option<Self> {
+        let messages_per_second = config.max_messages_per_second?;
+
+        return Some(Self::new(
+            messages_per_second,
+            config.burst_size.unwrap_or(messages_per_second),
+        ));
+    }
+
+    /// Takes a token if one is available.
+    pub fn try_acquire(&mut self, now: SystemTime) -> bool {
+        self.refill(now);
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+
+        self.tokens -= 1.0;
+        return true;
+    }
+
+    /// How long until the next token is available.
+    pub fn get_wait_time(&mut self, now: SystemTime) -> Duration {
+        self.refill(now);
+
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+
+        return Duration::from_secs_f64((1.0 - self.tokens) / self.messages_per_second);
+    }
+
+    fn refill(&mut self, now: SystemTime) {
+        if let Some(last_refill) = self.last_refill {
+            // A clock going backwards adds nothing rather than failing.
+            let elapsed = now.duration_since(last_refill).unwrap_or_default();
+            self.tokens = (self.tokens + elapsed.as_secs_f64() * self.messages_per_second)
+                .min(self.burst_size);
+        }
+
+        self.last_refill = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_throttle() {
+        let now = SystemTime::UNIX_EPOCH;
+        let mut limiter = RateLimiter::new(10, 3);
+
+        assert!(limiter.try_acquire(now));
+        assert!(limiter.try_acquire(now));
+        assert!(limiter.try_acquire(now));
+        assert!(!limiter.try_acquire(now));
+        assert_eq!(Duration::from_millis(100), limiter.get_wait_time(now));
+
+        let later = now + Duration::from_millis(100);
+        assert!(limiter.try_acquire(later));
+        assert!(!limiter.try_acquire(later));
+    }
+
+    #[test]
+    fn test_refill_is_capped_at_burst() {
+        let now = SystemTime::UNIX_EPOCH;
+        let mut limiter = RateLimiter::new(100, 2);
+        limiter.try_acquire(now);
+
+        let later = now + Duration::from_secs(60);
+        assert!(limiter.try_acquire(later));
+        assert!(limiter.try_acquire(later));
+        assert!(!limiter.try_acquire(later));
+    }
+}
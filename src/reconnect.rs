@@ -0,0 +1,227 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{SessionConfig, SessionId};
+
+/// How an initiator waits between connection attempts.
+///
+/// The delay starts at `initial_interval` and is multiplied by `multiplier` after every
+/// failed attempt, up to `max_interval`. `jitter` randomly shortens each delay by up to
+/// that fraction so many initiators don't reconnect in lockstep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    initial_interval: Duration,
+    max_interval: Duration,
+    multiplier: f64,
+    jitter: f64,
+    max_attempts: Option<u32>,
+}
+
+impl ReconnectPolicy {
+    /// Retries forever with a fixed interval.
+    pub fn new(interval: Duration) -> Self {
+        return Self {
+            initial_interval: interval,
+            max_interval: interval,
+            multiplier: 1.0,
+            jitter: 0.0,
+            max_attempts: None,
+        };
+    }
+
+    pub fn from_config(config: &SessionConfig) -> Self {
+        return Self::new(Duration::from_secs(config.reconnect_interval));
+    }
+
+    pub fn with_backoff(&mut self, multiplier: f64, max_interval: Duration) {
+        self.multiplier = multiplier.max(1.0);
+        self.max_interval = max_interval.max(self.initial_interval);
+    }
+
+    pub fn with_jitter(&mut self, jitter: f64) {
+        self.jitter = jitter.clamp(0.0, 1.0);
+    }
+
+    pub fn with_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = Some(max_attempts);
+    }
+
+    /// Delay before the given retry (starting at 0), with `random` in `[0, 1)` picking the jitter.
+    pub fn get_delay(&self, attempt: u32, random: f64) -> Option<Duration> {
+        if let Some(max_attempts) = self.max_attempts {
+            if attempt >= max_attempts {
+                return None;
+            }
+        }
+
+        let backoff = self.multiplier.powi(attempt.min(i32::MAX as u32) as i32);
+        let delay =
+            (self.initial_interval.as_secs_f64() * backoff).min(self.max_interval.as_secs_f64());
+        let delay = delay * (1.0 - self.jitter * random);
+
+        return Some(Duration::from_secs_f64(delay));
+    }
+}
+
+type ConnectionCallback = Box<dyn FnMut(&SessionId) + Send>;
+
+/// Keeps track of reconnect attempts for one session and notifies about connection changes.
+pub struct Reconnector {
+    session_id: SessionId,
+    policy: ReconnectPolicy,
+    attempt: u32,
+    random_state: u64,
+    on_connect: Option<ConnectionCallback>,
+    on_disconnect: Option<ConnectionCallback>,
+}
+
+impl Reconnector {
+    pub fn new(session_id: SessionId, policy: ReconnectPolicy) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+
+        return Self {
+            session_id,
+            policy,
+            attempt: 0,
+            random_state: seed | 1,
+            on_connect: None,
+            on_disconnect: None,
+        };
+    }
+
+    pub fn with_seed(&mut self, seed: u64) {
+        self.random_state = seed | 1;
+    }
+
+    pub fn with_on_connect(&mut self, callback: impl FnMut(&SessionId) + Send + 'static) {
+        self.on_connect = Some(Box::new(callback));
+    }
+
+    pub fn with_on_disconnect(&mut self, callback: impl FnMut(&SessionId) + Send + 'static) {
+        self.on_disconnect = Some(Box::new(callback));
+    }
+
+    pub fn get_policy(&self) -> &ReconnectPolicy {
+        return &self.policy;
+    }
+
+    pub fn get_attempt(&self) -> u32 {
+        return self.attempt;
+    }
+
+    /// Delay before the next attempt, or `None` when the policy gave up.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        let random = self.next_random();
+        let result = self.policy.get_delay(self.attempt, random)?;
+        self.attempt += 1;
+
+        return Some(result);
+    }
+
+    pub fn connected(&mut self) {
+        self.attempt = 0;
+
+        if let Some(callback) = &mut self.on_connect {
+            callback(&self.session_id);
+        }
+    }
+
+    pub fn disconnected(&mut self) {
+        if let Some(callback) = &mut self.on_disconnect {
+            callback(&self.session_id);
+        }
+    }
+
+    /// Calls `connect` until it succeeds, sleeping between attempts as the policy says.
+    pub fn connect_blocking<T>(
+        &mut self,
+        mut connect: impl FnMut() -> std::io::Result<T>,
+    ) -> std::io::Result<T> {
+        loop {
+            match connect() {
+                Ok(result) => {
+                    self.connected();
+                    return Ok(result);
+                }
+                Err(err) => match self.next_delay() {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(err),
+                },
+            }
+        }
+    }
+
+    // xorshift64, good enough to spread reconnects.
+    fn next_random(&mut self) -> f64 {
+        let mut x = self.random_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.random_state = x;
+
+        return (x >> 11) as f64 / (1u64 << 53) as f64;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_exponential_backoff() {
+        let mut policy = ReconnectPolicy::new(Duration::from_secs(1));
+        policy.with_backoff(2.0, Duration::from_secs(10));
+        policy.with_max_attempts(6);
+
+        let delays: Vec<Option<Duration>> = (0..7)
+            .map(|attempt| policy.get_delay(attempt, 0.5))
+            .collect();
+
+        assert_eq!(Some(Duration::from_secs(1)), delays[0]);
+        assert_eq!(Some(Duration::from_secs(8)), delays[3]);
+        assert_eq!(Some(Duration::from_secs(10)), delays[5]);
+        assert_eq!(None, delays[6]);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let mut policy = ReconnectPolicy::new(Duration::from_secs(10));
+        policy.with_jitter(0.5);
+
+        assert_eq!(Some(Duration::from_secs(10)), policy.get_delay(0, 0.0));
+        assert_eq!(Some(Duration::from_millis(7500)), policy.get_delay(0, 0.5));
+    }
+
+    #[test]
+    fn test_connect_blocking() {
+        let mut policy = ReconnectPolicy::new(Duration::from_millis(1));
+        policy.with_max_attempts(5);
+
+        let connects = Arc::new(AtomicU32::new(0));
+        let connects_in_callback = connects.clone();
+
+        let mut reconnector = Reconnector::new(SessionId::new("FIX.4.4", "BUY", "SELL"), policy);
+        reconnector.with_on_connect(move |_| {
+            connects_in_callback.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let mut calls = 0;
+        let result = reconnector.connect_blocking(|| {
+            calls += 1;
+            if calls < 3 {
+                return Err(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+            }
+            Ok(calls)
+        });
+
+        assert_eq!(3, result.unwrap());
+        assert_eq!(1, connects.load(Ordering::SeqCst));
+        assert_eq!(0, reconnector.get_attempt());
+    }
+}
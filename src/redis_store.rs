@@ -0,0 +1,198 @@
+use std::{sync::Mutex, time::Duration};
+
+use crate::{Leadership, MessageStore, SeqNumStore, SeqNums, SessionId};
+
+/// `SeqNumStore` and `MessageStore` on Redis (`redis` feature).
+///
+/// Together with `RedisLeadership` this lets a standby instance on another host take
+/// over a session with the sequence numbers and messages of the failed primary.
+/// Keys are prefixed with `rust-fix:{session_id}`.
+pub struct RedisStore {
+    connection: Mutex<redis::Connection>,
+    key_prefix: String,
+}
+
+impl std::fmt::Debug for RedisStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f
+            .debug_struct("RedisStore")
+            .field("key_prefix", &self.key_prefix)
+            .finish();
+    }
+}
+
+impl RedisStore {
+    pub fn new(client: &redis::Client, session_id: &SessionId) -> std::io::Result<Self> {
+        let connection = client.get_connection().map_err(into_io_error)?;
+
+        return Ok(Self {
+            connection: Mutex::new(connection),
+            key_prefix: format!("rust-fix:{}", session_id),
+        });
+    }
+
+    fn key(&self, name: &str) -> String {
+        return format!("{}:{}", self.key_prefix, name);
+    }
+}
+
+impl SeqNumStore for RedisStore {
+    fn load(&self) -> std::io::Result<Option<SeqNums>> {
+        let (sender, target): (Option<u64>, Option<u64>) = redis::cmd("HMGET")
+            .arg(self.key("seqnums"))
+            .arg("sender")
+            .arg("target")
+            .query(&mut *self.connection.lock().unwrap())
+            .map_err(into_io_error)?;
+
+        let (Some(next_sender_seq_num), Some(next_target_seq_num)) = (sender, target) else {
+            return Ok(None);
+        };
+
+        return Ok(Some(SeqNums {
+            next_sender_seq_num,
+            next_target_seq_num,
+        }));
+    }
+
+    fn save(&self, seq_nums: SeqNums) -> std::io::Result<()> {
+        redis::cmd("HSET")
+            .arg(self.key("seqnums"))
+            .arg("sender")
+            .arg(seq_nums.next_sender_seq_num)
+            .arg("target")
+            .arg(seq_nums.next_target_seq_num)
+            .exec(&mut *self.connection.lock().unwrap())
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+}
+
+impl MessageStore for RedisStore {
+    fn save(&self, seq_num: u64, message: &[u8]) -> std::io::Result<()> {
+        redis::pipe()
+            .atomic()
+            .hset(self.key("messages"), seq_num, message)
+            .ignore()
+            .zadd(self.key("message_index"), seq_num, seq_num)
+            .ignore()
+            .exec(&mut *self.connection.lock().unwrap())
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+
+    fn get_range(&self, begin: u64, end: u64) -> std::io::Result<Vec<(u64, Vec<u8>)>> {
+        if begin > end {
+            return Ok(vec![]);
+        }
+
+        let mut connection = self.connection.lock().unwrap();
+
+        let seq_nums: Vec<u64> = redis::cmd("ZRANGEBYSCORE")
+            .arg(self.key("message_index"))
+            .arg(begin)
+            .arg(end)
+            .query(&mut *connection)
+            .map_err(into_io_error)?;
+
+        if seq_nums.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let messages: Vec<Option<Vec<u8>>> = redis::cmd("HMGET")
+            .arg(self.key("messages"))
+            .arg(&seq_nums)
+            .query(&mut *connection)
+            .map_err(into_io_error)?;
+
+        return Ok(seq_nums
+            .into_iter()
+            .zip(messages)
+            .filter_map(|(seq_num, message)| Some((seq_num, message?)))
+            .collect());
+    }
+
+    fn reset(&self) -> std::io::Result<()> {
+        redis::cmd("DEL")
+            .arg(self.key("messages"))
+            .arg(self.key("message_index"))
+            .exec(&mut *self.connection.lock().unwrap())
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+}
+
+/// Leadership held as a Redis key with a lease of `ttl`, renewed on every `acquire`.
+pub struct RedisLeadership {
+    connection: Mutex<redis::Connection>,
+    instance_id: String,
+    ttl: Duration,
+}
+
+impl RedisLeadership {
+    /// `instance_id` has to be unique among the instances competing for the sessions.
+    pub fn new(client: &redis::Client, instance_id: &str, ttl: Duration) -> std::io::Result<Self> {
+        let connection = client.get_connection().map_err(into_io_error)?;
+
+        return Ok(Self {
+            connection: Mutex::new(connection),
+            instance_id: instance_id.to_string(),
+            ttl,
+        });
+    }
+
+    fn key(session_id: &SessionId) -> String {
+        return format!("rust-fix:{}:leader", session_id);
+    }
+}
+
+impl Leadership for RedisLeadership {
+    fn acquire(&self, session_id: &SessionId) -> std::io::Result<bool> {
+        let script = redis::Script::new(
+            r"
+            if redis.call('GET', KEYS[1]) == ARGV[1] then
+                return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+            end
+            if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+                return 1
+            end
+            return 0
+            ",
+        );
+
+        let acquired: i64 = script
+            .key(Self::key(session_id))
+            .arg(&self.instance_id)
+            .arg(self.ttl.as_millis() as u64)
+            .invoke(&mut *self.connection.lock().unwrap())
+            .map_err(into_io_error)?;
+
+        return Ok(acquired == 1);
+    }
+
+    fn release(&self, session_id: &SessionId) -> std::io::Result<()> {
+        let script = redis::Script::new(
+            r"
+            if redis.call('GET', KEYS[1]) == ARGV[1] then
+                return redis.call('DEL', KEYS[1])
+            end
+            return 0
+            ",
+        );
+
+        let _: i64 = script
+            .key(Self::key(session_id))
+            .arg(&self.instance_id)
+            .invoke(&mut *self.connection.lock().unwrap())
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+}
+
+fn into_io_error(err: redis::RedisError) -> std::io::Error {
+    return std::io::Error::other(err);
+}
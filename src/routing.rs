@@ -0,0 +1,139 @@
+use crate::FixMessageBuilder;
+
+const SENDER_TAGS: [u32; 3] = [49, 50, 142];
+const TARGET_TAGS: [u32; 3] = [56, 57, 143];
+const ON_BEHALF_OF_TAGS: [u32; 3] = [115, 116, 144];
+const DELIVER_TO_TAGS: [u32; 3] = [128, 129, 145];
+
+/// CompID with its optional SubID and LocationID, as carried by the sender, target,
+/// OnBehalfOf and DeliverTo header fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoutingId {
+    pub comp_id: String,
+    pub sub_id: Option<String>,
+    pub location_id: Option<String>,
+}
+
+impl RoutingId {
+    pub fn new(comp_id: &str) -> Self {
+        return Self {
+            comp_id: comp_id.to_string(),
+            sub_id: None,
+            location_id: None,
+        };
+    }
+
+    pub fn with_sub_id(mut self, sub_id: &str) -> Self {
+        self.sub_id = Some(sub_id.to_string());
+        return self;
+    }
+
+    pub fn with_location_id(mut self, location_id: &str) -> Self {
+        self.location_id = Some(location_id.to_string());
+        return self;
+    }
+}
+
+impl FixMessageBuilder {
+    /// OnBehalfOfCompID (115), OnBehalfOfSubID (116) and OnBehalfOfLocationID (144).
+    pub fn get_on_behalf_of(&self) -> Option<RoutingId> {
+        return self.get_routing_id(ON_BEHALF_OF_TAGS);
+    }
+
+    pub fn with_on_behalf_of(&mut self, id: &RoutingId) {
+        self.set_routing_id(ON_BEHALF_OF_TAGS, Some(id));
+    }
+
+    /// DeliverToCompID (128), DeliverToSubID (129) and DeliverToLocationID (145).
+    pub fn get_deliver_to(&self) -> Option<RoutingId> {
+        return self.get_routing_id(DELIVER_TO_TAGS);
+    }
+
+    pub fn with_deliver_to(&mut self, id: &RoutingId) {
+        self.set_routing_id(DELIVER_TO_TAGS, Some(id));
+    }
+
+    /// Copy of the message as a hub named `hub_comp_id` passes it on to its final
+    /// recipient: the DeliverTo fields become the target, the original sender moves
+    /// to OnBehalfOf and the hub becomes the sender. An OnBehalfOf already present is
+    /// kept, so the firm that originated the message stays visible across several hops.
+    ///
+    /// `None` when the message has no DeliverToCompID. MsgSeqNum and SendingTime are
+    /// left for the outbound session to stamp.
+    pub fn to_forwarded(&self, hub_comp_id: &str) -> Option<Self> {
+        let deliver_to = self.get_deliver_to()?;
+        let sender = self.get_routing_id(SENDER_TAGS);
+
+        let mut result = self.clone();
+        result.set_routing_id(DELIVER_TO_TAGS, None);
+        result.set_routing_id(SENDER_TAGS, Some(&RoutingId::new(hub_comp_id)));
+        result.set_routing_id(TARGET_TAGS, Some(&deliver_to));
+
+        if result.get_on_behalf_of().is_none() {
+            result.set_routing_id(ON_BEHALF_OF_TAGS, sender.as_ref());
+        }
+
+        return Some(result);
+    }
+
+    fn get_routing_id(&self, tags: [u32; 3]) -> Option<RoutingId> {
+        let value = |tag| {
+            return self
+                .get(tag)
+                .map(|value| String::from_utf8_lossy(value).to_string());
+        };
+
+        return Some(RoutingId {
+            comp_id: value(tags[0])?,
+            sub_id: value(tags[1]),
+            location_id: value(tags[2]),
+        });
+    }
+
+    fn set_routing_id(&mut self, tags: [u32; 3], id: Option<&RoutingId>) {
+        let values = [
+            id.map(|id| id.comp_id.as_str()),
+            id.and_then(|id| id.sub_id.as_deref()),
+            id.and_then(|id| id.location_id.as_deref()),
+        ];
+
+        for (tag, value) in tags.into_iter().zip(values) {
+            match value {
+                Some(value) => self.set_value(tag, value),
+                None => {
+                    self.remove_value(tag);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_forwarded() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(49, "BUYSIDE");
+        message.with_value(50, "DESK1");
+        message.with_value(56, "HUB");
+        message.with_deliver_to(&RoutingId::new("BROKER").with_sub_id("EQ"));
+        message.with_value(11, "ORDER1");
+
+        let forwarded = message.to_forwarded("HUB").unwrap();
+
+        assert_eq!(Some("HUB"), forwarded.get_sender_comp_id());
+        assert_eq!(None, forwarded.get(50));
+        assert_eq!(Some("BROKER"), forwarded.get_target_comp_id());
+        assert_eq!(Some(b"EQ".as_slice()), forwarded.get(57));
+        assert_eq!(
+            Some(RoutingId::new("BUYSIDE").with_sub_id("DESK1")),
+            forwarded.get_on_behalf_of()
+        );
+        assert_eq!(None, forwarded.get_deliver_to());
+        assert_eq!(Some(b"ORDER1".as_slice()), forwarded.get(11));
+
+        assert!(forwarded.to_forwarded("HUB2").is_none());
+    }
+}
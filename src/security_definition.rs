@@ -0,0 +1,171 @@
+use crate::{
+    group::{expect_msg_type, get_parsed, get_string, required, write_optional, GroupEntry},
+    instrument::{read_instrument, write_instrument},
+    BeginString, FieldParseError, FixMessageBuilder, Instrument,
+};
+
+const MSG_TYPE_SECURITY_DEFINITION_REQUEST: &str = "c";
+const MSG_TYPE_SECURITY_DEFINITION: &str = "d";
+
+/// SecurityDefinitionRequest (c).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityDefinitionRequest {
+    /// SecurityReqID (320).
+    pub security_req_id: String,
+    /// SecurityRequestType (321), e.g. `3` to request the securities matching the
+    /// instrument given.
+    pub security_request_type: u32,
+    /// Instrument to look up, empty to leave it to the counterparty.
+    pub instrument: Instrument,
+    /// Currency (15).
+    pub currency: Option<String>,
+    /// Text (58).
+    pub text: Option<String>,
+    /// SubscriptionRequestType (263).
+    pub subscription_request_type: Option<char>,
+}
+
+/// SecurityDefinition (d), the answer to a SecurityDefinitionRequest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityDefinition {
+    /// SecurityReqID (320).
+    pub security_req_id: String,
+    /// SecurityResponseID (322).
+    pub security_response_id: String,
+    /// SecurityResponseType (323), e.g. `1` to accept the request as is.
+    pub security_response_type: u32,
+    pub instrument: Instrument,
+    /// Currency (15).
+    pub currency: Option<String>,
+    /// Text (58).
+    pub text: Option<String>,
+}
+
+impl SecurityDefinitionRequest {
+    pub fn new(security_req_id: &str, security_request_type: u32) -> Self {
+        return Self {
+            security_req_id: security_req_id.to_string(),
+            security_request_type,
+            instrument: Instrument::default(),
+            currency: None,
+            text: None,
+            subscription_request_type: None,
+        };
+    }
+
+    /// The SecurityDefinitionRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message =
+            FixMessageBuilder::new(begin_string, MSG_TYPE_SECURITY_DEFINITION_REQUEST);
+        message.with_value(320, self.security_req_id.as_str());
+        message.with_value(321, self.security_request_type);
+        write_instrument(&mut message, &self.instrument);
+        write_optional(&mut message, 15, &self.currency);
+        write_optional(&mut message, 58, &self.text);
+        write_optional(&mut message, 263, &self.subscription_request_type);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for SecurityDefinitionRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_SECURITY_DEFINITION_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            security_req_id: required(get_string(&fields, 320)?, 320)?,
+            security_request_type: required(get_parsed(&fields, 321)?, 321)?,
+            instrument: read_instrument(&fields)?,
+            currency: get_string(&fields, 15)?,
+            text: get_string(&fields, 58)?,
+            subscription_request_type: get_parsed(&fields, 263)?,
+        });
+    }
+}
+
+impl SecurityDefinition {
+    pub fn new(
+        security_req_id: &str,
+        security_response_id: &str,
+        security_response_type: u32,
+        instrument: Instrument,
+    ) -> Self {
+        return Self {
+            security_req_id: security_req_id.to_string(),
+            security_response_id: security_response_id.to_string(),
+            security_response_type,
+            instrument,
+            currency: None,
+            text: None,
+        };
+    }
+
+    /// The SecurityDefinition as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_SECURITY_DEFINITION);
+        message.with_value(320, self.security_req_id.as_str());
+        message.with_value(322, self.security_response_id.as_str());
+        message.with_value(323, self.security_response_type);
+        write_instrument(&mut message, &self.instrument);
+        write_optional(&mut message, 15, &self.currency);
+        write_optional(&mut message, 58, &self.text);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for SecurityDefinition {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_SECURITY_DEFINITION)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            security_req_id: required(get_string(&fields, 320)?, 320)?,
+            security_response_id: required(get_string(&fields, 322)?, 322)?,
+            security_response_type: required(get_parsed(&fields, 323)?, 323)?,
+            instrument: read_instrument(&fields)?,
+            currency: get_string(&fields, 15)?,
+            text: get_string(&fields, 58)?,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_security_definition_round_trip() {
+        let mut request = SecurityDefinitionRequest::new("REQ1", 3);
+        request.instrument = Instrument::new("ESZ4");
+        request.subscription_request_type = Some('0');
+
+        let message = request.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=34|35=c|320=REQ1|321=3|55=ESZ4|263=0|10=210|",
+            message.to_string()
+        );
+        assert_eq!(Ok(request), SecurityDefinitionRequest::try_from(&message));
+
+        let mut instrument = Instrument::new("ESZ4");
+        instrument.security_type = Some("FUT".to_string());
+        instrument.maturity_month_year = Some("202412".to_string());
+        instrument.contract_multiplier = Some("50".to_string());
+        let definition = SecurityDefinition::new("REQ1", "RES1", 1, instrument);
+
+        let message = definition.to_message("FIX.4.4");
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(Ok(definition), SecurityDefinition::try_from(&parsed));
+        assert_eq!(
+            Err(FieldParseError::Missing { tag: 320 }),
+            SecurityDefinition::try_from(&FixMessageBuilder::new("FIX.4.4", "d"))
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    group::{
+        expect_msg_type, get_parsed, get_string, read_group, required, write_optional, GroupEntry,
+    },
+    instrument::{read_instrument, write_instrument, INSTRUMENT_TAGS},
+    BeginString, FieldParseError, FixMessageBuilder, Instrument,
+};
+
+const MSG_TYPE_SECURITY_LIST_REQUEST: &str = "x";
+const MSG_TYPE_SECURITY_LIST: &str = "y";
+
+const TAG_NO_RELATED_SYM: u32 = 146;
+
+/// SecurityListRequest (x).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityListRequest {
+    /// SecurityReqID (320).
+    pub security_req_id: String,
+    /// SecurityListRequestType (559), e.g. `0` for the given symbol or `4` for all
+    /// securities.
+    pub security_list_request_type: u32,
+    /// Instrument to match, empty when listing everything.
+    pub instrument: Instrument,
+    /// Currency (15).
+    pub currency: Option<String>,
+    /// Text (58).
+    pub text: Option<String>,
+    /// SubscriptionRequestType (263).
+    pub subscription_request_type: Option<char>,
+}
+
+/// Entry of NoRelatedSym (146) in a SecurityList.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityListEntry {
+    pub instrument: Instrument,
+    /// Currency (15).
+    pub currency: Option<String>,
+}
+
+/// SecurityList (y). Large lists come in several messages, the last with
+/// LastFragment (893) set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityList {
+    /// SecurityReqID (320).
+    pub security_req_id: String,
+    /// SecurityResponseID (322).
+    pub security_response_id: String,
+    /// SecurityRequestResult (560), `0` when the request was valid.
+    pub security_request_result: u32,
+    /// TotNoRelatedSym (393), the securities across all fragments.
+    pub tot_no_related_sym: Option<u32>,
+    /// LastFragment (893).
+    pub last_fragment: Option<bool>,
+    /// NoRelatedSym (146).
+    pub securities: Vec<SecurityListEntry>,
+}
+
+impl SecurityListRequest {
+    pub fn new(security_req_id: &str, security_list_request_type: u32) -> Self {
+        return Self {
+            security_req_id: security_req_id.to_string(),
+            security_list_request_type,
+            instrument: Instrument::default(),
+            currency: None,
+            text: None,
+            subscription_request_type: None,
+        };
+    }
+
+    /// The SecurityListRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_SECURITY_LIST_REQUEST);
+        message.with_value(320, self.security_req_id.as_str());
+        message.with_value(559, self.security_list_request_type);
+        write_instrument(&mut message, &self.instrument);
+        write_optional(&mut message, 15, &self.currency);
+        write_optional(&mut message, 58, &self.text);
+        write_optional(&mut message, 263, &self.subscription_request_type);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for SecurityListRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_SECURITY_LIST_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            security_req_id: required(get_string(&fields, 320)?, 320)?,
+            security_list_request_type: required(get_parsed(&fields, 559)?, 559)?,
+            instrument: read_instrument(&fields)?,
+            currency: get_string(&fields, 15)?,
+            text: get_string(&fields, 58)?,
+            subscription_request_type: get_parsed(&fields, 263)?,
+        });
+    }
+}
+
+impl SecurityList {
+    pub fn new(
+        security_req_id: &str,
+        security_response_id: &str,
+        security_request_result: u32,
+    ) -> Self {
+        return Self {
+            security_req_id: security_req_id.to_string(),
+            security_response_id: security_response_id.to_string(),
+            security_request_result,
+            tot_no_related_sym: None,
+            last_fragment: None,
+            securities: vec![],
+        };
+    }
+
+    /// The SecurityList as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_SECURITY_LIST);
+        message.with_value(320, self.security_req_id.as_str());
+        message.with_value(322, self.security_response_id.as_str());
+        message.with_value(560, self.security_request_result);
+        write_optional(&mut message, 393, &self.tot_no_related_sym);
+        write_optional(&mut message, 893, &self.last_fragment);
+
+        if !self.securities.is_empty() {
+            message.with_value(TAG_NO_RELATED_SYM, self.securities.len());
+
+            for security in &self.securities {
+                write_instrument(&mut message, &security.instrument);
+                write_optional(&mut message, 15, &security.currency);
+            }
+        }
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for SecurityList {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_SECURITY_LIST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let members: Vec<u32> = INSTRUMENT_TAGS.iter().chain(&[15]).copied().collect();
+
+        let mut securities = vec![];
+
+        for entry in read_group(&fields, TAG_NO_RELATED_SYM, &members)? {
+            securities.push(SecurityListEntry {
+                instrument: read_instrument(&entry)?,
+                currency: get_string(&entry, 15)?,
+            });
+        }
+
+        let last_fragment = match get_string(&fields, 893)?.as_deref() {
+            Some("Y") => Some(true),
+            Some("N") => Some(false),
+            Some(value) => {
+                return Err(FieldParseError::InvalidValue {
+                    tag: 893,
+                    value: value.to_string(),
+                    reason: "not a Boolean".to_string(),
+                })
+            }
+            None => None,
+        };
+
+        return Ok(Self {
+            security_req_id: required(get_string(&fields, 320)?, 320)?,
+            security_response_id: required(get_string(&fields, 322)?, 322)?,
+            security_request_result: required(get_parsed(&fields, 560)?, 560)?,
+            tot_no_related_sym: get_parsed(&fields, 393)?,
+            last_fragment,
+            securities,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_security_list_round_trip() {
+        let request = SecurityListRequest::new("REQ1", 4);
+        let message = request.to_message("FIX.4.4");
+        assert_eq!(Ok(request), SecurityListRequest::try_from(&message));
+
+        let mut list = SecurityList::new("REQ1", "RES1", 0);
+        list.tot_no_related_sym = Some(2);
+        list.last_fragment = Some(true);
+
+        let mut bund = Instrument::new("FGBLZ4");
+        bund.security_exchange = Some("XEUR".to_string());
+        list.securities = vec![
+            SecurityListEntry {
+                instrument: bund,
+                currency: Some("EUR".to_string()),
+            },
+            SecurityListEntry {
+                instrument: Instrument::new("ESZ4"),
+                currency: None,
+            },
+        ];
+
+        let message = list.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=81|35=y|320=REQ1|322=RES1|560=0|393=2|893=Y|146=2|55=FGBLZ4|207=XEUR|15=EUR|55=ESZ4|10=036|",
+            message.to_string()
+        );
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(Ok(list), SecurityList::try_from(&parsed));
+
+        let mut message = message;
+        message.set_value(146, 3);
+        assert!(matches!(
+            SecurityList::try_from(&message),
+            Err(FieldParseError::InvalidValue { tag: 146, .. })
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeqNums {
+    pub next_sender_seq_num: u64,
+    pub next_target_seq_num: u64,
+}
+
+/// Keeps a session's sequence numbers across restarts.
+///
+/// The session saves after every change, so implementations should make each
+/// `save` durable before returning.
+pub trait SeqNumStore: Send + Sync + std::fmt::Debug {
+    /// Returns `None` when nothing was saved yet.
+    fn load(&self) -> std::io::Result<Option<SeqNums>>;
+
+    fn save(&self, seq_nums: SeqNums) -> std::io::Result<()>;
+}
+
+/// Keeps sequence numbers only for the life of the process.
+#[derive(Debug, Default)]
+pub struct MemorySeqNumStore {
+    seq_nums: Mutex<Option<SeqNums>>,
+}
+
+impl MemorySeqNumStore {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+}
+
+impl SeqNumStore for MemorySeqNumStore {
+    fn load(&self) -> std::io::Result<Option<SeqNums>> {
+        return Ok(*self.seq_nums.lock().unwrap());
+    }
+
+    fn save(&self, seq_nums: SeqNums) -> std::io::Result<()> {
+        *self.seq_nums.lock().unwrap() = Some(seq_nums);
+        return Ok(());
+    }
+}
+
+/// Keeps sequence numbers in a file in the QuickFIX `.seqnums` format
+/// (`0000000002 : 0000000005`, sender first).
+///
+/// Every save writes a temporary file and renames it over the old one, so a crash
+/// leaves either the old or the new numbers behind, never a torn write.
+#[derive(Debug)]
+pub struct FileSeqNumStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileSeqNumStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        return Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        };
+    }
+
+    pub fn get_path(&self) -> &Path {
+        return &self.path;
+    }
+}
+
+impl SeqNumStore for FileSeqNumStore {
+    fn load(&self) -> std::io::Result<Option<SeqNums>> {
+        let _guard = self.lock.lock().unwrap();
+
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let invalid = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid sequence numbers file {}", self.path.display()),
+            )
+        };
+
+        let (sender, target) = content.split_once(':').ok_or_else(invalid)?;
+
+        return Ok(Some(SeqNums {
+            next_sender_seq_num: sender.trim().parse().map_err(|_| invalid())?,
+            next_target_seq_num: target.trim().parse().map_err(|_| invalid())?,
+        }));
+    }
+
+    fn save(&self, seq_nums: SeqNums) -> std::io::Result<()> {
+        let _guard = self.lock.lock().unwrap();
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+
+        let mut file = fs::File::create(&temp_path)?;
+        write!(
+            file,
+            "{:010} : {:010}",
+            seq_nums.next_sender_seq_num, seq_nums.next_target_seq_num
+        )?;
+        file.sync_all()?;
+
+        return fs::rename(&temp_path, &self.path);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("rust-fix-{}.seqnums", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let store = FileSeqNumStore::new(&path);
+
+        assert_eq!(None, store.load().unwrap());
+
+        let seq_nums = SeqNums {
+            next_sender_seq_num: 2,
+            next_target_seq_num: 5,
+        };
+        store.save(seq_nums).unwrap();
+
+        assert_eq!(
+            "0000000002 : 0000000005",
+            fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(Some(seq_nums), FileSeqNumStore::new(&path).load().unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
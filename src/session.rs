@@ -0,0 +1,2339 @@
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    fields::{self, BusinessRejectReason, FixField, SessionRejectReason},
+    format_utc_timestamp_with_precision, is_possible_duplicate, telemetry, trace, Authenticator,
+    Authorization, DedupCache, DedupKey, Dialect, Direction, DoNotSend, FixApplication,
+    FixMessageBuilder, LogonRequest, Logout, LogoutReason, MessageStore, Middleware,
+    MiddlewareAction, PresendValidator, SendingTimePolicy, SeqNumStore, SeqNums, SessionConfig,
+    SessionEvent, SessionId, SessionPermissions, SessionSchedule, SessionWarning, Signer,
+    TagRewriter,
+};
+
+pub const MSG_TYPE_HEARTBEAT: &[u8] = b"0";
+pub const MSG_TYPE_TEST_REQUEST: &[u8] = b"1";
+pub const MSG_TYPE_RESEND_REQUEST: &[u8] = b"2";
+pub const MSG_TYPE_REJECT: &[u8] = b"3";
+pub const MSG_TYPE_SEQUENCE_RESET: &[u8] = b"4";
+pub const MSG_TYPE_LOGOUT: &[u8] = b"5";
+pub const MSG_TYPE_LOGON: &[u8] = b"A";
+
+const MSG_TYPE_BUSINESS_MESSAGE_REJECT: &[u8] = b"j";
+
+// How long to wait for the counterparty to answer our Logon or Logout.
+const LOGON_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Session-level message types, which are handled by the session itself.
+pub fn is_admin_message_type(message_type: &[u8]) -> bool {
+    return [
+        MSG_TYPE_HEARTBEAT,
+        MSG_TYPE_TEST_REQUEST,
+        MSG_TYPE_RESEND_REQUEST,
+        MSG_TYPE_REJECT,
+        MSG_TYPE_SEQUENCE_RESET,
+        MSG_TYPE_LOGOUT,
+        MSG_TYPE_LOGON,
+    ]
+    .contains(&message_type);
+}
+
+/// What the transport should do after `Session::check_schedule`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleAction {
+    Nothing,
+    /// A new session period started and sequence numbers were reset: log on.
+    StartSession,
+    /// The session period is over: log out.
+    EndSession,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    Disconnected,
+    LogonSent,
+    LoggedOn,
+    LogoutSent,
+}
+
+/// Work the transport has to do on behalf of the session.
+#[derive(Clone)]
+pub enum SessionAction {
+    Send(FixMessageBuilder),
+    Disconnect,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionSendError {
+    NotLoggedOn,
+    DoNotSend(DoNotSend),
+    /// The sequence number store failed; the message must not be sent.
+    Store(String),
+    /// Refused by the session's `PresendValidator`.
+    Rejected(String),
+    /// The session is configured as a receive-only drop copy.
+    DropCopy,
+}
+
+// Username (553) and Password (554) an initiator logs on with, kept out of `Debug`.
+#[derive(Clone)]
+struct Credentials {
+    username: String,
+    password: String,
+    // NewPassword (925) to send on the next Logon.
+    new_password: Option<String>,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f
+            .debug_struct("Credentials")
+            .field("username", &self.username)
+            .finish_non_exhaustive();
+    }
+}
+
+/// State of a single FIX session.
+///
+/// The session does no IO: transports feed it inbound messages and timer ticks and
+/// carry out the returned `SessionAction`s.
+#[derive(Debug, Clone)]
+pub struct Session {
+    session_id: SessionId,
+    config: SessionConfig,
+    schedule: Option<SessionSchedule>,
+    session_start: Option<SystemTime>,
+    status: SessionStatus,
+    heartbeat_interval: u64,
+    next_sender_seq_num: u64,
+    next_target_seq_num: u64,
+    resend_requested_up_to: Option<u64>,
+    last_sent: Option<SystemTime>,
+    last_received: Option<SystemTime>,
+    status_changed: Option<SystemTime>,
+    test_request_pending: Option<String>,
+    test_request_counter: u64,
+    // TestReqID (112) of the last TestRequest and when it was sent; unlike
+    // `test_request_pending` only the echoing Heartbeat clears it.
+    rtt_probe: Option<(String, SystemTime)>,
+    last_rtt: Option<Duration>,
+    dedup_cache: DedupCache,
+    seq_num_store: Option<Arc<dyn SeqNumStore>>,
+    message_store: Option<Arc<dyn MessageStore>>,
+    persisted_seq_nums: Option<SeqNums>,
+    store_error: Option<String>,
+    signer: Option<Arc<dyn Signer>>,
+    dialect: Option<Arc<Dialect>>,
+    tag_rewriter: Option<Arc<TagRewriter>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    presend_validator: Option<Arc<dyn PresendValidator>>,
+    authenticator: Option<Arc<dyn Authenticator>>,
+    remote_address: Option<SocketAddr>,
+    permissions: SessionPermissions,
+    // Rejects sent and received within `reject_window`, oldest first.
+    rejects: VecDeque<(SystemTime, Direction)>,
+    last_logout: Option<Logout>,
+    logon_session_status: Option<fields::SessionStatus>,
+    credentials: Option<Credentials>,
+    // Sent a ResetSeqNumFlag (141=Y) Logon while logged on and waiting for the reply.
+    reset_pending: bool,
+}
+
+impl Session {
+    pub fn new(session_id: SessionId) -> Self {
+        let config = SessionConfig::new(&session_id);
+        return Self::from_config(config);
+    }
+
+    pub fn from_config(config: SessionConfig) -> Self {
+        return Self {
+            session_id: config.get_session_id(),
+            schedule: config.get_schedule(),
+            session_start: None,
+            status: SessionStatus::Disconnected,
+            heartbeat_interval: config.heartbeat_interval,
+            dedup_cache: DedupCache::new(config.dedup_cache_size),
+            config,
+            next_sender_seq_num: 1,
+            next_target_seq_num: 1,
+            resend_requested_up_to: None,
+            last_sent: None,
+            last_received: None,
+            status_changed: None,
+            test_request_pending: None,
+            test_request_counter: 0,
+            rtt_probe: None,
+            last_rtt: None,
+            seq_num_store: None,
+            message_store: None,
+            persisted_seq_nums: None,
+            store_error: None,
+            signer: None,
+            dialect: None,
+            tag_rewriter: None,
+            middlewares: vec![],
+            presend_validator: None,
+            authenticator: None,
+            remote_address: None,
+            permissions: SessionPermissions::default(),
+            rejects: VecDeque::new(),
+            last_logout: None,
+            logon_session_status: None,
+            credentials: None,
+            reset_pending: false,
+        };
+    }
+
+    pub fn get_session_id(&self) -> &SessionId {
+        return &self.session_id;
+    }
+
+    pub fn get_config(&self) -> &SessionConfig {
+        return &self.config;
+    }
+
+    pub fn get_status(&self) -> SessionStatus {
+        return self.status;
+    }
+
+    pub fn is_logged_on(&self) -> bool {
+        return self.status == SessionStatus::LoggedOn;
+    }
+
+    /// Round trip of the last TestRequest answered by a Heartbeat echoing its TestReqID (112).
+    pub fn get_last_rtt(&self) -> Option<Duration> {
+        return self.last_rtt;
+    }
+
+    /// HeartBtInt in seconds; an acceptor takes it from the counterparty's Logon.
+    pub fn get_heartbeat_interval(&self) -> u64 {
+        return self.heartbeat_interval;
+    }
+
+    pub fn get_next_sender_seq_num(&self) -> u64 {
+        return self.next_sender_seq_num;
+    }
+
+    pub fn get_next_target_seq_num(&self) -> u64 {
+        return self.next_target_seq_num;
+    }
+
+    pub fn set_next_sender_seq_num(&mut self, seq_num: u64) {
+        self.next_sender_seq_num = seq_num;
+        self.persist_seq_nums();
+    }
+
+    pub fn set_next_target_seq_num(&mut self, seq_num: u64) {
+        self.next_target_seq_num = seq_num;
+        self.persist_seq_nums();
+    }
+
+    /// Restores sequence numbers from the store and saves them there after every change.
+    pub fn with_seq_num_store(&mut self, store: Arc<dyn SeqNumStore>) -> std::io::Result<()> {
+        self.seq_num_store = Some(store);
+        return self.reload_seq_nums();
+    }
+
+    /// Loads sequence numbers from the store again, e.g. after taking over from another instance.
+    pub fn reload_seq_nums(&mut self) -> std::io::Result<()> {
+        let Some(store) = &self.seq_num_store else {
+            return Ok(());
+        };
+
+        if let Some(seq_nums) = store.load()? {
+            self.next_sender_seq_num = seq_nums.next_sender_seq_num;
+            self.next_target_seq_num = seq_nums.next_target_seq_num;
+            self.persisted_seq_nums = Some(seq_nums);
+        } else {
+            self.persisted_seq_nums = None;
+        }
+
+        self.store_error = None;
+        self.persist_seq_nums();
+
+        return match self.store_error.take() {
+            Some(err) => Err(std::io::Error::other(err)),
+            None => Ok(()),
+        };
+    }
+
+    /// Keeps every sent message so ResendRequests are answered with the original
+    /// application messages; without a store they are gap filled.
+    pub fn with_message_store(&mut self, store: Arc<dyn MessageStore>) {
+        self.message_store = Some(store);
+    }
+
+    /// Signs every outbound message, resends included, with SignatureLength (93) and Signature (89).
+    pub fn with_signer(&mut self, signer: Arc<dyn Signer>) {
+        self.signer = Some(signer);
+    }
+
+    /// Counterparty quirks; outbound messages are reordered to its field order.
+    pub fn with_dialect(&mut self, dialect: Arc<Dialect>) {
+        self.dialect = Some(dialect);
+    }
+
+    pub fn get_dialect(&self) -> Option<&Dialect> {
+        return self.dialect.as_deref();
+    }
+
+    /// Renumbers tags and maps values of inbound messages before they're processed, and
+    /// of outbound ones before their header is stamped.
+    pub fn with_tag_rewriter(&mut self, tag_rewriter: Arc<TagRewriter>) {
+        self.tag_rewriter = Some(tag_rewriter);
+    }
+
+    /// Appends `middleware` to the chain application messages go through.
+    pub fn with_middleware(&mut self, middleware: Arc<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+
+    /// Checks every application message right before it's sent, after `to_app` and
+    /// the middlewares.
+    pub fn with_presend_validator(&mut self, validator: Arc<dyn PresendValidator>) {
+        self.presend_validator = Some(validator);
+    }
+
+    /// Checks inbound Logons when the session is the acceptor.
+    pub fn with_authenticator(&mut self, authenticator: Arc<dyn Authenticator>) {
+        self.authenticator = Some(authenticator);
+    }
+
+    /// Set by the transport when a connection is accepted; passed to the `Authenticator`.
+    pub fn set_remote_address(&mut self, remote_address: Option<SocketAddr>) {
+        self.remote_address = remote_address;
+    }
+
+    pub fn get_remote_address(&self) -> Option<SocketAddr> {
+        return self.remote_address;
+    }
+
+    /// Granted by the `Authenticator` for the current connection.
+    pub fn get_permissions(&self) -> SessionPermissions {
+        return self.permissions;
+    }
+
+    /// The last failure to save sequence numbers or messages. The session disconnects when it happens.
+    pub fn get_store_error(&self) -> Option<&str> {
+        return self.store_error.as_deref();
+    }
+
+    pub fn with_schedule(&mut self, schedule: SessionSchedule) {
+        self.schedule = Some(schedule);
+    }
+
+    pub fn get_schedule(&self) -> Option<&SessionSchedule> {
+        return self.schedule.as_ref();
+    }
+
+    /// Tracks session period boundaries, resetting sequence numbers when a new period starts.
+    ///
+    /// Sessions without a schedule are always in session time.
+    pub fn check_schedule(&mut self, now: SystemTime) -> ScheduleAction {
+        let session_start = match &self.schedule {
+            Some(schedule) => schedule.get_session_start(now),
+            None => Some(self.session_start.unwrap_or(now)),
+        };
+
+        if session_start == self.session_start {
+            return ScheduleAction::Nothing;
+        }
+
+        let was_in_session = self.session_start.is_some();
+        self.session_start = session_start;
+
+        if session_start.is_none() {
+            return ScheduleAction::EndSession;
+        }
+
+        if was_in_session || self.schedule.is_some() {
+            self.reset_seq_nums();
+        }
+
+        return ScheduleAction::StartSession;
+    }
+
+    pub fn reset_seq_nums(&mut self) {
+        self.next_sender_seq_num = 1;
+        self.next_target_seq_num = 1;
+        self.dedup_cache.clear();
+        self.persist_seq_nums();
+        self.reset_message_store();
+    }
+
+    /// Builds the initiator's Logon and waits for the counterparty to confirm it.
+    pub fn logon(&mut self, now: SystemTime) -> FixMessageBuilder {
+        if self.config.reset_on_logon {
+            self.reset_seq_nums();
+        }
+
+        let mut message = self.build_logon();
+        self.prepare_outbound(&mut message, now);
+        self.set_status(SessionStatus::LogonSent, now);
+
+        return message;
+    }
+
+    /// Builds a Logon with ResetSeqNumFlag (141=Y) restarting both sides' sequence numbers
+    /// from 1 without dropping the connection.
+    pub fn reset_logon(&mut self, now: SystemTime) -> FixMessageBuilder {
+        self.reset_pending = true;
+
+        return self.build_reset_logon(now);
+    }
+
+    /// Builds a Logout and waits for the counterparty to confirm it; `on_timer` drops
+    /// the connection if that takes longer than `logout_timeout`.
+    pub fn logout(&mut self, text: Option<&str>, now: SystemTime) -> FixMessageBuilder {
+        let mut logout = Logout::new(LogoutReason::Normal);
+        logout.text = text.map(|text| text.to_string());
+
+        return self.logout_with_reason(&logout, now);
+    }
+
+    /// Same as `logout`, telling the counterparty why, e.g. `LogoutReason::PasswordExpired`.
+    pub fn logout_with_reason(&mut self, logout: &Logout, now: SystemTime) -> FixMessageBuilder {
+        let message = self.build_logout(logout, now);
+        self.set_status(SessionStatus::LogoutSent, now);
+
+        return message;
+    }
+
+    /// The last Logout the counterparty sent, to tell why a session ended.
+    pub fn get_last_logout(&self) -> Option<&Logout> {
+        return self.last_logout.as_ref();
+    }
+
+    /// SessionStatus (1409) to send on our Logons, e.g. SessionPasswordDueToExpire
+    /// from an acceptor. Left out before FIXT.
+    pub fn with_logon_session_status(&mut self, session_status: Option<fields::SessionStatus>) {
+        self.logon_session_status = session_status;
+    }
+
+    /// `time` as a UTCTimestamp at the session's `timestamp_precision`, e.g. for the
+    /// TransactTime (60) of an order.
+    pub fn format_timestamp(&self, time: SystemTime) -> String {
+        return format_utc_timestamp_with_precision(time, self.config.timestamp_precision);
+    }
+
+    /// Username (553) and Password (554) to send on our Logons.
+    pub fn with_credentials(&mut self, username: &str, password: &str) {
+        self.credentials = Some(Credentials {
+            username: username.to_string(),
+            password: password.to_string(),
+            new_password: None,
+        });
+    }
+
+    /// Sends `new_password` as NewPassword (925) on the next Logon, which takes over as
+    /// the password once the counterparty accepts it. Needs `with_credentials`; a
+    /// session that is logged on has to log out and back on for the change.
+    pub fn change_password(&mut self, new_password: &str) {
+        if let Some(credentials) = &mut self.credentials {
+            credentials.new_password = Some(new_password.to_string());
+        }
+    }
+
+    /// Must be called by the transport once the connection is gone.
+    pub fn disconnected(&mut self, app: &dyn FixApplication, now: SystemTime) {
+        let was_logged_on = matches!(
+            self.status,
+            SessionStatus::LoggedOn | SessionStatus::LogoutSent
+        );
+
+        self.set_status(SessionStatus::Disconnected, now);
+        self.resend_requested_up_to = None;
+        self.test_request_pending = None;
+        self.rtt_probe = None;
+        self.reset_pending = false;
+        self.remote_address = None;
+        self.permissions = SessionPermissions::default();
+
+        if was_logged_on {
+            app.on_logout(&self.session_id);
+        }
+    }
+
+    /// Passes an application message through `to_app` and stamps its header. The typed
+    /// messages' `to_message` build a body to pass in.
+    pub fn send_app(
+        &mut self,
+        mut message: FixMessageBuilder,
+        app: &dyn FixApplication,
+        now: SystemTime,
+    ) -> Result<FixMessageBuilder, SessionSendError> {
+        let _span = trace::enter_outbound(&self.session_id, &message);
+
+        if self.config.drop_copy {
+            return Err(SessionSendError::DropCopy);
+        }
+
+        if !self.is_logged_on() {
+            return Err(SessionSendError::NotLoggedOn);
+        }
+
+        app.to_app(&self.session_id, &mut message)
+            .map_err(SessionSendError::DoNotSend)?;
+
+        for middleware in &self.middlewares {
+            if let MiddlewareAction::Drop(reason) =
+                middleware.on_outbound(&self.session_id, &mut message)
+            {
+                return Err(SessionSendError::DoNotSend(DoNotSend(reason)));
+            }
+        }
+
+        if let Some(validator) = &self.presend_validator {
+            if let Err(reason) = validator.validate(&self.session_id, &mut message) {
+                app.on_send_rejected(&self.session_id, &message, &reason);
+                return Err(SessionSendError::Rejected(reason));
+            }
+        }
+        self.prepare_outbound(&mut message, now);
+
+        if let Some(err) = &self.store_error {
+            return Err(SessionSendError::Store(err.clone()));
+        }
+
+        return Ok(message);
+    }
+
+    /// Sets comp ids, MsgSeqNum (34) and SendingTime (52) and consumes a sequence number.
+    pub fn prepare_outbound(&mut self, message: &mut FixMessageBuilder, now: SystemTime) {
+        let seq_num = self.next_sender_seq_num;
+        self.next_sender_seq_num += 1;
+        self.persist_seq_nums();
+
+        if let Some(tag_rewriter) = &self.tag_rewriter {
+            tag_rewriter.rewrite_outbound(message);
+        }
+
+        self.stamp_header(message, seq_num, now);
+        self.record_reject(message, Direction::Outbound, now);
+        trace::sent(&self.session_id, message, seq_num);
+        telemetry::sent(&self.session_id, message);
+
+        if let Some(store) = &self.message_store {
+            if let Err(err) = store.save(seq_num, &message.as_bytes()) {
+                self.store_error = Some(err.to_string());
+            }
+        }
+    }
+
+    pub fn on_inbound(
+        &mut self,
+        message: &FixMessageBuilder,
+        app: &dyn FixApplication,
+        now: SystemTime,
+    ) -> Vec<SessionAction> {
+        let _span = trace::enter_inbound(&self.session_id, message);
+        telemetry::received(&self.session_id, message);
+
+        let rewritten;
+        let message = match &self.tag_rewriter {
+            Some(tag_rewriter) => {
+                let mut message = message.clone();
+                tag_rewriter.rewrite_inbound(&mut message);
+                rewritten = message;
+                &rewritten
+            }
+            None => message,
+        };
+
+        let mut actions = self.process_inbound(message, app, now);
+        self.persist_seq_nums();
+        self.record_reject(message, Direction::Inbound, now);
+
+        if let Some(text) = self.check_reject_storm(&actions) {
+            self.push_logout(&mut actions, LogoutReason::RejectStorm, &text, now);
+        }
+
+        if self.store_error.is_some() {
+            actions.push(SessionAction::Disconnect);
+        }
+
+        return actions;
+    }
+
+    fn process_inbound(
+        &mut self,
+        message: &FixMessageBuilder,
+        app: &dyn FixApplication,
+        now: SystemTime,
+    ) -> Vec<SessionAction> {
+        self.last_received = Some(now);
+        self.record_rtt(message, now);
+        self.test_request_pending = None;
+
+        let message_type = message.get_message_type().as_slice();
+        let mut actions = vec![];
+
+        if self.status == SessionStatus::Disconnected && message_type != MSG_TYPE_LOGON {
+            actions.push(SessionAction::Disconnect);
+            return actions;
+        }
+
+        let Ok(Some(seq_num)) = message.get_msg_seq_num() else {
+            self.push_logout(
+                &mut actions,
+                LogoutReason::Other,
+                "MsgSeqNum (34) is missing or invalid",
+                now,
+            );
+            return actions;
+        };
+
+        // Nothing from a Logon that fails authentication, its Password included, reaches
+        // the application or resets the session.
+        if self.status == SessionStatus::Disconnected {
+            if let Authorization::Reject(reason) = self.authenticate(message) {
+                let logout_reason = LogoutReason::from_text(&reason);
+                self.push_logout(&mut actions, logout_reason, &reason, now);
+                return actions;
+            }
+        }
+
+        if is_admin_message_type(message_type) {
+            app.on_admin_message(&self.session_id, message);
+        }
+
+        if message_type == MSG_TYPE_LOGON && message.get(141) == Some(b"Y".as_slice()) {
+            self.next_target_seq_num = 1;
+
+            if self.status == SessionStatus::Disconnected {
+                self.next_sender_seq_num = 1;
+                self.reset_message_store();
+            }
+        }
+
+        if message_type == MSG_TYPE_SEQUENCE_RESET && message.get(123) != Some(b"Y".as_slice()) {
+            if let Ok(Some(new_seq_num)) = message.get_as::<u64>(36) {
+                self.next_target_seq_num = new_seq_num;
+            }
+
+            return actions;
+        }
+
+        if seq_num < self.next_target_seq_num {
+            if !message.is_poss_dup() && !self.config.drop_copy {
+                let text = format!(
+                    "MsgSeqNum too low, expecting {} but received {}",
+                    self.next_target_seq_num, seq_num
+                );
+                self.push_logout(&mut actions, LogoutReason::SeqNumTooLow, &text, now);
+            }
+
+            return actions;
+        }
+
+        let is_gap = seq_num > self.next_target_seq_num;
+
+        if !is_gap {
+            self.next_target_seq_num += 1;
+
+            if let Some(up_to) = self.resend_requested_up_to {
+                if self.next_target_seq_num > up_to {
+                    self.resend_requested_up_to = None;
+                }
+            }
+
+            if !self.check_sending_time(message, seq_num, app, &mut actions, now) {
+                return actions;
+            }
+        }
+
+        match message_type {
+            MSG_TYPE_LOGON => self.handle_logon(message, app, &mut actions, now),
+            MSG_TYPE_LOGOUT => self.handle_logout(message, app, &mut actions, now),
+            _ if is_gap => {}
+            MSG_TYPE_HEARTBEAT | MSG_TYPE_REJECT => {}
+            MSG_TYPE_TEST_REQUEST => {
+                let mut heartbeat = FixMessageBuilder::new(self.session_id.get_begin_string(), "0");
+
+                if let Some(test_request_id) = message.get(112) {
+                    heartbeat.with_value(112, test_request_id);
+                }
+
+                self.prepare_outbound(&mut heartbeat, now);
+                actions.push(SessionAction::Send(heartbeat));
+            }
+            MSG_TYPE_RESEND_REQUEST => {
+                self.handle_resend_request(message, seq_num, &mut actions, now)
+            }
+            MSG_TYPE_SEQUENCE_RESET => {
+                if let Ok(Some(new_seq_num)) = message.get_as::<u64>(36) {
+                    if new_seq_num > self.next_target_seq_num {
+                        self.next_target_seq_num = new_seq_num;
+                    }
+                }
+            }
+            _ if self.permissions.read_only => {
+                self.push_business_reject(message, seq_num, &mut actions, now);
+            }
+            _ => {
+                if self.is_logged_on() {
+                    self.deliver_app_message(message, app);
+                }
+            }
+        }
+
+        let already_requested = self
+            .resend_requested_up_to
+            .map(|up_to| up_to >= seq_num)
+            .unwrap_or(false);
+
+        // A Logon carrying NextExpectedMsgSeqNum (789) means the counterparty resends
+        // from the number we sent it, so asking again would only duplicate the resends.
+        let resends_unasked = self.config.enable_next_expected_msg_seq_num
+            && message_type == MSG_TYPE_LOGON
+            && message.get(789).is_some();
+
+        if is_gap && resends_unasked {
+            self.resend_requested_up_to = Some(seq_num);
+        } else if is_gap && !already_requested && self.status != SessionStatus::Disconnected {
+            let mut resend_request =
+                FixMessageBuilder::new(self.session_id.get_begin_string(), "2");
+            resend_request.with_value(7, self.next_target_seq_num);
+            resend_request.with_value(16, "0");
+
+            self.prepare_outbound(&mut resend_request, now);
+            self.resend_requested_up_to = Some(seq_num);
+            telemetry::resend_requested(&self.session_id, "outbound");
+            actions.push(SessionAction::Send(resend_request));
+        }
+
+        return actions;
+    }
+
+    /// Sends heartbeats and test requests and drops dead connections.
+    pub fn on_timer(&mut self, now: SystemTime) -> Vec<SessionAction> {
+        let mut actions = vec![];
+
+        if self.store_error.is_some() && self.status != SessionStatus::Disconnected {
+            actions.push(SessionAction::Disconnect);
+            return actions;
+        }
+
+        match self.status {
+            SessionStatus::Disconnected => return actions,
+            SessionStatus::LogonSent => {
+                if elapsed(self.status_changed, now) >= LOGON_TIMEOUT {
+                    actions.push(SessionAction::Disconnect);
+                }
+                return actions;
+            }
+            SessionStatus::LogoutSent => {
+                if elapsed(self.status_changed, now)
+                    >= Duration::from_secs(self.config.logout_timeout)
+                {
+                    actions.push(SessionAction::Disconnect);
+                }
+                return actions;
+            }
+            SessionStatus::LoggedOn => {}
+        }
+
+        if self.heartbeat_interval == 0 {
+            return actions;
+        }
+
+        let interval = Duration::from_secs(self.heartbeat_interval);
+        let tolerance = interval / 5;
+        let since_received = elapsed(self.last_received, now);
+
+        if since_received >= interval * 2 + tolerance && self.test_request_pending.is_some() {
+            actions.push(SessionAction::Disconnect);
+            return actions;
+        }
+
+        if since_received >= interval + tolerance && self.test_request_pending.is_none() {
+            actions.push(SessionAction::Send(self.test_request(now)));
+        }
+
+        if elapsed(self.last_sent, now) >= interval {
+            let mut heartbeat = FixMessageBuilder::new(self.session_id.get_begin_string(), "0");
+            self.prepare_outbound(&mut heartbeat, now);
+            actions.push(SessionAction::Send(heartbeat));
+        }
+
+        return actions;
+    }
+
+    /// Builds a TestRequest, e.g. to measure the round trip on a busy link where
+    /// the timer never sends one; the answering Heartbeat updates `get_last_rtt`.
+    pub fn test_request(&mut self, now: SystemTime) -> FixMessageBuilder {
+        self.test_request_counter += 1;
+        let test_request_id = format!("TEST{}", self.test_request_counter);
+
+        let mut test_request = FixMessageBuilder::new(self.session_id.get_begin_string(), "1");
+        test_request.with_value(112, &test_request_id);
+
+        self.prepare_outbound(&mut test_request, now);
+        self.test_request_pending = Some(test_request_id.clone());
+        self.rtt_probe = Some((test_request_id, now));
+
+        return test_request;
+    }
+
+    fn record_rtt(&mut self, message: &FixMessageBuilder, now: SystemTime) {
+        let Some((test_request_id, sent)) = &self.rtt_probe else {
+            return;
+        };
+
+        if message.get_message_type().as_slice() != MSG_TYPE_HEARTBEAT
+            || message.get(112) != Some(test_request_id.as_bytes())
+        {
+            return;
+        }
+
+        let rtt = now.duration_since(*sent).unwrap_or_default();
+        self.rtt_probe = None;
+        self.last_rtt = Some(rtt);
+        telemetry::heartbeat_rtt(&self.session_id, rtt);
+    }
+
+    fn reset_message_store(&mut self) {
+        if let Some(store) = &self.message_store {
+            if let Err(err) = store.reset() {
+                self.store_error = Some(err.to_string());
+            }
+        }
+    }
+
+    fn persist_seq_nums(&mut self) {
+        let Some(store) = &self.seq_num_store else {
+            return;
+        };
+
+        let seq_nums = SeqNums {
+            next_sender_seq_num: self.next_sender_seq_num,
+            next_target_seq_num: self.next_target_seq_num,
+        };
+
+        if self.persisted_seq_nums == Some(seq_nums) {
+            return;
+        }
+
+        match store.save(seq_nums) {
+            Ok(()) => {
+                self.persisted_seq_nums = Some(seq_nums);
+                self.store_error = None;
+            }
+            Err(err) => self.store_error = Some(err.to_string()),
+        }
+    }
+
+    // Possible duplicates are handed over once; every delivered message is remembered
+    // so a later PossDup/PossResend copy of it can be recognised.
+    fn deliver_app_message(&mut self, message: &FixMessageBuilder, app: &dyn FixApplication) {
+        let amended;
+        let message = match self.middlewares.is_empty() {
+            true => message,
+            false => {
+                let mut message = message.clone();
+
+                for middleware in &self.middlewares {
+                    if let MiddlewareAction::Drop(_) =
+                        middleware.on_inbound(&self.session_id, &mut message)
+                    {
+                        return;
+                    }
+                }
+
+                amended = message;
+                &amended
+            }
+        };
+
+        let is_new = match DedupKey::from_message(message) {
+            Some(key) => self.dedup_cache.insert(key),
+            None => true,
+        };
+
+        if !is_possible_duplicate(message) {
+            app.on_app_message(&self.session_id, message);
+        } else if is_new {
+            app.on_possible_duplicate(&self.session_id, message);
+        }
+    }
+
+    fn handle_logon(
+        &mut self,
+        message: &FixMessageBuilder,
+        app: &dyn FixApplication,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) {
+        match self.status {
+            SessionStatus::LogonSent => {
+                let Some(resend_from) = self.check_next_expected(message, actions, now) else {
+                    return;
+                };
+
+                if let Some(credentials) = &mut self.credentials {
+                    if let Some(new_password) = credentials.new_password.take() {
+                        credentials.password = new_password;
+                    }
+                }
+
+                self.set_status(SessionStatus::LoggedOn, now);
+                app.on_logon(&self.session_id);
+                self.notify_logon_session_status(message, app);
+
+                if let Some(begin_seq_num) = resend_from {
+                    self.resend(begin_seq_num, self.next_sender_seq_num - 1, actions, now);
+                }
+            }
+            SessionStatus::Disconnected => {
+                if let Ok(Some(heartbeat_interval)) = message.get_as::<u64>(108) {
+                    self.heartbeat_interval = heartbeat_interval;
+                }
+
+                // Checked before our Logon takes a sequence number.
+                let Some(resend_from) = self.check_next_expected(message, actions, now) else {
+                    return;
+                };
+
+                let is_password_changed = match self.change_password_on_logon(message) {
+                    Ok(is_password_changed) => is_password_changed,
+                    Err(text) => {
+                        self.push_logout(actions, LogoutReason::PasswordPolicy, &text, now);
+                        return;
+                    }
+                };
+
+                let mut logon = self.build_logon();
+
+                if message.get(141) == Some(b"Y".as_slice()) {
+                    logon.set_value(141, "Y");
+                }
+
+                if is_password_changed && self.is_fixt() {
+                    logon.remove_value(fields::SessionStatus::TAG);
+                    logon.with_field(fields::SessionStatus::SessionPasswordChanged);
+                }
+
+                self.prepare_outbound(&mut logon, now);
+                actions.push(SessionAction::Send(logon));
+
+                self.set_status(SessionStatus::LoggedOn, now);
+                app.on_logon(&self.session_id);
+                self.notify_logon_session_status(message, app);
+
+                if let Some(begin_seq_num) = resend_from {
+                    self.resend(begin_seq_num, self.next_sender_seq_num - 1, actions, now);
+                }
+            }
+            SessionStatus::LoggedOn if message.get(141) == Some(b"Y".as_slice()) => {
+                // Unless it confirms our own reset, the counterparty is owed a Logon
+                // restarting our sequence numbers too.
+                if std::mem::take(&mut self.reset_pending) {
+                    return;
+                }
+
+                let logon = self.build_reset_logon(now);
+                actions.push(SessionAction::Send(logon));
+            }
+            SessionStatus::LoggedOn | SessionStatus::LogoutSent => {}
+        }
+    }
+
+    fn build_reset_logon(&mut self, now: SystemTime) -> FixMessageBuilder {
+        self.next_sender_seq_num = 1;
+        self.reset_message_store();
+
+        let mut logon = self.build_logon();
+        logon.set_value(141, "Y");
+        self.prepare_outbound(&mut logon, now);
+
+        return logon;
+    }
+
+    // An unknown SessionStatus is ignored rather than failing the Logon.
+    fn notify_logon_session_status(&self, message: &FixMessageBuilder, app: &dyn FixApplication) {
+        let Ok(Some(session_status)) = message.get_field::<fields::SessionStatus>() else {
+            return;
+        };
+
+        let event = SessionEvent::Logon {
+            session_status,
+            text: message.get_string_lossy(58),
+        };
+        app.on_session_event(&self.session_id, &event);
+    }
+
+    // Compares the counterparty's NextExpectedMsgSeqNum (789) with what we've sent.
+    // Returns where to start resending, or `None` after logging out when it expects
+    // a message we never sent. The resend runs through our own Logon, which the
+    // counterparty couldn't accept in sequence and so gets covered by the gap fill.
+    fn check_next_expected(
+        &mut self,
+        message: &FixMessageBuilder,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) -> Option<Option<u64>> {
+        if !self.config.enable_next_expected_msg_seq_num {
+            return Some(None);
+        }
+
+        let Ok(Some(next_expected)) = message.get_as::<u64>(789) else {
+            return Some(None);
+        };
+
+        if next_expected > self.next_sender_seq_num {
+            let text = format!(
+                "NextExpectedMsgSeqNum (789) too high, expecting at most {} but received {}",
+                self.next_sender_seq_num, next_expected
+            );
+            self.push_logout(actions, LogoutReason::NextExpectedSeqNumTooHigh, &text, now);
+            return None;
+        }
+
+        if next_expected < self.next_sender_seq_num {
+            return Some(Some(next_expected));
+        }
+
+        return Some(None);
+    }
+
+    fn handle_logout(
+        &mut self,
+        message: &FixMessageBuilder,
+        app: &dyn FixApplication,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) {
+        self.last_logout = Logout::try_from(message).ok();
+
+        // A Logout answering our Logon refused the new password along with it.
+        if self.status == SessionStatus::LogonSent {
+            if let Some(credentials) = &mut self.credentials {
+                credentials.new_password = None;
+            }
+        }
+
+        if let Some(logout) = &self.last_logout {
+            app.on_session_event(&self.session_id, &SessionEvent::Logout(logout.clone()));
+        }
+
+        if self.status != SessionStatus::LogoutSent {
+            let logout = self.build_logout(&Logout::new(LogoutReason::Normal), now);
+            actions.push(SessionAction::Send(logout));
+        }
+
+        actions.push(SessionAction::Disconnect);
+    }
+
+    // Without a message store everything we sent is skipped with a SequenceReset-GapFill.
+    fn handle_resend_request(
+        &mut self,
+        message: &FixMessageBuilder,
+        seq_num: u64,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) {
+        telemetry::resend_requested(&self.session_id, "inbound");
+
+        let Ok(Some(begin_seq_num)) = message.get_as::<u64>(7) else {
+            return;
+        };
+
+        // Sequence numbers start at 1, a gap fill from 0 would go out with MsgSeqNum 0.
+        if begin_seq_num == 0 {
+            let mut reject = FixMessageBuilder::new(self.session_id.get_begin_string(), "3");
+            reject.with_value(45, seq_num);
+            reject.with_value(371, 7);
+            reject.with_value(372, MSG_TYPE_RESEND_REQUEST);
+            reject.with_field(SessionRejectReason::ValueIsIncorrect);
+            reject.with_value(58, "BeginSeqNo must be at least 1");
+
+            self.prepare_outbound(&mut reject, now);
+            actions.push(SessionAction::Send(reject));
+            return;
+        }
+
+        if begin_seq_num >= self.next_sender_seq_num {
+            return;
+        }
+
+        let last_sent_seq_num = self.next_sender_seq_num - 1;
+        let end_seq_num = match message.get_as::<u64>(16) {
+            Ok(Some(end_seq_num)) if end_seq_num != 0 => end_seq_num.min(last_sent_seq_num),
+            _ => last_sent_seq_num,
+        };
+
+        self.resend(begin_seq_num, end_seq_num, actions, now);
+    }
+
+    fn resend(
+        &mut self,
+        begin_seq_num: u64,
+        end_seq_num: u64,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) {
+        let stored = match &self.message_store {
+            Some(store) => match store.get_range(begin_seq_num, end_seq_num) {
+                Ok(stored) => stored,
+                Err(err) => {
+                    self.store_error = Some(err.to_string());
+                    vec![]
+                }
+            },
+            None => vec![],
+        };
+
+        // Session-level messages are never resent, they become part of a gap fill.
+        let mut next_seq_num = begin_seq_num;
+
+        for (seq_num, bytes) in stored {
+            let Ok(original) = FixMessageBuilder::from_bytes(&bytes, false) else {
+                continue;
+            };
+
+            if is_admin_message_type(original.get_message_type()) {
+                continue;
+            }
+
+            if seq_num > next_seq_num {
+                let gap_fill = self.build_gap_fill(next_seq_num, seq_num, now);
+                actions.push(SessionAction::Send(gap_fill));
+            }
+
+            let resent = self.build_resend(original, seq_num, now);
+            actions.push(SessionAction::Send(resent));
+            next_seq_num = seq_num + 1;
+        }
+
+        if next_seq_num <= end_seq_num {
+            let gap_fill = self.build_gap_fill(next_seq_num, end_seq_num + 1, now);
+            actions.push(SessionAction::Send(gap_fill));
+        }
+    }
+
+    fn build_gap_fill(
+        &mut self,
+        seq_num: u64,
+        new_seq_num: u64,
+        now: SystemTime,
+    ) -> FixMessageBuilder {
+        let mut gap_fill = FixMessageBuilder::new(self.session_id.get_begin_string(), "4");
+        gap_fill.with_value(43, "Y");
+        gap_fill.with_value(123, "Y");
+        gap_fill.with_value(36, new_seq_num);
+
+        self.stamp_header(&mut gap_fill, seq_num, now);
+        return gap_fill;
+    }
+
+    fn build_resend(
+        &mut self,
+        mut message: FixMessageBuilder,
+        seq_num: u64,
+        now: SystemTime,
+    ) -> FixMessageBuilder {
+        let orig_sending_time = message.get(52).map(|value| value.to_vec());
+
+        // The stored signature covered the original header.
+        for tag in [43, 97, 122, 93, 89] {
+            message.remove_value(tag);
+        }
+
+        let header_len = self.stamp_header(&mut message, seq_num, now);
+        message.insert_value(header_len, 43, "Y");
+
+        if let Some(orig_sending_time) = orig_sending_time {
+            message.insert_value(header_len + 1, 122, orig_sending_time.as_slice());
+        }
+
+        return message;
+    }
+
+    // Returns false when the message was rejected and must not be processed further.
+    fn check_sending_time(
+        &mut self,
+        message: &FixMessageBuilder,
+        seq_num: u64,
+        app: &dyn FixApplication,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) -> bool {
+        if self.config.sending_time_policy == SendingTimePolicy::Ignore || self.config.drop_copy {
+            return true;
+        }
+
+        // A missing or malformed SendingTime is left to validation.
+        let Ok(Some(sending_time)) = message.get_sending_time() else {
+            return true;
+        };
+
+        let skew = match now.duration_since(sending_time) {
+            Ok(skew) => skew,
+            Err(err) => err.duration(),
+        };
+
+        if skew <= Duration::from_secs(self.config.max_latency) {
+            return true;
+        }
+
+        let message_type = message.get_message_type().as_slice();
+
+        if self.config.sending_time_policy == SendingTimePolicy::Warn
+            || [MSG_TYPE_LOGON, MSG_TYPE_LOGOUT].contains(&message_type)
+        {
+            app.on_session_warning(
+                &self.session_id,
+                &SessionWarning::SendingTimeSkew { seq_num, skew },
+            );
+            return true;
+        }
+
+        let mut reject = FixMessageBuilder::new(self.session_id.get_begin_string(), "3");
+        reject.with_value(45, seq_num);
+        reject.with_value(372, message.get_message_type_as_string());
+        reject.with_field(SessionRejectReason::SendingTimeAccuracyProblem);
+        reject.with_value(58, format!("SendingTime is off by {} ms", skew.as_millis()));
+
+        self.prepare_outbound(&mut reject, now);
+        actions.push(SessionAction::Send(reject));
+
+        return false;
+    }
+
+    fn authenticate(&mut self, message: &FixMessageBuilder) -> Authorization {
+        let Some(authenticator) = &self.authenticator else {
+            return Authorization::Accept(SessionPermissions::default());
+        };
+
+        let authorization = authenticator.authenticate(&LogonRequest {
+            session_id: &self.session_id,
+            logon: message,
+            remote_address: self.remote_address,
+        });
+
+        if let Authorization::Accept(permissions) = authorization {
+            self.permissions = permissions;
+        }
+
+        return authorization;
+    }
+
+    // Passes a NewPassword (925) to the authenticator; returns whether the password
+    // was changed, or the Text of the Logout refusing it.
+    fn change_password_on_logon(&self, message: &FixMessageBuilder) -> Result<bool, String> {
+        let (Some(authenticator), Some(new_password)) = (&self.authenticator, message.get(925))
+        else {
+            return Ok(false);
+        };
+
+        let new_password = String::from_utf8_lossy(new_password);
+        let request = LogonRequest {
+            session_id: &self.session_id,
+            logon: message,
+            remote_address: self.remote_address,
+        };
+        authenticator.change_password(&request, &new_password)?;
+
+        return Ok(true);
+    }
+
+    fn push_business_reject(
+        &mut self,
+        message: &FixMessageBuilder,
+        seq_num: u64,
+        actions: &mut Vec<SessionAction>,
+        now: SystemTime,
+    ) {
+        let mut reject = FixMessageBuilder::new(self.session_id.get_begin_string(), "j");
+        reject.with_value(45, seq_num);
+        reject.with_value(372, message.get_message_type_as_string());
+        reject.with_field(BusinessRejectReason::NotAuthorized);
+        reject.with_value(58, "Session is read-only");
+
+        self.prepare_outbound(&mut reject, now);
+        actions.push(SessionAction::Send(reject));
+    }
+
+    fn record_reject(
+        &mut self,
+        message: &FixMessageBuilder,
+        direction: Direction,
+        now: SystemTime,
+    ) {
+        if self.config.max_rejects.is_none() {
+            return;
+        }
+
+        let message_type = message.get_message_type().as_slice();
+
+        if message_type == MSG_TYPE_REJECT || message_type == MSG_TYPE_BUSINESS_MESSAGE_REJECT {
+            self.rejects.push_back((now, direction));
+        }
+
+        let window = Duration::from_secs(self.config.reject_window);
+
+        while let Some((at, _)) = self.rejects.front() {
+            if elapsed(Some(*at), now) < window {
+                break;
+            }
+
+            self.rejects.pop_front();
+        }
+    }
+
+    // Returns the Logout text when rejects going both ways reached `max_rejects`.
+    fn check_reject_storm(&mut self, actions: &[SessionAction]) -> Option<String> {
+        let max_rejects = self.config.max_rejects?;
+
+        if self.status != SessionStatus::LoggedOn
+            || actions
+                .iter()
+                .any(|action| matches!(action, SessionAction::Disconnect))
+        {
+            return None;
+        }
+
+        let received = self
+            .rejects
+            .iter()
+            .filter(|(_, direction)| *direction == Direction::Inbound)
+            .count();
+        let sent = self.rejects.len() - received;
+
+        if received == 0 || sent == 0 || self.rejects.len() < max_rejects as usize {
+            return None;
+        }
+
+        self.rejects.clear();
+
+        return Some(format!(
+            "Reject storm: {} rejects received and {} sent within {}s",
+            received, sent, self.config.reject_window
+        ));
+    }
+
+    fn push_logout(
+        &mut self,
+        actions: &mut Vec<SessionAction>,
+        reason: LogoutReason,
+        text: &str,
+        now: SystemTime,
+    ) {
+        let mut logout = Logout::new(reason);
+        logout.with_text(text);
+
+        actions.push(SessionAction::Send(self.build_logout(&logout, now)));
+        actions.push(SessionAction::Disconnect);
+    }
+
+    fn build_logon(&self) -> FixMessageBuilder {
+        let mut logon = FixMessageBuilder::new(self.session_id.get_begin_string(), "A");
+        logon.with_value(98, "0");
+        logon.with_value(108, self.heartbeat_interval);
+
+        if self.config.reset_on_logon {
+            logon.with_value(141, "Y");
+        }
+
+        if self.config.enable_next_expected_msg_seq_num {
+            logon.with_value(789, self.next_target_seq_num);
+        }
+
+        if let Some(credentials) = &self.credentials {
+            logon.with_value(553, credentials.username.as_str());
+            logon.with_value(554, credentials.password.as_str());
+
+            if let Some(new_password) = &credentials.new_password {
+                logon.with_value(925, new_password.as_str());
+            }
+        }
+
+        if let Some(session_status) = self.logon_session_status.filter(|_| self.is_fixt()) {
+            logon.with_field(session_status);
+        }
+
+        return logon;
+    }
+
+    // SessionStatus (1409) is only defined from FIXT on.
+    fn is_fixt(&self) -> bool {
+        return self
+            .session_id
+            .get_begin_string()
+            .as_bytes()
+            .starts_with(b"FIXT");
+    }
+
+    fn build_logout(&mut self, logout: &Logout, now: SystemTime) -> FixMessageBuilder {
+        let mut message = logout.to_message(self.session_id.get_begin_string());
+
+        self.prepare_outbound(&mut message, now);
+        return message;
+    }
+
+    // Returns how many header fields were put at the front of the body.
+    fn stamp_header(
+        &mut self,
+        message: &mut FixMessageBuilder,
+        seq_num: u64,
+        now: SystemTime,
+    ) -> usize {
+        for tag in [49, 56, 50, 57, 34, 52] {
+            message.remove_value(tag);
+        }
+
+        let mut header = vec![
+            (49, self.session_id.get_sender_comp_id().to_string()),
+            (56, self.session_id.get_target_comp_id().to_string()),
+        ];
+
+        if let Some(sender_sub_id) = self.session_id.get_sender_sub_id() {
+            header.push((50, sender_sub_id.to_string()));
+        }
+
+        if let Some(target_sub_id) = self.session_id.get_target_sub_id() {
+            header.push((57, target_sub_id.to_string()));
+        }
+
+        header.push((34, seq_num.to_string()));
+        header.push((52, self.format_timestamp(now)));
+
+        for (index, (tag, value)) in header.iter().enumerate() {
+            message.insert_value(index, *tag, value);
+        }
+
+        if let Some(dialect) = &self.dialect {
+            dialect.apply_field_order(message);
+        }
+
+        if let Some(signer) = &self.signer {
+            message.with_signer(signer.clone());
+        }
+
+        self.last_sent = Some(now);
+        return header.len();
+    }
+
+    fn set_status(&mut self, status: SessionStatus, now: SystemTime) {
+        trace::status_changed(&self.session_id, self.status, status);
+        self.status = status;
+        self.status_changed = Some(now);
+    }
+}
+
+fn elapsed(since: Option<SystemTime>, now: SystemTime) -> Duration {
+    return match since {
+        Some(since) => now.duration_since(since).unwrap_or_default(),
+        None => Duration::MAX,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NoopApplication, RequiredTagsValidator, TimeOfDay};
+    use std::{
+        sync::Mutex,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    #[derive(Default)]
+    struct RecordingApplication {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl FixApplication for RecordingApplication {
+        fn on_logon(&self, session_id: &SessionId) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("logon {}", session_id));
+        }
+
+        fn on_logout(&self, session_id: &SessionId) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("logout {}", session_id));
+        }
+
+        fn on_app_message(&self, _: &SessionId, message: &FixMessageBuilder) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("app {}", message.get_message_type_as_string()));
+        }
+
+        fn on_send_rejected(&self, _: &SessionId, message: &FixMessageBuilder, reason: &str) {
+            self.events.lock().unwrap().push(format!(
+                "rejected {}: {}",
+                message.get_message_type_as_string(),
+                reason
+            ));
+        }
+
+        fn on_possible_duplicate(&self, _: &SessionId, message: &FixMessageBuilder) {
+            self.events.lock().unwrap().push(format!(
+                "duplicate {}",
+                message.get_message_type_as_string()
+            ));
+        }
+
+        fn on_session_warning(&self, _: &SessionId, warning: &SessionWarning) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("warning {:?}", warning));
+        }
+
+        fn on_session_event(&self, _: &SessionId, event: &SessionEvent) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("event {:?}", event));
+        }
+    }
+
+    fn now() -> SystemTime {
+        return UNIX_EPOCH + Duration::from_secs(1_537_467_899);
+    }
+
+    fn sent(actions: &[SessionAction]) -> Vec<FixMessageBuilder> {
+        return actions
+            .iter()
+            .filter_map(|action| match action {
+                SessionAction::Send(message) => Some(message.clone()),
+                SessionAction::Disconnect => None,
+            })
+            .collect();
+    }
+
+    fn is_disconnect(actions: &[SessionAction]) -> bool {
+        return actions
+            .iter()
+            .any(|action| matches!(action, SessionAction::Disconnect));
+    }
+
+    fn logged_on_pair(app: &dyn FixApplication) -> (Session, Session) {
+        let mut initiator = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        let mut acceptor = Session::new(SessionId::new("FIX.4.4", "SELL", "BUY"));
+
+        let logon = initiator.logon(now());
+        let replies = sent(&acceptor.on_inbound(&logon, app, now()));
+        initiator.on_inbound(&replies[0], app, now());
+
+        return (initiator, acceptor);
+    }
+
+    #[test]
+    fn test_logon_handshake() {
+        let app = RecordingApplication::default();
+        let (initiator, acceptor) = logged_on_pair(&app);
+
+        assert!(initiator.is_logged_on());
+        assert!(acceptor.is_logged_on());
+        assert_eq!(2, initiator.get_next_sender_seq_num());
+        assert_eq!(2, initiator.get_next_target_seq_num());
+        assert_eq!(
+            vec!["logon FIX.4.4:SELL->BUY", "logon FIX.4.4:BUY->SELL"],
+            *app.events.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_app_message_and_to_app() {
+        let app = RecordingApplication::default();
+        let (mut initiator, mut acceptor) = logged_on_pair(&app);
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        let order = initiator.send_app(order, &app, now()).unwrap();
+
+        assert_eq!(
+            "8=FIX.4.4|9=60|35=D|49=BUY|56=SELL|34=2|52=20180920-18:24:59.000|11=ORDER1|10=087|",
+            order.to_string()
+        );
+
+        acceptor.on_inbound(&order, &app, now());
+        assert_eq!("app D", app.events.lock().unwrap().last().unwrap());
+    }
+
+    #[derive(Debug)]
+    struct AccountMiddleware;
+
+    impl Middleware for AccountMiddleware {
+        fn on_outbound(&self, _: &SessionId, message: &mut FixMessageBuilder) -> MiddlewareAction {
+            if message.get(11) == Some(b"BLOCKED".as_slice()) {
+                return MiddlewareAction::Drop("blocked".to_string());
+            }
+
+            message.set_value(1, "ACC1");
+            return MiddlewareAction::Continue;
+        }
+
+        fn on_inbound(&self, _: &SessionId, message: &mut FixMessageBuilder) -> MiddlewareAction {
+            match message.get(1) {
+                Some(b"ACC1") => return MiddlewareAction::Continue,
+                _ => return MiddlewareAction::Drop("unknown account".to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_middleware() {
+        let app = RecordingApplication::default();
+        let (mut initiator, mut acceptor) = logged_on_pair(&app);
+        initiator.with_middleware(Arc::new(AccountMiddleware));
+        acceptor.with_middleware(Arc::new(AccountMiddleware));
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "BLOCKED");
+        assert_eq!(
+            Err(SessionSendError::DoNotSend(DoNotSend(
+                "blocked".to_string()
+            ))),
+            initiator.send_app(order, &app, now()).map(|_| ())
+        );
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        let order = initiator.send_app(order, &app, now()).unwrap();
+        assert_eq!(Some(b"ACC1".as_slice()), order.get(1));
+
+        acceptor.on_inbound(&order, &app, now());
+        assert_eq!("app D", app.events.lock().unwrap().last().unwrap());
+
+        let mut order = order.clone();
+        order.set_value(34, 3u64);
+        order.set_value(1, "ACC2");
+        let events = app.events.lock().unwrap().len();
+        acceptor.on_inbound(&order, &app, now());
+
+        assert_eq!(events, app.events.lock().unwrap().len());
+        assert_eq!(4, acceptor.get_next_target_seq_num());
+    }
+
+    #[test]
+    fn test_presend_validator() {
+        let app = RecordingApplication::default();
+        let (mut initiator, _) = logged_on_pair(&app);
+
+        let mut validator = RequiredTagsValidator::new();
+        validator.require("D", 1);
+        initiator.with_presend_validator(Arc::new(validator));
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+
+        assert_eq!(
+            Err(SessionSendError::Rejected(
+                "Tag 1 is required in MsgType D".to_string()
+            )),
+            initiator.send_app(order, &app, now()).map(|_| ())
+        );
+        assert_eq!(
+            "rejected D: Tag 1 is required in MsgType D",
+            app.events.lock().unwrap().last().unwrap()
+        );
+        assert_eq!(2, initiator.get_next_sender_seq_num());
+    }
+
+    #[test]
+    fn test_possible_duplicates_are_delivered_once() {
+        let app = RecordingApplication::default();
+        let (_, mut acceptor) = logged_on_pair(&app);
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(34, "2");
+        order.with_value(11, "ORDER1");
+        acceptor.on_inbound(&order, &app, now());
+
+        let mut resent = order.clone();
+        resent.set_value(34, "3");
+        resent.with_value(97, "Y");
+        acceptor.on_inbound(&resent, &app, now());
+
+        let mut new_order = FixMessageBuilder::new("FIX.4.4", "D");
+        new_order.with_value(34, "4");
+        new_order.with_value(11, "ORDER2");
+        new_order.with_value(97, "Y");
+        acceptor.on_inbound(&new_order, &app, now());
+
+        let events = app.events.lock().unwrap();
+        assert_eq!(vec!["app D", "duplicate D"], events[events.len() - 2..]);
+    }
+
+    #[test]
+    fn test_seq_nums_are_persisted() {
+        let store = Arc::new(crate::MemorySeqNumStore::new());
+        store
+            .save(SeqNums {
+                next_sender_seq_num: 7,
+                next_target_seq_num: 3,
+            })
+            .unwrap();
+
+        let mut session = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        session.with_seq_num_store(store.clone()).unwrap();
+        assert_eq!(7, session.get_next_sender_seq_num());
+
+        session.logon(now());
+
+        assert_eq!(
+            Some(SeqNums {
+                next_sender_seq_num: 8,
+                next_target_seq_num: 3,
+            }),
+            store.load().unwrap()
+        );
+
+        store
+            .save(SeqNums {
+                next_sender_seq_num: 20,
+                next_target_seq_num: 9,
+            })
+            .unwrap();
+        session.reload_seq_nums().unwrap();
+        assert_eq!(20, session.get_next_sender_seq_num());
+        assert_eq!(9, session.get_next_target_seq_num());
+    }
+
+    #[test]
+    fn test_send_before_logon() {
+        let mut session = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        let order = FixMessageBuilder::new("FIX.4.4", "D");
+
+        let result = session.send_app(order, &NoopApplication, now());
+
+        assert_eq!(Err(SessionSendError::NotLoggedOn), result.map(|_| ()));
+    }
+
+    #[test]
+    fn test_test_request_is_answered() {
+        let (_, mut acceptor) = logged_on_pair(&NoopApplication);
+
+        let mut test_request = FixMessageBuilder::new("FIX.4.4", "1");
+        test_request.with_value(34, "2");
+        test_request.with_value(112, "PING");
+
+        let replies = sent(&acceptor.on_inbound(&test_request, &NoopApplication, now()));
+
+        assert_eq!("0", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"PING".as_slice()), replies[0].get(112));
+    }
+
+    #[test]
+    fn test_gap_triggers_resend_request() {
+        let (_, mut acceptor) = logged_on_pair(&NoopApplication);
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(34, "5");
+
+        let replies = sent(&acceptor.on_inbound(&order, &NoopApplication, now()));
+        let repeated = sent(&acceptor.on_inbound(&order, &NoopApplication, now()));
+
+        assert_eq!("2", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"2".as_slice()), replies[0].get(7));
+        assert_eq!(Some(b"0".as_slice()), replies[0].get(16));
+        assert!(repeated.is_empty());
+        assert_eq!(2, acceptor.get_next_target_seq_num());
+
+        let mut gap_fill = FixMessageBuilder::new("FIX.4.4", "4");
+        gap_fill.with_value(34, "2");
+        gap_fill.with_value(43, "Y");
+        gap_fill.with_value(123, "Y");
+        gap_fill.with_value(36, "6");
+        acceptor.on_inbound(&gap_fill, &NoopApplication, now());
+
+        assert_eq!(6, acceptor.get_next_target_seq_num());
+    }
+
+    #[test]
+    fn test_seq_num_too_low_logs_out() {
+        let (_, mut acceptor) = logged_on_pair(&NoopApplication);
+
+        let mut heartbeat = FixMessageBuilder::new("FIX.4.4", "0");
+        heartbeat.with_value(34, "1");
+
+        let actions = acceptor.on_inbound(&heartbeat, &NoopApplication, now());
+
+        assert_eq!("5", sent(&actions)[0].get_message_type_as_string());
+        assert!(is_disconnect(&actions));
+        assert_eq!(
+            Ok(LogoutReason::SeqNumTooLow),
+            Logout::try_from(&sent(&actions)[0]).map(|logout| logout.reason)
+        );
+    }
+
+    #[test]
+    fn test_resend_request_is_gap_filled() {
+        let (mut initiator, _) = logged_on_pair(&NoopApplication);
+        initiator.set_next_sender_seq_num(10);
+
+        let mut resend_request = FixMessageBuilder::new("FIX.4.4", "2");
+        resend_request.with_value(34, "2");
+        resend_request.with_value(7, "3");
+        resend_request.with_value(16, "0");
+
+        let replies = sent(&initiator.on_inbound(&resend_request, &NoopApplication, now()));
+
+        assert_eq!("4", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"3".as_slice()), replies[0].get(34));
+        assert_eq!(Some(b"10".as_slice()), replies[0].get(36));
+        assert_eq!(10, initiator.get_next_sender_seq_num());
+    }
+
+    #[test]
+    fn test_resend_request_from_zero_is_rejected() {
+        let (mut initiator, _) = logged_on_pair(&NoopApplication);
+        initiator.set_next_sender_seq_num(10);
+
+        let mut resend_request = FixMessageBuilder::new("FIX.4.4", "2");
+        resend_request.with_value(34, "2");
+        resend_request.with_value(7, "0");
+        resend_request.with_value(16, "0");
+
+        let replies = sent(&initiator.on_inbound(&resend_request, &NoopApplication, now()));
+
+        assert_eq!(1, replies.len());
+        assert_eq!("3", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"2".as_slice()), replies[0].get(45));
+        assert_eq!(Some(b"7".as_slice()), replies[0].get(371));
+        assert_eq!(
+            Ok(Some(SessionRejectReason::ValueIsIncorrect)),
+            replies[0].get_field::<SessionRejectReason>()
+        );
+        assert_eq!(11, initiator.get_next_sender_seq_num());
+    }
+
+    #[test]
+    fn test_resend_request_replays_stored_messages() {
+        let (mut initiator, mut acceptor) = logged_on_pair(&NoopApplication);
+        initiator.with_message_store(Arc::new(crate::MemoryMessageStore::new()));
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        initiator.send_app(order, &NoopApplication, now()).unwrap();
+        initiator.on_timer(now() + Duration::from_secs(30));
+
+        let mut resend_request = FixMessageBuilder::new("FIX.4.4", "2");
+        resend_request.with_value(34, "2");
+        resend_request.with_value(7, "1");
+        resend_request.with_value(16, "0");
+
+        let later = now() + Duration::from_secs(31);
+        let replies = sent(&initiator.on_inbound(&resend_request, &NoopApplication, later));
+
+        assert_eq!(3, replies.len());
+        assert_eq!(Some(b"2".as_slice()), replies[0].get(36));
+        assert_eq!(
+            "8=FIX.4.4|9=91|35=D|49=BUY|56=SELL|34=2|52=20180920-18:25:30.000|43=Y|122=20180920-18:24:59.000|11=ORDER1|10=084|",
+            replies[1].to_string()
+        );
+        assert_eq!(Some(b"3".as_slice()), replies[2].get(34));
+        assert_eq!(Some(b"4".as_slice()), replies[2].get(36));
+
+        acceptor.set_next_target_seq_num(1);
+        for reply in &replies {
+            acceptor.on_inbound(reply, &NoopApplication, later);
+        }
+        assert_eq!(4, acceptor.get_next_target_seq_num());
+    }
+
+    #[test]
+    fn test_next_expected_msg_seq_num() {
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.enable_next_expected_msg_seq_num = true;
+        let mut initiator = Session::from_config(config);
+        initiator.set_next_target_seq_num(4);
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "SELL", "BUY"));
+        config.enable_next_expected_msg_seq_num = true;
+        let mut acceptor = Session::from_config(config);
+        acceptor.set_next_sender_seq_num(6);
+
+        let logon = initiator.logon(now());
+        assert_eq!(Some(b"4".as_slice()), logon.get(789));
+
+        let replies = sent(&acceptor.on_inbound(&logon, &NoopApplication, now()));
+
+        assert_eq!(2, replies.len());
+        assert_eq!(Some(b"6".as_slice()), replies[0].get(34));
+        assert_eq!(Some(b"2".as_slice()), replies[0].get(789));
+        assert_eq!("4", replies[1].get_message_type_as_string());
+        assert_eq!(Some(b"4".as_slice()), replies[1].get(34));
+        assert_eq!(Some(b"7".as_slice()), replies[1].get(36));
+
+        for reply in &replies {
+            let actions = initiator.on_inbound(reply, &NoopApplication, now());
+            assert!(actions.is_empty());
+        }
+
+        assert!(initiator.is_logged_on());
+        assert_eq!(7, initiator.get_next_target_seq_num());
+    }
+
+    #[test]
+    fn test_next_expected_msg_seq_num_too_high() {
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "SELL", "BUY"));
+        config.enable_next_expected_msg_seq_num = true;
+        let mut acceptor = Session::from_config(config);
+
+        let mut logon = FixMessageBuilder::new("FIX.4.4", "A");
+        logon.with_value(34, "1");
+        logon.with_value(108, "30");
+        logon.with_value(789, "9");
+
+        let actions = acceptor.on_inbound(&logon, &NoopApplication, now());
+
+        assert_eq!("5", sent(&actions)[0].get_message_type_as_string());
+        assert!(is_disconnect(&actions));
+        assert!(!acceptor.is_logged_on());
+    }
+
+    #[test]
+    fn test_authenticator() {
+        let mut authenticator = crate::PasswordAuthenticator::new();
+        authenticator.add_credentials(
+            &SessionId::new("FIX.4.4", "SELL", "BUY"),
+            "buy",
+            "secret",
+            SessionPermissions { read_only: true },
+        );
+        let authenticator = Arc::new(authenticator);
+
+        let mut initiator = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        let mut acceptor = Session::new(SessionId::new("FIX.4.4", "SELL", "BUY"));
+        acceptor.with_authenticator(authenticator.clone());
+
+        let mut logon = initiator.logon(now());
+        logon.with_value(553, "buy");
+        logon.with_value(554, "wrong");
+
+        let actions = acceptor.on_inbound(&logon, &NoopApplication, now());
+        let replies = sent(&actions);
+
+        assert_eq!("5", replies[0].get_message_type_as_string());
+        assert_eq!(
+            Some("Invalid username or password".to_string()),
+            replies[0].get_string_lossy(58)
+        );
+        assert!(is_disconnect(&actions));
+        assert!(!acceptor.is_logged_on());
+
+        let mut initiator = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        let mut acceptor = Session::new(SessionId::new("FIX.4.4", "SELL", "BUY"));
+        acceptor.with_authenticator(authenticator);
+
+        let mut logon = initiator.logon(now());
+        logon.with_value(553, "buy");
+        logon.with_value(554, "secret");
+
+        let replies = sent(&acceptor.on_inbound(&logon, &NoopApplication, now()));
+        initiator.on_inbound(&replies[0], &NoopApplication, now());
+        assert!(acceptor.is_logged_on());
+        assert!(acceptor.get_permissions().read_only);
+
+        let app = RecordingApplication::default();
+        let order = initiator
+            .send_app(FixMessageBuilder::new("FIX.4.4", "D"), &app, now())
+            .unwrap();
+        let replies = sent(&acceptor.on_inbound(&order, &app, now()));
+
+        assert!(app.events.lock().unwrap().is_empty());
+        assert_eq!("j", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"2".as_slice()), replies[0].get(45));
+        assert_eq!(Some(b"6".as_slice()), replies[0].get(380));
+    }
+
+    #[test]
+    fn test_rejected_reset_logon_keeps_session() {
+        #[derive(Default)]
+        struct AdminRecorder {
+            admin_messages: Mutex<Vec<String>>,
+        }
+
+        impl FixApplication for AdminRecorder {
+            fn on_admin_message(&self, _: &SessionId, message: &FixMessageBuilder) {
+                self.admin_messages
+                    .lock()
+                    .unwrap()
+                    .push(message.get_message_type_as_string());
+            }
+        }
+
+        let mut authenticator = crate::PasswordAuthenticator::new();
+        authenticator.add_credentials(
+            &SessionId::new("FIX.4.4", "SELL", "BUY"),
+            "buy",
+            "secret",
+            SessionPermissions::default(),
+        );
+
+        let store = Arc::new(crate::MemoryMessageStore::new());
+        store.save(1, b"8=FIX.4.4\x01").unwrap();
+
+        let mut acceptor = Session::new(SessionId::new("FIX.4.4", "SELL", "BUY"));
+        acceptor.with_authenticator(Arc::new(authenticator));
+        acceptor.with_message_store(store.clone());
+        acceptor.set_next_sender_seq_num(5);
+        acceptor.set_next_target_seq_num(7);
+
+        let mut initiator = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        let mut logon = initiator.logon(now());
+        logon.with_value(141, "Y");
+        logon.with_value(553, "buy");
+        logon.with_value(554, "wrong");
+
+        let app = AdminRecorder::default();
+        let actions = acceptor.on_inbound(&logon, &app, now());
+
+        assert_eq!("5", sent(&actions)[0].get_message_type_as_string());
+        assert_eq!(Some(b"5".as_slice()), sent(&actions)[0].get(34));
+        assert!(is_disconnect(&actions));
+        assert_eq!(7, acceptor.get_next_target_seq_num());
+        assert_eq!(1, store.get_range(1, 1).unwrap().len());
+        assert!(app.admin_messages.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reset_logon_while_logged_on() {
+        let (mut initiator, mut acceptor) = logged_on_pair(&NoopApplication);
+        let order = initiator
+            .send_app(
+                FixMessageBuilder::new("FIX.4.4", "D"),
+                &NoopApplication,
+                now(),
+            )
+            .unwrap();
+        acceptor.on_inbound(&order, &NoopApplication, now());
+
+        let logon = initiator.reset_logon(now());
+        assert_eq!(Some(b"1".as_slice()), logon.get(34));
+
+        let replies = sent(&acceptor.on_inbound(&logon, &NoopApplication, now()));
+
+        assert_eq!(1, replies.len());
+        assert_eq!("A", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"Y".as_slice()), replies[0].get(141));
+        assert_eq!(Some(b"1".as_slice()), replies[0].get(34));
+        assert_eq!(2, acceptor.get_next_sender_seq_num());
+        assert_eq!(2, acceptor.get_next_target_seq_num());
+
+        // The reply confirms the reset and isn't answered again.
+        let actions = initiator.on_inbound(&replies[0], &NoopApplication, now());
+
+        assert!(actions.is_empty());
+        assert!(initiator.is_logged_on());
+        assert_eq!(2, initiator.get_next_sender_seq_num());
+        assert_eq!(2, initiator.get_next_target_seq_num());
+    }
+
+    #[test]
+    fn test_heartbeat_and_test_request_timers() {
+        let (mut initiator, _) = logged_on_pair(&NoopApplication);
+
+        let actions = initiator.on_timer(now() + Duration::from_secs(30));
+        assert_eq!("0", sent(&actions)[0].get_message_type_as_string());
+
+        let actions = initiator.on_timer(now() + Duration::from_secs(36));
+        assert_eq!("1", sent(&actions)[0].get_message_type_as_string());
+
+        let actions = initiator.on_timer(now() + Duration::from_secs(65));
+        assert!(!is_disconnect(&actions));
+
+        let actions = initiator.on_timer(now() + Duration::from_secs(66));
+        assert!(is_disconnect(&actions));
+    }
+
+    #[test]
+    fn test_heartbeat_round_trip() {
+        let (mut initiator, mut acceptor) = logged_on_pair(&NoopApplication);
+        assert_eq!(None, initiator.get_last_rtt());
+
+        let test_request = initiator.test_request(now());
+        let replies = sent(&acceptor.on_inbound(&test_request, &NoopApplication, now()));
+
+        // Other messages arriving in between don't end the measurement.
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        acceptor.prepare_outbound(&mut order, now());
+        initiator.on_inbound(&order, &NoopApplication, now());
+        assert_eq!(None, initiator.get_last_rtt());
+
+        let at = now() + Duration::from_millis(25);
+        initiator.on_inbound(&replies[0], &NoopApplication, at);
+
+        assert_eq!(Some(Duration::from_millis(25)), initiator.get_last_rtt());
+    }
+
+    #[test]
+    fn test_sending_time_skew() {
+        let app = RecordingApplication::default();
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "SELL", "BUY"));
+        config.sending_time_policy = SendingTimePolicy::Reject;
+        config.max_latency = 5;
+
+        let mut acceptor = Session::from_config(config);
+        let mut initiator = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        let late = now() + Duration::from_secs(60);
+
+        // A late Logon is only reported so the session still comes up.
+        let logon = initiator.logon(now());
+        acceptor.on_inbound(&logon, &app, late);
+        assert!(acceptor.is_logged_on());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        initiator.prepare_outbound(&mut order, now());
+        let replies = sent(&acceptor.on_inbound(&order, &app, late));
+
+        assert_eq!("3", replies[0].get_message_type_as_string());
+        assert_eq!(Some("2".to_string()), replies[0].get_string_lossy(45));
+        assert_eq!(
+            Ok(Some(SessionRejectReason::SendingTimeAccuracyProblem)),
+            replies[0].get_field::<SessionRejectReason>()
+        );
+        assert_eq!(3, acceptor.get_next_target_seq_num());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        initiator.prepare_outbound(&mut order, now());
+        let replies = sent(&acceptor.on_inbound(&order, &app, now() + Duration::from_secs(5)));
+        assert!(replies.is_empty());
+
+        let events = app.events.lock().unwrap();
+        assert_eq!(
+            "warning SendingTimeSkew { seq_num: 1, skew: 60s }",
+            events[0]
+        );
+        assert_eq!(vec!["app D"], events[2..]);
+    }
+
+    #[test]
+    fn test_reject_storm_logs_out() {
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "SELL", "BUY"));
+        config.max_rejects = Some(4);
+        config.reject_window = 10;
+
+        let mut acceptor = Session::from_config(config);
+        let mut initiator = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        acceptor.on_inbound(&initiator.logon(now()), &NoopApplication, now());
+
+        let mut reject = |acceptor: &mut Session, at: SystemTime| {
+            let mut reject = FixMessageBuilder::new("FIX.4.4", "3");
+            reject.with_value(45, "2");
+            initiator.prepare_outbound(&mut reject, at);
+            let actions = acceptor.on_inbound(&reject, &NoopApplication, at);
+
+            let mut answer = FixMessageBuilder::new("FIX.4.4", "j");
+            acceptor.prepare_outbound(&mut answer, at);
+
+            return actions;
+        };
+
+        // Rejects spread over more than the window don't add up.
+        for seconds in [0, 6, 12] {
+            assert!(sent(&reject(&mut acceptor, now() + Duration::from_secs(seconds))).is_empty());
+        }
+
+        let actions = reject(&mut acceptor, now() + Duration::from_secs(13));
+        let logout = &sent(&actions)[0];
+        assert_eq!("5", logout.get_message_type_as_string());
+        assert_eq!(
+            Some("Reject storm: 3 rejects received and 2 sent within 10s".to_string()),
+            logout.get_string_lossy(58)
+        );
+        assert!(is_disconnect(&actions));
+    }
+
+    #[test]
+    fn test_logout_exchange() {
+        let app = RecordingApplication::default();
+        let (mut initiator, mut acceptor) = logged_on_pair(&app);
+
+        let logout = initiator.logout(Some("bye"), now());
+        let actions = acceptor.on_inbound(&logout, &app, now());
+        assert!(is_disconnect(&actions));
+        assert_eq!(
+            Some(&Logout {
+                reason: LogoutReason::Other,
+                session_status: None,
+                text: Some("bye".to_string()),
+            }),
+            acceptor.get_last_logout()
+        );
+        acceptor.disconnected(&app, now());
+
+        let actions = initiator.on_inbound(&sent(&actions)[0], &app, now());
+        assert!(is_disconnect(&actions));
+        initiator.disconnected(&app, now());
+
+        assert_eq!(SessionStatus::Disconnected, initiator.get_status());
+        assert_eq!(
+            "logout FIX.4.4:BUY->SELL",
+            app.events.lock().unwrap().last().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_session_status() {
+        let app = RecordingApplication::default();
+        let mut initiator = Session::new(SessionId::new("FIXT.1.1", "BUY", "SELL"));
+        let mut acceptor = Session::new(SessionId::new("FIXT.1.1", "SELL", "BUY"));
+        acceptor.with_logon_session_status(Some(fields::SessionStatus::SessionPasswordDueToExpire));
+
+        let logon = initiator.logon(now());
+        assert_eq!(None, logon.get(1409));
+        let replies = sent(&acceptor.on_inbound(&logon, &app, now()));
+        assert_eq!(Some(b"2".as_slice()), replies[0].get(1409));
+        initiator.on_inbound(&replies[0], &app, now());
+
+        let logout =
+            acceptor.logout_with_reason(&Logout::new(LogoutReason::PasswordExpired), now());
+        initiator.on_inbound(&logout, &app, now());
+
+        assert_eq!(
+            vec![
+                "logon FIXT.1.1:SELL->BUY".to_string(),
+                "logon FIXT.1.1:BUY->SELL".to_string(),
+                format!(
+                    "event {:?}",
+                    SessionEvent::Logon {
+                        session_status: fields::SessionStatus::SessionPasswordDueToExpire,
+                        text: None,
+                    }
+                ),
+                format!(
+                    "event {:?}",
+                    SessionEvent::Logout(Logout::new(LogoutReason::PasswordExpired))
+                ),
+            ],
+            *app.events.lock().unwrap()
+        );
+
+        let mut authenticator = crate::PasswordAuthenticator::new();
+        authenticator.add_credentials(
+            &SessionId::new("FIXT.1.1", "SELL", "BUY"),
+            "buy",
+            "secret",
+            SessionPermissions::default(),
+        );
+
+        let mut initiator = Session::new(SessionId::new("FIXT.1.1", "BUY", "SELL"));
+        let mut acceptor = Session::new(SessionId::new("FIXT.1.1", "SELL", "BUY"));
+        acceptor.with_authenticator(Arc::new(authenticator));
+
+        let mut logon = initiator.logon(now());
+        logon.with_value(553, "buy");
+        logon.with_value(554, "wrong");
+
+        let replies = sent(&acceptor.on_inbound(&logon, &app, now()));
+        assert_eq!("5", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"5".as_slice()), replies[0].get(1409));
+    }
+
+    #[test]
+    fn test_change_password() {
+        let mut authenticator = crate::PasswordAuthenticator::new();
+        authenticator.add_credentials(
+            &SessionId::new("FIXT.1.1", "SELL", "BUY"),
+            "buy",
+            "secret",
+            SessionPermissions::default(),
+        );
+        let authenticator = Arc::new(authenticator);
+
+        let pair = |password: &str| -> (Session, Session) {
+            let mut initiator = Session::new(SessionId::new("FIXT.1.1", "BUY", "SELL"));
+            let mut acceptor = Session::new(SessionId::new("FIXT.1.1", "SELL", "BUY"));
+            initiator.with_credentials("buy", password);
+            acceptor.with_authenticator(authenticator.clone());
+
+            return (initiator, acceptor);
+        };
+
+        let (mut initiator, mut acceptor) = pair("secret");
+        initiator.change_password("new-secret");
+        let logon = initiator.logon(now());
+        assert_eq!(Some(b"secret".as_slice()), logon.get(554));
+        assert_eq!(Some(b"new-secret".as_slice()), logon.get(925));
+        assert!(!format!("{:?}", initiator).contains("secret"));
+
+        let replies = sent(&acceptor.on_inbound(&logon, &NoopApplication, now()));
+        assert_eq!(Some(b"1".as_slice()), replies[0].get(1409));
+        initiator.on_inbound(&replies[0], &NoopApplication, now());
+        assert!(initiator.is_logged_on());
+        assert_eq!(
+            Some("new-secret".to_string()),
+            authenticator.get_password(acceptor.get_session_id())
+        );
+
+        initiator.disconnected(&NoopApplication, now());
+        assert_eq!(
+            Some(b"new-secret".as_slice()),
+            initiator.logon(now()).get(554)
+        );
+
+        let (mut initiator, mut acceptor) = pair("new-secret");
+        initiator.change_password("new-secret");
+        let logon = initiator.logon(now());
+        let actions = acceptor.on_inbound(&logon, &NoopApplication, now());
+        let replies = sent(&actions);
+        assert_eq!("5", replies[0].get_message_type_as_string());
+        assert_eq!(Some(b"3".as_slice()), replies[0].get(1409));
+        assert!(is_disconnect(&actions));
+
+        initiator.on_inbound(&replies[0], &NoopApplication, now());
+        assert_eq!(
+            Some(LogoutReason::PasswordPolicy),
+            initiator.get_last_logout().map(|logout| logout.reason)
+        );
+        assert_eq!(None, initiator.logon(now()).get(925));
+    }
+
+    #[test]
+    fn test_timestamp_precision() {
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.timestamp_precision = crate::TimestampPrecision::Micros;
+        let mut session = Session::from_config(config);
+        let now = now() + Duration::from_nanos(1_234_567);
+
+        assert_eq!(
+            Some(b"20180920-18:24:59.001234".as_slice()),
+            session.logon(now).get(52)
+        );
+        assert_eq!("20180920-18:24:59.001234", session.format_timestamp(now));
+    }
+
+    #[test]
+    fn test_check_schedule() {
+        let day_start = UNIX_EPOCH + Duration::from_secs(1_537_401_600);
+        let mut session = Session::new(SessionId::new("FIX.4.4", "BUY", "SELL"));
+        session.with_schedule(SessionSchedule::daily(
+            TimeOfDay::new(8, 0, 0),
+            TimeOfDay::new(17, 0, 0),
+        ));
+
+        assert_eq!(ScheduleAction::Nothing, session.check_schedule(day_start));
+
+        let opened = day_start + Duration::from_secs(8 * 3600);
+        assert_eq!(ScheduleAction::StartSession, session.check_schedule(opened));
+        session.set_next_sender_seq_num(10);
+        assert_eq!(
+            ScheduleAction::Nothing,
+            session.check_schedule(opened + Duration::from_secs(60))
+        );
+
+        let closed = day_start + Duration::from_secs(17 * 3600);
+        assert_eq!(ScheduleAction::EndSession, session.check_schedule(closed));
+
+        let next_day = opened + Duration::from_secs(86400);
+        assert_eq!(
+            ScheduleAction::StartSession,
+            session.check_schedule(next_day)
+        );
+        assert_eq!(1, session.get_next_sender_seq_num());
+    }
+}
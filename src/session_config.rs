@@ -0,0 +1,377 @@
+use crate::{
+    SessionId, SessionSchedule, TimeOfDay, TimestampPrecision, UtcOffset, Weekday,
+    DEFAULT_MAX_FIELD_COUNT, DEFAULT_MAX_MESSAGE_SIZE, DEFAULT_MAX_VALUE_LENGTH,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "lowercase"))]
+pub enum ConnectionType {
+    #[default]
+    Initiator,
+    Acceptor,
+}
+
+/// What a session does with inbound messages whose SendingTime (52) is further off
+/// the local clock than `SessionConfig::max_latency`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "lowercase"))]
+pub enum SendingTimePolicy {
+    #[default]
+    Ignore,
+    /// Delivers the message and reports `SessionWarning::SendingTimeSkew`.
+    Warn,
+    /// Answers with a Reject (35=3) for SendingTimeAccuracyProblem instead of delivering it.
+    Reject,
+}
+
+/// Settings of one session, named after their QuickFIX `settings.cfg` counterparts.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "PascalCase"))]
+pub struct SessionConfig {
+    pub begin_string: String,
+    #[cfg_attr(feature = "config", serde(rename = "SenderCompID"))]
+    pub sender_comp_id: String,
+    #[cfg_attr(feature = "config", serde(rename = "TargetCompID"))]
+    pub target_comp_id: String,
+    #[cfg_attr(feature = "config", serde(rename = "SenderSubID", default))]
+    pub sender_sub_id: Option<String>,
+    #[cfg_attr(feature = "config", serde(rename = "TargetSubID", default))]
+    pub target_sub_id: Option<String>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub session_qualifier: Option<String>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub connection_type: ConnectionType,
+    /// HeartBtInt (108) in seconds.
+    #[cfg_attr(
+        feature = "config",
+        serde(rename = "HeartBtInt", default = "default_heartbeat_interval")
+    )]
+    pub heartbeat_interval: u64,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub reset_on_logon: bool,
+    /// Sends NextExpectedMsgSeqNum (789) on Logon and, when the counterparty's Logon
+    /// carries one, resends what it missed without waiting for a ResendRequest.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub enable_next_expected_msg_seq_num: bool,
+    /// Session start time of day, `HH:MM:SS` in `time_zone`.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub start_time: Option<TimeOfDay>,
+    /// Session end time of day, `HH:MM:SS` in `time_zone`.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub end_time: Option<TimeOfDay>,
+    /// Set together with `end_day` for weekly sessions.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub start_day: Option<Weekday>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub end_day: Option<Weekday>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub time_zone: UtcOffset,
+    /// Seconds between reconnect attempts of an initiator.
+    #[cfg_attr(feature = "config", serde(default = "default_reconnect_interval"))]
+    pub reconnect_interval: u64,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub socket_connect_host: Option<String>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub socket_connect_port: Option<u16>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub socket_accept_port: Option<u16>,
+    #[cfg_attr(feature = "config", serde(default))]
+    pub data_dictionary: Option<String>,
+    /// Application messages a session buffers before senders are pushed back.
+    #[cfg_attr(feature = "config", serde(default = "default_outbound_queue_capacity"))]
+    pub outbound_queue_capacity: usize,
+    /// Caps application messages sent per second; unlimited when unset.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub max_messages_per_second: Option<u32>,
+    /// Messages that may go out back to back before `max_messages_per_second` applies.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub burst_size: Option<u32>,
+    /// Delivered application messages remembered to drop repeated PossDup/PossResend copies.
+    #[cfg_attr(feature = "config", serde(default = "default_dedup_cache_size"))]
+    pub dedup_cache_size: usize,
+    /// Inbound messages larger than this many bytes drop the connection.
+    #[cfg_attr(feature = "config", serde(default = "default_max_message_size"))]
+    pub max_message_size: usize,
+    #[cfg_attr(feature = "config", serde(default = "default_max_field_count"))]
+    pub max_field_count: usize,
+    #[cfg_attr(feature = "config", serde(default = "default_max_value_length"))]
+    pub max_value_length: usize,
+    /// Checks inbound SendingTime (52) against the local clock; Logon and Logout are
+    /// never rejected so the session can still be established and closed.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub sending_time_policy: SendingTimePolicy,
+    /// Tolerated SendingTime skew in seconds.
+    #[cfg_attr(feature = "config", serde(default = "default_max_latency"))]
+    pub max_latency: u64,
+    /// Seconds to wait for the counterparty to confirm our Logout before dropping the connection.
+    #[cfg_attr(feature = "config", serde(default = "default_logout_timeout"))]
+    pub logout_timeout: u64,
+    /// Receive-only session for drop-copy feeds: application messages can't be sent,
+    /// SendingTime isn't checked and a MsgSeqNum that's too low is ignored rather than
+    /// ending the session.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub drop_copy: bool,
+    /// Rejects (35=3 and 35=j) sent and received within `reject_window` that end the
+    /// session with a Logout, once both sides have rejected at least one; a loop of
+    /// mutual rejects would otherwise flood the link. Unlimited when unset.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub max_rejects: Option<u32>,
+    /// Seconds over which rejects are counted towards `max_rejects`.
+    #[cfg_attr(feature = "config", serde(default = "default_reject_window"))]
+    pub reject_window: u64,
+    /// Fractional digits of the SendingTime (52) the session stamps, 0, 3, 6 or 9 in
+    /// the TOML; see also `Session::format_timestamp` for TransactTime (60).
+    #[cfg_attr(feature = "config", serde(default))]
+    pub timestamp_precision: TimestampPrecision,
+}
+
+fn default_heartbeat_interval() -> u64 {
+    return 30;
+}
+
+fn default_reconnect_interval() -> u64 {
+    return 30;
+}
+
+fn default_outbound_queue_capacity() -> usize {
+    return 1024;
+}
+
+fn default_dedup_cache_size() -> usize {
+    return 10_000;
+}
+
+fn default_max_message_size() -> usize {
+    return DEFAULT_MAX_MESSAGE_SIZE;
+}
+
+fn default_max_field_count() -> usize {
+    return DEFAULT_MAX_FIELD_COUNT;
+}
+
+fn default_max_value_length() -> usize {
+    return DEFAULT_MAX_VALUE_LENGTH;
+}
+
+fn default_max_latency() -> u64 {
+    return 120;
+}
+
+fn default_logout_timeout() -> u64 {
+    return 10;
+}
+
+fn default_reject_window() -> u64 {
+    return 10;
+}
+
+impl SessionConfig {
+    pub fn new(session_id: &SessionId) -> Self {
+        return Self {
+            begin_string: session_id.get_begin_string().to_string(),
+            sender_comp_id: session_id.get_sender_comp_id().to_string(),
+            target_comp_id: session_id.get_target_comp_id().to_string(),
+            sender_sub_id: session_id
+                .get_sender_sub_id()
+                .map(|value| value.to_string()),
+            target_sub_id: session_id
+                .get_target_sub_id()
+                .map(|value| value.to_string()),
+            session_qualifier: session_id.get_qualifier().map(|value| value.to_string()),
+            connection_type: ConnectionType::Initiator,
+            heartbeat_interval: default_heartbeat_interval(),
+            reset_on_logon: false,
+            enable_next_expected_msg_seq_num: false,
+            start_time: None,
+            end_time: None,
+            start_day: None,
+            end_day: None,
+            time_zone: UtcOffset::default(),
+            reconnect_interval: default_reconnect_interval(),
+            socket_connect_host: None,
+            socket_connect_port: None,
+            socket_accept_port: None,
+            data_dictionary: None,
+            outbound_queue_capacity: default_outbound_queue_capacity(),
+            max_messages_per_second: None,
+            burst_size: None,
+            dedup_cache_size: default_dedup_cache_size(),
+            max_message_size: default_max_message_size(),
+            max_field_count: default_max_field_count(),
+            max_value_length: default_max_value_length(),
+            sending_time_policy: SendingTimePolicy::Ignore,
+            max_latency: default_max_latency(),
+            logout_timeout: default_logout_timeout(),
+            drop_copy: false,
+            max_rejects: None,
+            reject_window: default_reject_window(),
+            timestamp_precision: TimestampPrecision::default(),
+        };
+    }
+
+    /// Returns the schedule described by the start/end settings, if both times are set.
+    pub fn get_schedule(&self) -> Option<SessionSchedule> {
+        let (Some(start_time), Some(end_time)) = (self.start_time, self.end_time) else {
+            return None;
+        };
+
+        let mut result = match (self.start_day, self.end_day) {
+            (Some(start_day), Some(end_day)) => {
+                SessionSchedule::weekly(start_day, start_time, end_day, end_time)
+            }
+            _ => SessionSchedule::daily(start_time, end_time),
+        };
+        result.with_utc_offset(self.time_zone);
+
+        return Some(result);
+    }
+
+    pub fn get_session_id(&self) -> SessionId {
+        let mut result = SessionId::new(
+            self.begin_string.as_str(),
+            &self.sender_comp_id,
+            &self.target_comp_id,
+        );
+
+        if let Some(sender_sub_id) = &self.sender_sub_id {
+            result.with_sender_sub_id(sender_sub_id);
+        }
+
+        if let Some(target_sub_id) = &self.target_sub_id {
+            result.with_target_sub_id(target_sub_id);
+        }
+
+        if let Some(qualifier) = &self.session_qualifier {
+            result.with_qualifier(qualifier);
+        }
+
+        return result;
+    }
+}
+
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub enum SessionConfigError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+}
+
+#[cfg(feature = "config")]
+impl SessionConfig {
+    /// Loads every `[[session]]` of a TOML document, filling missing keys from `[default]`
+    /// the same way QuickFIX applies its `[DEFAULT]` section.
+    ///
+    /// ```toml
+    /// [default]
+    /// BeginString = "FIX.4.4"
+    /// SenderCompID = "BUY"
+    /// HeartBtInt = 30
+    ///
+    /// [[session]]
+    /// TargetCompID = "SELL"
+    /// ```
+    pub fn from_toml_str(source: &str) -> Result<Vec<Self>, SessionConfigError> {
+        let mut document: toml::Table = toml::from_str(source).map_err(SessionConfigError::Toml)?;
+
+        let defaults = match document.remove("default") {
+            Some(toml::Value::Table(defaults)) => defaults,
+            _ => toml::Table::new(),
+        };
+
+        let sessions = match document.remove("session") {
+            Some(toml::Value::Array(sessions)) => sessions,
+            _ => vec![],
+        };
+
+        let mut result = vec![];
+
+        for session in sessions {
+            let mut merged = defaults.clone();
+
+            if let toml::Value::Table(session) = session {
+                merged.extend(session);
+            }
+
+            result.push(
+                toml::Value::Table(merged)
+                    .try_into()
+                    .map_err(SessionConfigError::Toml)?,
+            );
+        }
+
+        return Ok(result);
+    }
+
+    pub fn from_toml_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Vec<Self>, SessionConfigError> {
+        let source = std::fs::read_to_string(path).map_err(SessionConfigError::Io)?;
+        return Self::from_toml_str(&source);
+    }
+}
+
+#[cfg(all(test, feature = "config"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_toml_str() {
+        let source = r#"
+            [default]
+            BeginString = "FIX.4.4"
+            SenderCompID = "BUY"
+            HeartBtInt = 20
+            ResetOnLogon = true
+
+            [[session]]
+            TargetCompID = "SELL1"
+            SocketConnectHost = "127.0.0.1"
+            SocketConnectPort = 9876
+
+            [[session]]
+            TargetCompID = "SELL2"
+            HeartBtInt = 60
+            ConnectionType = "acceptor"
+            StartTime = "08:00:00"
+            EndTime = "17:00:00"
+            TimeZone = "+01:00"
+            TimestampPrecision = 6
+        "#;
+
+        let configs = SessionConfig::from_toml_str(source).unwrap();
+
+        assert_eq!(2, configs.len());
+        assert_eq!(20, configs[0].heartbeat_interval);
+        assert!(configs[0].reset_on_logon);
+        assert_eq!(Some(9876), configs[0].socket_connect_port);
+        assert_eq!(30, configs[0].reconnect_interval);
+        assert_eq!(60, configs[1].heartbeat_interval);
+        assert_eq!(ConnectionType::Acceptor, configs[1].connection_type);
+        assert_eq!(Some(TimeOfDay::new(8, 0, 0)), configs[1].start_time);
+        assert_eq!(UtcOffset::from_seconds(3600), configs[1].time_zone);
+        assert_eq!(TimestampPrecision::Millis, configs[0].timestamp_precision);
+        assert_eq!(TimestampPrecision::Micros, configs[1].timestamp_precision);
+        assert!(configs[0].get_schedule().is_none());
+        assert!(configs[1].get_schedule().is_some());
+        assert_eq!(
+            SessionId::new("FIX.4.4", "BUY", "SELL2"),
+            configs[1].get_session_id()
+        );
+    }
+
+    #[test]
+    fn test_missing_required_key() {
+        let source = r#"
+            [[session]]
+            BeginString = "FIX.4.4"
+            SenderCompID = "BUY"
+        "#;
+
+        let result = SessionConfig::from_toml_str(source);
+
+        assert!(matches!(result, Err(SessionConfigError::Toml(_))));
+    }
+}
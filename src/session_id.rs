@@ -0,0 +1,195 @@
+use std::{fmt, str::FromStr};
+
+use crate::{BeginString, FixMessageBuilder};
+
+/// Identifies a FIX session from our side of the connection.
+///
+/// Formats as `BeginString:SenderCompID[/SenderSubID]->TargetCompID[/TargetSubID][:Qualifier]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId {
+    begin_string: BeginString,
+    sender_comp_id: String,
+    target_comp_id: String,
+    sender_sub_id: Option<String>,
+    target_sub_id: Option<String>,
+    qualifier: Option<String>,
+}
+
+impl SessionId {
+    pub fn new(
+        begin_string: impl Into<BeginString>,
+        sender_comp_id: &str,
+        target_comp_id: &str,
+    ) -> Self {
+        return Self {
+            begin_string: begin_string.into(),
+            sender_comp_id: sender_comp_id.to_string(),
+            target_comp_id: target_comp_id.to_string(),
+            sender_sub_id: None,
+            target_sub_id: None,
+            qualifier: None,
+        };
+    }
+
+    /// Builds the id of the session an inbound message belongs to, so the counterparty's
+    /// SenderCompID (49) becomes our TargetCompID.
+    pub fn from_inbound(message: &FixMessageBuilder) -> Option<Self> {
+        let sender_comp_id = message.get_string(56).ok()??;
+        let target_comp_id = message.get_string(49).ok()??;
+
+        let mut result = Self::new(message.get_fix_version(), &sender_comp_id, &target_comp_id);
+        result.sender_sub_id = message.get_string(57).ok().flatten();
+        result.target_sub_id = message.get_string(50).ok().flatten();
+
+        return Some(result);
+    }
+
+    pub fn with_sender_sub_id(&mut self, sender_sub_id: &str) {
+        self.sender_sub_id = Some(sender_sub_id.to_string());
+    }
+
+    pub fn with_target_sub_id(&mut self, target_sub_id: &str) {
+        self.target_sub_id = Some(target_sub_id.to_string());
+    }
+
+    pub fn with_qualifier(&mut self, qualifier: &str) {
+        self.qualifier = Some(qualifier.to_string());
+    }
+
+    pub fn get_begin_string(&self) -> &BeginString {
+        return &self.begin_string;
+    }
+
+    pub fn get_sender_comp_id(&self) -> &str {
+        return &self.sender_comp_id;
+    }
+
+    pub fn get_target_comp_id(&self) -> &str {
+        return &self.target_comp_id;
+    }
+
+    pub fn get_sender_sub_id(&self) -> Option<&str> {
+        return self.sender_sub_id.as_deref();
+    }
+
+    pub fn get_target_sub_id(&self) -> Option<&str> {
+        return self.target_sub_id.as_deref();
+    }
+
+    pub fn get_qualifier(&self) -> Option<&str> {
+        return self.qualifier.as_deref();
+    }
+
+    /// Compares everything except the qualifier, which never travels on the wire.
+    pub fn matches_ignoring_qualifier(&self, other: &SessionId) -> bool {
+        return self.begin_string == other.begin_string
+            && self.sender_comp_id == other.sender_comp_id
+            && self.target_comp_id == other.target_comp_id
+            && self.sender_sub_id == other.sender_sub_id
+            && self.target_sub_id == other.target_sub_id;
+    }
+
+    /// Sets the header fields identifying this session on an outbound message.
+    pub fn apply_to(&self, message: &mut FixMessageBuilder) {
+        message.with_value(49, &self.sender_comp_id);
+        message.with_value(56, &self.target_comp_id);
+
+        if let Some(sender_sub_id) = &self.sender_sub_id {
+            message.with_value(50, sender_sub_id);
+        }
+
+        if let Some(target_sub_id) = &self.target_sub_id {
+            message.with_value(57, target_sub_id);
+        }
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.begin_string, self.sender_comp_id)?;
+
+        if let Some(sender_sub_id) = &self.sender_sub_id {
+            write!(f, "/{}", sender_sub_id)?;
+        }
+
+        write!(f, "->{}", self.target_comp_id)?;
+
+        if let Some(target_sub_id) = &self.target_sub_id {
+            write!(f, "/{}", target_sub_id)?;
+        }
+
+        if let Some(qualifier) = &self.qualifier {
+            write!(f, ":{}", qualifier)?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionIdParseError(pub String);
+
+impl FromStr for SessionId {
+    type Err = SessionIdParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let err = || SessionIdParseError(value.to_string());
+
+        let (begin_string, rest) = value.split_once(':').ok_or_else(err)?;
+        let (sender, rest) = rest.split_once("->").ok_or_else(err)?;
+        let (target, qualifier) = match rest.split_once(':') {
+            Some((target, qualifier)) => (target, Some(qualifier)),
+            None => (rest, None),
+        };
+
+        let (sender_comp_id, sender_sub_id) = split_sub_id(sender);
+        let (target_comp_id, target_sub_id) = split_sub_id(target);
+
+        if begin_string.is_empty() || sender_comp_id.is_empty() || target_comp_id.is_empty() {
+            return Err(err());
+        }
+
+        let mut result = SessionId::new(begin_string, sender_comp_id, target_comp_id);
+        result.sender_sub_id = sender_sub_id.map(|value| value.to_string());
+        result.target_sub_id = target_sub_id.map(|value| value.to_string());
+        result.qualifier = qualifier.map(|value| value.to_string());
+
+        return Ok(result);
+    }
+}
+
+fn split_sub_id(value: &str) -> (&str, Option<&str>) {
+    return match value.split_once('/') {
+        Some((comp_id, sub_id)) => (comp_id, Some(sub_id)),
+        None => (value, None),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_and_parse() {
+        let mut session_id = SessionId::new(BeginString::Fix44, "BUY", "SELL");
+        session_id.with_sender_sub_id("DESK");
+        session_id.with_qualifier("MD");
+
+        let formatted = session_id.to_string();
+
+        assert_eq!("FIX.4.4:BUY/DESK->SELL:MD", formatted);
+        assert_eq!(Ok(session_id), formatted.parse());
+        assert!("FIX.4.4:BUY".parse::<SessionId>().is_err());
+    }
+
+    #[test]
+    fn test_from_inbound() {
+        let mut message = FixMessageBuilder::new("FIX.4.4", "0");
+        message.with_value(49, "SELL");
+        message.with_value(56, "BUY");
+
+        let session_id = SessionId::from_inbound(&message).unwrap();
+
+        assert_eq!(SessionId::new("FIX.4.4", "BUY", "SELL"), session_id);
+    }
+}
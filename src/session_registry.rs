@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::{FixMessageBuilder, Session, SessionId};
+
+/// Sessions hosted by one process, looked up by id or by inbound message.
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    sessions: HashMap<SessionId, Session>,
+}
+
+impl SessionRegistry {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Adds a session, returning the one previously registered under the same id.
+    pub fn register(&mut self, session: Session) -> Option<Session> {
+        return self
+            .sessions
+            .insert(session.get_session_id().clone(), session);
+    }
+
+    pub fn remove(&mut self, session_id: &SessionId) -> Option<Session> {
+        return self.sessions.remove(session_id);
+    }
+
+    pub fn get(&self, session_id: &SessionId) -> Option<&Session> {
+        return self.sessions.get(session_id);
+    }
+
+    pub fn get_mut(&mut self, session_id: &SessionId) -> Option<&mut Session> {
+        return self.sessions.get_mut(session_id);
+    }
+
+    /// Finds the session an inbound message belongs to using its BeginString and comp/sub ids.
+    pub fn find_for_inbound(&mut self, message: &FixMessageBuilder) -> Option<&mut Session> {
+        let session_id = SessionId::from_inbound(message)?;
+
+        if self.sessions.contains_key(&session_id) {
+            return self.sessions.get_mut(&session_id);
+        }
+
+        return self.sessions.values_mut().find(|session| {
+            session
+                .get_session_id()
+                .matches_ignoring_qualifier(&session_id)
+        });
+    }
+
+    pub fn session_ids(&self) -> impl Iterator<Item = &SessionId> {
+        return self.sessions.keys();
+    }
+
+    pub fn len(&self) -> usize {
+        return self.sessions.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.sessions.is_empty();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_for_inbound() {
+        let mut registry = SessionRegistry::new();
+
+        let mut qualified = SessionId::new("FIX.4.4", "BUY", "SELL1");
+        qualified.with_qualifier("ORDERS");
+        registry.register(Session::new(qualified.clone()));
+        registry.register(Session::new(SessionId::new("FIX.4.4", "BUY", "SELL2")));
+
+        let mut message = FixMessageBuilder::new("FIX.4.4", "0");
+        message.with_value(49, "SELL1");
+        message.with_value(56, "BUY");
+
+        let session = registry.find_for_inbound(&message).unwrap();
+        session.set_next_target_seq_num(2);
+
+        assert_eq!(&qualified, session.get_session_id());
+        assert_eq!(
+            2,
+            registry.get(&qualified).unwrap().get_next_target_seq_num()
+        );
+
+        message.with_value(57, "UNKNOWN");
+        assert!(registry.find_for_inbound(&message).is_none());
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const SECONDS_IN_DAY: i64 = 86400;
+const SECONDS_IN_WEEK: i64 = 7 * SECONDS_IN_DAY;
+// 1970-01-01 was a Thursday.
+const EPOCH_WEEKDAY: i64 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleParseError(pub String);
+
+impl fmt::Display for ScheduleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "invalid schedule value: {}", self.0);
+    }
+}
+
+/// Time of day formatted as `HH:MM:SS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(try_from = "String"))]
+pub struct TimeOfDay {
+    seconds: u32,
+}
+
+impl TimeOfDay {
+    pub fn new(hour: u32, minute: u32, second: u32) -> Self {
+        return Self {
+            seconds: hour * 3600 + minute * 60 + second,
+        };
+    }
+
+    pub fn seconds_from_midnight(&self) -> u32 {
+        return self.seconds;
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ScheduleParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = value.split(':').collect();
+
+        let parsed: Vec<u32> = parts.iter().filter_map(|part| part.parse().ok()).collect();
+
+        if parts.len() != 3
+            || parsed.len() != 3
+            || parsed[0] > 23
+            || parsed[1] > 59
+            || parsed[2] > 59
+        {
+            return Err(ScheduleParseError(value.to_string()));
+        }
+
+        return Ok(Self::new(parsed[0], parsed[1], parsed[2]));
+    }
+}
+
+impl TryFrom<String> for TimeOfDay {
+    type Error = ScheduleParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        return value.parse();
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            self.seconds / 3600,
+            self.seconds % 3600 / 60,
+            self.seconds % 60
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(try_from = "String"))]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    fn days_from_sunday(&self) -> i64 {
+        return *self as i64;
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = ScheduleParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lowercase = value.to_ascii_lowercase();
+
+        return match lowercase.get(..3) {
+            Some("sun") => Ok(Weekday::Sunday),
+            Some("mon") => Ok(Weekday::Monday),
+            Some("tue") => Ok(Weekday::Tuesday),
+            Some("wed") => Ok(Weekday::Wednesday),
+            Some("thu") => Ok(Weekday::Thursday),
+            Some("fri") => Ok(Weekday::Friday),
+            Some("sat") => Ok(Weekday::Saturday),
+            _ => Err(ScheduleParseError(value.to_string())),
+        };
+    }
+}
+
+impl TryFrom<String> for Weekday {
+    type Error = ScheduleParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        return value.parse();
+    }
+}
+
+/// Fixed offset from UTC, written as `UTC`, `+02:00` or `-05:30`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(try_from = "String"))]
+pub struct UtcOffset {
+    seconds: i32,
+}
+
+impl UtcOffset {
+    pub fn from_seconds(seconds: i32) -> Self {
+        return Self { seconds };
+    }
+
+    pub fn as_seconds(&self) -> i32 {
+        return self.seconds;
+    }
+}
+
+impl FromStr for UtcOffset {
+    type Err = ScheduleParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let err = || ScheduleParseError(value.to_string());
+
+        if value.eq_ignore_ascii_case("utc") || value == "Z" {
+            return Ok(Self::default());
+        }
+
+        let sign = match value.get(..1) {
+            Some("+") => 1,
+            Some("-") => -1,
+            _ => return Err(err()),
+        };
+
+        let (hours, minutes) = value[1..].split_once(':').ok_or_else(err)?;
+        let hours: i32 = hours.parse().map_err(|_| err())?;
+        let minutes: i32 = minutes.parse().map_err(|_| err())?;
+
+        if hours > 14 || minutes > 59 {
+            return Err(err());
+        }
+
+        return Ok(Self::from_seconds(sign * (hours * 3600 + minutes * 60)));
+    }
+}
+
+impl TryFrom<String> for UtcOffset {
+    type Error = ScheduleParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        return value.parse();
+    }
+}
+
+/// Daily or weekly window during which a session should be connected.
+///
+/// When the end is before the start the window wraps over midnight (or over the
+/// week end for weekly schedules). Equal start and end mean the session never closes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionSchedule {
+    start_time: TimeOfDay,
+    end_time: TimeOfDay,
+    start_day: Option<Weekday>,
+    end_day: Option<Weekday>,
+    utc_offset: UtcOffset,
+}
+
+impl SessionSchedule {
+    pub fn daily(start_time: TimeOfDay, end_time: TimeOfDay) -> Self {
+        return Self {
+            start_time,
+            end_time,
+            start_day: None,
+            end_day: None,
+            utc_offset: UtcOffset::default(),
+        };
+    }
+
+    pub fn weekly(
+        start_day: Weekday,
+        start_time: TimeOfDay,
+        end_day: Weekday,
+        end_time: TimeOfDay,
+    ) -> Self {
+        return Self {
+            start_time,
+            end_time,
+            start_day: Some(start_day),
+            end_day: Some(end_day),
+            utc_offset: UtcOffset::default(),
+        };
+    }
+
+    pub fn with_utc_offset(&mut self, utc_offset: UtcOffset) {
+        self.utc_offset = utc_offset;
+    }
+
+    pub fn is_session_time(&self, now: SystemTime) -> bool {
+        let (position, start, end) = self.positions(now);
+        let period = self.period();
+
+        if start == end {
+            return true;
+        }
+
+        return (position - start).rem_euclid(period) < (end - start).rem_euclid(period);
+    }
+
+    /// Returns when the session window containing `now` started, or `None` outside of it.
+    pub fn get_session_start(&self, now: SystemTime) -> Option<SystemTime> {
+        if !self.is_session_time(now) {
+            return None;
+        }
+
+        let (position, start, _) = self.positions(now);
+        let elapsed = (position - start).rem_euclid(self.period());
+        let now_seconds = unix_seconds(now);
+
+        return Some(UNIX_EPOCH + Duration::from_secs((now_seconds - elapsed).max(0) as u64));
+    }
+
+    fn period(&self) -> i64 {
+        return match self.start_day {
+            Some(_) => SECONDS_IN_WEEK,
+            None => SECONDS_IN_DAY,
+        };
+    }
+
+    // Position of `now` and of the window bounds within the current day or week, in local seconds.
+    fn positions(&self, now: SystemTime) -> (i64, i64, i64) {
+        let local = unix_seconds(now) + self.utc_offset.as_seconds() as i64;
+        let start = self.start_time.seconds_from_midnight() as i64;
+        let end = self.end_time.seconds_from_midnight() as i64;
+
+        return match (self.start_day, self.end_day) {
+            (Some(start_day), Some(end_day)) => {
+                let days = local.div_euclid(SECONDS_IN_DAY);
+                let weekday = (days + EPOCH_WEEKDAY).rem_euclid(7);
+                let position = weekday * SECONDS_IN_DAY + local.rem_euclid(SECONDS_IN_DAY);
+
+                (
+                    position,
+                    start_day.days_from_sunday() * SECONDS_IN_DAY + start,
+                    end_day.days_from_sunday() * SECONDS_IN_DAY + end,
+                )
+            }
+            _ => (local.rem_euclid(SECONDS_IN_DAY), start, end),
+        };
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    return match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(err) => -(err.duration().as_secs() as i64),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 2018-09-20 was a Thursday.
+    fn at(day: u64, hour: u64, minute: u64) -> SystemTime {
+        return UNIX_EPOCH
+            + Duration::from_secs(1_537_401_600 + day * 86400 + hour * 3600 + minute * 60);
+    }
+
+    #[test]
+    fn test_daily_schedule() {
+        let schedule = SessionSchedule::daily(TimeOfDay::new(8, 0, 0), TimeOfDay::new(17, 0, 0));
+
+        assert!(!schedule.is_session_time(at(0, 7, 59)));
+        assert!(schedule.is_session_time(at(0, 8, 0)));
+        assert!(!schedule.is_session_time(at(0, 17, 0)));
+        assert_eq!(Some(at(1, 8, 0)), schedule.get_session_start(at(1, 12, 30)));
+    }
+
+    #[test]
+    fn test_daily_schedule_over_midnight_with_offset() {
+        let mut schedule =
+            SessionSchedule::daily(TimeOfDay::new(22, 0, 0), TimeOfDay::new(6, 0, 0));
+        schedule.with_utc_offset("+02:00".parse().unwrap());
+
+        assert!(schedule.is_session_time(at(0, 20, 0)));
+        assert!(schedule.is_session_time(at(1, 3, 59)));
+        assert!(!schedule.is_session_time(at(1, 4, 0)));
+        assert_eq!(Some(at(0, 20, 0)), schedule.get_session_start(at(1, 2, 0)));
+    }
+
+    #[test]
+    fn test_weekly_schedule() {
+        let schedule = SessionSchedule::weekly(
+            Weekday::Sunday,
+            TimeOfDay::new(22, 0, 0),
+            Weekday::Friday,
+            TimeOfDay::new(21, 0, 0),
+        );
+
+        // Thursday, Friday evening, Saturday and Sunday evening.
+        assert!(schedule.is_session_time(at(0, 12, 0)));
+        assert!(!schedule.is_session_time(at(1, 21, 0)));
+        assert!(!schedule.is_session_time(at(2, 12, 0)));
+        assert!(schedule.is_session_time(at(3, 22, 0)));
+        assert_eq!(Some(at(3, 22, 0)), schedule.get_session_start(at(4, 9, 0)));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(TimeOfDay::new(8, 30, 0)), "08:30:00".parse());
+        assert!("25:00:00".parse::<TimeOfDay>().is_err());
+        assert_eq!(Ok(Weekday::Friday), "Fri".parse());
+        assert_eq!(Ok(UtcOffset::from_seconds(-19800)), "-05:30".parse());
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    clock::{civil_from_days, days_from_civil},
+    group::get_date,
+    FieldParseError, FixMessageBuilder, FixValue, Weekday,
+};
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+];
+
+/// Date of a LocalMktDate field such as SettlDate (64) or TradeDate (75), written
+/// `YYYYMMDD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixDate {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl FixDate {
+    pub fn new(year: u32, month: u32, day: u32) -> Self {
+        return Self { year, month, day };
+    }
+
+    /// Parses `YYYYMMDD`; `None` when it isn't a real date.
+    pub fn parse(value: &str) -> Option<Self> {
+        if value.len() != 8 || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let date = Self::new(
+            value[..4].parse().ok()?,
+            value[4..6].parse().ok()?,
+            value[6..].parse().ok()?,
+        );
+
+        if !(1..=12).contains(&date.month) || date.day == 0 || date.day > date.get_month_len() {
+            return None;
+        }
+
+        return Some(date);
+    }
+
+    /// UTC date of `time`.
+    pub fn from_time(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        return Self::from_days((seconds / 86400) as i64);
+    }
+
+    pub fn get_weekday(&self) -> Weekday {
+        return WEEKDAYS[self.to_days().rem_euclid(7) as usize];
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        return Self::from_days(self.to_days() + days);
+    }
+
+    /// The same day `months` later, moved back to the last day of a shorter month.
+    pub fn add_months(&self, months: u32) -> Self {
+        let month_index = self.month - 1 + months;
+        let mut result = Self::new(self.year + month_index / 12, month_index % 12 + 1, 1);
+        result.day = self.day.min(result.get_month_len());
+
+        return result;
+    }
+
+    fn get_month_len(&self) -> u32 {
+        let first = Self::new(self.year, self.month, 1);
+        let next_first = if self.month == 12 {
+            Self::new(self.year + 1, 1, 1)
+        } else {
+            Self::new(self.year, self.month + 1, 1)
+        };
+
+        return (next_first.to_days() - first.to_days()) as u32;
+    }
+
+    fn to_days(self) -> i64 {
+        return days_from_civil(self.year as i64, self.month, self.day);
+    }
+
+    fn from_days(days: i64) -> Self {
+        let (year, month, day) = civil_from_days(days);
+        return Self::new(year as u32, month, day);
+    }
+}
+
+impl fmt::Display for FixDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{:04}{:02}{:02}", self.year, self.month, self.day);
+    }
+}
+
+impl From<FixDate> for FixValue {
+    fn from(value: FixDate) -> Self {
+        return FixValue::String(value.to_string());
+    }
+}
+
+/// Business days of a market or currency pair, used to turn a SettlType into a date.
+pub trait SettlementCalendar {
+    fn is_business_day(&self, date: FixDate) -> bool;
+
+    /// First business day on or after `date`.
+    fn roll_forward(&self, date: FixDate) -> FixDate {
+        let mut result = date;
+
+        while !self.is_business_day(result) {
+            result = result.add_days(1);
+        }
+
+        return result;
+    }
+
+    /// `date` moved `days` business days forward; with 0 days, `date` itself or the next
+    /// business day when it is a holiday.
+    fn add_business_days(&self, date: FixDate, days: u32) -> FixDate {
+        let mut result = date;
+        let mut remaining = days;
+
+        while remaining > 0 {
+            result = result.add_days(1);
+
+            if self.is_business_day(result) {
+                remaining -= 1;
+            }
+        }
+
+        return self.roll_forward(result);
+    }
+}
+
+/// Monday to Friday, except the added holidays.
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    holidays: HashSet<FixDate>,
+}
+
+impl HolidayCalendar {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn with_holiday(&mut self, date: FixDate) {
+        self.holidays.insert(date);
+    }
+}
+
+impl SettlementCalendar for HolidayCalendar {
+    fn is_business_day(&self, date: FixDate) -> bool {
+        return !matches!(date.get_weekday(), Weekday::Saturday | Weekday::Sunday)
+            && !self.holidays.contains(&date);
+    }
+}
+
+/// Unit of a SettlType tenor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TenorUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// SettlType (63).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettlType {
+    Regular,
+    Cash,
+    NextDay,
+    TPlus2,
+    TPlus3,
+    TPlus4,
+    Future,
+    WhenIssued,
+    SellersOption,
+    TPlus5,
+    BrokenDate,
+    FxSpotNextSettlement,
+    /// Tenor of FIX 5.0 such as `W1` or `M3`, counted from the regular settlement date.
+    Tenor(TenorUnit, u32),
+}
+
+impl SettlType {
+    /// SettlDate of a trade on `trade_date`, where `Regular` settles `regular_days`
+    /// business days later, e.g. 2 for FX spot. `None` for the types that need an
+    /// explicit SettlDate (64): `Future`, `WhenIssued`, `SellersOption` and `BrokenDate`.
+    pub fn resolve(
+        &self,
+        trade_date: FixDate,
+        regular_days: u32,
+        calendar: &impl SettlementCalendar,
+    ) -> Option<FixDate> {
+        let days = match self {
+            SettlType::Regular => regular_days,
+            SettlType::Cash => 0,
+            SettlType::NextDay => 1,
+            SettlType::TPlus2 => 2,
+            SettlType::TPlus3 => 3,
+            SettlType::TPlus4 => 4,
+            SettlType::TPlus5 => 5,
+            SettlType::FxSpotNextSettlement => regular_days + 1,
+            SettlType::Tenor(unit, count) => {
+                let spot = calendar.add_business_days(trade_date, regular_days);
+
+                return Some(match unit {
+                    TenorUnit::Day => calendar.add_business_days(spot, *count),
+                    TenorUnit::Week => calendar.roll_forward(spot.add_days(7 * *count as i64)),
+                    TenorUnit::Month => calendar.roll_forward(spot.add_months(*count)),
+                    TenorUnit::Year => calendar.roll_forward(spot.add_months(12 * *count)),
+                });
+            }
+            SettlType::Future
+            | SettlType::WhenIssued
+            | SettlType::SellersOption
+            | SettlType::BrokenDate => return None,
+        };
+
+        return Some(calendar.add_business_days(trade_date, days));
+    }
+}
+
+impl TryFrom<&[u8]> for SettlType {
+    type Error = FieldParseError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let invalid = || FieldParseError::InvalidValue {
+            tag: 63,
+            value: String::from_utf8_lossy(value).to_string(),
+            reason: "unknown SettlType value".to_string(),
+        };
+
+        return match value {
+            b"0" => Ok(SettlType::Regular),
+            b"1" => Ok(SettlType::Cash),
+            b"2" => Ok(SettlType::NextDay),
+            b"3" => Ok(SettlType::TPlus2),
+            b"4" => Ok(SettlType::TPlus3),
+            b"5" => Ok(SettlType::TPlus4),
+            b"6" => Ok(SettlType::Future),
+            b"7" => Ok(SettlType::WhenIssued),
+            b"8" => Ok(SettlType::SellersOption),
+            b"9" => Ok(SettlType::TPlus5),
+            b"B" => Ok(SettlType::BrokenDate),
+            b"C" => Ok(SettlType::FxSpotNextSettlement),
+            [unit, count @ ..] if !count.is_empty() => {
+                let unit = match unit {
+                    b'D' => TenorUnit::Day,
+                    b'W' => TenorUnit::Week,
+                    b'M' => TenorUnit::Month,
+                    b'Y' => TenorUnit::Year,
+                    _ => return Err(invalid()),
+                };
+
+                let count = std::str::from_utf8(count)
+                    .ok()
+                    .filter(|count| count.bytes().all(|byte| byte.is_ascii_digit()))
+                    .and_then(|count| count.parse().ok())
+                    .ok_or_else(invalid)?;
+
+                Ok(SettlType::Tenor(unit, count))
+            }
+            _ => Err(invalid()),
+        };
+    }
+}
+
+impl fmt::Display for SettlType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            SettlType::Regular => "0",
+            SettlType::Cash => "1",
+            SettlType::NextDay => "2",
+            SettlType::TPlus2 => "3",
+            SettlType::TPlus3 => "4",
+            SettlType::TPlus4 => "5",
+            SettlType::Future => "6",
+            SettlType::WhenIssued => "7",
+            SettlType::SellersOption => "8",
+            SettlType::TPlus5 => "9",
+            SettlType::BrokenDate => "B",
+            SettlType::FxSpotNextSettlement => "C",
+            SettlType::Tenor(unit, count) => {
+                let unit = match unit {
+                    TenorUnit::Day => 'D',
+                    TenorUnit::Week => 'W',
+                    TenorUnit::Month => 'M',
+                    TenorUnit::Year => 'Y',
+                };
+
+                return write!(f, "{}{}", unit, count);
+            }
+        };
+
+        return f.write_str(value);
+    }
+}
+
+impl From<SettlType> for FixValue {
+    fn from(value: SettlType) -> Self {
+        return FixValue::String(value.to_string());
+    }
+}
+
+impl FixMessageBuilder {
+    /// SettlType (63).
+    pub fn get_settl_type(&self) -> Result<Option<SettlType>, FieldParseError> {
+        return self.get(63).map(SettlType::try_from).transpose();
+    }
+
+    /// SettlDate (64).
+    pub fn get_settl_date(&self) -> Result<Option<FixDate>, FieldParseError> {
+        return self.get_date(64);
+    }
+
+    /// TradeDate (75).
+    pub fn get_trade_date(&self) -> Result<Option<FixDate>, FieldParseError> {
+        return self.get_date(75);
+    }
+
+    /// SettlDate (64) when present, otherwise the date SettlType (63) gives for the
+    /// TradeDate (75), a missing SettlType meaning `Regular`. `None` without a TradeDate
+    /// or for a SettlType that needs an explicit date.
+    pub fn resolve_settl_date(
+        &self,
+        regular_days: u32,
+        calendar: &impl SettlementCalendar,
+    ) -> Result<Option<FixDate>, FieldParseError> {
+        if let Some(settl_date) = self.get_settl_date()? {
+            return Ok(Some(settl_date));
+        }
+
+        let Some(trade_date) = self.get_trade_date()? else {
+            return Ok(None);
+        };
+
+        let settl_type = self.get_settl_type()?.unwrap_or(SettlType::Regular);
+
+        return Ok(settl_type.resolve(trade_date, regular_days, calendar));
+    }
+
+    fn get_date(&self, tag: u32) -> Result<Option<FixDate>, FieldParseError> {
+        let Some(value) = self.get(tag) else {
+            return Ok(None);
+        };
+
+        return get_date(&[(tag, value)], tag);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fix_date() {
+        assert_eq!(Some(FixDate::new(2024, 2, 29)), FixDate::parse("20240229"));
+        assert_eq!(None, FixDate::parse("20230229"));
+        assert_eq!(None, FixDate::parse("2024-1-1"));
+        assert_eq!(Weekday::Thursday, FixDate::new(1970, 1, 1).get_weekday());
+        assert_eq!(Weekday::Friday, FixDate::new(2024, 3, 29).get_weekday());
+        assert_eq!(
+            FixDate::new(2024, 2, 29),
+            FixDate::new(2024, 1, 31).add_months(1)
+        );
+        assert_eq!(
+            FixDate::new(2025, 1, 2),
+            FixDate::new(2024, 12, 30).add_days(3)
+        );
+        assert_eq!("20240105", FixDate::new(2024, 1, 5).to_string());
+    }
+
+    #[test]
+    fn test_settl_type_round_trip() {
+        for value in ["0", "3", "B", "C", "W1", "M12"] {
+            let settl_type = SettlType::try_from(value.as_bytes()).unwrap();
+            assert_eq!(value, settl_type.to_string());
+        }
+
+        assert_eq!(
+            Ok(SettlType::Tenor(TenorUnit::Month, 3)),
+            SettlType::try_from(b"M3".as_slice())
+        );
+        assert!(SettlType::try_from(b"X1".as_slice()).is_err());
+        assert!(SettlType::try_from(b"M".as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_resolve_settl_date() {
+        let mut calendar = HolidayCalendar::new();
+        calendar.with_holiday(FixDate::new(2024, 4, 1));
+
+        // Thursday before Easter: Friday is a business day here, Monday is not.
+        let trade_date = FixDate::new(2024, 3, 28);
+
+        let resolve = |settl_type: SettlType| settl_type.resolve(trade_date, 2, &calendar);
+        assert_eq!(Some(FixDate::new(2024, 3, 28)), resolve(SettlType::Cash));
+        assert_eq!(Some(FixDate::new(2024, 3, 29)), resolve(SettlType::NextDay));
+        assert_eq!(Some(FixDate::new(2024, 4, 2)), resolve(SettlType::Regular));
+        assert_eq!(
+            Some(FixDate::new(2024, 4, 3)),
+            resolve(SettlType::FxSpotNextSettlement)
+        );
+        assert_eq!(
+            Some(FixDate::new(2024, 4, 9)),
+            resolve(SettlType::Tenor(TenorUnit::Week, 1))
+        );
+        assert_eq!(None, resolve(SettlType::BrokenDate));
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(63, SettlType::TPlus3);
+        order.with_value(75, trade_date);
+        assert_eq!(
+            Ok(Some(FixDate::new(2024, 4, 3))),
+            order.resolve_settl_date(2, &calendar)
+        );
+
+        order.with_value(64, FixDate::new(2024, 4, 5));
+        assert_eq!(
+            Ok(Some(FixDate::new(2024, 4, 5))),
+            order.resolve_settl_date(2, &calendar)
+        );
+
+        order.set_value(64, "20240431");
+        assert!(matches!(
+            order.get_settl_date(),
+            Err(FieldParseError::InvalidValue { tag: 64, .. })
+        ));
+    }
+}
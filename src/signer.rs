@@ -0,0 +1,9 @@
+/// Signs outbound messages for venues that require SignatureLength (93) and
+/// Signature (89) in the trailer.
+///
+/// `sign` gets the message body as it goes on the wire, from MsgType (35) up to and
+/// including the SOH before SignatureLength, once every other field is final. The
+/// returned bytes become the Signature value and count towards BodyLength and CheckSum.
+pub trait Signer: Send + Sync + std::fmt::Debug {
+    fn sign(&self, body: &[u8]) -> Vec<u8>;
+}
@@ -0,0 +1,133 @@
+use crate::{MessageStore, SeqNumStore, SeqNums, SessionId};
+
+const SEQ_NUMS_KEY: &[u8] = b"seqnums";
+
+/// `SeqNumStore` and `MessageStore` on a sled database (`sled` feature).
+///
+/// Several sessions can share one database; each gets its own trees named after
+/// its `SessionId`. Every write is flushed before returning.
+#[derive(Debug, Clone)]
+pub struct SledStore {
+    db: sled::Db,
+    seq_nums: sled::Tree,
+    messages: sled::Tree,
+}
+
+impl SledStore {
+    pub fn new(db: sled::Db, session_id: &SessionId) -> std::io::Result<Self> {
+        let seq_nums = db
+            .open_tree(format!("{}/seqnums", session_id))
+            .map_err(into_io_error)?;
+        let messages = db
+            .open_tree(format!("{}/messages", session_id))
+            .map_err(into_io_error)?;
+
+        return Ok(Self {
+            db,
+            seq_nums,
+            messages,
+        });
+    }
+
+    pub fn open(
+        path: impl AsRef<std::path::Path>,
+        session_id: &SessionId,
+    ) -> std::io::Result<Self> {
+        let db = sled::open(path).map_err(into_io_error)?;
+        return Self::new(db, session_id);
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.db.flush().map_err(into_io_error)?;
+        return Ok(());
+    }
+}
+
+impl SeqNumStore for SledStore {
+    fn load(&self) -> std::io::Result<Option<SeqNums>> {
+        let Some(value) = self.seq_nums.get(SEQ_NUMS_KEY).map_err(into_io_error)? else {
+            return Ok(None);
+        };
+
+        let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid seqnums");
+        let bytes: [u8; 16] = value.as_ref().try_into().map_err(|_| invalid())?;
+
+        return Ok(Some(SeqNums {
+            next_sender_seq_num: u64::from_be_bytes(bytes[..8].try_into().unwrap()),
+            next_target_seq_num: u64::from_be_bytes(bytes[8..].try_into().unwrap()),
+        }));
+    }
+
+    fn save(&self, seq_nums: SeqNums) -> std::io::Result<()> {
+        let mut value = seq_nums.next_sender_seq_num.to_be_bytes().to_vec();
+        value.extend_from_slice(&seq_nums.next_target_seq_num.to_be_bytes());
+
+        self.seq_nums
+            .insert(SEQ_NUMS_KEY, value)
+            .map_err(into_io_error)?;
+        return self.flush();
+    }
+}
+
+impl MessageStore for SledStore {
+    fn save(&self, seq_num: u64, message: &[u8]) -> std::io::Result<()> {
+        self.messages
+            .insert(seq_num.to_be_bytes(), message)
+            .map_err(into_io_error)?;
+        return self.flush();
+    }
+
+    fn get_range(&self, begin: u64, end: u64) -> std::io::Result<Vec<(u64, Vec<u8>)>> {
+        if begin > end {
+            return Ok(vec![]);
+        }
+
+        let mut result = vec![];
+
+        // Big-endian keys sort like the numbers they encode.
+        for entry in self.messages.range(begin.to_be_bytes()..=end.to_be_bytes()) {
+            let (key, value) = entry.map_err(into_io_error)?;
+            let seq_num = u64::from_be_bytes(key.as_ref().try_into().unwrap_or_default());
+            result.push((seq_num, value.to_vec()));
+        }
+
+        return Ok(result);
+    }
+
+    fn reset(&self) -> std::io::Result<()> {
+        self.messages.clear().map_err(into_io_error)?;
+        return self.flush();
+    }
+}
+
+fn into_io_error(err: sled::Error) -> std::io::Error {
+    return match err {
+        sled::Error::Io(err) => err,
+        err => std::io::Error::other(err),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sled_store() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStore::new(db, &SessionId::new("FIX.4.4", "BUY", "SELL")).unwrap();
+
+        let seq_nums = SeqNums {
+            next_sender_seq_num: 3,
+            next_target_seq_num: 256,
+        };
+        SeqNumStore::save(&store, seq_nums).unwrap();
+        MessageStore::save(&store, 1, b"one").unwrap();
+        MessageStore::save(&store, 256, b"many").unwrap();
+
+        assert_eq!(Some(seq_nums), store.load().unwrap());
+        assert_eq!(
+            vec![(256, b"many".to_vec())],
+            store.get_range(2, 300).unwrap()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::{path::Path, sync::Mutex};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{MessageStore, SeqNumStore, SeqNums, SessionId};
+
+/// `SeqNumStore` and `MessageStore` on a SQLite database (`sqlite` feature).
+///
+/// Several sessions can share one database file; rows are keyed by the `SessionId`.
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Mutex<Connection>,
+    session_key: String,
+}
+
+impl SqliteStore {
+    pub fn new(connection: Connection, session_id: &SessionId) -> std::io::Result<Self> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS seq_nums (
+                    session TEXT PRIMARY KEY,
+                    next_sender_seq_num INTEGER NOT NULL,
+                    next_target_seq_num INTEGER NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS messages (
+                    session TEXT NOT NULL,
+                    seq_num INTEGER NOT NULL,
+                    message BLOB NOT NULL,
+                    PRIMARY KEY (session, seq_num)
+                );",
+            )
+            .map_err(into_io_error)?;
+
+        return Ok(Self {
+            connection: Mutex::new(connection),
+            session_key: session_id.to_string(),
+        });
+    }
+
+    pub fn open(path: impl AsRef<Path>, session_id: &SessionId) -> std::io::Result<Self> {
+        let connection = Connection::open(path).map_err(into_io_error)?;
+        return Self::new(connection, session_id);
+    }
+}
+
+impl SeqNumStore for SqliteStore {
+    fn load(&self) -> std::io::Result<Option<SeqNums>> {
+        return self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT next_sender_seq_num, next_target_seq_num FROM seq_nums WHERE session = ?1",
+                params![self.session_key],
+                |row| {
+                    Ok(SeqNums {
+                        next_sender_seq_num: row.get::<_, i64>(0)? as u64,
+                        next_target_seq_num: row.get::<_, i64>(1)? as u64,
+                    })
+                },
+            )
+            .optional()
+            .map_err(into_io_error);
+    }
+
+    fn save(&self, seq_nums: SeqNums) -> std::io::Result<()> {
+        self.connection
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO seq_nums (session, next_sender_seq_num, next_target_seq_num)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (session) DO UPDATE SET
+                    next_sender_seq_num = excluded.next_sender_seq_num,
+                    next_target_seq_num = excluded.next_target_seq_num",
+                params![
+                    self.session_key,
+                    seq_nums.next_sender_seq_num as i64,
+                    seq_nums.next_target_seq_num as i64
+                ],
+            )
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+}
+
+impl MessageStore for SqliteStore {
+    fn save(&self, seq_num: u64, message: &[u8]) -> std::io::Result<()> {
+        self.connection
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT OR REPLACE INTO messages (session, seq_num, message) VALUES (?1, ?2, ?3)",
+                params![self.session_key, seq_num as i64, message],
+            )
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+
+    fn get_range(&self, begin: u64, end: u64) -> std::io::Result<Vec<(u64, Vec<u8>)>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection
+            .prepare(
+                "SELECT seq_num, message FROM messages
+                WHERE session = ?1 AND seq_num BETWEEN ?2 AND ?3
+                ORDER BY seq_num",
+            )
+            .map_err(into_io_error)?;
+
+        let rows = statement
+            .query_map(
+                params![
+                    self.session_key,
+                    begin.min(i64::MAX as u64) as i64,
+                    end.min(i64::MAX as u64) as i64
+                ],
+                |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, Vec<u8>>(1)?)),
+            )
+            .map_err(into_io_error)?;
+
+        return rows.collect::<Result<Vec<_>, _>>().map_err(into_io_error);
+    }
+
+    fn reset(&self) -> std::io::Result<()> {
+        self.connection
+            .lock()
+            .unwrap()
+            .execute(
+                "DELETE FROM messages WHERE session = ?1",
+                params![self.session_key],
+            )
+            .map_err(into_io_error)?;
+
+        return Ok(());
+    }
+}
+
+fn into_io_error(err: rusqlite::Error) -> std::io::Error {
+    return std::io::Error::other(err);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_store() {
+        let connection = Connection::open_in_memory().unwrap();
+        let store =
+            SqliteStore::new(connection, &SessionId::new("FIX.4.4", "BUY", "SELL")).unwrap();
+
+        assert_eq!(None, store.load().unwrap());
+
+        let seq_nums = SeqNums {
+            next_sender_seq_num: 3,
+            next_target_seq_num: 5,
+        };
+        SeqNumStore::save(&store, seq_nums).unwrap();
+        SeqNumStore::save(&store, seq_nums).unwrap();
+        MessageStore::save(&store, 1, b"one").unwrap();
+        MessageStore::save(&store, 2, b"two").unwrap();
+
+        assert_eq!(Some(seq_nums), store.load().unwrap());
+        assert_eq!(vec![(2, b"two".to_vec())], store.get_range(2, 9).unwrap());
+
+        store.reset().unwrap();
+        assert!(store.get_range(0, 9).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+//! `proptest` strategies producing arbitrary but valid FIX input (`proptest` feature).
+//!
+//! Messages follow a dictionary's message definitions: required fields are always
+//! present, values match the field types and enumerations. `any::<FixMessageBuilder>()`
+//! picks an application message of `FixDictionary::fix44()`, and the coded fields of
+//! `fields` implement `Arbitrary` as well, e.g. `any::<Side>()`.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use proptest::{
+    arbitrary::Arbitrary,
+    collection, option,
+    prelude::{any, BoxedStrategy, Just, Strategy},
+    sample,
+    strategy::Union,
+};
+
+use crate::{format_utc_timestamp, FieldType, FixDictionary, FixMessageBuilder, MemberDef};
+
+const MAX_TEXT_LEN: usize = 16;
+const MAX_GROUP_ENTRIES: usize = 3;
+const MAX_GROUP_DEPTH: usize = 2;
+
+// Between 2000 and 2037, in milliseconds.
+const MIN_TIME: u64 = 946_684_800_000;
+const MAX_TIME: u64 = 2_145_916_800_000;
+
+// Tags the builder writes itself.
+const GENERATED_TAGS: &[u32] = &[8, 9, 35, 10];
+
+type Fields = Vec<(u32, String)>;
+
+/// Alphanumeric text of 1 to `max_len` characters.
+pub fn text(max_len: usize) -> BoxedStrategy<String> {
+    return collection::vec(
+        sample::select(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789".to_vec()),
+        1..=max_len.max(1),
+    )
+    .prop_map(|bytes| String::from_utf8(bytes).unwrap())
+    .boxed();
+}
+
+pub fn int() -> BoxedStrategy<String> {
+    return (1u64..1_000_000)
+        .prop_map(|value| value.to_string())
+        .boxed();
+}
+
+/// Non-negative decimal with two fractional digits, for Price, Qty, Amt and the like.
+pub fn decimal() -> BoxedStrategy<String> {
+    return (0u64..100_000, 0u64..100)
+        .prop_map(|(whole, fraction)| format!("{}.{:02}", whole, fraction))
+        .boxed();
+}
+
+pub fn utc_timestamp() -> BoxedStrategy<String> {
+    return (MIN_TIME..MAX_TIME)
+        .prop_map(|millis| format_utc_timestamp(UNIX_EPOCH + Duration::from_millis(millis)))
+        .boxed();
+}
+
+/// `YYYYMMDD`, for UTCDateOnly and LocalMktDate.
+pub fn date() -> BoxedStrategy<String> {
+    return utc_timestamp()
+        .prop_map(|timestamp| timestamp[..8].to_string())
+        .boxed();
+}
+
+/// Value of the field's type, or one of its enumerated values.
+pub fn field_value(dictionary: &FixDictionary, tag: u32) -> BoxedStrategy<String> {
+    let Some(field) = dictionary.get_field(tag) else {
+        return text(MAX_TEXT_LEN);
+    };
+
+    if !field.values.is_empty() {
+        let values: Vec<String> = field
+            .values
+            .iter()
+            .map(|(value, _)| value.clone())
+            .collect();
+        return sample::select(values).boxed();
+    }
+
+    return match field.field_type {
+        FieldType::Int | FieldType::Length | FieldType::SeqNum | FieldType::TagNum => int(),
+        // A NumInGroup outside a group definition can't have entries.
+        FieldType::NumInGroup => Just("0".to_string()).boxed(),
+        FieldType::DayOfMonth => (1u32..=31).prop_map(|day| day.to_string()).boxed(),
+        FieldType::Float
+        | FieldType::Qty
+        | FieldType::Price
+        | FieldType::PriceOffset
+        | FieldType::Amt
+        | FieldType::Percentage => decimal(),
+        FieldType::Char => (b'A'..=b'Z')
+            .prop_map(|byte| (byte as char).to_string())
+            .boxed(),
+        FieldType::Boolean => any::<bool>()
+            .prop_map(|value| if value { "Y" } else { "N" }.to_string())
+            .boxed(),
+        FieldType::Currency => select(&["USD", "EUR", "GBP", "JPY", "CHF"]),
+        FieldType::Exchange => select(&["XNYS", "XNAS", "XLON", "XETR"]),
+        FieldType::Country => select(&["US", "GB", "DE", "JP"]),
+        FieldType::Language => Just("en".to_string()).boxed(),
+        FieldType::UtcTimestamp => utc_timestamp(),
+        FieldType::UtcDateOnly | FieldType::LocalMktDate => date(),
+        FieldType::UtcTimeOnly => utc_timestamp()
+            .prop_map(|timestamp| timestamp[9..].to_string())
+            .boxed(),
+        FieldType::MonthYear => date().prop_map(|date| date[..6].to_string()).boxed(),
+        _ => text(MAX_TEXT_LEN),
+    };
+}
+
+/// Message of the given type, `None` when the dictionary doesn't define it.
+///
+/// Holds the required header fields, the required body fields and any mix of the
+/// optional ones, with up to three entries per repeating group.
+pub fn message_of(
+    dictionary: &FixDictionary,
+    msg_type: &str,
+) -> Option<BoxedStrategy<FixMessageBuilder>> {
+    let message_def = dictionary.get_message(msg_type)?;
+
+    let header: Vec<BoxedStrategy<Fields>> = dictionary
+        .get_header()
+        .iter()
+        .filter(|member| match member {
+            MemberDef::Field { tag, required } => *required && !GENERATED_TAGS.contains(tag),
+            MemberDef::Group(_) => false,
+        })
+        .map(|member| member_fields(dictionary, std::slice::from_ref(member), 0))
+        .collect();
+
+    let body = member_fields(dictionary, &message_def.members, 0);
+    let begin_string = dictionary.get_begin_string().to_string();
+    let msg_type = msg_type.to_string();
+
+    return Some(
+        (header, body)
+            .prop_map(move |(header, body)| {
+                let mut result = FixMessageBuilder::new(begin_string.as_str(), &msg_type);
+
+                for (tag, value) in header.into_iter().flatten().chain(body) {
+                    result.with_value(tag, value);
+                }
+
+                result
+            })
+            .boxed(),
+    );
+}
+
+/// Any application message of the dictionary; panics when it defines none.
+pub fn message(dictionary: &FixDictionary) -> BoxedStrategy<FixMessageBuilder> {
+    let mut msg_types: Vec<&str> = dictionary
+        .get_messages()
+        .filter(|message| !message.is_admin)
+        .map(|message| message.msg_type.as_str())
+        .collect();
+    msg_types.sort();
+
+    return Union::new(
+        msg_types
+            .into_iter()
+            .filter_map(|msg_type| message_of(dictionary, msg_type)),
+    )
+    .boxed();
+}
+
+/// NewOrderSingle of `FixDictionary::fix44()`.
+pub fn new_order_single() -> BoxedStrategy<FixMessageBuilder> {
+    return message_of(&FixDictionary::fix44(), "D").unwrap();
+}
+
+impl Arbitrary for FixMessageBuilder {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<FixMessageBuilder>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return message(&FixDictionary::fix44());
+    }
+}
+
+fn select(values: &[&str]) -> BoxedStrategy<String> {
+    let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    return sample::select(values).boxed();
+}
+
+fn member_fields(
+    dictionary: &FixDictionary,
+    members: &[MemberDef],
+    depth: usize,
+) -> BoxedStrategy<Fields> {
+    let mut parts: Vec<BoxedStrategy<Fields>> = vec![];
+    let mut index = 0;
+
+    while index < members.len() {
+        let (part, required) = match &members[index] {
+            MemberDef::Field { tag, required } => {
+                let tag = *tag;
+
+                let part = match data_tag_after(dictionary, tag, members.get(index + 1)) {
+                    // The Length field has to tell the size of the Data field after it.
+                    Some(data_tag) => {
+                        index += 1;
+
+                        text(MAX_TEXT_LEN)
+                            .prop_map(move |value| {
+                                vec![(tag, value.len().to_string()), (data_tag, value)]
+                            })
+                            .boxed()
+                    }
+                    None => field_value(dictionary, tag)
+                        .prop_map(move |value| vec![(tag, value)])
+                        .boxed(),
+                };
+
+                (part, *required)
+            }
+            MemberDef::Group(group) => {
+                if depth >= MAX_GROUP_DEPTH {
+                    index += 1;
+                    continue;
+                }
+
+                let count_tag = group.count_tag;
+                let entries = collection::vec(
+                    entry_fields(dictionary, &group.members, depth + 1),
+                    1..=MAX_GROUP_ENTRIES,
+                );
+
+                let part = entries
+                    .prop_map(move |entries| {
+                        let mut result = vec![(count_tag, entries.len().to_string())];
+                        result.extend(entries.into_iter().flatten());
+                        result
+                    })
+                    .boxed();
+
+                (part, group.required)
+            }
+        };
+
+        parts.push(match required {
+            true => part,
+            false => option::of(part).prop_map(Option::unwrap_or_default).boxed(),
+        });
+
+        index += 1;
+    }
+
+    return parts
+        .prop_map(|parts| parts.into_iter().flatten().collect())
+        .boxed();
+}
+
+// The delimiter field starts every entry, so it's always there.
+fn entry_fields(
+    dictionary: &FixDictionary,
+    members: &[MemberDef],
+    depth: usize,
+) -> BoxedStrategy<Fields> {
+    let Some((first, rest)) = members.split_first() else {
+        return Just(vec![]).boxed();
+    };
+
+    let first = match first {
+        MemberDef::Field { tag, .. } => MemberDef::Field {
+            tag: *tag,
+            required: true,
+        },
+        MemberDef::Group(group) => {
+            let mut group = group.clone();
+            group.required = true;
+            MemberDef::Group(group)
+        }
+    };
+
+    return (
+        member_fields(dictionary, std::slice::from_ref(&first), depth),
+        member_fields(dictionary, rest, depth),
+    )
+        .prop_map(|(mut first, rest)| {
+            first.extend(rest);
+            first
+        })
+        .boxed();
+}
+
+fn data_tag_after(dictionary: &FixDictionary, tag: u32, next: Option<&MemberDef>) -> Option<u32> {
+    let is_length = dictionary
+        .get_field(tag)
+        .is_some_and(|field| field.field_type == FieldType::Length);
+
+    let Some(MemberDef::Field { tag: next_tag, .. }) = next else {
+        return None;
+    };
+
+    let is_data = dictionary
+        .get_field(*next_tag)
+        .is_some_and(|field| matches!(field.field_type, FieldType::Data | FieldType::XmlData));
+
+    return (is_length && is_data).then_some(*next_tag);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        fields::{FixField, Side},
+        FieldDef, GroupDef, MessageDef, ValidationError,
+    };
+    use proptest::proptest;
+
+    fn parties_dictionary() -> FixDictionary {
+        let mut result = FixDictionary::fix44();
+
+        for (tag, name, field_type) in [
+            (453, "NoPartyIDs", FieldType::NumInGroup),
+            (448, "PartyID", FieldType::String),
+            (802, "NoPartySubIDs", FieldType::NumInGroup),
+            (523, "PartySubID", FieldType::String),
+        ] {
+            result.add_field(FieldDef {
+                tag,
+                name: name.to_string(),
+                field_type,
+                values: vec![],
+            });
+        }
+
+        result.add_message(MessageDef {
+            msg_type: "U1".to_string(),
+            name: "Parties".to_string(),
+            is_admin: false,
+            members: vec![MemberDef::Group(GroupDef {
+                count_tag: 453,
+                required: true,
+                members: vec![
+                    MemberDef::Field {
+                        tag: 448,
+                        required: true,
+                    },
+                    MemberDef::Group(GroupDef {
+                        count_tag: 802,
+                        required: false,
+                        members: vec![MemberDef::Field {
+                            tag: 523,
+                            required: true,
+                        }],
+                    }),
+                ],
+            })],
+        });
+
+        return result;
+    }
+
+    proptest! {
+        #[test]
+        fn test_arbitrary_message_is_valid(message in any::<FixMessageBuilder>()) {
+            let bytes = message.as_bytes();
+
+            assert_eq!(Vec::<ValidationError>::new(), FixDictionary::fix44().validate(&bytes));
+            assert_eq!(message, FixMessageBuilder::from_bytes_lenient(&bytes).0);
+        }
+
+        #[test]
+        fn test_new_order_single(message in new_order_single(), side in any::<Side>()) {
+            assert_eq!("D", message.get_message_type_as_string());
+            assert!(message.get_string_lossy(11).is_some());
+            assert_eq!(Ok(side), Side::try_from(side.as_bytes()));
+        }
+
+        #[test]
+        fn test_groups(message in message_of(&parties_dictionary(), "U1").unwrap()) {
+            let count: usize = message.get_string_lossy(453).unwrap().parse().unwrap();
+            let tags: Vec<u32> = message.get_fields().map(|(tag, _)| tag).collect();
+
+            assert_eq!(count, tags.iter().filter(|tag| **tag == 448).count());
+            assert!(tags.iter().filter(|tag| **tag == 802).count() <= count);
+            assert_eq!(message, FixMessageBuilder::from_bytes_lenient(&message.as_bytes()).0);
+        }
+    }
+}
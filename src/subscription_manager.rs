@@ -0,0 +1,360 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    group::{expect_msg_type, get_parsed, get_string, required, GroupEntry},
+    FieldParseError, FixMessageBuilder,
+};
+
+const MSG_TYPE_MARKET_DATA_REQUEST: &str = "V";
+const MSG_TYPE_MARKET_DATA_SNAPSHOT: &[u8] = b"W";
+const MSG_TYPE_MARKET_DATA_INCREMENTAL_REFRESH: &[u8] = b"X";
+const MSG_TYPE_MARKET_DATA_REQUEST_REJECT: &[u8] = b"Y";
+
+const SUBSCRIPTION_REQUEST_TYPE_SUBSCRIBE: char = '1';
+const SUBSCRIPTION_REQUEST_TYPE_UNSUBSCRIBE: &str = "2";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionState {
+    /// Sent, no data or reject received yet.
+    Pending,
+    /// Data received within the stale interval.
+    Active,
+    /// No data for longer than the stale interval.
+    Stale,
+    /// Rejected by a MarketDataRequestReject (Y); not resubscribed after a reconnect.
+    Rejected {
+        /// MDReqRejReason (281).
+        reason: Option<char>,
+        /// Text (58).
+        text: Option<String>,
+    },
+}
+
+/// A MarketDataRequest (V) and what came back for it.
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    request: FixMessageBuilder,
+    state: SubscriptionState,
+    subscribed_at: SystemTime,
+    last_update: Option<SystemTime>,
+}
+
+impl Subscription {
+    /// The MarketDataRequest as it was subscribed with.
+    pub fn get_request(&self) -> &FixMessageBuilder {
+        return &self.request;
+    }
+
+    pub fn get_state(&self) -> &SubscriptionState {
+        return &self.state;
+    }
+
+    /// When the request was last sent, first or after a reconnect.
+    pub fn get_subscribed_at(&self) -> SystemTime {
+        return self.subscribed_at;
+    }
+
+    /// When the last snapshot or incremental refresh arrived.
+    pub fn get_last_update(&self) -> Option<SystemTime> {
+        return self.last_update;
+    }
+}
+
+/// What a market-data message meant for the subscriptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionEvent {
+    /// First data of a pending subscription, or data again for a stale one.
+    Activated(String),
+    /// More data for an active subscription.
+    Updated(String),
+    Rejected {
+        md_req_id: String,
+        reason: Option<char>,
+        text: Option<String>,
+    },
+    /// Data or a reject for an MDReqID (262) that isn't subscribed, e.g. after an
+    /// unsubscribe crossed an update.
+    Unknown(String),
+}
+
+/// Tracks the MarketDataRequest (V) subscriptions of a client by MDReqID (262).
+///
+/// Feed it the requests sent and the snapshots (W), incremental refreshes (X) and
+/// rejects (Y) received; call `check_stale` from the timer and `resubscribe` after every
+/// logon.
+#[derive(Debug, Clone)]
+pub struct SubscriptionManager {
+    subscriptions: HashMap<String, Subscription>,
+    stale_after: Duration,
+}
+
+impl SubscriptionManager {
+    /// Subscriptions without data for `stale_after` are reported stale.
+    pub fn new(stale_after: Duration) -> Self {
+        return Self {
+            subscriptions: HashMap::new(),
+            stale_after,
+        };
+    }
+
+    /// Records a MarketDataRequest for snapshot plus updates, SubscriptionRequestType
+    /// (263) `1`, before it is sent. Subscribing an MDReqID again replaces it.
+    pub fn subscribe(
+        &mut self,
+        request: &FixMessageBuilder,
+        now: SystemTime,
+    ) -> Result<(), FieldParseError> {
+        expect_msg_type(request, MSG_TYPE_MARKET_DATA_REQUEST)?;
+
+        let fields: GroupEntry = request.get_fields().collect();
+        let md_req_id = required(get_string(&fields, 262)?, 262)?;
+        let request_type: char = required(get_parsed(&fields, 263)?, 263)?;
+
+        if request_type != SUBSCRIPTION_REQUEST_TYPE_SUBSCRIBE {
+            return Err(FieldParseError::InvalidValue {
+                tag: 263,
+                value: request_type.to_string(),
+                reason: "not a subscription to snapshot plus updates".to_string(),
+            });
+        }
+
+        self.subscriptions.insert(
+            md_req_id,
+            Subscription {
+                request: request.clone(),
+                state: SubscriptionState::Pending,
+                subscribed_at: now,
+                last_update: None,
+            },
+        );
+
+        return Ok(());
+    }
+
+    /// Forgets the subscription and returns the request cancelling it, the original
+    /// request with SubscriptionRequestType (263) `2`.
+    pub fn unsubscribe(&mut self, md_req_id: &str) -> Option<FixMessageBuilder> {
+        let mut request = self.subscriptions.remove(md_req_id)?.request;
+        request.set_value(263, SUBSCRIPTION_REQUEST_TYPE_UNSUBSCRIBE);
+
+        return Some(request);
+    }
+
+    /// Matches a MarketDataSnapshotFullRefresh (W), MarketDataIncrementalRefresh (X) or
+    /// MarketDataRequestReject (Y) to its subscription. `None` for other messages and
+    /// for refreshes without MDReqID.
+    pub fn on_message(
+        &mut self,
+        message: &FixMessageBuilder,
+        now: SystemTime,
+    ) -> Result<Option<SubscriptionEvent>, FieldParseError> {
+        let msg_type = message.get_message_type().as_slice();
+
+        if msg_type != MSG_TYPE_MARKET_DATA_SNAPSHOT
+            && msg_type != MSG_TYPE_MARKET_DATA_INCREMENTAL_REFRESH
+            && msg_type != MSG_TYPE_MARKET_DATA_REQUEST_REJECT
+        {
+            return Ok(None);
+        }
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        let Some(md_req_id) = get_string(&fields, 262)? else {
+            return Ok(None);
+        };
+
+        let Some(subscription) = self.subscriptions.get_mut(&md_req_id) else {
+            return Ok(Some(SubscriptionEvent::Unknown(md_req_id)));
+        };
+
+        if msg_type == MSG_TYPE_MARKET_DATA_REQUEST_REJECT {
+            let reason = get_parsed(&fields, 281)?;
+            let text = get_string(&fields, 58)?;
+
+            subscription.state = SubscriptionState::Rejected {
+                reason,
+                text: text.clone(),
+            };
+
+            return Ok(Some(SubscriptionEvent::Rejected {
+                md_req_id,
+                reason,
+                text,
+            }));
+        }
+
+        subscription.last_update = Some(now);
+
+        if subscription.state == SubscriptionState::Active {
+            return Ok(Some(SubscriptionEvent::Updated(md_req_id)));
+        }
+
+        subscription.state = SubscriptionState::Active;
+
+        return Ok(Some(SubscriptionEvent::Activated(md_req_id)));
+    }
+
+    /// Marks pending and active subscriptions without data for the stale interval as
+    /// stale, returning their MDReqIDs.
+    pub fn check_stale(&mut self, now: SystemTime) -> Vec<String> {
+        let mut result = vec![];
+
+        for (md_req_id, subscription) in &mut self.subscriptions {
+            if !matches!(
+                subscription.state,
+                SubscriptionState::Pending | SubscriptionState::Active
+            ) {
+                continue;
+            }
+
+            let last_seen = subscription
+                .last_update
+                .unwrap_or(subscription.subscribed_at)
+                .max(subscription.subscribed_at);
+
+            if now.duration_since(last_seen).unwrap_or_default() >= self.stale_after {
+                subscription.state = SubscriptionState::Stale;
+                result.push(md_req_id.clone());
+            }
+        }
+
+        result.sort();
+
+        return result;
+    }
+
+    /// Requests to send again after a logon, every subscription but the rejected ones,
+    /// which go back to pending.
+    pub fn resubscribe(&mut self, now: SystemTime) -> Vec<FixMessageBuilder> {
+        let mut subscriptions: Vec<(&String, &mut Subscription)> = self
+            .subscriptions
+            .iter_mut()
+            .filter(|(_, subscription)| {
+                !matches!(subscription.state, SubscriptionState::Rejected { .. })
+            })
+            .collect();
+
+        subscriptions.sort_by_key(|(md_req_id, _)| *md_req_id);
+
+        return subscriptions
+            .into_iter()
+            .map(|(_, subscription)| {
+                subscription.state = SubscriptionState::Pending;
+                subscription.subscribed_at = now;
+
+                subscription.request.clone()
+            })
+            .collect();
+    }
+
+    pub fn get(&self, md_req_id: &str) -> Option<&Subscription> {
+        return self.subscriptions.get(md_req_id);
+    }
+
+    pub fn get_md_req_ids(&self) -> Vec<&str> {
+        let mut result: Vec<&str> = self.subscriptions.keys().map(String::as_str).collect();
+        result.sort();
+
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        return SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+    }
+
+    fn message(msg_type: &str, fields: &[(u32, &str)]) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new("FIX.4.4", msg_type);
+
+        for (tag, value) in fields {
+            message.with_value(*tag, *value);
+        }
+
+        return message;
+    }
+
+    #[test]
+    fn test_subscription_lifecycle() {
+        let mut manager = SubscriptionManager::new(Duration::from_secs(10));
+
+        let eur = message(
+            "V",
+            &[
+                (262, "MD1"),
+                (263, "1"),
+                (264, "1"),
+                (146, "1"),
+                (55, "EURUSD"),
+            ],
+        );
+        let gbp = message(
+            "V",
+            &[
+                (262, "MD2"),
+                (263, "1"),
+                (264, "1"),
+                (146, "1"),
+                (55, "GBPUSD"),
+            ],
+        );
+        manager.subscribe(&eur, at(0)).unwrap();
+        manager.subscribe(&gbp, at(0)).unwrap();
+
+        assert!(matches!(
+            manager.subscribe(&message("V", &[(262, "MD3"), (263, "0")]), at(0)),
+            Err(FieldParseError::InvalidValue { tag: 263, .. })
+        ));
+
+        assert_eq!(
+            Ok(Some(SubscriptionEvent::Activated("MD1".to_string()))),
+            manager.on_message(&message("W", &[(262, "MD1"), (55, "EURUSD")]), at(1))
+        );
+        assert_eq!(
+            Ok(Some(SubscriptionEvent::Updated("MD1".to_string()))),
+            manager.on_message(&message("X", &[(262, "MD1")]), at(5))
+        );
+        assert_eq!(
+            Ok(Some(SubscriptionEvent::Rejected {
+                md_req_id: "MD2".to_string(),
+                reason: Some('0'),
+                text: Some("Unknown symbol".to_string()),
+            })),
+            manager.on_message(
+                &message("Y", &[(262, "MD2"), (281, "0"), (58, "Unknown symbol")]),
+                at(1)
+            )
+        );
+        assert_eq!(
+            Ok(Some(SubscriptionEvent::Unknown("MD9".to_string()))),
+            manager.on_message(&message("X", &[(262, "MD9")]), at(1))
+        );
+        assert_eq!(Ok(None), manager.on_message(&message("0", &[]), at(1)));
+
+        assert!(manager.check_stale(at(14)).is_empty());
+        assert_eq!(vec!["MD1"], manager.check_stale(at(15)));
+        assert_eq!(
+            &SubscriptionState::Stale,
+            manager.get("MD1").unwrap().get_state()
+        );
+
+        let requests = manager.resubscribe(at(20));
+        assert_eq!(1, requests.len());
+        assert_eq!(Some(b"MD1".as_slice()), requests[0].get(262));
+        assert_eq!(
+            &SubscriptionState::Pending,
+            manager.get("MD1").unwrap().get_state()
+        );
+        assert!(manager.check_stale(at(29)).is_empty());
+
+        let cancel = manager.unsubscribe("MD1").unwrap();
+        assert_eq!(Some(b"2".as_slice()), cancel.get(263));
+        assert_eq!(vec!["MD2"], manager.get_md_req_ids());
+    }
+}
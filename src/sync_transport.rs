@@ -0,0 +1,789 @@
+use std::{
+    io::{Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    acquire_leadership, find_fix_frame_len_with_limits, is_admin_message_type, is_leader,
+    Direction, FixApplication, FixMessageBuilder, Journal, Leadership, OutboundPriority,
+    OutboundQueue, OutboundQueueFull, ParseLimits, RateLimiter, ReconnectPolicy, Reconnector,
+    ScheduleAction, Session, SessionAction, SessionConfig, SessionId, SessionSendError,
+};
+
+const TIMER_INTERVAL: Duration = Duration::from_secs(1);
+const FIRST_MESSAGE_TIMEOUT: Duration = Duration::from_secs(10);
+const LOGOUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const READ_CHUNK_SIZE: usize = 4096;
+
+#[derive(Debug)]
+pub enum SyncSendError {
+    Session(SessionSendError),
+    Io(std::io::Error),
+    /// `try_send` found the outbound queue full; the socket isn't keeping up.
+    Full(FixMessageBuilder),
+}
+
+struct SyncSessionShared {
+    session_id: SessionId,
+    session: Mutex<Session>,
+    stream: Mutex<Option<TcpStream>>,
+    rate_limiter: Mutex<Option<RateLimiter>>,
+    queue: Mutex<OutboundQueue>,
+    // Signalled whenever messages leave the queue.
+    queue_space: Condvar,
+    limits: ParseLimits,
+    journal: Mutex<Option<Arc<Journal>>>,
+    app: Arc<dyn FixApplication>,
+    logout_requested: AtomicBool,
+}
+
+impl SyncSessionShared {
+    fn new(session: Session, app: Arc<dyn FixApplication>) -> Arc<Self> {
+        let rate_limiter = RateLimiter::from_config(session.get_config());
+        let limits = ParseLimits::from_config(session.get_config());
+        let queue = OutboundQueue::new(session.get_config().outbound_queue_capacity);
+
+        return Arc::new(Self {
+            session_id: session.get_session_id().clone(),
+            limits,
+            session: Mutex::new(session),
+            rate_limiter: Mutex::new(rate_limiter),
+            queue: Mutex::new(queue),
+            queue_space: Condvar::new(),
+            journal: Mutex::new(None),
+            stream: Mutex::new(None),
+            app,
+            logout_requested: AtomicBool::new(false),
+        });
+    }
+
+    // Called with the session locked so messages hit the wire in sequence number order.
+    fn execute(&self, actions: Vec<SessionAction>) -> std::io::Result<bool> {
+        let mut stream = self.stream.lock().unwrap();
+
+        let Some(stream) = stream.as_mut() else {
+            return Ok(false);
+        };
+
+        for action in actions {
+            match action {
+                SessionAction::Send(message) => {
+                    let bytes = message.as_bytes();
+                    self.journal(Direction::Outbound, &bytes);
+                    stream.write_all(&bytes)?;
+                }
+                SessionAction::Disconnect => {
+                    let _ = stream.shutdown(Shutdown::Both);
+                    return Ok(false);
+                }
+            }
+        }
+
+        return Ok(true);
+    }
+
+    // Writes queued messages until the queue is empty, taking the session lock per
+    // message so heartbeats and replies from other threads go out in between. Sequence
+    // numbers are assigned here, once a message leaves the queue. Fails with the first
+    // error among the messages written, which for a lone sender is its own.
+    fn drain(&self, respect_rate_limit: bool) -> Result<(), SyncSendError> {
+        let mut result = Ok(());
+
+        loop {
+            let mut session = self.session.lock().unwrap();
+            let mut queue = self.queue.lock().unwrap();
+
+            if !session.is_logged_on() {
+                if !queue.is_empty() && result.is_ok() {
+                    result = Err(SyncSendError::Session(SessionSendError::NotLoggedOn));
+                }
+
+                queue.clear();
+                self.queue_space.notify_all();
+                return result;
+            }
+
+            if respect_rate_limit && queue.peek_priority() == Some(OutboundPriority::App) {
+                if let Some(limiter) = self.rate_limiter.lock().unwrap().as_mut() {
+                    let now = SystemTime::now();
+
+                    if !limiter.try_acquire(now) {
+                        let wait = limiter.get_wait_time(now);
+                        drop(queue);
+                        drop(session);
+                        thread::sleep(wait);
+                        continue;
+                    }
+                }
+            }
+
+            let Some((priority, mut message)) = queue.pop() else {
+                return result;
+            };
+
+            drop(queue);
+            self.queue_space.notify_one();
+
+            let prepared = match priority {
+                OutboundPriority::Admin => {
+                    session.prepare_outbound(&mut message, SystemTime::now());
+                    Ok(message)
+                }
+                OutboundPriority::App => {
+                    session.send_app(message, self.app.as_ref(), SystemTime::now())
+                }
+            };
+
+            match prepared {
+                Ok(message) => {
+                    self.execute(vec![SessionAction::Send(message)])
+                        .map_err(SyncSendError::Io)?;
+                }
+                Err(err) if result.is_ok() => result = Err(SyncSendError::Session(err)),
+                Err(_) => {}
+            }
+        }
+    }
+
+    // Journal failures don't affect the session.
+    fn journal(&self, direction: Direction, message: &[u8]) {
+        if let Some(journal) = self.journal.lock().unwrap().as_ref() {
+            let _ = journal.append(direction, &self.session_id, message, SystemTime::now());
+        }
+    }
+}
+
+/// Sends messages through a session run by `SyncInitiator` or `SyncAcceptor`.
+#[derive(Clone)]
+pub struct SyncSessionHandle {
+    shared: Arc<SyncSessionShared>,
+}
+
+impl SyncSessionHandle {
+    pub fn get_session_id(&self) -> SessionId {
+        return self.shared.session.lock().unwrap().get_session_id().clone();
+    }
+
+    pub fn is_logged_on(&self) -> bool {
+        return self.shared.session.lock().unwrap().is_logged_on();
+    }
+
+    /// Round trip of the last TestRequest answered by the counterparty.
+    pub fn get_last_rtt(&self) -> Option<Duration> {
+        return self.shared.session.lock().unwrap().get_last_rtt();
+    }
+
+    /// Sends a TestRequest to measure the round trip, reported by `get_last_rtt`.
+    pub fn send_test_request(&self) -> std::io::Result<()> {
+        let mut session = self.shared.session.lock().unwrap();
+        let test_request = session.test_request(SystemTime::now());
+        self.shared
+            .execute(vec![SessionAction::Send(test_request)])?;
+
+        return Ok(());
+    }
+
+    /// Queues a message and writes out the queue before returning; fails when the
+    /// session isn't logged on.
+    ///
+    /// Session-level messages go ahead of queued application ones. Blocks while
+    /// `outbound_queue_capacity` application messages are waiting, e.g. behind a slow
+    /// socket, and while the session's `max_messages_per_second` is exhausted.
+    pub fn send(&self, message: FixMessageBuilder) -> Result<(), SyncSendError> {
+        self.check_logged_on()?;
+
+        let mut queue = self.shared.queue.lock().unwrap();
+
+        while queue.is_full() && !is_admin_message_type(message.get_message_type()) {
+            queue = self.shared.queue_space.wait(queue).unwrap();
+        }
+
+        let _ = queue.push(message);
+        drop(queue);
+
+        return self.shared.drain(true);
+    }
+
+    /// Same as `send`, failing with `SyncSendError::Full` instead of waiting for room
+    /// in the queue.
+    pub fn try_send(&self, message: FixMessageBuilder) -> Result<(), SyncSendError> {
+        self.check_logged_on()?;
+
+        if let Err(OutboundQueueFull(message)) = self.shared.queue.lock().unwrap().push(message) {
+            return Err(SyncSendError::Full(message));
+        }
+
+        return self.shared.drain(true);
+    }
+
+    fn check_logged_on(&self) -> Result<(), SyncSendError> {
+        if !self.is_logged_on() {
+            return Err(SyncSendError::Session(SessionSendError::NotLoggedOn));
+        }
+
+        return Ok(());
+    }
+
+    /// Sends a Logout and waits until the counterparty confirms it or `logout_timeout`
+    /// passes, then closes the connection; the initiator won't reconnect afterwards.
+    ///
+    /// Messages still queued by other senders are written first, bypassing the rate limit.
+    pub fn logout(&self, text: Option<&str>) -> std::io::Result<()> {
+        self.shared.logout_requested.store(true, Ordering::SeqCst);
+
+        if let Err(SyncSendError::Io(err)) = self.shared.drain(false) {
+            return Err(err);
+        }
+
+        let timeout = {
+            let mut session = self.shared.session.lock().unwrap();
+            let logout = session.logout(text, SystemTime::now());
+            self.shared.execute(vec![SessionAction::Send(logout)])?;
+
+            Duration::from_secs(session.get_config().logout_timeout)
+        };
+
+        // The read loop drops the stream once the confirming Logout arrives.
+        let deadline = Instant::now() + timeout;
+        while self.shared.stream.lock().unwrap().is_some() && Instant::now() < deadline {
+            thread::sleep(LOGOUT_POLL_INTERVAL);
+        }
+
+        self.disconnect_now();
+
+        return Ok(());
+    }
+
+    /// Closes the connection without a Logout, e.g. when the counterparty misbehaves;
+    /// the initiator won't reconnect afterwards.
+    pub fn disconnect_now(&self) {
+        self.shared.logout_requested.store(true, Ordering::SeqCst);
+
+        if let Some(stream) = self.shared.stream.lock().unwrap().as_ref() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// Blocking initiator built on `std::net::TcpStream`.
+///
+/// `run` reads on the calling thread while a background thread sends heartbeats.
+pub struct SyncInitiator {
+    shared: Arc<SyncSessionShared>,
+    reconnector: Reconnector,
+    leadership: Option<Arc<dyn Leadership>>,
+}
+
+impl SyncInitiator {
+    pub fn new(config: SessionConfig, app: Arc<dyn FixApplication>) -> Self {
+        return Self::from_session(Session::from_config(config), app);
+    }
+
+    /// Runs an already set up session, e.g. one with a `SeqNumStore` attached.
+    pub fn from_session(session: Session, app: Arc<dyn FixApplication>) -> Self {
+        let reconnector = Reconnector::new(
+            session.get_session_id().clone(),
+            ReconnectPolicy::from_config(session.get_config()),
+        );
+
+        return Self {
+            shared: SyncSessionShared::new(session, app),
+            reconnector,
+            leadership: None,
+        };
+    }
+
+    pub fn get_reconnector_mut(&mut self) -> &mut Reconnector {
+        return &mut self.reconnector;
+    }
+
+    /// Only connects while this instance leads the session.
+    pub fn with_leadership(&mut self, leadership: Arc<dyn Leadership>) {
+        self.leadership = Some(leadership);
+    }
+
+    /// Records every message sent and received.
+    pub fn with_journal(&mut self, journal: Arc<Journal>) {
+        *self.shared.journal.lock().unwrap() = Some(journal);
+    }
+
+    pub fn handle(&self) -> SyncSessionHandle {
+        return SyncSessionHandle {
+            shared: self.shared.clone(),
+        };
+    }
+
+    /// Runs the session until it is logged out through a handle or reconnecting gives up.
+    pub fn run(&mut self) -> std::io::Result<()> {
+        let address = {
+            let session = self.shared.session.lock().unwrap();
+            let config = session.get_config();
+
+            format!(
+                "{}:{}",
+                config
+                    .socket_connect_host
+                    .clone()
+                    .unwrap_or_else(|| "127.0.0.1".to_string()),
+                config.socket_connect_port.unwrap_or(0)
+            )
+        };
+
+        loop {
+            self.wait_for_session_time();
+
+            if !acquire_leadership(
+                self.leadership.as_deref(),
+                &mut self.shared.session.lock().unwrap(),
+            ) {
+                thread::sleep(TIMER_INTERVAL);
+                continue;
+            }
+
+            let stream = self
+                .reconnector
+                .connect_blocking(|| TcpStream::connect(&address))?;
+
+            {
+                let mut session = self.shared.session.lock().unwrap();
+                *self.shared.stream.lock().unwrap() = Some(stream.try_clone()?);
+
+                let logon = session.logon(SystemTime::now());
+                self.shared.execute(vec![SessionAction::Send(logon)])?;
+            }
+
+            let result = run_connection(&self.shared, stream, vec![], self.leadership.clone());
+            self.reconnector.disconnected();
+
+            if self.shared.logout_requested.load(Ordering::SeqCst) {
+                return result;
+            }
+
+            if let Err(err) = result {
+                if self.reconnector.next_delay().is_none() {
+                    return Err(err);
+                }
+            }
+
+            let reconnect_interval = self
+                .shared
+                .session
+                .lock()
+                .unwrap()
+                .get_config()
+                .reconnect_interval;
+            thread::sleep(Duration::from_secs(reconnect_interval));
+        }
+    }
+
+    fn wait_for_session_time(&self) {
+        loop {
+            let mut session = self.shared.session.lock().unwrap();
+            let now = SystemTime::now();
+            session.check_schedule(now);
+
+            let is_session_time = match session.get_schedule() {
+                Some(schedule) => schedule.is_session_time(now),
+                None => true,
+            };
+
+            if is_session_time {
+                return;
+            }
+
+            drop(session);
+            thread::sleep(TIMER_INTERVAL);
+        }
+    }
+}
+
+/// Blocking acceptor running every connection on its own thread.
+#[derive(Clone)]
+pub struct SyncAcceptor {
+    sessions: Arc<Mutex<Vec<Arc<SyncSessionShared>>>>,
+    app: Arc<dyn FixApplication>,
+    leadership: Option<Arc<dyn Leadership>>,
+    journal: Option<Arc<Journal>>,
+}
+
+impl SyncAcceptor {
+    pub fn new(app: Arc<dyn FixApplication>) -> Self {
+        return Self {
+            sessions: Arc::new(Mutex::new(vec![])),
+            app,
+            leadership: None,
+            journal: None,
+        };
+    }
+
+    /// Only accepts Logons for sessions this instance leads.
+    pub fn with_leadership(&mut self, leadership: Arc<dyn Leadership>) {
+        self.leadership = Some(leadership);
+    }
+
+    /// Records every message sent and received by all sessions.
+    pub fn with_journal(&mut self, journal: Arc<Journal>) {
+        for shared in self.sessions.lock().unwrap().iter() {
+            *shared.journal.lock().unwrap() = Some(journal.clone());
+        }
+
+        self.journal = Some(journal);
+    }
+
+    pub fn add_session(&self, config: SessionConfig) -> SyncSessionHandle {
+        return self.register_session(Session::from_config(config));
+    }
+
+    /// Adds an already set up session, e.g. one with a `SeqNumStore` attached.
+    pub fn register_session(&self, session: Session) -> SyncSessionHandle {
+        let shared = SyncSessionShared::new(session, self.app.clone());
+        *shared.journal.lock().unwrap() = self.journal.clone();
+        self.sessions.lock().unwrap().push(shared.clone());
+
+        return SyncSessionHandle { shared };
+    }
+
+    /// Accepts connections until the listener fails.
+    pub fn serve(&self, listener: TcpListener) -> std::io::Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let acceptor = self.clone();
+
+            thread::spawn(move || {
+                let _ = acceptor.handle_connection(stream);
+            });
+        }
+
+        return Ok(());
+    }
+
+    fn handle_connection(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        stream.set_read_timeout(Some(FIRST_MESSAGE_TIMEOUT))?;
+
+        let mut buffer = vec![];
+        let limits = ParseLimits::default();
+        let frame_len = read_frame(&mut stream, &mut buffer, &limits)?;
+        let first_frame: Vec<u8> = buffer.drain(..frame_len).collect();
+        let first = parse_frame(&first_frame, &limits)?;
+
+        stream.set_read_timeout(None)?;
+
+        let Some(session_id) = SessionId::from_inbound(&first) else {
+            return Ok(());
+        };
+
+        let shared = self
+            .sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|shared| {
+                shared
+                    .session
+                    .lock()
+                    .unwrap()
+                    .get_session_id()
+                    .matches_ignoring_qualifier(&session_id)
+            })
+            .cloned();
+
+        let Some(shared) = shared else {
+            return Ok(());
+        };
+
+        {
+            let mut session = shared.session.lock().unwrap();
+            let mut writer = shared.stream.lock().unwrap();
+
+            if writer.is_some() || !acquire_leadership(self.leadership.as_deref(), &mut session) {
+                return Ok(());
+            }
+
+            *writer = Some(stream.try_clone()?);
+            drop(writer);
+
+            shared.journal(Direction::Inbound, &first_frame);
+            session.set_remote_address(stream.peer_addr().ok());
+            let actions = session.on_inbound(&first, shared.app.as_ref(), SystemTime::now());
+            shared.execute(actions)?;
+        }
+
+        return run_connection(&shared, stream, buffer, self.leadership.clone());
+    }
+}
+
+fn run_connection(
+    shared: &Arc<SyncSessionShared>,
+    mut stream: TcpStream,
+    mut buffer: Vec<u8>,
+    leadership: Option<Arc<dyn Leadership>>,
+) -> std::io::Result<()> {
+    let connected = Arc::new(AtomicBool::new(true));
+
+    let heartbeat_thread = {
+        let shared = shared.clone();
+        let connected = connected.clone();
+
+        thread::spawn(move || {
+            while connected.load(Ordering::SeqCst) {
+                thread::sleep(TIMER_INTERVAL);
+
+                let mut session = shared.session.lock().unwrap();
+                let now = SystemTime::now();
+                let mut actions = vec![];
+
+                if session.check_schedule(now) == ScheduleAction::EndSession {
+                    actions.push(SessionAction::Send(session.logout(None, now)));
+                }
+
+                actions.extend(session.on_timer(now));
+
+                if !is_leader(leadership.as_deref(), session.get_session_id()) {
+                    actions.push(SessionAction::Disconnect);
+                }
+
+                if !matches!(shared.execute(actions), Ok(true)) {
+                    return;
+                }
+            }
+        })
+    };
+
+    let result = read_loop(shared, &mut stream, &mut buffer);
+
+    connected.store(false, Ordering::SeqCst);
+    let _ = stream.shutdown(Shutdown::Both);
+    let _ = heartbeat_thread.join();
+
+    let mut session = shared.session.lock().unwrap();
+    *shared.stream.lock().unwrap() = None;
+    session.disconnected(shared.app.as_ref(), SystemTime::now());
+
+    // Senders still waiting for room fail rather than go out on the next connection.
+    shared.queue.lock().unwrap().clear();
+    shared.queue_space.notify_all();
+
+    return result;
+}
+
+fn read_loop(
+    shared: &Arc<SyncSessionShared>,
+    stream: &mut TcpStream,
+    buffer: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    loop {
+        let frame_len = match read_frame(stream, buffer, &shared.limits) {
+            Ok(frame_len) => frame_len,
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        shared.journal(Direction::Inbound, &buffer[..frame_len]);
+        let message = parse_frame(&buffer[..frame_len], &shared.limits)?;
+        buffer.drain(..frame_len);
+
+        let mut session = shared.session.lock().unwrap();
+        let actions = session.on_inbound(&message, shared.app.as_ref(), SystemTime::now());
+
+        if !shared.execute(actions)? {
+            return Ok(());
+        }
+    }
+}
+
+// Reads until `buffer` starts with a complete message and returns its length.
+pub(crate) fn read_frame(
+    stream: &mut TcpStream,
+    buffer: &mut Vec<u8>,
+    limits: &ParseLimits,
+) -> std::io::Result<usize> {
+    let mut chunk = [0u8; READ_CHUNK_SIZE];
+
+    loop {
+        match find_fix_frame_len_with_limits(buffer, limits) {
+            Ok(Some(frame_len)) => return Ok(frame_len),
+            Ok(None) => {}
+            Err(err) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{:?}", err),
+                ))
+            }
+        }
+
+        let read = stream.read(&mut chunk)?;
+
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+pub(crate) fn parse_frame(
+    frame: &[u8],
+    limits: &ParseLimits,
+) -> std::io::Result<FixMessageBuilder> {
+    return FixMessageBuilder::from_bytes_with_limits(frame, true, limits)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", err)));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingApplication {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl FixApplication for RecordingApplication {
+        fn on_app_message(&self, _: &SessionId, message: &FixMessageBuilder) {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(message.get_string_lossy(11).unwrap_or_default());
+        }
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if condition() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_sync_initiator_and_acceptor() {
+        let acceptor_app = Arc::new(RecordingApplication::default());
+        let acceptor = SyncAcceptor::new(acceptor_app.clone());
+        let acceptor_handle = acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || acceptor.serve(listener));
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+
+        let journal_path =
+            std::env::temp_dir().join(format!("rust-fix-sync-{}.journal", std::process::id()));
+        let _ = std::fs::remove_file(&journal_path);
+
+        let mut initiator = SyncInitiator::new(config, Arc::new(crate::NoopApplication));
+        initiator.with_journal(Arc::new(Journal::open(&journal_path).unwrap()));
+        let handle = initiator.handle();
+        let initiator_thread = thread::spawn(move || initiator.run());
+
+        wait_until(|| handle.is_logged_on());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        handle.send(order).unwrap();
+
+        wait_until(|| !acceptor_app.messages.lock().unwrap().is_empty());
+        assert_eq!(vec!["ORDER1"], *acceptor_app.messages.lock().unwrap());
+
+        handle.logout(None).unwrap();
+        assert!(!handle.is_logged_on());
+        assert!(initiator_thread.join().unwrap().is_ok());
+
+        wait_until(|| !acceptor_handle.is_logged_on());
+        assert!(!acceptor_handle.is_logged_on());
+
+        let journal: Vec<(Direction, String)> = crate::JournalReader::open(&journal_path)
+            .unwrap()
+            .replay(true)
+            .map(|entry| {
+                let (entry, message) = entry.unwrap();
+                (entry.direction, message.get_message_type_as_string())
+            })
+            .collect();
+
+        assert_eq!((Direction::Outbound, "A".to_string()), journal[0]);
+        assert_eq!((Direction::Inbound, "A".to_string()), journal[1]);
+        assert!(journal.contains(&(Direction::Outbound, "D".to_string())));
+        assert!(journal.contains(&(Direction::Inbound, "5".to_string())));
+
+        std::fs::remove_file(&journal_path).unwrap();
+    }
+
+    #[test]
+    fn test_disconnect_now() {
+        let acceptor = SyncAcceptor::new(Arc::new(crate::NoopApplication));
+        let acceptor_handle = acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || acceptor.serve(listener));
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+
+        let mut initiator = SyncInitiator::new(config, Arc::new(crate::NoopApplication));
+        let handle = initiator.handle();
+        let initiator_thread = thread::spawn(move || initiator.run());
+
+        wait_until(|| acceptor_handle.is_logged_on());
+
+        handle.disconnect_now();
+        assert!(initiator_thread.join().unwrap().is_ok());
+        assert!(!handle.is_logged_on());
+
+        wait_until(|| !acceptor_handle.is_logged_on());
+        assert!(!acceptor_handle.is_logged_on());
+    }
+
+    #[test]
+    fn test_try_send_reports_backpressure() {
+        let acceptor = SyncAcceptor::new(Arc::new(crate::NoopApplication));
+        acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || acceptor.serve(listener));
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+        config.outbound_queue_capacity = 0;
+
+        let mut initiator = SyncInitiator::new(config, Arc::new(crate::NoopApplication));
+        let handle = initiator.handle();
+
+        assert!(matches!(
+            handle.try_send(FixMessageBuilder::new("FIX.4.4", "D")),
+            Err(SyncSendError::Session(SessionSendError::NotLoggedOn))
+        ));
+
+        let initiator_thread = thread::spawn(move || initiator.run());
+        wait_until(|| handle.is_logged_on());
+
+        assert!(matches!(
+            handle.try_send(FixMessageBuilder::new("FIX.4.4", "D")),
+            Err(SyncSendError::Full(_))
+        ));
+
+        // Session-level messages never count against the capacity.
+        let mut test_request = FixMessageBuilder::new("FIX.4.4", "1");
+        test_request.with_value(112, "PING");
+        handle.try_send(test_request).unwrap();
+
+        handle.logout(None).unwrap();
+        assert!(initiator_thread.join().unwrap().is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use crate::FixMessageBuilder;
+
+/// Tag renumbering and value mapping applied to messages going one way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RewriteRules {
+    tags: HashMap<u32, u32>,
+    values: HashMap<u32, HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl RewriteRules {
+    pub fn map_tag(&mut self, from: u32, to: u32) {
+        self.tags.insert(from, to);
+    }
+
+    /// Replaces `from` with `to` in the values of `tag`, the tag being the one after
+    /// renumbering.
+    pub fn map_value(&mut self, tag: u32, from: &str, to: &str) {
+        self.values
+            .entry(tag)
+            .or_default()
+            .insert(from.as_bytes().to_vec(), to.as_bytes().to_vec());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.tags.is_empty() && self.values.is_empty();
+    }
+
+    pub fn apply(&self, message: &mut FixMessageBuilder) {
+        if self.is_empty() {
+            return;
+        }
+
+        message.for_each_field_mut(|tag, value| {
+            if let Some(to) = self.tags.get(tag) {
+                *tag = *to;
+            }
+
+            if let Some(to) = self.values.get(tag).and_then(|values| values.get(value)) {
+                *value = to.clone();
+            }
+        });
+    }
+}
+
+/// Per session tag renumbering and value mapping, so the application works with the
+/// same tags whatever a counterparty uses. Inbound rules run before the session and
+/// the application see a message, outbound rules before the header is stamped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagRewriter {
+    pub inbound: RewriteRules,
+    pub outbound: RewriteRules,
+}
+
+impl TagRewriter {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Receives `counterparty_tag` as `local_tag` and sends `local_tag` as `counterparty_tag`.
+    pub fn map_tag(&mut self, counterparty_tag: u32, local_tag: u32) {
+        self.inbound.map_tag(counterparty_tag, local_tag);
+        self.outbound.map_tag(local_tag, counterparty_tag);
+    }
+
+    pub fn rewrite_inbound(&self, message: &mut FixMessageBuilder) {
+        self.inbound.apply(message);
+    }
+
+    pub fn rewrite_outbound(&self, message: &mut FixMessageBuilder) {
+        self.outbound.apply(message);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rewrite() {
+        let mut rewriter = TagRewriter::new();
+        rewriter.map_tag(20001, 58);
+        rewriter.inbound.map_value(54, "B", "1");
+        rewriter.outbound.map_value(54, "1", "B");
+
+        let mut message = FixMessageBuilder::new("FIX.4.4", "D");
+        message.with_value(54, "B");
+        message.with_value(20001, "note");
+
+        rewriter.rewrite_inbound(&mut message);
+        assert_eq!(
+            vec![(54, b"1".as_slice()), (58, b"note".as_slice())],
+            message.get_fields().collect::<Vec<_>>()
+        );
+
+        rewriter.rewrite_outbound(&mut message);
+        assert_eq!(
+            vec![(54, b"B".as_slice()), (20001, b"note".as_slice())],
+            message.get_fields().collect::<Vec<_>>()
+        );
+    }
+}
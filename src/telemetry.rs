@@ -0,0 +1,209 @@
+//! Counters and histograms recorded through the `metrics` crate facade (`metrics`
+//! feature), so any exporter, e.g. `metrics-exporter-prometheus`, can publish them.
+//! Without the feature every function here compiles to nothing.
+//!
+//! - `fix_messages_received_total` and `fix_messages_sent_total`, labelled with
+//!   `session_id` and `msg_type`
+//! - `fix_parse_errors_total`, labelled with the `error`
+//! - `fix_checksum_failures_total`
+//! - `fix_resend_requests_total`, labelled with `session_id` and `direction`
+//!   (`inbound` for requests received, `outbound` for requests sent)
+//! - `fix_parse_duration_seconds` and `fix_serialize_duration_seconds` histograms
+//! - `fix_heartbeat_rtt_seconds` histogram of TestRequest to Heartbeat round trips,
+//!   labelled with `session_id`
+
+use crate::{FixMessageBuilder, FixSerializeError, SessionId};
+
+/// Start of a measured operation; holds nothing without the `metrics` feature.
+pub(crate) struct Timer {
+    #[cfg(feature = "metrics")]
+    started: std::time::Instant,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        return Self {
+            #[cfg(feature = "metrics")]
+            started: std::time::Instant::now(),
+        };
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn parsed(result: &Result<FixMessageBuilder, FixSerializeError>, timer: Timer) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::histogram!("fix_parse_duration_seconds").record(timer.started.elapsed());
+
+        if let Err(err) = result {
+            metrics::counter!("fix_parse_errors_total", "error" => format!("{:?}", err))
+                .increment(1);
+
+            if *err == FixSerializeError::InvalidCheckSum {
+                metrics::counter!("fix_checksum_failures_total").increment(1);
+            }
+        }
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn serialized(timer: Timer) {
+    #[cfg(feature = "metrics")]
+    metrics::histogram!("fix_serialize_duration_seconds").record(timer.started.elapsed());
+}
+
+#[allow(unused_variables)]
+pub(crate) fn received(session_id: &SessionId, message: &FixMessageBuilder) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "fix_messages_received_total",
+        "session_id" => session_id.to_string(),
+        "msg_type" => message.get_message_type_as_string(),
+    )
+    .increment(1);
+}
+
+#[allow(unused_variables)]
+pub(crate) fn sent(session_id: &SessionId, message: &FixMessageBuilder) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "fix_messages_sent_total",
+        "session_id" => session_id.to_string(),
+        "msg_type" => message.get_message_type_as_string(),
+    )
+    .increment(1);
+}
+
+#[allow(unused_variables)]
+pub(crate) fn heartbeat_rtt(session_id: &SessionId, rtt: std::time::Duration) {
+    #[cfg(feature = "metrics")]
+    metrics::histogram!("fix_heartbeat_rtt_seconds", "session_id" => session_id.to_string())
+        .record(rtt);
+}
+
+/// `direction` is `inbound` for a ResendRequest received, `outbound` for one sent.
+#[allow(unused_variables)]
+pub(crate) fn resend_requested(session_id: &SessionId, direction: &'static str) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "fix_resend_requests_total",
+        "session_id" => session_id.to_string(),
+        "direction" => direction,
+    )
+    .increment(1);
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod test {
+    use super::*;
+    use crate::{NoopApplication, Session, SessionConfig};
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+    use std::{
+        sync::{Arc, Mutex},
+        time::SystemTime,
+    };
+
+    type Recorded = Arc<Mutex<Vec<String>>>;
+
+    #[derive(Default)]
+    struct RecordingRecorder {
+        recorded: Recorded,
+    }
+
+    struct Handle {
+        key: String,
+        recorded: Recorded,
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.recorded
+                .lock()
+                .unwrap()
+                .push(format!("{} +{}", self.key, value));
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, _: f64) {
+            self.recorded.lock().unwrap().push(self.key.clone());
+        }
+    }
+
+    impl RecordingRecorder {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            let labels: Vec<String> = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+
+            return Arc::new(Handle {
+                key: format!("{}{{{}}}", key.name(), labels.join(",")),
+                recorded: self.recorded.clone(),
+            });
+        }
+    }
+
+    impl Recorder for RecordingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            return Counter::from_arc(self.handle(key));
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            return Gauge::noop();
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            return Histogram::from_arc(self.handle(key));
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        let recorder = RecordingRecorder::default();
+        let session_id = SessionId::new("FIX.4.4", "SELL", "BUY");
+        let mut session = Session::from_config(SessionConfig::new(&session_id));
+
+        metrics::with_local_recorder(&recorder, || {
+            let logon = b"8=FIX.4.4\x019=55\x0135=A\x0149=BUY\x0156=SELL\x0134=1\x0152=20240101-00:00:00\x0198=0\x01108=30\x0110=000\x01";
+            let logon = FixMessageBuilder::from_bytes(logon, false).unwrap();
+            session.on_inbound(&logon, &NoopApplication, SystemTime::now());
+
+            let _ = FixMessageBuilder::from_bytes(b"8=FIX.4.4\x019=5\x0135=0\x0110=000\x01", true);
+            logon.as_bytes();
+        });
+
+        let recorded = recorder.recorded.lock().unwrap();
+        let session_id = session_id.to_string();
+
+        assert_eq!(
+            vec![
+                "fix_parse_duration_seconds{}".to_string(),
+                format!(
+                    "fix_messages_received_total{{session_id={},msg_type=A}} +1",
+                    session_id
+                ),
+                format!(
+                    "fix_messages_sent_total{{session_id={},msg_type=A}} +1",
+                    session_id
+                ),
+                "fix_parse_duration_seconds{}".to_string(),
+                "fix_parse_errors_total{error=InvalidCheckSum} +1".to_string(),
+                "fix_checksum_failures_total{} +1".to_string(),
+                "fix_serialize_duration_seconds{}".to_string(),
+            ],
+            *recorded
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Helpers for tests of code built on the engine, e.g. venue certification tests
+//! checking the messages sent against recorded sessions (`testing` feature).
+//!
+//! Golden files hold one message per line with fields separated by `|`, as printed
+//! by `FixMessageBuilder::to_string`. Blank lines and lines starting with `#` are
+//! skipped, so files can be annotated.
+
+use std::path::Path;
+
+use crate::{fix_diff, FixMessageBuilder, FixSerializeError, VOLATILE_TAGS};
+
+mod mock_counterparty;
+
+pub use mock_counterparty::*;
+
+/// Parses a `|` delimited message. BodyLength and CheckSum aren't checked, so
+/// hand-written messages may leave them out.
+pub fn parse_pipe_delimited(line: &str) -> Result<FixMessageBuilder, FixSerializeError> {
+    let bytes = line.trim().replace('|', "\x01");
+    return FixMessageBuilder::from_bytes(bytes.as_bytes(), false);
+}
+
+/// Parses every message of a golden file's text; errors tell the 1-based line.
+pub fn read_golden(text: &str) -> Result<Vec<FixMessageBuilder>, String> {
+    let mut result = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_pipe_delimited(line) {
+            Ok(message) => result.push(message),
+            Err(err) => return Err(format!("line {}: {:?}", index + 1, err)),
+        }
+    }
+
+    return Ok(result);
+}
+
+pub fn load_golden(path: impl AsRef<Path>) -> std::io::Result<Vec<FixMessageBuilder>> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+
+    return read_golden(&text).map_err(|err| {
+        return std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), err),
+        );
+    });
+}
+
+/// Compares two message sequences pairwise with `semantically_equals`, describing
+/// the first mismatch with its index and field differences.
+pub fn compare_messages(
+    expected: &[FixMessageBuilder],
+    actual: &[FixMessageBuilder],
+    ignore: &[u32],
+) -> Result<(), String> {
+    for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+        if expected.semantically_equals(actual, ignore) {
+            continue;
+        }
+
+        let diff: Vec<String> = fix_diff(expected, actual)
+            .into_iter()
+            .filter(|diff| !ignore.contains(&diff.get_tag()))
+            .map(|diff| format!("  {}", diff))
+            .collect();
+
+        return Err(format!(
+            "message {} differs\n  expected: {}\n  actual:   {}\n{}",
+            index + 1,
+            expected,
+            actual,
+            diff.join("\n")
+        ));
+    }
+
+    if expected.len() != actual.len() {
+        return Err(format!(
+            "expected {} messages, got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    return Ok(());
+}
+
+/// Panics unless `actual` matches the messages of the golden file at `path`,
+/// ignoring `VOLATILE_TAGS`.
+#[track_caller]
+pub fn assert_golden(path: impl AsRef<Path>, actual: &[FixMessageBuilder]) {
+    let expected = load_golden(path).unwrap();
+
+    if let Err(err) = compare_messages(&expected, actual, VOLATILE_TAGS) {
+        panic!("{}", err);
+    }
+}
+
+/// Panics unless the messages of both golden files match pairwise, ignoring
+/// `VOLATILE_TAGS`.
+#[track_caller]
+pub fn assert_golden_files(expected: impl AsRef<Path>, actual: impl AsRef<Path>) {
+    let actual = load_golden(actual).unwrap();
+    assert_golden(expected, &actual);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GOLDEN: &str = "
+# logon
+8=FIX.4.4|9=65|35=A|49=BUY|56=SELL|34=1|52=20180920-18:24:59.000|98=0|108=30|10=000|
+
+8=FIX.4.4|35=D|49=BUY|56=SELL|11=ORDER1|55=IBM|
+";
+
+    #[test]
+    fn test_compare_with_golden() {
+        let expected = read_golden(GOLDEN).unwrap();
+        assert_eq!(2, expected.len());
+
+        let mut logon = FixMessageBuilder::new("FIX.4.4", "A");
+        logon.with_value(49, "BUY");
+        logon.with_value(56, "SELL");
+        logon.with_value(34, 1u64);
+        logon.with_value(52, "20240102-03:04:05.000");
+        logon.with_value(98, 0u64);
+        logon.with_value(108, 30u64);
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(49, "BUY");
+        order.with_value(56, "SELL");
+        order.with_value(11, "ORDER1");
+        order.with_value(55, "MSFT");
+
+        let actual = vec![logon.clone(), order];
+        let err = compare_messages(&expected, &actual, VOLATILE_TAGS).unwrap_err();
+
+        assert!(err.starts_with("message 2 differs"));
+        assert!(err.ends_with("  ~ 55: IBM -> MSFT"));
+        assert_eq!(
+            Err("expected 2 messages, got 1".to_string()),
+            compare_messages(&expected, &[logon], VOLATILE_TAGS)
+        );
+        assert_eq!(
+            Err("line 2: MessageTypeTagNotFoundInSource".to_string()),
+            read_golden("\n8=FIX.4.4|49=BUY|")
+        );
+    }
+}
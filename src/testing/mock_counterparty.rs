@@ -0,0 +1,562 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    parse_frame, read_frame, FixApplication, FixMessageBuilder, MemoryMessageStore, ParseLimits,
+    Session, SessionAction, SessionConfig, SessionId, SessionSendError,
+};
+
+const TIMER_INTERVAL: Duration = Duration::from_millis(200);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+type Responder = Box<dyn Fn(&FixMessageBuilder) -> Vec<FixMessageBuilder> + Send + Sync>;
+
+/// Fault injected into the messages a mock counterparty sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Skips this many sequence numbers before the next application message.
+    SequenceGap(u64),
+    /// Sends the next message with a wrong CheckSum.
+    BadCheckSum,
+    /// Holds back every Heartbeat by this long until the faults are cleared.
+    SlowHeartbeats(Duration),
+}
+
+#[derive(Default)]
+struct Inbox {
+    messages: Mutex<Vec<FixMessageBuilder>>,
+}
+
+impl std::fmt::Debug for Inbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str("Inbox");
+    }
+}
+
+impl FixApplication for Inbox {
+    fn on_app_message(&self, _: &SessionId, message: &FixMessageBuilder) {
+        self.messages.lock().unwrap().push(message.clone());
+    }
+}
+
+struct MockShared {
+    session: Mutex<Session>,
+    stream: Mutex<Option<TcpStream>>,
+    inbox: Inbox,
+    responders: Mutex<HashMap<String, Responder>>,
+    received: Mutex<Vec<FixMessageBuilder>>,
+    faults: Mutex<Vec<Fault>>,
+}
+
+/// Counterparty side of a `MockAcceptor` or `MockInitiator`.
+///
+/// Runs a regular `Session`, answers application messages with the responses
+/// scripted for their MsgType and records everything it receives.
+#[derive(Clone)]
+pub struct MockCounterparty {
+    shared: Arc<MockShared>,
+}
+
+impl MockCounterparty {
+    // Sent messages are kept so resend requests, e.g. after a `SequenceGap`, replay them.
+    fn new(config: SessionConfig) -> Self {
+        let mut session = Session::from_config(config);
+        session.with_message_store(Arc::new(MemoryMessageStore::new()));
+
+        return Self {
+            shared: Arc::new(MockShared {
+                session: Mutex::new(session),
+                stream: Mutex::new(None),
+                inbox: Inbox::default(),
+                responders: Mutex::new(HashMap::new()),
+                received: Mutex::new(vec![]),
+                faults: Mutex::new(vec![]),
+            }),
+        };
+    }
+
+    /// Answers every application message of `msg_type` with the messages `responder` builds.
+    pub fn respond_to(
+        &self,
+        msg_type: &str,
+        responder: impl Fn(&FixMessageBuilder) -> Vec<FixMessageBuilder> + Send + Sync + 'static,
+    ) {
+        self.shared
+            .responders
+            .lock()
+            .unwrap()
+            .insert(msg_type.to_string(), Box::new(responder));
+    }
+
+    /// Answers every application message of `msg_type` with a copy of `response`.
+    pub fn respond_with(&self, msg_type: &str, response: FixMessageBuilder) {
+        self.respond_to(msg_type, move |_| vec![response.clone()]);
+    }
+
+    pub fn inject(&self, fault: Fault) {
+        self.shared.faults.lock().unwrap().push(fault);
+    }
+
+    pub fn clear_faults(&self) {
+        self.shared.faults.lock().unwrap().clear();
+    }
+
+    pub fn is_logged_on(&self) -> bool {
+        return self.shared.session.lock().unwrap().is_logged_on();
+    }
+
+    pub fn get_session_id(&self) -> SessionId {
+        return self.shared.session.lock().unwrap().get_session_id().clone();
+    }
+
+    /// Every message received so far, session messages included.
+    pub fn get_received(&self) -> Vec<FixMessageBuilder> {
+        return self.shared.received.lock().unwrap().clone();
+    }
+
+    /// Waits for the first received message of `msg_type`.
+    pub fn wait_for(&self, msg_type: &str, timeout: Duration) -> Option<FixMessageBuilder> {
+        let started = SystemTime::now();
+
+        loop {
+            let found = self
+                .shared
+                .received
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|message| message.get_message_type_as_string() == msg_type)
+                .cloned();
+
+            if found.is_some() {
+                return found;
+            }
+
+            if started.elapsed().unwrap_or_default() >= timeout {
+                return None;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Sends an unsolicited application message.
+    pub fn send(&self, message: FixMessageBuilder) -> Result<(), SessionSendError> {
+        let mut session = self.shared.session.lock().unwrap();
+        return self.send_locked(&mut session, message);
+    }
+
+    /// Drops the connection without a Logout.
+    pub fn disconnect(&self) {
+        if let Some(stream) = self.shared.stream.lock().unwrap().as_ref() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+
+    fn send_locked(
+        &self,
+        session: &mut Session,
+        message: FixMessageBuilder,
+    ) -> Result<(), SessionSendError> {
+        let gap = self.take_fault(|fault| match fault {
+            Fault::SequenceGap(count) => Some(*count),
+            _ => None,
+        });
+
+        if let Some(count) = gap {
+            let next = session.get_next_sender_seq_num();
+            session.set_next_sender_seq_num(next + count);
+        }
+
+        let message = session.send_app(message, &self.shared.inbox, SystemTime::now())?;
+        self.execute(vec![SessionAction::Send(message)]);
+
+        return Ok(());
+    }
+
+    // Removes the first pending one-shot fault `select` matches.
+    fn take_fault<T>(&self, select: impl Fn(&Fault) -> Option<T>) -> Option<T> {
+        let mut faults = self.shared.faults.lock().unwrap();
+        let index = faults.iter().position(|fault| select(fault).is_some())?;
+
+        return select(&faults.remove(index));
+    }
+
+    fn get_heartbeat_delay(&self) -> Option<Duration> {
+        return self
+            .shared
+            .faults
+            .lock()
+            .unwrap()
+            .iter()
+            .find_map(|fault| match fault {
+                Fault::SlowHeartbeats(delay) => Some(*delay),
+                _ => None,
+            });
+    }
+
+    // Called with the session locked so messages hit the wire in sequence number order.
+    fn execute(&self, actions: Vec<SessionAction>) -> bool {
+        let mut stream = self.shared.stream.lock().unwrap();
+
+        let Some(stream) = stream.as_mut() else {
+            return false;
+        };
+
+        for action in actions {
+            match action {
+                SessionAction::Send(message) => {
+                    let mut bytes = message.as_bytes();
+
+                    if self
+                        .take_fault(|fault| (*fault == Fault::BadCheckSum).then_some(()))
+                        .is_some()
+                    {
+                        corrupt_check_sum(&mut bytes);
+                    }
+
+                    if stream.write_all(&bytes).is_err() {
+                        return false;
+                    }
+                }
+                SessionAction::Disconnect => {
+                    let _ = stream.shutdown(Shutdown::Both);
+                    return false;
+                }
+            }
+        }
+
+        return true;
+    }
+
+    fn run_connection(&self, mut stream: TcpStream) {
+        let connected = Arc::new(AtomicBool::new(true));
+
+        let timer_thread = {
+            let counterparty = self.clone();
+            let connected = connected.clone();
+
+            thread::spawn(move || {
+                while connected.load(Ordering::SeqCst) {
+                    thread::sleep(TIMER_INTERVAL);
+                    counterparty.on_timer();
+                }
+            })
+        };
+
+        let mut buffer = vec![];
+        let limits = ParseLimits::default();
+
+        while let Ok(frame_len) = read_frame(&mut stream, &mut buffer, &limits) {
+            let frame: Vec<u8> = buffer.drain(..frame_len).collect();
+
+            let Ok(message) = parse_frame(&frame, &limits) else {
+                break;
+            };
+
+            if !self.on_message(message) {
+                break;
+            }
+        }
+
+        connected.store(false, Ordering::SeqCst);
+        let _ = stream.shutdown(Shutdown::Both);
+        let _ = timer_thread.join();
+
+        let mut session = self.shared.session.lock().unwrap();
+        *self.shared.stream.lock().unwrap() = None;
+        session.disconnected(&self.shared.inbox, SystemTime::now());
+    }
+
+    fn on_message(&self, message: FixMessageBuilder) -> bool {
+        self.shared.received.lock().unwrap().push(message.clone());
+
+        let mut session = self.shared.session.lock().unwrap();
+        let actions = session.on_inbound(&message, &self.shared.inbox, SystemTime::now());
+
+        if !self.execute(actions) {
+            return false;
+        }
+
+        let delivered: Vec<FixMessageBuilder> = self
+            .shared
+            .inbox
+            .messages
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect();
+
+        for message in delivered {
+            let responses = match self
+                .shared
+                .responders
+                .lock()
+                .unwrap()
+                .get(&message.get_message_type_as_string())
+            {
+                Some(responder) => responder(&message),
+                None => continue,
+            };
+
+            for response in responses {
+                let _ = self.send_locked(&mut session, response);
+            }
+        }
+
+        return true;
+    }
+
+    fn on_timer(&self) {
+        let delay = self.get_heartbeat_delay();
+        let mut session = self.shared.session.lock().unwrap();
+        let actions = session.on_timer(SystemTime::now());
+
+        let Some(delay) = delay else {
+            self.execute(actions);
+            return;
+        };
+
+        drop(session);
+
+        // Sequence numbers are already taken, so a held back Heartbeat still has to go out.
+        for action in actions {
+            if let SessionAction::Send(message) = &action {
+                if message.get_message_type_as_string() == "0" {
+                    thread::sleep(delay);
+                }
+            }
+
+            let _session = self.shared.session.lock().unwrap();
+            self.execute(vec![action]);
+        }
+    }
+}
+
+// Replaces the CheckSum value with one that's off by one.
+fn corrupt_check_sum(bytes: &mut [u8]) {
+    let len = bytes.len();
+
+    if len < 4 {
+        return;
+    }
+
+    let sum = bytes[len - 4..len - 1].iter().fold(0u8, |acc, digit| {
+        acc.wrapping_mul(10).wrapping_add(digit - b'0')
+    });
+    let wrong = format!("{:03}", sum.wrapping_add(1));
+
+    bytes[len - 4..len - 1].copy_from_slice(wrong.as_bytes());
+}
+
+/// Accepts one connection at a time on a local port and plays the counterparty
+/// of the session in `config`.
+pub struct MockAcceptor {
+    counterparty: MockCounterparty,
+    port: u16,
+}
+
+impl MockAcceptor {
+    /// Listens on an ephemeral port of 127.0.0.1.
+    pub fn start(config: SessionConfig) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let port = listener.local_addr()?.port();
+        let counterparty = MockCounterparty::new(config);
+
+        {
+            let counterparty = counterparty.clone();
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(stream) = stream else {
+                        return;
+                    };
+
+                    let Ok(writer) = stream.try_clone() else {
+                        continue;
+                    };
+
+                    *counterparty.shared.stream.lock().unwrap() = Some(writer);
+                    counterparty.run_connection(stream);
+                }
+            });
+        }
+
+        return Ok(Self { counterparty, port });
+    }
+
+    pub fn get_port(&self) -> u16 {
+        return self.port;
+    }
+}
+
+impl Deref for MockAcceptor {
+    type Target = MockCounterparty;
+
+    fn deref(&self) -> &MockCounterparty {
+        return &self.counterparty;
+    }
+}
+
+/// Connects to an acceptor, sends the Logon and plays the counterparty of the
+/// session in `config`.
+pub struct MockInitiator {
+    counterparty: MockCounterparty,
+}
+
+impl MockInitiator {
+    pub fn connect(config: SessionConfig, address: SocketAddr) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        let counterparty = MockCounterparty::new(config);
+
+        {
+            let mut session = counterparty.shared.session.lock().unwrap();
+            *counterparty.shared.stream.lock().unwrap() = Some(stream.try_clone()?);
+
+            let logon = session.logon(SystemTime::now());
+            counterparty.execute(vec![SessionAction::Send(logon)]);
+        }
+
+        {
+            let counterparty = counterparty.clone();
+            thread::spawn(move || counterparty.run_connection(stream));
+        }
+
+        return Ok(Self { counterparty });
+    }
+}
+
+impl Deref for MockInitiator {
+    type Target = MockCounterparty;
+
+    fn deref(&self) -> &MockCounterparty {
+        return &self.counterparty;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{SyncAcceptor, SyncInitiator};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[derive(Debug, Default)]
+    struct RecordingApplication {
+        messages: Mutex<Vec<FixMessageBuilder>>,
+    }
+
+    impl FixApplication for RecordingApplication {
+        fn on_app_message(&self, _: &SessionId, message: &FixMessageBuilder) {
+            self.messages.lock().unwrap().push(message.clone());
+        }
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..250 {
+            if condition() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_mock_acceptor() {
+        let mock = MockAcceptor::start(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )))
+        .unwrap();
+
+        mock.respond_to("D", |order| {
+            let mut report = FixMessageBuilder::new("FIX.4.4", "8");
+            report.with_value(11, order.get_string_lossy(11).unwrap_or_default());
+            report.with_value(150, "0");
+            vec![report]
+        });
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(mock.get_port());
+
+        let app = Arc::new(RecordingApplication::default());
+        let mut initiator = SyncInitiator::new(config, app.clone());
+        let handle = initiator.handle();
+        thread::spawn(move || initiator.run());
+
+        wait_until(|| handle.is_logged_on() && mock.is_logged_on());
+        assert!(mock.wait_for("A", TIMEOUT).is_some());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        handle.send(order).unwrap();
+
+        wait_until(|| !app.messages.lock().unwrap().is_empty());
+        assert_eq!(
+            Some("ORDER1".to_string()),
+            app.messages.lock().unwrap()[0].get_string_lossy(11)
+        );
+
+        // The initiator asks for the skipped messages and gets the message resent.
+        mock.inject(Fault::SequenceGap(2));
+        mock.send(FixMessageBuilder::new("FIX.4.4", "B")).unwrap();
+
+        let resend_request = mock.wait_for("2", TIMEOUT).unwrap();
+        assert_eq!(Some("3".to_string()), resend_request.get_string_lossy(7));
+
+        wait_until(|| app.messages.lock().unwrap().len() == 2);
+        assert_eq!(
+            "B",
+            app.messages.lock().unwrap()[1].get_message_type_as_string()
+        );
+
+        handle.logout(None).unwrap();
+        wait_until(|| !mock.is_logged_on());
+        assert!(!mock.is_logged_on());
+    }
+
+    #[test]
+    fn test_mock_initiator_bad_check_sum() {
+        let acceptor = SyncAcceptor::new(Arc::new(crate::NoopApplication));
+        let handle = acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || acceptor.serve(listener));
+
+        let mock = MockInitiator::connect(
+            SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL")),
+            address,
+        )
+        .unwrap();
+
+        wait_until(|| handle.is_logged_on());
+        assert!(mock.wait_for("A", TIMEOUT).is_some());
+
+        mock.inject(Fault::BadCheckSum);
+        mock.send(FixMessageBuilder::new("FIX.4.4", "B")).unwrap();
+
+        wait_until(|| !handle.is_logged_on());
+        assert!(!handle.is_logged_on());
+    }
+
+    #[test]
+    fn test_corrupt_check_sum() {
+        let mut bytes = FixMessageBuilder::new("FIX.4.4", "0").as_bytes();
+        corrupt_check_sum(&mut bytes);
+
+        assert!(FixMessageBuilder::from_bytes(&bytes, true).is_err());
+        assert!(FixMessageBuilder::from_bytes(&bytes, false).is_ok());
+    }
+}
@@ -0,0 +1,897 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+
+use bytes::BytesMut;
+use futures_util::{Sink, SinkExt, StreamExt};
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc,
+};
+use tokio_util::{
+    codec::{Decoder, Encoder, Framed},
+    sync::PollSender,
+};
+
+use crate::{
+    acquire_leadership, is_admin_message_type, is_leader, Direction, FixApplication, FixCodec,
+    FixCodecError, FixMessage, FixMessageBuilder, Journal, Leadership, OutboundPriority,
+    OutboundQueue, ParseLimits, RateLimiter, ReconnectPolicy, Reconnector, ScheduleAction, Session,
+    SessionAction, SessionConfig, SessionId, SessionRegistry,
+};
+
+const TIMER_INTERVAL: Duration = Duration::from_secs(1);
+const FIRST_MESSAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionEnd {
+    Disconnected,
+    LoggedOutByHandle,
+}
+
+enum Control {
+    Logout(Option<String>),
+    DisconnectNow,
+}
+
+struct SessionCommands {
+    messages: mpsc::Receiver<FixMessageBuilder>,
+    control: mpsc::UnboundedReceiver<Control>,
+}
+
+// Optional collaborators of a running connection.
+#[derive(Clone, Copy)]
+struct ConnectionHooks<'a> {
+    leadership: Option<&'a dyn Leadership>,
+    journal: Option<&'a Journal>,
+}
+
+impl ConnectionHooks<'_> {
+    // Journal failures don't affect the session.
+    fn journal(&self, direction: Direction, session_id: &SessionId, message: &[u8]) {
+        if let Some(journal) = self.journal {
+            let _ = journal.append(direction, session_id, message, SystemTime::now());
+        }
+    }
+}
+
+// `FixCodec` also yielding each received frame, so the journal keeps the bytes as they
+// came off the wire rather than the parsed message serialized again.
+#[derive(Default)]
+struct RawFrameCodec(FixCodec);
+
+impl Decoder for RawFrameCodec {
+    type Item = (BytesMut, FixMessageBuilder);
+    type Error = FixCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        return self.0.decode_frame(src);
+    }
+}
+
+impl Encoder<FixMessageBuilder> for RawFrameCodec {
+    type Error = FixCodecError;
+
+    fn encode(&mut self, item: FixMessageBuilder, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        return self.0.encode(item, dst);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionClosed;
+
+pub enum SessionTrySendError {
+    /// The outbound queue is full; the socket isn't keeping up.
+    Full(FixMessageBuilder),
+    Closed(FixMessageBuilder),
+}
+
+/// Sends application messages through a running session.
+///
+/// Messages sent before the session is logged on are held until it is. At most
+/// `outbound_queue_capacity` messages are buffered; beyond that `send` waits and
+/// `try_send` fails with [`SessionTrySendError::Full`].
+#[derive(Clone)]
+pub struct SessionHandle {
+    session_id: SessionId,
+    messages: mpsc::Sender<FixMessageBuilder>,
+    control: mpsc::UnboundedSender<Control>,
+}
+
+impl SessionHandle {
+    fn channel(session_id: SessionId, capacity: usize) -> (Self, SessionCommands) {
+        let (messages, messages_receiver) = mpsc::channel(capacity.max(1));
+        let (control, control_receiver) = mpsc::unbounded_channel();
+
+        let handle = Self {
+            session_id,
+            messages,
+            control,
+        };
+        let commands = SessionCommands {
+            messages: messages_receiver,
+            control: control_receiver,
+        };
+
+        return (handle, commands);
+    }
+
+    pub fn get_session_id(&self) -> &SessionId {
+        return &self.session_id;
+    }
+
+    pub async fn send(&self, message: FixMessageBuilder) -> Result<(), SessionClosed> {
+        return self.messages.send(message).await.map_err(|_| SessionClosed);
+    }
+
+    pub fn try_send(&self, message: FixMessageBuilder) -> Result<(), SessionTrySendError> {
+        return self.messages.try_send(message).map_err(|err| match err {
+            mpsc::error::TrySendError::Full(message) => SessionTrySendError::Full(message),
+            mpsc::error::TrySendError::Closed(message) => SessionTrySendError::Closed(message),
+        });
+    }
+
+    /// Sends a Logout once the messages already handed to `send` are out, then closes
+    /// the connection when the counterparty confirms it or `logout_timeout` passes.
+    /// The initiator won't reconnect afterwards.
+    pub fn logout(&self, text: Option<&str>) -> Result<(), SessionClosed> {
+        return self
+            .control
+            .send(Control::Logout(text.map(|text| text.to_string())))
+            .map_err(|_| SessionClosed);
+    }
+
+    /// Closes the connection without a Logout, dropping any queued messages.
+    /// The initiator won't reconnect afterwards.
+    pub fn disconnect_now(&self) -> Result<(), SessionClosed> {
+        return self
+            .control
+            .send(Control::DisconnectNow)
+            .map_err(|_| SessionClosed);
+    }
+
+    /// The outbound side as a `Sink`, waiting like `send` when the queue is full.
+    pub fn sink(&self) -> SessionSink {
+        return SessionSink {
+            messages: PollSender::new(self.messages.clone()),
+        };
+    }
+}
+
+/// `Sink` of application messages for a running session, from `SessionHandle::sink`.
+pub struct SessionSink {
+    messages: PollSender<FixMessageBuilder>,
+}
+
+impl Sink<FixMessage> for SessionSink {
+    type Error = SessionClosed;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        return self.messages.poll_reserve(cx).map_err(|_| SessionClosed);
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: FixMessage) -> Result<(), Self::Error> {
+        return self
+            .messages
+            .send_item(message.to_builder())
+            .map_err(|_| SessionClosed);
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        return Poll::Ready(Ok(()));
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.messages.close();
+        return Poll::Ready(Ok(()));
+    }
+}
+
+/// Connects to a counterparty, logs on and keeps the session alive, reconnecting on failures.
+pub struct FixInitiator {
+    session: Session,
+    app: Arc<dyn FixApplication>,
+    reconnector: Reconnector,
+    handle: SessionHandle,
+    commands: SessionCommands,
+    leadership: Option<Arc<dyn Leadership>>,
+    journal: Option<Arc<Journal>>,
+}
+
+impl FixInitiator {
+    pub fn new(config: SessionConfig, app: Arc<dyn FixApplication>) -> Self {
+        return Self::from_session(Session::from_config(config), app);
+    }
+
+    /// Runs an already set up session, e.g. one with a `SeqNumStore` attached.
+    pub fn from_session(session: Session, app: Arc<dyn FixApplication>) -> Self {
+        let reconnector = Reconnector::new(
+            session.get_session_id().clone(),
+            ReconnectPolicy::from_config(session.get_config()),
+        );
+        let (handle, commands) = SessionHandle::channel(
+            session.get_session_id().clone(),
+            session.get_config().outbound_queue_capacity,
+        );
+
+        return Self {
+            session,
+            app,
+            reconnector,
+            handle,
+            commands,
+            leadership: None,
+            journal: None,
+        };
+    }
+
+    pub fn get_reconnector_mut(&mut self) -> &mut Reconnector {
+        return &mut self.reconnector;
+    }
+
+    /// Only connects while this instance leads the session.
+    pub fn with_leadership(&mut self, leadership: Arc<dyn Leadership>) {
+        self.leadership = Some(leadership);
+    }
+
+    /// Records every message sent and received.
+    pub fn with_journal(&mut self, journal: Arc<Journal>) {
+        self.journal = Some(journal);
+    }
+
+    pub fn handle(&self) -> SessionHandle {
+        return self.handle.clone();
+    }
+
+    /// Runs the session until the reconnect policy gives up, returning the last error.
+    pub async fn run(mut self) -> std::io::Result<()> {
+        let address = format!(
+            "{}:{}",
+            self.session
+                .get_config()
+                .socket_connect_host
+                .clone()
+                .unwrap_or_else(|| "127.0.0.1".to_string()),
+            self.session.get_config().socket_connect_port.unwrap_or(0)
+        );
+
+        loop {
+            while self.session.check_schedule(SystemTime::now()) == ScheduleAction::EndSession
+                || !self.is_session_time()
+            {
+                tokio::time::sleep(TIMER_INTERVAL).await;
+            }
+
+            if !acquire_leadership(self.leadership.as_deref(), &mut self.session) {
+                tokio::time::sleep(TIMER_INTERVAL).await;
+                continue;
+            }
+
+            match TcpStream::connect(&address).await {
+                Ok(stream) => {
+                    self.reconnector.connected();
+
+                    let logon = self.session.logon(SystemTime::now());
+                    let result = run_connection(
+                        stream,
+                        &mut self.session,
+                        self.app.as_ref(),
+                        &mut self.commands,
+                        ConnectionHooks {
+                            leadership: self.leadership.as_deref(),
+                            journal: self.journal.as_deref(),
+                        },
+                        vec![logon],
+                    )
+                    .await;
+
+                    self.reconnector.disconnected();
+
+                    match result {
+                        Ok(ConnectionEnd::LoggedOutByHandle) => return Ok(()),
+                        Ok(ConnectionEnd::Disconnected) => {}
+                        Err(err) => {
+                            if self.reconnector.next_delay().is_none() {
+                                return Err(err);
+                            }
+                        }
+                    }
+
+                    tokio::time::sleep(Duration::from_secs(
+                        self.session.get_config().reconnect_interval,
+                    ))
+                    .await;
+                }
+                Err(err) => match self.reconnector.next_delay() {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(err),
+                },
+            }
+        }
+    }
+
+    fn is_session_time(&self) -> bool {
+        return match self.session.get_schedule() {
+            Some(schedule) => schedule.is_session_time(SystemTime::now()),
+            None => true,
+        };
+    }
+}
+
+struct AcceptorState {
+    registry: SessionRegistry,
+    receivers: HashMap<SessionId, SessionCommands>,
+}
+
+/// Accepts connections for a set of configured sessions.
+///
+/// The session is picked from the comp ids of the counterparty's Logon.
+#[derive(Clone)]
+pub struct FixAcceptor {
+    state: Arc<Mutex<AcceptorState>>,
+    handles: Arc<Mutex<HashMap<SessionId, SessionHandle>>>,
+    app: Arc<dyn FixApplication>,
+    leadership: Option<Arc<dyn Leadership>>,
+    journal: Option<Arc<Journal>>,
+}
+
+impl FixAcceptor {
+    pub fn new(app: Arc<dyn FixApplication>) -> Self {
+        return Self {
+            state: Arc::new(Mutex::new(AcceptorState {
+                registry: SessionRegistry::new(),
+                receivers: HashMap::new(),
+            })),
+            handles: Arc::new(Mutex::new(HashMap::new())),
+            app,
+            leadership: None,
+            journal: None,
+        };
+    }
+
+    /// Only accepts Logons for sessions this instance leads.
+    pub fn with_leadership(&mut self, leadership: Arc<dyn Leadership>) {
+        self.leadership = Some(leadership);
+    }
+
+    /// Records every message sent and received.
+    pub fn with_journal(&mut self, journal: Arc<Journal>) {
+        self.journal = Some(journal);
+    }
+
+    pub fn add_session(&self, config: SessionConfig) -> SessionHandle {
+        return self.register_session(Session::from_config(config));
+    }
+
+    /// Adds an already set up session, e.g. one with a `SeqNumStore` attached.
+    pub fn register_session(&self, session: Session) -> SessionHandle {
+        let session_id = session.get_session_id().clone();
+        let (handle, receiver) = SessionHandle::channel(
+            session_id.clone(),
+            session.get_config().outbound_queue_capacity,
+        );
+
+        let mut state = self.state.lock().unwrap();
+        state.registry.register(session);
+        state.receivers.insert(session_id.clone(), receiver);
+        self.handles
+            .lock()
+            .unwrap()
+            .insert(session_id, handle.clone());
+
+        return handle;
+    }
+
+    pub fn handle(&self, session_id: &SessionId) -> Option<SessionHandle> {
+        return self.handles.lock().unwrap().get(session_id).cloned();
+    }
+
+    pub async fn bind(&self, address: impl ToSocketAddrs) -> std::io::Result<TcpListener> {
+        return TcpListener::bind(address).await;
+    }
+
+    /// Accepts connections forever, running every session on its own task.
+    pub async fn serve(&self, listener: TcpListener) -> std::io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let acceptor = self.clone();
+
+            tokio::spawn(async move {
+                let _ = acceptor.handle_connection(stream).await;
+            });
+        }
+    }
+
+    async fn handle_connection(&self, stream: TcpStream) -> std::io::Result<()> {
+        let mut framed = Framed::new(stream, RawFrameCodec::default());
+
+        let (first_frame, first) =
+            match tokio::time::timeout(FIRST_MESSAGE_TIMEOUT, framed.next()).await {
+                Ok(Some(Ok(frame))) => frame,
+                _ => return Ok(()),
+            };
+
+        let Some((mut session, mut receiver)) = self.take_session(&first) else {
+            return Ok(());
+        };
+
+        framed
+            .codec_mut()
+            .0
+            .with_limits(ParseLimits::from_config(session.get_config()));
+
+        if !acquire_leadership(self.leadership.as_deref(), &mut session) {
+            self.return_session(session, receiver);
+            return Ok(());
+        }
+
+        let hooks = ConnectionHooks {
+            leadership: self.leadership.as_deref(),
+            journal: self.journal.as_deref(),
+        };
+        hooks.journal(Direction::Inbound, session.get_session_id(), &first_frame);
+        session.set_remote_address(framed.get_ref().peer_addr().ok());
+
+        let actions = session.on_inbound(&first, self.app.as_ref(), SystemTime::now());
+        let result = run_framed(
+            framed,
+            &mut session,
+            self.app.as_ref(),
+            &mut receiver,
+            hooks,
+            actions,
+        )
+        .await;
+
+        self.return_session(session, receiver);
+
+        return result.map(|_| ());
+    }
+
+    fn return_session(&self, session: Session, receiver: SessionCommands) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .receivers
+            .insert(session.get_session_id().clone(), receiver);
+        state.registry.register(session);
+    }
+
+    fn take_session(&self, first: &FixMessageBuilder) -> Option<(Session, SessionCommands)> {
+        let mut state = self.state.lock().unwrap();
+        let session_id = state
+            .registry
+            .find_for_inbound(first)?
+            .get_session_id()
+            .clone();
+
+        let receiver = state.receivers.remove(&session_id)?;
+        let session = state.registry.remove(&session_id)?;
+
+        return Some((session, receiver));
+    }
+}
+
+async fn run_connection(
+    stream: TcpStream,
+    session: &mut Session,
+    app: &dyn FixApplication,
+    commands: &mut SessionCommands,
+    hooks: ConnectionHooks<'_>,
+    initial: Vec<FixMessageBuilder>,
+) -> std::io::Result<ConnectionEnd> {
+    let mut codec = FixCodec::default();
+    codec.with_limits(ParseLimits::from_config(session.get_config()));
+
+    let framed = Framed::new(stream, RawFrameCodec(codec));
+    let actions = initial.into_iter().map(SessionAction::Send).collect();
+
+    return run_framed(framed, session, app, commands, hooks, actions).await;
+}
+
+async fn run_framed(
+    mut framed: Framed<TcpStream, RawFrameCodec>,
+    session: &mut Session,
+    app: &dyn FixApplication,
+    commands: &mut SessionCommands,
+    hooks: ConnectionHooks<'_>,
+    mut actions: Vec<SessionAction>,
+) -> std::io::Result<ConnectionEnd> {
+    let mut timer = tokio::time::interval(TIMER_INTERVAL);
+    let mut queue = OutboundQueue::new(session.get_config().outbound_queue_capacity);
+    let mut rate_limiter = RateLimiter::from_config(session.get_config());
+    let mut messages_open = true;
+    let mut control_open = true;
+    let mut end = ConnectionEnd::Disconnected;
+
+    let result = 'connection: loop {
+        // Sequence numbers are assigned here, once a message leaves the queue, so
+        // admin messages can overtake application ones without opening a gap.
+        let mut throttle = None;
+
+        while session.is_logged_on() {
+            if let (Some(OutboundPriority::App), Some(limiter)) =
+                (queue.peek_priority(), rate_limiter.as_mut())
+            {
+                let now = SystemTime::now();
+
+                if !limiter.try_acquire(now) {
+                    throttle = Some(limiter.get_wait_time(now));
+                    break;
+                }
+            }
+
+            let Some((priority, message)) = queue.pop() else {
+                break;
+            };
+
+            prepare_dequeued(session, app, priority, message, &mut actions);
+        }
+
+        for action in actions.drain(..) {
+            match action {
+                SessionAction::Send(message) => {
+                    hooks.journal(
+                        Direction::Outbound,
+                        session.get_session_id(),
+                        &message.as_bytes(),
+                    );
+
+                    if let Err(err) = framed.send(message).await {
+                        break 'connection Err(into_io_error(err));
+                    }
+                }
+                SessionAction::Disconnect => {
+                    // Flushes what's buffered and shuts the write half down.
+                    let _ = framed.close().await;
+                    break 'connection Ok(end);
+                }
+            }
+        }
+
+        tokio::select! {
+            frame = framed.next() => match frame {
+                Some(Ok((frame, message))) => {
+                    hooks.journal(Direction::Inbound, session.get_session_id(), &frame);
+                    actions = session.on_inbound(&message, app, SystemTime::now());
+                }
+                Some(Err(err)) => break 'connection Err(into_io_error(err)),
+                None => break 'connection Ok(end),
+            },
+            _ = tokio::time::sleep(throttle.unwrap_or_default()), if throttle.is_some() => {}
+            _ = timer.tick() => {
+                let now = SystemTime::now();
+
+                if session.check_schedule(now) == ScheduleAction::EndSession {
+                    actions.push(SessionAction::Send(session.logout(None, now)));
+                }
+
+                actions.extend(session.on_timer(now));
+
+                if !is_leader(hooks.leadership, session.get_session_id()) {
+                    actions.push(SessionAction::Disconnect);
+                }
+            }
+            // Leaving messages in the channel while the queue is full is what makes
+            // `SessionHandle::send` wait.
+            message = commands.messages.recv(), if messages_open && !queue.is_full() => match message {
+                Some(message) => {
+                    let _ = queue.push(message);
+                }
+                None => messages_open = false,
+            },
+            control = commands.control.recv(), if control_open => match control {
+                Some(Control::Logout(text)) => {
+                    flush_queue(session, app, &mut queue, &mut commands.messages, &mut actions);
+
+                    let logout = session.logout(text.as_deref(), SystemTime::now());
+                    actions.push(SessionAction::Send(logout));
+                    end = ConnectionEnd::LoggedOutByHandle;
+                }
+                Some(Control::DisconnectNow) => break 'connection Ok(ConnectionEnd::LoggedOutByHandle),
+                None => control_open = false,
+            },
+        }
+    };
+
+    session.disconnected(app, SystemTime::now());
+
+    return result;
+}
+
+// Sends whatever is queued or waiting in the channel ahead of a Logout, bypassing the
+// rate limit; nothing can be sent once the Logout is out.
+fn flush_queue(
+    session: &mut Session,
+    app: &dyn FixApplication,
+    queue: &mut OutboundQueue,
+    messages: &mut mpsc::Receiver<FixMessageBuilder>,
+    actions: &mut Vec<SessionAction>,
+) {
+    if !session.is_logged_on() {
+        return;
+    }
+
+    while let Some((priority, message)) = queue.pop() {
+        prepare_dequeued(session, app, priority, message, actions);
+    }
+
+    while let Ok(message) = messages.try_recv() {
+        let priority = match is_admin_message_type(message.get_message_type()) {
+            true => OutboundPriority::Admin,
+            false => OutboundPriority::App,
+        };
+
+        prepare_dequeued(session, app, priority, message, actions);
+    }
+}
+
+fn prepare_dequeued(
+    session: &mut Session,
+    app: &dyn FixApplication,
+    priority: OutboundPriority,
+    mut message: FixMessageBuilder,
+    actions: &mut Vec<SessionAction>,
+) {
+    match priority {
+        OutboundPriority::Admin => {
+            session.prepare_outbound(&mut message, SystemTime::now());
+            actions.push(SessionAction::Send(message));
+        }
+        OutboundPriority::App => {
+            if let Ok(message) = session.send_app(message, app, SystemTime::now()) {
+                actions.push(SessionAction::Send(message));
+            }
+        }
+    }
+}
+
+fn into_io_error(err: FixCodecError) -> std::io::Error {
+    return match err {
+        FixCodecError::Io(err) => err,
+        FixCodecError::Fix(err) => {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", err))
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingApplication {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl FixApplication for RecordingApplication {
+        fn on_app_message(&self, _: &SessionId, message: &FixMessageBuilder) {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(message.get_string_lossy(11).unwrap_or_default());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_initiator_and_acceptor() {
+        let acceptor_app = Arc::new(RecordingApplication::default());
+        let acceptor = FixAcceptor::new(acceptor_app.clone());
+        acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = acceptor.bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { acceptor.serve(listener).await });
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+
+        let initiator = FixInitiator::new(config, Arc::new(crate::NoopApplication));
+        let handle = initiator.handle();
+        tokio::spawn(initiator.run());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        handle.send(order).await.unwrap();
+
+        for _ in 0..100 {
+            if !acceptor_app.messages.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        assert_eq!(vec!["ORDER1"], *acceptor_app.messages.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_journal_keeps_received_bytes() {
+        use std::io::{Read, Write};
+
+        let path =
+            std::env::temp_dir().join(format!("rust-fix-{}-raw.journal", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut acceptor = FixAcceptor::new(Arc::new(crate::NoopApplication));
+        acceptor.with_journal(Arc::new(Journal::open(&path).unwrap()));
+        acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = acceptor.bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { acceptor.serve(listener).await });
+
+        let mut logon = format!(
+            "8=FIX.4.4\x019=0\x0135=A\x0134=1\x0149=BUY\x0156=SELL\x0152={}\x0198=0\x01108=30\x01",
+            crate::format_utc_timestamp(SystemTime::now())
+        )
+        .into_bytes();
+        let body_len = logon.len() - 14;
+        logon.splice(12..13, body_len.to_string().into_bytes());
+        let check_sum = crate::utils::checksum_bytes(&logon);
+        logon.extend_from_slice(b"10=");
+        logon.extend_from_slice(&check_sum);
+        logon.push(crate::utils::FIX_DELIMETR);
+
+        // The acceptor's Logon comes back only once ours is journaled.
+        let sent = logon.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+            stream.write_all(&sent).unwrap();
+            let _ = stream.read(&mut [0u8; 256]).unwrap();
+        })
+        .await
+        .unwrap();
+
+        let inbound = crate::JournalReader::open(&path)
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .find(|entry| entry.direction == Direction::Inbound)
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(logon, inbound.message);
+    }
+
+    #[tokio::test]
+    async fn test_stream_and_sink() {
+        let (acceptor_app, mut inbound) = crate::StreamApplication::new();
+        let acceptor = FixAcceptor::new(Arc::new(acceptor_app));
+        acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = acceptor.bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { acceptor.serve(listener).await });
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+
+        let initiator = FixInitiator::new(config, Arc::new(crate::NoopApplication));
+        let mut sink = initiator.handle().sink();
+        tokio::spawn(initiator.run());
+
+        let orders = ["ORDER1", "ORDER2"].map(|cl_ord_id| {
+            let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+            order.with_value(11, cl_ord_id);
+            return Ok(order.build());
+        });
+        sink.send_all(&mut futures_util::stream::iter(orders))
+            .await
+            .unwrap();
+
+        let received: Vec<(SessionId, FixMessage)> =
+            tokio::time::timeout(Duration::from_secs(5), (&mut inbound).take(2).collect())
+                .await
+                .unwrap();
+
+        assert_eq!(
+            vec![
+                (
+                    SessionId::new("FIX.4.4", "SELL", "BUY"),
+                    "ORDER1".to_string()
+                ),
+                (
+                    SessionId::new("FIX.4.4", "SELL", "BUY"),
+                    "ORDER2".to_string()
+                ),
+            ],
+            received
+                .into_iter()
+                .map(|(session_id, message)| (session_id, message.get_string_lossy(11).unwrap()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logout_flushes_queue() {
+        let acceptor_app = Arc::new(RecordingApplication::default());
+        let acceptor = FixAcceptor::new(acceptor_app.clone());
+        acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = acceptor.bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { acceptor.serve(listener).await });
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+
+        let initiator = FixInitiator::new(config, Arc::new(crate::NoopApplication));
+        let handle = initiator.handle();
+        let initiator_task = tokio::spawn(initiator.run());
+
+        let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        handle.send(order).await.unwrap();
+
+        for _ in 0..100 {
+            if !acceptor_app.messages.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        for cl_ord_id in ["ORDER2", "ORDER3"] {
+            let mut order = FixMessageBuilder::new("FIX.4.4", "D");
+            order.with_value(11, cl_ord_id);
+            assert!(handle.try_send(order).is_ok());
+        }
+        handle.logout(Some("done")).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), initiator_task).await;
+        assert!(result.unwrap().unwrap().is_ok());
+        assert_eq!(
+            vec!["ORDER1", "ORDER2", "ORDER3"],
+            *acceptor_app.messages.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_now() {
+        let acceptor = FixAcceptor::new(Arc::new(crate::NoopApplication));
+        acceptor.add_session(SessionConfig::new(&SessionId::new(
+            "FIX.4.4", "SELL", "BUY",
+        )));
+
+        let listener = acceptor.bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { acceptor.serve(listener).await });
+
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.socket_connect_port = Some(port);
+
+        let initiator = FixInitiator::new(config, Arc::new(crate::NoopApplication));
+        let handle = initiator.handle();
+        let initiator_task = tokio::spawn(initiator.run());
+
+        handle.disconnect_now().unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), initiator_task).await;
+        assert!(result.unwrap().unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_try_send_reports_backpressure() {
+        let mut config = SessionConfig::new(&SessionId::new("FIX.4.4", "BUY", "SELL"));
+        config.outbound_queue_capacity = 1;
+
+        let initiator = FixInitiator::new(config, Arc::new(crate::NoopApplication));
+        let handle = initiator.handle();
+
+        let order = FixMessageBuilder::new("FIX.4.4", "D");
+        assert!(handle.try_send(order.clone()).is_ok());
+        assert!(matches!(
+            handle.try_send(order),
+            Err(SessionTrySendError::Full(_))
+        ));
+    }
+}
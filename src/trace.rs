@@ -0,0 +1,196 @@
+//! Spans and events for the `tracing` crate (`tracing` feature); without the feature
+//! every function here compiles to nothing.
+//!
+//! Spans are named `fix.inbound` and `fix.outbound` and carry `session_id`, `msg_type`
+//! and `seq_num`, so a subscriber can follow an order through a gateway.
+
+use crate::{FixMessageBuilder, FixSerializeError, SessionId, SessionStatus, ValidationError};
+
+#[cfg(feature = "tracing")]
+pub(crate) type SpanGuard = tracing::span::EnteredSpan;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct SpanGuard;
+
+#[cfg(feature = "tracing")]
+fn get_seq_num(message: &FixMessageBuilder) -> u64 {
+    return message.get_msg_seq_num().ok().flatten().unwrap_or_default();
+}
+
+/// Entered while a session processes a received message.
+#[allow(unused_variables)]
+pub(crate) fn enter_inbound(session_id: &SessionId, message: &FixMessageBuilder) -> SpanGuard {
+    #[cfg(feature = "tracing")]
+    return tracing::info_span!(
+        "fix.inbound",
+        session_id = %session_id,
+        msg_type = %message.get_message_type_as_string(),
+        seq_num = get_seq_num(message),
+    )
+    .entered();
+
+    #[cfg(not(feature = "tracing"))]
+    return SpanGuard;
+}
+
+/// Entered while a session prepares an application message; the sequence number
+/// is recorded once it's taken.
+#[allow(unused_variables)]
+pub(crate) fn enter_outbound(session_id: &SessionId, message: &FixMessageBuilder) -> SpanGuard {
+    #[cfg(feature = "tracing")]
+    return tracing::info_span!(
+        "fix.outbound",
+        session_id = %session_id,
+        msg_type = %message.get_message_type_as_string(),
+        seq_num = tracing::field::Empty,
+    )
+    .entered();
+
+    #[cfg(not(feature = "tracing"))]
+    return SpanGuard;
+}
+
+#[allow(unused_variables)]
+pub(crate) fn parsed(payload: &[u8], result: &Result<FixMessageBuilder, FixSerializeError>) {
+    #[cfg(feature = "tracing")]
+    match result {
+        Ok(message) => tracing::trace!(
+            msg_type = %message.get_message_type_as_string(),
+            seq_num = get_seq_num(message),
+            len = payload.len(),
+            "parsed message"
+        ),
+        Err(err) => tracing::debug!(error = ?err, len = payload.len(), "failed to parse message"),
+    }
+}
+
+// Only the tag: the payload may hold a Password (554).
+#[allow(unused_variables)]
+pub(crate) fn tag_not_found(tag: &[u8]) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(tag = %String::from_utf8_lossy(tag), "tag not found");
+}
+
+#[allow(unused_variables)]
+pub(crate) fn validated(payload: &[u8], errors: &[ValidationError]) {
+    #[cfg(feature = "tracing")]
+    if errors.is_empty() {
+        tracing::trace!(len = payload.len(), "message is valid");
+    } else {
+        tracing::debug!(errors = ?errors, len = payload.len(), "message is invalid");
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn sent(session_id: &SessionId, message: &FixMessageBuilder, seq_num: u64) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::Span::current().record("seq_num", seq_num);
+        tracing::debug!(
+            session_id = %session_id,
+            msg_type = %message.get_message_type_as_string(),
+            seq_num,
+            "sending message"
+        );
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn status_changed(session_id: &SessionId, from: SessionStatus, to: SessionStatus) {
+    #[cfg(feature = "tracing")]
+    if from != to {
+        tracing::info!(session_id = %session_id, from = ?from, to = ?to, "session status changed");
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod test {
+    use super::*;
+    use crate::{NoopApplication, Session, SessionConfig};
+    use std::{
+        fmt::Write,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        time::SystemTime,
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    #[derive(Default)]
+    struct RecordingSubscriber {
+        lines: Arc<Mutex<Vec<String>>>,
+        next_id: AtomicU64,
+    }
+
+    struct LineVisitor(String);
+
+    impl Visit for LineVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    impl Subscriber for RecordingSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            return true;
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut visitor = LineVisitor(span.metadata().name().to_string());
+            span.record(&mut visitor);
+            self.lines.lock().unwrap().push(visitor.0);
+
+            return span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1);
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = LineVisitor(String::new());
+            event.record(&mut visitor);
+            self.lines
+                .lock()
+                .unwrap()
+                .push(visitor.0.trim().to_string());
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn test_inbound_logon() {
+        let subscriber = RecordingSubscriber::default();
+        let lines = subscriber.lines.clone();
+
+        let session_id = SessionId::new("FIX.4.4", "SELL", "BUY");
+        let mut session = Session::from_config(SessionConfig::new(&session_id));
+
+        let bytes = b"8=FIX.4.4\x019=55\x0135=A\x0149=BUY\x0156=SELL\x0134=1\x0152=20240101-00:00:00\x0198=0\x01108=30\x0110=000\x01";
+
+        tracing::subscriber::with_default(subscriber, || {
+            let logon = FixMessageBuilder::from_bytes(bytes, false).unwrap();
+            session.on_inbound(&logon, &NoopApplication, SystemTime::now());
+        });
+
+        let lines = lines.lock().unwrap();
+        assert!(lines[0].starts_with("message=parsed message msg_type=A seq_num=1"));
+        assert!(lines.contains(&format!(
+            "fix.inbound session_id={} msg_type=A seq_num=1",
+            session_id
+        )));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("message=sending message") && line.contains("seq_num=1")));
+        assert!(lines
+            .iter()
+            .any(|line| line.ends_with("from=Disconnected to=LoggedOn")));
+    }
+}
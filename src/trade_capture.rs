@@ -0,0 +1,363 @@
+use std::time::SystemTime;
+
+use crate::{
+    fields::{FixField, Side},
+    group::{
+        expect_msg_type, get_coded, get_parsed, get_string, get_timestamp, read_group, required,
+        write_optional, GroupEntry,
+    },
+    parties::{read_parties, write_parties, PARTIES_TAGS},
+    BeginString, FieldParseError, FixMessageBuilder, Party,
+};
+
+const MSG_TYPE_TRADE_CAPTURE_REPORT: &str = "AE";
+const MSG_TYPE_TRADE_CAPTURE_REPORT_REQUEST: &str = "AD";
+
+const TAG_NO_SIDES: u32 = 552;
+const TAG_NO_DATES: u32 = 580;
+
+const SIDE_TAGS: [u32; 4] = [54, 37, 11, 1];
+
+/// Entry of NoSides (552) in a TradeCaptureReport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeCaptureReportSide {
+    /// Side (54).
+    pub side: Side,
+    /// OrderID (37).
+    pub order_id: Option<String>,
+    /// ClOrdID (11).
+    pub cl_ord_id: Option<String>,
+    /// Parties of the side, e.g. the executing firm and, for a give-up, the
+    /// GiveupClearingFirm (PartyRole 14).
+    pub parties: Vec<Party>,
+    /// Account (1).
+    pub account: Option<String>,
+}
+
+/// TradeCaptureReport (AE).
+///
+/// Prices and quantities are kept as written, so no digits are lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeCaptureReport {
+    /// TradeReportID (571).
+    pub trade_report_id: String,
+    /// TradeReportTransType (487).
+    pub trade_report_trans_type: Option<u32>,
+    /// TradeReportType (856).
+    pub trade_report_type: Option<u32>,
+    /// TradeRequestID (568), when the report answers a TradeCaptureReportRequest.
+    pub trade_request_id: Option<String>,
+    /// TrdType (828).
+    pub trd_type: Option<u32>,
+    /// ExecID (17).
+    pub exec_id: Option<String>,
+    /// PreviouslyReported (570).
+    pub previously_reported: bool,
+    /// Symbol (55).
+    pub symbol: Option<String>,
+    /// SecurityID (48).
+    pub security_id: Option<String>,
+    /// SecurityIDSource (22).
+    pub security_id_source: Option<String>,
+    /// LastQty (32).
+    pub last_qty: String,
+    /// LastPx (31).
+    pub last_px: String,
+    /// TradeDate (75).
+    pub trade_date: String,
+    /// TransactTime (60).
+    pub transact_time: Option<SystemTime>,
+    /// NoSides (552), one or two entries.
+    pub sides: Vec<TradeCaptureReportSide>,
+}
+
+/// TradeCaptureReportRequest (AD).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeCaptureReportRequest {
+    /// TradeRequestID (568).
+    pub trade_request_id: String,
+    /// TradeRequestType (569), e.g. `0` for all trades.
+    pub trade_request_type: u32,
+    /// SubscriptionRequestType (263).
+    pub subscription_request_type: Option<char>,
+    /// TradeReportID (571).
+    pub trade_report_id: Option<String>,
+    /// ExecID (17).
+    pub exec_id: Option<String>,
+    /// OrderID (37).
+    pub order_id: Option<String>,
+    /// ClOrdID (11).
+    pub cl_ord_id: Option<String>,
+    pub parties: Vec<Party>,
+    /// Symbol (55).
+    pub symbol: Option<String>,
+    /// SecurityID (48).
+    pub security_id: Option<String>,
+    /// SecurityIDSource (22).
+    pub security_id_source: Option<String>,
+    /// TradeDate (75) of each NoDates (580) entry.
+    pub trade_dates: Vec<String>,
+}
+
+impl TradeCaptureReportSide {
+    pub fn new(side: Side) -> Self {
+        return Self {
+            side,
+            order_id: None,
+            cl_ord_id: None,
+            parties: vec![],
+            account: None,
+        };
+    }
+
+    fn read(entry: &GroupEntry) -> Result<Self, FieldParseError> {
+        return Ok(Self {
+            side: required(get_coded(entry, Side::TAG)?, Side::TAG)?,
+            order_id: get_string(entry, 37)?,
+            cl_ord_id: get_string(entry, 11)?,
+            parties: read_parties(entry)?,
+            account: get_string(entry, 1)?,
+        });
+    }
+}
+
+impl TradeCaptureReport {
+    pub fn new(trade_report_id: &str, last_qty: &str, last_px: &str, trade_date: &str) -> Self {
+        return Self {
+            trade_report_id: trade_report_id.to_string(),
+            trade_report_trans_type: None,
+            trade_report_type: None,
+            trade_request_id: None,
+            trd_type: None,
+            exec_id: None,
+            previously_reported: false,
+            symbol: None,
+            security_id: None,
+            security_id_source: None,
+            last_qty: last_qty.to_string(),
+            last_px: last_px.to_string(),
+            trade_date: trade_date.to_string(),
+            transact_time: None,
+            sides: vec![],
+        };
+    }
+
+    /// The TradeCaptureReport as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_TRADE_CAPTURE_REPORT);
+        message.with_value(571, self.trade_report_id.as_str());
+        write_optional(&mut message, 487, &self.trade_report_trans_type);
+        write_optional(&mut message, 856, &self.trade_report_type);
+        write_optional(&mut message, 568, &self.trade_request_id);
+        write_optional(&mut message, 828, &self.trd_type);
+        write_optional(&mut message, 17, &self.exec_id);
+        message.with_value(570, self.previously_reported);
+        write_optional(&mut message, 55, &self.symbol);
+        write_optional(&mut message, 48, &self.security_id);
+        write_optional(&mut message, 22, &self.security_id_source);
+        message.with_value(32, self.last_qty.as_str());
+        message.with_value(31, self.last_px.as_str());
+        message.with_value(75, self.trade_date.as_str());
+        write_optional(&mut message, 60, &self.transact_time);
+
+        message.with_value(TAG_NO_SIDES, self.sides.len());
+
+        for side in &self.sides {
+            message.with_field(side.side);
+            write_optional(&mut message, 37, &side.order_id);
+            write_optional(&mut message, 11, &side.cl_ord_id);
+            write_parties(&mut message, &side.parties);
+            write_optional(&mut message, 1, &side.account);
+        }
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for TradeCaptureReport {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_TRADE_CAPTURE_REPORT)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let members: Vec<u32> = SIDE_TAGS.iter().chain(&PARTIES_TAGS).copied().collect();
+
+        let mut sides = vec![];
+
+        for entry in read_group(&fields, TAG_NO_SIDES, &members)? {
+            sides.push(TradeCaptureReportSide::read(&entry)?);
+        }
+
+        if sides.is_empty() {
+            return Err(FieldParseError::Missing { tag: TAG_NO_SIDES });
+        }
+
+        return Ok(Self {
+            trade_report_id: required(get_string(&fields, 571)?, 571)?,
+            trade_report_trans_type: get_parsed(&fields, 487)?,
+            trade_report_type: get_parsed(&fields, 856)?,
+            trade_request_id: get_string(&fields, 568)?,
+            trd_type: get_parsed(&fields, 828)?,
+            exec_id: get_string(&fields, 17)?,
+            previously_reported: get_string(&fields, 570)?.as_deref() == Some("Y"),
+            symbol: get_string(&fields, 55)?,
+            security_id: get_string(&fields, 48)?,
+            security_id_source: get_string(&fields, 22)?,
+            last_qty: required(get_string(&fields, 32)?, 32)?,
+            last_px: required(get_string(&fields, 31)?, 31)?,
+            trade_date: required(get_string(&fields, 75)?, 75)?,
+            transact_time: get_timestamp(&fields, 60)?,
+            sides,
+        });
+    }
+}
+
+impl TradeCaptureReportRequest {
+    pub fn new(trade_request_id: &str, trade_request_type: u32) -> Self {
+        return Self {
+            trade_request_id: trade_request_id.to_string(),
+            trade_request_type,
+            subscription_request_type: None,
+            trade_report_id: None,
+            exec_id: None,
+            order_id: None,
+            cl_ord_id: None,
+            parties: vec![],
+            symbol: None,
+            security_id: None,
+            security_id_source: None,
+            trade_dates: vec![],
+        };
+    }
+
+    /// The TradeCaptureReportRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message =
+            FixMessageBuilder::new(begin_string, MSG_TYPE_TRADE_CAPTURE_REPORT_REQUEST);
+        message.with_value(568, self.trade_request_id.as_str());
+        message.with_value(569, self.trade_request_type);
+        write_optional(&mut message, 263, &self.subscription_request_type);
+        write_optional(&mut message, 571, &self.trade_report_id);
+        write_optional(&mut message, 17, &self.exec_id);
+        write_optional(&mut message, 37, &self.order_id);
+        write_optional(&mut message, 11, &self.cl_ord_id);
+        write_parties(&mut message, &self.parties);
+        write_optional(&mut message, 55, &self.symbol);
+        write_optional(&mut message, 48, &self.security_id);
+        write_optional(&mut message, 22, &self.security_id_source);
+
+        if !self.trade_dates.is_empty() {
+            message.with_value(TAG_NO_DATES, self.trade_dates.len());
+
+            for trade_date in &self.trade_dates {
+                message.with_value(75, trade_date.as_str());
+            }
+        }
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for TradeCaptureReportRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_TRADE_CAPTURE_REPORT_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+        let mut trade_dates = vec![];
+
+        for entry in read_group(&fields, TAG_NO_DATES, &[75, 60])? {
+            trade_dates.push(required(get_string(&entry, 75)?, 75)?);
+        }
+
+        return Ok(Self {
+            trade_request_id: required(get_string(&fields, 568)?, 568)?,
+            trade_request_type: required(get_parsed(&fields, 569)?, 569)?,
+            subscription_request_type: get_parsed(&fields, 263)?,
+            trade_report_id: get_string(&fields, 571)?,
+            exec_id: get_string(&fields, 17)?,
+            order_id: get_string(&fields, 37)?,
+            cl_ord_id: get_string(&fields, 11)?,
+            parties: read_parties(&fields)?,
+            symbol: get_string(&fields, 55)?,
+            security_id: get_string(&fields, 48)?,
+            security_id_source: get_string(&fields, 22)?,
+            trade_dates,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn give_up() -> TradeCaptureReport {
+        let mut report = TradeCaptureReport::new("TCR1", "100", "101.25", "20240102");
+        report.exec_id = Some("EXEC1".to_string());
+        report.symbol = Some("ESH5".to_string());
+        report.transact_time = Some(UNIX_EPOCH + Duration::from_secs(1_704_153_600));
+
+        let mut buy = TradeCaptureReportSide::new(Side::Buy);
+        buy.cl_ord_id = Some("ORDER1".to_string());
+        buy.parties = vec![
+            Party::new("EXECFIRM", "D", 1),
+            Party::new("CLEARFIRM", "D", 14),
+        ];
+        buy.account = Some("ACC1".to_string());
+
+        report.sides = vec![buy, TradeCaptureReportSide::new(Side::Sell)];
+        return report;
+    }
+
+    #[test]
+    fn test_trade_capture_report_round_trip() {
+        let message = give_up().to_message("FIX.4.4");
+
+        assert_eq!(
+            "571=TCR1|17=EXEC1|570=N|55=ESH5|32=100|31=101.25|75=20240102|60=20240102-00:00:00.000|552=2|54=1|11=ORDER1|453=2|448=EXECFIRM|447=D|452=1|448=CLEARFIRM|447=D|452=14|1=ACC1|54=2|",
+            message
+                .get_fields()
+                .map(|(tag, value)| format!("{}={}|", tag, String::from_utf8_lossy(value)))
+                .collect::<String>()
+        );
+        assert_eq!(Ok(give_up()), TradeCaptureReport::try_from(&message));
+
+        let (parsed, _) = FixMessageBuilder::from_bytes_lenient(&message.as_bytes());
+        assert_eq!(Ok(give_up()), TradeCaptureReport::try_from(&parsed));
+    }
+
+    #[test]
+    fn test_trade_capture_report_errors() {
+        let mut message = give_up().to_message("FIX.4.4");
+        message.remove_value(31);
+        assert_eq!(
+            Err(FieldParseError::Missing { tag: 31 }),
+            TradeCaptureReport::try_from(&message)
+        );
+
+        let order = FixMessageBuilder::new("FIX.4.4", "D");
+        assert!(matches!(
+            TradeCaptureReport::try_from(&order),
+            Err(FieldParseError::InvalidValue { tag: 35, .. })
+        ));
+    }
+
+    #[test]
+    fn test_trade_capture_report_request_round_trip() {
+        let mut request = TradeCaptureReportRequest::new("REQ1", 0);
+        request.subscription_request_type = Some('1');
+        request.parties = vec![Party::new("EXECFIRM", "D", 1)];
+        request.trade_dates = vec!["20240102".to_string(), "20240103".to_string()];
+
+        let message = request.to_message("FIX.4.4");
+
+        assert_eq!(
+            "8=FIX.4.4|9=88|35=AD|568=REQ1|569=0|263=1|453=1|448=EXECFIRM|447=D|452=1|580=2|75=20240102|75=20240103|10=040|",
+            message.to_string()
+        );
+        assert_eq!(Ok(request), TradeCaptureReportRequest::try_from(&message));
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use crate::{FixDictionary, FixMessageBuilder};
+
+/// Explicit rules for `translate`, taking precedence over what the dictionaries say.
+#[derive(Debug, Clone, Default)]
+pub struct TranslationMapping {
+    tags: HashMap<u32, u32>,
+    values: HashMap<u32, HashMap<Vec<u8>, Vec<u8>>>,
+    dropped: Vec<u32>,
+}
+
+impl TranslationMapping {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Sends the values of `from` as `to`.
+    pub fn map_tag(&mut self, from: u32, to: u32) {
+        self.tags.insert(from, to);
+    }
+
+    /// Replaces `from` with `to` in the values of `tag`, the tag being the one after
+    /// renumbering.
+    pub fn map_value(&mut self, tag: u32, from: &str, to: &str) {
+        self.values
+            .entry(tag)
+            .or_default()
+            .insert(from.as_bytes().to_vec(), to.as_bytes().to_vec());
+    }
+
+    /// Leaves `tag` out even when the target dictionary knows it.
+    pub fn drop_tag(&mut self, tag: u32) {
+        self.dropped.push(tag);
+    }
+}
+
+/// Rewrites a message of the `from` dictionary's version for the `to` one, e.g. to
+/// pass FIX 4.4 messages of an internal bus on to a FIX 4.2 counterparty.
+///
+/// Every field is renumbered by `mapping`, or else to the tag of the same name in
+/// `to`. Fields `to` doesn't know or doesn't allow in the message type are dropped,
+/// as are those `mapping` drops. Values are mapped after renumbering.
+pub fn translate(
+    message: &FixMessageBuilder,
+    from: &FixDictionary,
+    to: &FixDictionary,
+    mapping: &TranslationMapping,
+) -> FixMessageBuilder {
+    let msg_type = message.get_message_type_as_string();
+    let message_def = to.get_message(&msg_type);
+    let mut result = FixMessageBuilder::new(to.get_begin_string(), &msg_type);
+
+    for (tag, value) in message.get_fields() {
+        if mapping.dropped.contains(&tag) {
+            continue;
+        }
+
+        let tag = match mapping.tags.get(&tag) {
+            Some(tag) => Some(*tag),
+            None if to.get_field(tag).is_some() => Some(tag),
+            None => from
+                .get_tag_name(tag)
+                .and_then(|name| to.get_field_by_name(name))
+                .map(|field| field.tag),
+        };
+
+        let Some(tag) = tag else {
+            continue;
+        };
+
+        let is_allowed = to.is_header_tag(tag)
+            || to.is_trailer_tag(tag)
+            || message_def.is_none_or(|message_def| message_def.contains_tag(tag));
+
+        if !is_allowed || mapping.dropped.contains(&tag) {
+            continue;
+        }
+
+        let value = mapping
+            .values
+            .get(&tag)
+            .and_then(|values| values.get(value))
+            .map_or(value, |value| value.as_slice());
+
+        result.with_value(tag, value);
+    }
+
+    return result;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BeginString, FieldDef, FieldType, MemberDef, MessageDef};
+
+    fn field(tag: u32, name: &str) -> FieldDef {
+        return FieldDef {
+            tag,
+            name: name.to_string(),
+            field_type: FieldType::String,
+            values: vec![],
+        };
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut from = FixDictionary::fix44();
+        from.add_field(field(9001, "VenueAccount"));
+
+        let mut to = FixDictionary::new("FIX.4.2");
+        to.with_header(vec![MemberDef::Field {
+            tag: 49,
+            required: true,
+        }]);
+
+        for (tag, name) in [
+            (49, "SenderCompID"),
+            (17, "ExecID"),
+            (20, "ExecTransType"),
+            (150, "ExecType"),
+            (58, "Text"),
+            (5001, "VenueAccount"),
+        ] {
+            to.add_field(field(tag, name));
+        }
+
+        to.add_message(MessageDef {
+            msg_type: "8".to_string(),
+            name: "ExecutionReport".to_string(),
+            is_admin: false,
+            members: [17, 20, 150, 5001]
+                .into_iter()
+                .map(|tag| MemberDef::Field {
+                    tag,
+                    required: false,
+                })
+                .collect(),
+        });
+
+        let mut mapping = TranslationMapping::new();
+        mapping.map_value(150, "F", "2");
+
+        let mut message = FixMessageBuilder::new("FIX.4.4", "8");
+        message.with_value(49, "BUS");
+        message.with_value(17, "EXEC1");
+        message.with_value(150, "F");
+        message.with_value(58, "not in 4.2 ExecutionReport");
+        message.with_value(9001, "ACC");
+        message.with_value(1128, "9");
+
+        let result = translate(&message, &from, &to, &mapping);
+
+        assert_eq!(BeginString::Fix42, result.get_fix_version());
+        assert_eq!(
+            vec![
+                (49, b"BUS".as_slice()),
+                (17, b"EXEC1".as_slice()),
+                (150, b"2".as_slice()),
+                (5001, b"ACC".as_slice()),
+            ],
+            result.get_fields().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use crate::{
+    fields::{FixField, UserRequestType, UserStatus},
+    group::{expect_msg_type, get_coded, get_string, required, write_optional, GroupEntry},
+    BeginString, FieldParseError, FixMessageBuilder, REDACTED_VALUE,
+};
+
+const MSG_TYPE_USER_REQUEST: &str = "BE";
+const MSG_TYPE_USER_RESPONSE: &str = "BF";
+
+/// UserRequest (BE), to log a user on or off, change their password or ask for their
+/// status. `Debug` hides the passwords.
+#[derive(Clone, PartialEq, Eq)]
+pub struct UserRequest {
+    /// UserRequestID (923).
+    pub user_request_id: String,
+    pub user_request_type: UserRequestType,
+    /// Username (553).
+    pub username: String,
+    /// Password (554).
+    pub password: Option<String>,
+    /// NewPassword (925).
+    pub new_password: Option<String>,
+}
+
+/// UserResponse (BF), the answer to a UserRequest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserResponse {
+    /// UserRequestID (923).
+    pub user_request_id: String,
+    /// Username (553).
+    pub username: String,
+    pub user_status: Option<UserStatus>,
+    /// UserStatusText (927).
+    pub user_status_text: Option<String>,
+}
+
+impl UserRequest {
+    pub fn new(user_request_id: &str, user_request_type: UserRequestType, username: &str) -> Self {
+        return Self {
+            user_request_id: user_request_id.to_string(),
+            user_request_type,
+            username: username.to_string(),
+            password: None,
+            new_password: None,
+        };
+    }
+
+    /// A request to change the password of `username` from `password` to `new_password`.
+    pub fn change_password(
+        user_request_id: &str,
+        username: &str,
+        password: &str,
+        new_password: &str,
+    ) -> Self {
+        let mut request = Self::new(
+            user_request_id,
+            UserRequestType::ChangePasswordForUser,
+            username,
+        );
+        request.password = Some(password.to_string());
+        request.new_password = Some(new_password.to_string());
+
+        return request;
+    }
+
+    /// The UserRequest as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_USER_REQUEST);
+        message.with_value(923, self.user_request_id.as_str());
+        message.with_field(self.user_request_type);
+        message.with_value(553, self.username.as_str());
+        write_optional(&mut message, 554, &self.password);
+        write_optional(&mut message, 925, &self.new_password);
+
+        return message;
+    }
+}
+
+impl fmt::Debug for UserRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = String::from_utf8_lossy(REDACTED_VALUE);
+
+        return f
+            .debug_struct("UserRequest")
+            .field("user_request_id", &self.user_request_id)
+            .field("user_request_type", &self.user_request_type)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| &redacted))
+            .field(
+                "new_password",
+                &self.new_password.as_ref().map(|_| &redacted),
+            )
+            .finish();
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for UserRequest {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_USER_REQUEST)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            user_request_id: required(get_string(&fields, 923)?, 923)?,
+            user_request_type: required(
+                get_coded(&fields, UserRequestType::TAG)?,
+                UserRequestType::TAG,
+            )?,
+            username: required(get_string(&fields, 553)?, 553)?,
+            password: get_string(&fields, 554)?,
+            new_password: get_string(&fields, 925)?,
+        });
+    }
+}
+
+impl UserResponse {
+    pub fn new(user_request_id: &str, username: &str, user_status: UserStatus) -> Self {
+        return Self {
+            user_request_id: user_request_id.to_string(),
+            username: username.to_string(),
+            user_status: Some(user_status),
+            user_status_text: None,
+        };
+    }
+
+    /// The UserResponse as a message body.
+    pub fn to_message(&self, begin_string: impl Into<BeginString>) -> FixMessageBuilder {
+        let mut message = FixMessageBuilder::new(begin_string, MSG_TYPE_USER_RESPONSE);
+        message.with_value(923, self.user_request_id.as_str());
+        message.with_value(553, self.username.as_str());
+
+        if let Some(user_status) = self.user_status {
+            message.with_field(user_status);
+        }
+
+        write_optional(&mut message, 927, &self.user_status_text);
+
+        return message;
+    }
+}
+
+impl TryFrom<&FixMessageBuilder> for UserResponse {
+    type Error = FieldParseError;
+
+    fn try_from(message: &FixMessageBuilder) -> Result<Self, Self::Error> {
+        expect_msg_type(message, MSG_TYPE_USER_RESPONSE)?;
+
+        let fields: GroupEntry = message.get_fields().collect();
+
+        return Ok(Self {
+            user_request_id: required(get_string(&fields, 923)?, 923)?,
+            username: required(get_string(&fields, 553)?, 553)?,
+            user_status: get_coded(&fields, UserStatus::TAG)?,
+            user_status_text: get_string(&fields, 927)?,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_user_request_round_trip() {
+        let request = UserRequest::change_password("U1", "trader", "hunter2", "s3cret");
+
+        let message = request.to_message("FIX.4.4");
+        assert_eq!(
+            "8=FIX.4.4|9=53|35=BE|923=U1|924=3|553=trader|554=hunter2|925=s3cret|10=210|",
+            message.to_string()
+        );
+        assert_eq!(Ok(request.clone()), UserRequest::try_from(&message));
+
+        let debug = format!("{:?}", request);
+        assert!(debug.contains("trader"));
+        assert!(!debug.contains("hunter2") && !debug.contains("s3cret"));
+    }
+
+    #[test]
+    fn test_user_response_round_trip() {
+        let mut response = UserResponse::new("U1", "trader", UserStatus::PasswordChanged);
+        response.user_status_text = Some("Password changed".to_string());
+
+        let message = response.to_message("FIX.4.4");
+        assert_eq!(Ok(response), UserResponse::try_from(&message));
+
+        let mut message = message;
+        message.set_value(926, "9");
+        assert!(matches!(
+            UserResponse::try_from(&message),
+            Err(FieldParseError::InvalidValue { tag: 926, .. })
+        ));
+    }
+}
@@ -1,78 +1,217 @@
-use std::collections::HashMap;
+use std::fmt;
+
+use crate::ParseWarning;
 
 pub const FIX_EQUALS: u8 = 0x3d;
 pub const FIX_DELIMETR: u8 = 0x1;
 
+/// Tags and values of a message in wire order, repeated tags included.
+pub type FixTags = Vec<(Vec<u8>, Vec<u8>)>;
+
 pub fn calculate_check_sum(body: &[u8]) -> String {
-    let mut sum = 0u8;
-    for byte in body {
-        sum = sum.wrapping_add(*byte);
+    return String::from_utf8_lossy(&checksum_bytes(body)).to_string();
+}
+
+/// CheckSum (10) value of `data` as three ASCII digits, without allocating.
+pub fn checksum_bytes(data: &[u8]) -> [u8; 3] {
+    return format_checksum(checksum_u8(data));
+}
+
+/// CheckSum (10) of `data` as a number, the byte sum modulo 256.
+pub fn checksum_u8(data: &[u8]) -> u8 {
+    let mut accumulator = ChecksumAccumulator::new();
+    accumulator.update(data);
+
+    return accumulator.get_sum();
+}
+
+/// A CheckSum as written on the wire, three ASCII digits with leading zeros.
+pub fn format_checksum(sum: u8) -> [u8; 3] {
+    return [b'0' + sum / 100, b'0' + sum / 10 % 10, b'0' + sum % 10];
+}
+
+/// Value of a received CheckSum (10); `None` unless it is three ASCII digits up to 255.
+pub fn parse_checksum(value: &[u8]) -> Option<u8> {
+    let [hundreds, tens, units] = value else {
+        return None;
+    };
+
+    if !value.iter().all(u8::is_ascii_digit) {
+        return None;
     }
 
-    return format!("{:0>3}", sum.to_string());
+    let result = (hundreds - b'0') as u32 * 100 + (tens - b'0') as u32 * 10 + (units - b'0') as u32;
+
+    return u8::try_from(result).ok();
+}
+
+/// CheckSum (10) computed over a message fed in chunks, for writers that don't hold
+/// the whole message in one buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChecksumAccumulator {
+    sum: u8,
+}
+
+impl ChecksumAccumulator {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        for byte in chunk {
+            self.sum = self.sum.wrapping_add(*byte);
+        }
+    }
+
+    /// CheckSum value of everything fed so far, as three ASCII digits.
+    pub fn finish(&self) -> [u8; 3] {
+        return format_checksum(self.sum);
+    }
+
+    /// CheckSum value of everything fed so far, as a number.
+    pub fn get_sum(&self) -> u8 {
+        return self.sum;
+    }
+}
+
+/// Parses a tag number; `None` unless `tag` is made of ASCII digits only.
+pub fn parse_tag(tag: &[u8]) -> Option<u32> {
+    if tag.is_empty() || !tag.iter().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut result: u32 = 0;
+
+    for byte in tag {
+        result = result.checked_mul(10)?.checked_add((byte - b'0') as u32)?;
+    }
+
+    return Some(result);
 }
 
 pub fn compile_fix_chunk(key: &[u8], value: &[u8]) -> Vec<u8> {
     let mut result: Vec<u8> = vec![];
 
     result.extend_from_slice(key);
-    result.extend_from_slice(&vec![FIX_EQUALS]);
-    result.extend_from_slice(&value);
-    result.extend_from_slice(&vec![FIX_DELIMETR]);
+    result.push(FIX_EQUALS);
+    result.extend_from_slice(value);
+    result.push(FIX_DELIMETR);
 
     return result;
 }
 
 pub fn bytes_to_fix_string(data: &[u8]) -> String {
-    let mut str = vec![];
+    let mut result = String::with_capacity(data.len());
+    write_fix_string(&mut result, data).unwrap();
 
-    for byte in data {
-        if byte == &FIX_DELIMETR {
-            str.extend_from_slice(b"|");
-        } else {
-            str.push(*byte);
+    return result;
+}
+
+/// Writes a message with SOH shown as `|`; bytes that aren't valid UTF-8 are written
+/// as U+FFFD instead of failing.
+pub fn write_fix_string(f: &mut impl fmt::Write, data: &[u8]) -> fmt::Result {
+    for chunk in data.utf8_chunks() {
+        let mut parts = chunk.valid().split(FIX_DELIMETR as char);
+
+        if let Some(first) = parts.next() {
+            f.write_str(first)?;
+        }
+
+        for part in parts {
+            f.write_char('|')?;
+            f.write_str(part)?;
+        }
+
+        if !chunk.invalid().is_empty() {
+            f.write_char(char::REPLACEMENT_CHARACTER)?;
         }
     }
 
-    return String::from_utf8(str).unwrap();
+    return Ok(());
 }
 
-pub fn split_fix_to_tags(fix: &[u8]) -> HashMap<Vec<u8>, Vec<Vec<u8>>> {
-    let mut result: HashMap<Vec<u8>, Vec<Vec<u8>>> = HashMap::new();
+/// Splits a message into its tags and values, keeping their order so that repeating
+/// groups stay intact; a last field not terminated by SOH is dropped.
+pub fn split_fix_to_tags(fix: &[u8]) -> FixTags {
+    let mut result = FixTags::new();
     let mut key_buffer = Vec::new();
     let mut value_buffer = Vec::new();
     let mut is_equals_raised = false;
 
     for byte in fix {
         if byte == &FIX_DELIMETR {
+            result.push((
+                std::mem::take(&mut key_buffer),
+                std::mem::take(&mut value_buffer),
+            ));
+            is_equals_raised = false;
+            continue;
+        }
 
-            if let Some(data_to_insert) = result.get_mut(&key_buffer){
-                data_to_insert.push(value_buffer.clone());
-            } else {
-                result.insert(key_buffer.clone(), vec![value_buffer.clone()]);
-            }
+        if byte == &FIX_EQUALS && !is_equals_raised {
+            is_equals_raised = true;
+            continue;
+        }
+
+        match is_equals_raised {
+            true => value_buffer.push(*byte),
+            false => key_buffer.push(*byte),
+        };
+    }
+
+    return result;
+}
+
+/// Splits a message into tags like `split_fix_to_tags`, but keeps the last field when it is
+/// not terminated by SOH and stops at the CheckSum (10) field, reporting both cases.
+pub fn split_fix_to_tags_with_warnings(fix: &[u8]) -> (FixTags, Vec<ParseWarning>) {
+    let mut result = FixTags::new();
+    let mut warnings = vec![];
+    let mut key_buffer = Vec::new();
+    let mut value_buffer = Vec::new();
+    let mut is_equals_raised = false;
 
-            key_buffer.clear();
-            value_buffer.clear();
+    for (offset, byte) in fix.iter().enumerate() {
+        if byte == &FIX_DELIMETR {
+            let is_check_sum = key_buffer.as_slice() == b"10";
+            result.push((
+                std::mem::take(&mut key_buffer),
+                std::mem::take(&mut value_buffer),
+            ));
             is_equals_raised = false;
+
+            if is_check_sum && offset + 1 < fix.len() {
+                warnings.push(ParseWarning::TrailingGarbage {
+                    offset: offset + 1,
+                    len: fix.len() - offset - 1,
+                });
+                return (result, warnings);
+            }
+
             continue;
         }
 
-        if byte == &FIX_EQUALS {
+        if byte == &FIX_EQUALS && !is_equals_raised {
             is_equals_raised = true;
             continue;
         }
 
         match is_equals_raised {
-            true => value_buffer.push(byte.clone()),
-            false => key_buffer.push(byte.clone()),
+            true => value_buffer.push(*byte),
+            false => key_buffer.push(*byte),
         };
     }
 
-    return result;
+    if !key_buffer.is_empty() || !value_buffer.is_empty() {
+        result.push((key_buffer, value_buffer));
+        warnings.push(ParseWarning::MissingTrailingDelimeter);
+    }
+
+    return (result, warnings);
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod test {
     use super::*;
 
@@ -109,6 +248,29 @@ mod test {
         assert_eq!(check_sum, result.as_bytes());
     }
 
+    #[test]
+    fn test_checksum_accumulator() {
+        let message = b"8=FIX.4.4\x019=5\x0135=0\x01";
+
+        let mut accumulator = ChecksumAccumulator::new();
+        for chunk in message.chunks(4) {
+            accumulator.update(chunk);
+        }
+
+        assert_eq!(*b"163", accumulator.finish());
+        assert_eq!(*b"163", checksum_bytes(message));
+        assert_eq!(*b"000", checksum_bytes(b""));
+        assert_eq!(*b"007", checksum_bytes(b"\x07"));
+
+        assert_eq!(163, checksum_u8(message));
+        assert_eq!(163, accumulator.get_sum());
+        assert_eq!(Some(163), parse_checksum(b"163"));
+        assert_eq!(Some(7), parse_checksum(b"007"));
+        assert_eq!(None, parse_checksum(b"256"));
+        assert_eq!(None, parse_checksum(b"63"));
+        assert_eq!(None, parse_checksum(b"1a3"));
+    }
+
     #[test]
     fn test_bytes_to_fix_string() {
         let fix_text_string = "8=FIX.4.4|9=75|35=A|";
@@ -119,6 +281,21 @@ mod test {
         assert_eq!(string_message.as_str(), fix_text_string);
     }
 
+    #[test]
+    fn test_bytes_to_fix_string_invalid_utf8() {
+        assert_eq!("58=a\u{FFFD}b|", bytes_to_fix_string(b"58=a\xffb\x01"));
+    }
+
+    #[test]
+    fn test_split_fix_to_tags_keeps_equals_in_value() {
+        let test_body = build_test_body(vec!["8=FIX.4.4", "35=A", "96=dGVzdA==", "58=a=b"]);
+
+        let tags = split_fix_to_tags(&test_body);
+
+        assert_eq!((b"96".to_vec(), b"dGVzdA==".to_vec()), tags[2]);
+        assert_eq!((b"58".to_vec(), b"a=b".to_vec()), tags[3]);
+    }
+
     fn build_test_body(data: Vec<&str>) -> Vec<u8> {
         let mut result = vec![];
         for itm in data {
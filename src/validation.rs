@@ -0,0 +1,647 @@
+use std::fmt;
+
+use crate::{
+    checksum_u8, errors::occurrence, fields::SessionRejectReason, format_checksum, parse_checksum,
+    parse_tag, parse_utc_timestamp, trace, FieldType, FixDictionary, MemberDef,
+};
+
+/// Problem found in a raw message; `offset` is the position of the offending field
+/// within the message, or 0 for problems of the message as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub offset: usize,
+    /// Which occurrence of the offending tag is at fault, starting at 1; 0 when the
+    /// field is missing or the problem isn't about one field.
+    pub occurrence: usize,
+    pub kind: ValidationErrorKind,
+}
+
+impl ValidationError {
+    /// RefTagID (371) of a Reject for this problem.
+    pub fn get_ref_tag_id(&self) -> Option<u32> {
+        return match &self.kind {
+            ValidationErrorKind::MissingBeginString => Some(8),
+            ValidationErrorKind::MissingBodyLength
+            | ValidationErrorKind::InvalidBodyLength { .. } => Some(9),
+            ValidationErrorKind::MissingCheckSum | ValidationErrorKind::InvalidCheckSum { .. } => {
+                Some(10)
+            }
+            ValidationErrorKind::MissingMsgType | ValidationErrorKind::UnknownMessageType(_) => {
+                Some(35)
+            }
+            ValidationErrorKind::MalformedField => None,
+            ValidationErrorKind::TagSpecifiedWithoutValue(tag)
+            | ValidationErrorKind::UnknownTag(tag)
+            | ValidationErrorKind::TagNotDefinedForMessage(tag)
+            | ValidationErrorKind::RequiredTagMissing(tag)
+            | ValidationErrorKind::ValueIsIncorrect(tag)
+            | ValidationErrorKind::IncorrectDataFormat(tag)
+            | ValidationErrorKind::InvalidCharacter { tag, .. } => Some(*tag),
+        };
+    }
+
+    /// SessionRejectReason (373) of a Reject for this problem.
+    pub fn get_session_reject_reason(&self) -> SessionRejectReason {
+        return match &self.kind {
+            ValidationErrorKind::MissingBeginString
+            | ValidationErrorKind::MissingBodyLength
+            | ValidationErrorKind::MissingCheckSum
+            | ValidationErrorKind::MissingMsgType
+            | ValidationErrorKind::RequiredTagMissing(_) => SessionRejectReason::RequiredTagMissing,
+            ValidationErrorKind::InvalidBodyLength { .. }
+            | ValidationErrorKind::InvalidCheckSum { .. }
+            | ValidationErrorKind::ValueIsIncorrect(_) => SessionRejectReason::ValueIsIncorrect,
+            ValidationErrorKind::MalformedField | ValidationErrorKind::UnknownTag(_) => {
+                SessionRejectReason::InvalidTagNumber
+            }
+            ValidationErrorKind::TagSpecifiedWithoutValue(_) => {
+                SessionRejectReason::TagSpecifiedWithoutValue
+            }
+            ValidationErrorKind::UnknownMessageType(_) => SessionRejectReason::InvalidMsgType,
+            ValidationErrorKind::TagNotDefinedForMessage(_) => {
+                SessionRejectReason::TagNotDefinedForMessageType
+            }
+            ValidationErrorKind::IncorrectDataFormat(_)
+            | ValidationErrorKind::InvalidCharacter { .. } => {
+                SessionRejectReason::IncorrectDataFormat
+            }
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    MissingBeginString,
+    MissingBodyLength,
+    InvalidBodyLength {
+        declared: String,
+        actual: usize,
+    },
+    MissingCheckSum,
+    InvalidCheckSum {
+        declared: String,
+        actual: String,
+    },
+    MissingMsgType,
+    /// A field without `=` or with a non numeric tag.
+    MalformedField,
+    TagSpecifiedWithoutValue(u32),
+    UnknownMessageType(String),
+    UnknownTag(u32),
+    TagNotDefinedForMessage(u32),
+    RequiredTagMissing(u32),
+    ValueIsIncorrect(u32),
+    IncorrectDataFormat(u32),
+    /// A byte outside printable ASCII in a field that isn't a data field; `position`
+    /// is its offset in the message.
+    InvalidCharacter {
+        tag: u32,
+        position: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match &self.kind {
+            ValidationErrorKind::MissingBeginString => write!(f, "BeginString (8) is not first"),
+            ValidationErrorKind::MissingBodyLength => write!(f, "BodyLength (9) is not second"),
+            ValidationErrorKind::InvalidBodyLength { declared, actual } => {
+                write!(
+                    f,
+                    "BodyLength (9) is {} but the body is {} bytes",
+                    declared, actual
+                )
+            }
+            ValidationErrorKind::MissingCheckSum => write!(f, "CheckSum (10) is not last"),
+            ValidationErrorKind::InvalidCheckSum { declared, actual } => {
+                write!(f, "CheckSum (10) is {} but should be {}", declared, actual)
+            }
+            ValidationErrorKind::MissingMsgType => write!(f, "MsgType (35) is not third"),
+            ValidationErrorKind::MalformedField => write!(f, "malformed field"),
+            ValidationErrorKind::TagSpecifiedWithoutValue(tag) => {
+                write!(f, "tag {} specified without a value", tag)
+            }
+            ValidationErrorKind::UnknownMessageType(message_type) => {
+                write!(f, "unknown MsgType {}", message_type)
+            }
+            ValidationErrorKind::UnknownTag(tag) => write!(f, "unknown tag {}", tag),
+            ValidationErrorKind::TagNotDefinedForMessage(tag) => {
+                write!(f, "tag {} not defined for this message type", tag)
+            }
+            ValidationErrorKind::RequiredTagMissing(tag) => {
+                write!(f, "required tag {} missing", tag)
+            }
+            ValidationErrorKind::ValueIsIncorrect(tag) => {
+                write!(f, "value is incorrect (out of range) for tag {}", tag)
+            }
+            ValidationErrorKind::IncorrectDataFormat(tag) => {
+                write!(f, "incorrect data format for tag {}", tag)
+            }
+            ValidationErrorKind::InvalidCharacter { tag, position } => {
+                write!(
+                    f,
+                    "unexpected character in tag {} at byte {}",
+                    tag, position
+                )
+            }
+        };
+    }
+}
+
+/// Field of a raw message with its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawField<'a> {
+    pub offset: usize,
+    pub tag: &'a [u8],
+    pub value: &'a [u8],
+}
+
+impl RawField<'_> {
+    pub fn get_tag(&self) -> Option<u32> {
+        return parse_tag(self.tag);
+    }
+}
+
+/// Splits a raw message into its fields in wire order; a trailing chunk without SOH
+/// is returned as a field as well.
+pub fn split_raw_fields(message: &[u8]) -> Vec<RawField<'_>> {
+    let mut result = vec![];
+    let mut offset = 0;
+
+    for chunk in message.split(|byte| *byte == 1) {
+        if !chunk.is_empty() {
+            let (tag, value) = match chunk.iter().position(|byte| *byte == b'=') {
+                Some(separator) => (&chunk[..separator], &chunk[separator + 1..]),
+                None => (chunk, &chunk[chunk.len()..]),
+            };
+
+            result.push(RawField { offset, tag, value });
+        }
+
+        offset += chunk.len() + 1;
+    }
+
+    return result;
+}
+
+/// Checks the framing of a raw message: BeginString, BodyLength and MsgType first,
+/// CheckSum last, and that BodyLength and CheckSum match the bytes.
+pub fn validate_frame(message: &[u8]) -> Vec<ValidationError> {
+    let fields = split_raw_fields(message);
+    let mut result = vec![];
+
+    let error = |offset, kind| ValidationError {
+        offset,
+        occurrence: 0,
+        kind,
+    };
+
+    if fields.first().map(|field| field.tag) != Some(b"8".as_slice()) {
+        result.push(error(0, ValidationErrorKind::MissingBeginString));
+    }
+
+    if fields.get(2).map(|field| field.tag) != Some(b"35".as_slice()) {
+        let offset = fields.get(2).map(|field| field.offset).unwrap_or(0);
+        result.push(error(offset, ValidationErrorKind::MissingMsgType));
+    }
+
+    let Some(check_sum) = fields.last().filter(|field| field.tag == b"10") else {
+        let offset = fields.last().map(|field| field.offset).unwrap_or(0);
+        result.push(error(offset, ValidationErrorKind::MissingCheckSum));
+        return with_occurrences(&fields, result);
+    };
+
+    match fields.get(1).filter(|field| field.tag == b"9") {
+        Some(body_length) => {
+            let body_start =
+                body_length.offset + body_length.tag.len() + body_length.value.len() + 2;
+            let actual = check_sum.offset.saturating_sub(body_start);
+
+            if body_length.value != actual.to_string().as_bytes() {
+                result.push(error(
+                    body_length.offset,
+                    ValidationErrorKind::InvalidBodyLength {
+                        declared: String::from_utf8_lossy(body_length.value).to_string(),
+                        actual,
+                    },
+                ));
+            }
+        }
+        None => {
+            let offset = fields.get(1).map(|field| field.offset).unwrap_or(0);
+            result.push(error(offset, ValidationErrorKind::MissingBodyLength));
+        }
+    }
+
+    let actual = checksum_u8(&message[..check_sum.offset]);
+
+    if parse_checksum(check_sum.value) != Some(actual) {
+        result.push(error(
+            check_sum.offset,
+            ValidationErrorKind::InvalidCheckSum {
+                declared: String::from_utf8_lossy(check_sum.value).to_string(),
+                actual: String::from_utf8_lossy(&format_checksum(actual)).to_string(),
+            },
+        ));
+    }
+
+    return with_occurrences(&fields, result);
+}
+
+/// Data fields of FIX 4.4, which may hold any byte: Signature (89), SecureData (91),
+/// RawData (96), XmlData (213) and the Encoded fields such as EncodedText (355).
+pub const DEFAULT_DATA_TAGS: &[u32] = &[
+    89, 91, 96, 213, 349, 351, 353, 355, 357, 359, 361, 363, 365, 446, 619, 622,
+];
+
+/// Checks that fields other than `data_tags` hold printable ASCII only, as the spec
+/// requires, so corrupted or wrongly encoded values are caught on arrival. Fields with
+/// a malformed tag are left to `FixDictionary::validate`.
+pub fn validate_charset(message: &[u8], data_tags: &[u32]) -> Vec<ValidationError> {
+    let fields = split_raw_fields(message);
+    let mut result = vec![];
+
+    for field in &fields {
+        let Some(tag) = field.get_tag() else {
+            continue;
+        };
+
+        if data_tags.contains(&tag) {
+            continue;
+        }
+
+        if let Some(position) = field
+            .value
+            .iter()
+            .position(|byte| !(0x20..0x7f).contains(byte))
+        {
+            result.push(ValidationError {
+                offset: field.offset,
+                occurrence: 0,
+                kind: ValidationErrorKind::InvalidCharacter {
+                    tag,
+                    position: field.offset + field.tag.len() + 1 + position,
+                },
+            });
+        }
+    }
+
+    return with_occurrences(&fields, result);
+}
+
+impl FixDictionary {
+    /// Tags of the Data and XmlData fields, to pass to `validate_charset`.
+    pub fn get_data_tags(&self) -> Vec<u32> {
+        let mut result: Vec<u32> = self
+            .get_fields()
+            .filter(|field| matches!(field.field_type, FieldType::Data | FieldType::XmlData))
+            .map(|field| field.tag)
+            .collect();
+        result.sort();
+
+        return result;
+    }
+
+    /// Checks loose fields, e.g. those of a component, against their definitions: known
+    /// tag, a value that is well formed and allowed.
+    pub fn validate_fields<'a>(
+        &self,
+        fields: impl IntoIterator<Item = (u32, &'a [u8])>,
+    ) -> Vec<ValidationErrorKind> {
+        let mut result = vec![];
+
+        for (tag, value) in fields {
+            if value.is_empty() {
+                result.push(ValidationErrorKind::TagSpecifiedWithoutValue(tag));
+                continue;
+            }
+
+            let Some(field_def) = self.get_field(tag) else {
+                result.push(ValidationErrorKind::UnknownTag(tag));
+                continue;
+            };
+
+            if !is_valid_format(&field_def.field_type, value) {
+                result.push(ValidationErrorKind::IncorrectDataFormat(tag));
+            } else if !is_allowed_value(field_def, value) {
+                result.push(ValidationErrorKind::ValueIsIncorrect(tag));
+            }
+        }
+
+        return result;
+    }
+
+    /// Checks a raw message against the dictionary: known message type and tags,
+    /// required fields present, enumerated values allowed and values well formed.
+    /// Repeating group structure isn't checked.
+    pub fn validate(&self, message: &[u8]) -> Vec<ValidationError> {
+        let fields = split_raw_fields(message);
+        let mut result = vec![];
+
+        let error = |offset, kind| ValidationError {
+            offset,
+            occurrence: 0,
+            kind,
+        };
+
+        let message_type = fields
+            .iter()
+            .find(|field| field.tag == b"35")
+            .map(|field| String::from_utf8_lossy(field.value).to_string());
+
+        let message_def = match &message_type {
+            Some(message_type) => match self.get_message(message_type) {
+                Some(message_def) => Some(message_def),
+                None => {
+                    result.push(error(
+                        0,
+                        ValidationErrorKind::UnknownMessageType(message_type.clone()),
+                    ));
+                    None
+                }
+            },
+            None => {
+                result.push(error(0, ValidationErrorKind::MissingMsgType));
+                None
+            }
+        };
+
+        let mut present = vec![];
+
+        for field in &fields {
+            let Some(tag) = field.get_tag() else {
+                result.push(error(field.offset, ValidationErrorKind::MalformedField));
+                continue;
+            };
+
+            present.push(tag);
+
+            if field.value.is_empty() {
+                result.push(error(
+                    field.offset,
+                    ValidationErrorKind::TagSpecifiedWithoutValue(tag),
+                ));
+                continue;
+            }
+
+            let Some(field_def) = self.get_field(tag) else {
+                result.push(error(field.offset, ValidationErrorKind::UnknownTag(tag)));
+                continue;
+            };
+
+            if let Some(message_def) = message_def {
+                if !self.is_header_tag(tag)
+                    && !self.is_trailer_tag(tag)
+                    && !message_def.contains_tag(tag)
+                {
+                    result.push(error(
+                        field.offset,
+                        ValidationErrorKind::TagNotDefinedForMessage(tag),
+                    ));
+                }
+            }
+
+            if !is_valid_format(&field_def.field_type, field.value) {
+                result.push(error(
+                    field.offset,
+                    ValidationErrorKind::IncorrectDataFormat(tag),
+                ));
+                continue;
+            }
+
+            if !is_allowed_value(field_def, field.value) {
+                result.push(error(
+                    field.offset,
+                    ValidationErrorKind::ValueIsIncorrect(tag),
+                ));
+            }
+        }
+
+        let required_header = self.get_header().iter().chain(self.get_trailer());
+        let required_body = message_def.map(|message_def| message_def.members.as_slice());
+
+        for member in required_header.chain(required_body.unwrap_or_default()) {
+            let (tag, required) = match member {
+                MemberDef::Field { tag, required } => (*tag, *required),
+                MemberDef::Group(group) => (group.count_tag, group.required),
+            };
+
+            if required && !present.contains(&tag) {
+                result.push(error(0, ValidationErrorKind::RequiredTagMissing(tag)));
+            }
+        }
+
+        let result = with_occurrences(&fields, result);
+        trace::validated(message, &result);
+
+        return result;
+    }
+}
+
+fn with_occurrences(fields: &[RawField], mut errors: Vec<ValidationError>) -> Vec<ValidationError> {
+    for error in errors.iter_mut() {
+        let field = fields.iter().find(|field| {
+            field.offset == error.offset && field.get_tag() == error.get_ref_tag_id()
+        });
+
+        if let Some(field) = field {
+            error.occurrence = occurrence(fields, field);
+        }
+    }
+
+    return errors;
+}
+
+fn is_valid_format(field_type: &FieldType, value: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(value) else {
+        return matches!(field_type, FieldType::Data | FieldType::XmlData);
+    };
+
+    let is_int = |text: &str| {
+        let digits = text.strip_prefix('-').unwrap_or(text);
+        return !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit());
+    };
+
+    return match field_type {
+        FieldType::Int
+        | FieldType::Length
+        | FieldType::SeqNum
+        | FieldType::NumInGroup
+        | FieldType::TagNum
+        | FieldType::DayOfMonth => is_int(text),
+        FieldType::Float
+        | FieldType::Qty
+        | FieldType::Price
+        | FieldType::PriceOffset
+        | FieldType::Amt
+        | FieldType::Percentage => {
+            let (whole, fraction) = text.split_once('.').unwrap_or((text, "0"));
+            let whole = if whole.is_empty() || whole == "-" {
+                format!("{}0", whole)
+            } else {
+                whole.to_string()
+            };
+
+            is_int(&whole) && (fraction.is_empty() || fraction.bytes().all(|b| b.is_ascii_digit()))
+        }
+        FieldType::Char => value.len() == 1,
+        FieldType::Boolean => value == b"Y" || value == b"N",
+        FieldType::UtcTimestamp => parse_utc_timestamp(text).is_some(),
+        FieldType::UtcDateOnly | FieldType::LocalMktDate => {
+            value.len() == 8 && value.iter().all(|byte| byte.is_ascii_digit())
+        }
+        _ => true,
+    };
+}
+
+fn is_allowed_value(field_def: &crate::FieldDef, value: &[u8]) -> bool {
+    if field_def.values.is_empty() {
+        return true;
+    }
+
+    let is_allowed = |value: &[u8]| {
+        field_def
+            .values
+            .iter()
+            .any(|(allowed, _)| allowed.as_bytes() == value)
+    };
+
+    return match field_def.field_type {
+        FieldType::MultipleValueString | FieldType::MultipleCharValue => {
+            value.split(|byte| *byte == b' ').all(is_allowed)
+        }
+        _ => is_allowed(value),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_frame() {
+        let message = b"8=FIX.4.4\x019=15\x0135=D\x0154=1\x0155=X\x0110=192\x01";
+        assert_eq!(Vec::<ValidationError>::new(), validate_frame(message));
+
+        let message = b"8=FIX.4.4\x019=17\x0135=D\x0154=1\x0155=X\x0110=000\x01";
+        assert_eq!(
+            vec![
+                ValidationError {
+                    offset: 10,
+                    occurrence: 1,
+                    kind: ValidationErrorKind::InvalidBodyLength {
+                        declared: "17".to_string(),
+                        actual: 15
+                    }
+                },
+                ValidationError {
+                    offset: 30,
+                    occurrence: 1,
+                    kind: ValidationErrorKind::InvalidCheckSum {
+                        declared: "000".to_string(),
+                        actual: "194".to_string()
+                    }
+                },
+            ],
+            validate_frame(message)
+        );
+
+        let message = b"8=FIX.4.4\x019=15\x0135=D\x0154=1\x0155=X\x01";
+        assert_eq!(
+            ValidationErrorKind::MissingCheckSum,
+            validate_frame(message)[0].kind
+        );
+    }
+
+    #[test]
+    fn test_validate_charset() {
+        let message =
+            b"8=FIX.4.4\x019=26\x0135=D\x0158=caf\xc3\xa9\x01355=caf\xc3\xa9\x0110=000\x01";
+
+        let errors = validate_charset(message, DEFAULT_DATA_TAGS);
+        assert_eq!(
+            vec![ValidationError {
+                offset: 20,
+                occurrence: 1,
+                kind: ValidationErrorKind::InvalidCharacter {
+                    tag: 58,
+                    position: 26
+                },
+            }],
+            errors
+        );
+        assert_eq!(Some(58), errors[0].get_ref_tag_id());
+
+        let mut dictionary = FixDictionary::fix44();
+        dictionary.add_field(crate::FieldDef {
+            tag: 58,
+            name: "Text".to_string(),
+            field_type: FieldType::Data,
+            values: vec![],
+        });
+        assert_eq!(vec![58], dictionary.get_data_tags());
+        assert_eq!(
+            vec![Some(355)],
+            validate_charset(message, &dictionary.get_data_tags())
+                .iter()
+                .map(ValidationError::get_ref_tag_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_validate_with_dictionary() {
+        let dictionary = FixDictionary::fix44();
+        let message =
+            b"8=FIX.4.4\x019=15\x0135=D\x0149=A\x0156=B\x0134=x\x0154=9\x01112=1\x019999=1\x0110=000\x01";
+
+        let kinds: Vec<(usize, ValidationErrorKind)> = dictionary
+            .validate(message)
+            .into_iter()
+            .map(|error| (error.offset, error.kind))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (30, ValidationErrorKind::IncorrectDataFormat(34)),
+                (35, ValidationErrorKind::ValueIsIncorrect(54)),
+                (40, ValidationErrorKind::TagNotDefinedForMessage(112)),
+                (46, ValidationErrorKind::UnknownTag(9999)),
+                (0, ValidationErrorKind::RequiredTagMissing(52)),
+                (0, ValidationErrorKind::RequiredTagMissing(11)),
+                (0, ValidationErrorKind::RequiredTagMissing(55)),
+                (0, ValidationErrorKind::RequiredTagMissing(60)),
+                (0, ValidationErrorKind::RequiredTagMissing(40)),
+            ],
+            kinds
+        );
+    }
+
+    #[test]
+    fn test_reject_fields() {
+        let dictionary = FixDictionary::fix44();
+        let message = b"8=FIX.4.4\x019=15\x0135=D\x0154=1\x0154=9\x0110=000\x01";
+
+        let error = dictionary
+            .validate(message)
+            .into_iter()
+            .find(|error| error.kind == ValidationErrorKind::ValueIsIncorrect(54))
+            .unwrap();
+
+        assert_eq!(25, error.offset);
+        assert_eq!(2, error.occurrence);
+        assert_eq!(Some(54), error.get_ref_tag_id());
+        assert_eq!(
+            SessionRejectReason::ValueIsIncorrect,
+            error.get_session_reject_reason()
+        );
+
+        let error = &dictionary.validate(message)[1];
+        assert_eq!(ValidationErrorKind::RequiredTagMissing(49), error.kind);
+        assert_eq!(0, error.occurrence);
+        assert_eq!(
+            SessionRejectReason::RequiredTagMissing,
+            error.get_session_reject_reason()
+        );
+    }
+}
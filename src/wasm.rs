@@ -0,0 +1,135 @@
+//! wasm-bindgen wrappers (`wasm` feature) so browser log viewers run the same parser
+//! as the engine. Build with `wasm-pack build --target web -- --features wasm`; the
+//! crate needs no other feature on `wasm32-unknown-unknown`.
+//!
+//! Nothing here reads the system clock: SendingTime (52) is only stamped when a
+//! `Clock` is attached, which these wrappers never do.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{parse_all, FixMessageBuilder};
+
+/// A parsed or built message, exported to JavaScript as `FixMessage`.
+#[wasm_bindgen(js_name = FixMessage)]
+#[derive(Debug, Clone)]
+pub struct WasmFixMessage {
+    inner: FixMessageBuilder,
+}
+
+#[wasm_bindgen(js_class = FixMessage)]
+impl WasmFixMessage {
+    #[wasm_bindgen(constructor)]
+    pub fn new(begin_string: &str, msg_type: &str) -> Self {
+        return Self {
+            inner: FixMessageBuilder::new(begin_string, msg_type),
+        };
+    }
+
+    /// Parses one complete message, throwing on malformed input.
+    pub fn parse(data: &[u8], check_sum_validation: bool) -> Result<WasmFixMessage, JsError> {
+        return match FixMessageBuilder::from_bytes(data, check_sum_validation) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(err) => Err(JsError::new(&format!("{:?}", err))),
+        };
+    }
+
+    /// Never throws and keeps the fields in wire order; meant for damaged logs.
+    #[wasm_bindgen(js_name = parseLenient)]
+    pub fn parse_lenient(data: &[u8]) -> WasmFixMessage {
+        let (inner, _) = FixMessageBuilder::from_bytes_lenient(data);
+        return Self { inner };
+    }
+
+    #[wasm_bindgen(getter, js_name = beginString)]
+    pub fn get_begin_string(&self) -> String {
+        return self.inner.get_fix_version().to_string();
+    }
+
+    #[wasm_bindgen(getter, js_name = msgType)]
+    pub fn get_msg_type(&self) -> String {
+        return self.inner.get_message_type_as_string();
+    }
+
+    /// Value of the first occurrence of `tag`, decoded lossily as UTF-8.
+    pub fn get(&self, tag: u32) -> Option<String> {
+        return self
+            .inner
+            .get(tag)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+    }
+
+    /// Body tags in message order, repeated tags included.
+    pub fn tags(&self) -> Vec<u32> {
+        return self.inner.get_fields().map(|(tag, _)| tag).collect();
+    }
+
+    /// Body values in the order of `tags`.
+    pub fn values(&self) -> Vec<String> {
+        return self
+            .inner
+            .get_fields()
+            .map(|(_, value)| String::from_utf8_lossy(value).to_string())
+            .collect();
+    }
+
+    #[wasm_bindgen(js_name = withValue)]
+    pub fn with_value(&mut self, tag: u32, value: &str) {
+        self.inner.with_value(tag, value);
+    }
+
+    /// The message with BodyLength and CheckSum, as a `Uint8Array`.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        return self.inner.as_bytes();
+    }
+
+    /// The message with `|` in place of SOH.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        return self.inner.to_string();
+    }
+}
+
+/// Every complete message in `data`, e.g. a whole log file. Throws at the first
+/// malformed one.
+#[wasm_bindgen(js_name = parseAll)]
+pub fn wasm_parse_all(
+    data: &[u8],
+    check_sum_validation: bool,
+) -> Result<Vec<WasmFixMessage>, JsError> {
+    let (messages, consumed, err) = parse_all(data, check_sum_validation);
+
+    if let Some(err) = err {
+        return Err(JsError::new(&format!("{:?} at byte {}", err, consumed)));
+    }
+
+    return Ok(messages
+        .into_iter()
+        .map(|inner| WasmFixMessage { inner })
+        .collect());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wasm_round_trip() {
+        let mut order = WasmFixMessage::new("FIX.4.4", "D");
+        order.with_value(11, "ORDER1");
+        order.with_value(54, "1");
+
+        let mut data = order.to_bytes();
+        data.extend(order.to_bytes());
+
+        let messages = wasm_parse_all(&data, true).unwrap();
+        assert_eq!(2, messages.len());
+        assert_eq!("D", messages[1].get_msg_type());
+        assert_eq!(Some("ORDER1".to_string()), messages[1].get(11));
+
+        let parsed = WasmFixMessage::parse_lenient(&order.to_bytes());
+        assert_eq!(vec![11, 54], parsed.tags());
+        assert_eq!(vec!["ORDER1", "1"], parsed.values());
+        assert_eq!(order.to_js_string(), parsed.to_js_string());
+    }
+}